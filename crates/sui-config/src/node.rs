@@ -18,6 +18,7 @@ use std::usize;
 use sui_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use sui_protocol_config::SupportedProtocolVersions;
 use sui_storage::object_store::ObjectStoreConfig;
+use sui_storage::IndexStoreConfig;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::crypto::KeypairTraits;
@@ -98,6 +99,16 @@ pub struct NodeConfig {
 
     #[serde(default)]
     pub indirect_objects_threshold: usize,
+
+    /// Selects which of the optional fullnode indexes are maintained. Ignored on validators,
+    /// which do not maintain an index store.
+    #[serde(default)]
+    pub index_store_config: IndexStoreConfig,
+
+    /// Controls the hourly telemetry event of the node. If unspecified, telemetry is sent to
+    /// Mysten Labs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
 }
 
 fn default_authority_store_pruning_config() -> AuthorityStorePruningConfig {
@@ -332,6 +343,30 @@ pub struct MetricsConfig {
     pub push_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TelemetryConfig {
+    /// Set to false to never send telemetry.
+    #[serde(default = "bool_true")]
+    pub enabled: bool,
+    /// Post the telemetry payload, as JSON, to this url instead of Mysten Labs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Only log the telemetry payload, without sending it anywhere.
+    #[serde(default)]
+    pub log_only: bool,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            url: None,
+            log_only: false,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DBCheckpointConfig {
@@ -608,11 +643,13 @@ mod tests {
     use fastcrypto::traits::KeyPair;
     use rand::{rngs::StdRng, SeedableRng};
     use sui_keys::keypair_file::{write_authority_keypair_to_file, write_keypair_to_file};
+    use sui_storage::indexes::OptionalIndex;
+    use sui_storage::IndexStoreConfig;
     use sui_types::crypto::{
         get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair, NetworkKeyPair, SuiKeyPair,
     };
 
-    use super::Genesis;
+    use super::{Genesis, TelemetryConfig};
     use crate::NodeConfig;
 
     #[test]
@@ -675,6 +712,40 @@ mod tests {
         let _template: NodeConfig = serde_yaml::from_str(TEMPLATE).unwrap();
     }
 
+    #[test]
+    fn telemetry_config() {
+        let config: TelemetryConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config, TelemetryConfig::default());
+        assert!(config.enabled);
+
+        let config: TelemetryConfig = serde_yaml::from_str("enabled: false").unwrap();
+        assert!(!config.enabled);
+
+        let config: TelemetryConfig =
+            serde_yaml::from_str("url: https://telemetry.example.com\nlog-only: true").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.url.as_deref(), Some("https://telemetry.example.com"));
+        assert!(config.log_only);
+    }
+
+    #[test]
+    fn index_store_config() {
+        let config: IndexStoreConfig =
+            serde_yaml::from_str("disabled-indexes: [owner_index, event_by_time]").unwrap();
+        assert!(!config.is_enabled(OptionalIndex::OwnerIndex));
+        assert!(!config.is_enabled(OptionalIndex::EventByTime));
+        assert!(config.is_enabled(OptionalIndex::DynamicFieldIndex));
+
+        let config: IndexStoreConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.disabled_indexes.is_empty());
+        assert!(!config.is_pruning_enabled());
+
+        let config: IndexStoreConfig =
+            serde_yaml::from_str("num-checkpoints-to-retain: 1000").unwrap();
+        assert_eq!(config.num_checkpoints_to_retain, Some(1000));
+        assert!(config.is_pruning_enabled());
+    }
+
     #[test]
     fn load_key_pairs_to_node_config() {
         let protocol_key_pair: AuthorityKeyPair =
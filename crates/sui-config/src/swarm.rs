@@ -279,6 +279,8 @@ impl<'a> FullnodeConfigBuilder<'a> {
             supported_protocol_versions: Some(supported_protocol_versions),
             db_checkpoint_config: self.db_checkpoint_config,
             indirect_objects_threshold: usize::MAX,
+            index_store_config: Default::default(),
+            telemetry: None,
         })
     }
 }
@@ -15,6 +15,7 @@ use fastcrypto::traits::KeyPair;
 use itertools::Itertools;
 use move_binary_format::compatibility::Compatibility;
 use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::language_storage::ModuleId;
 use parking_lot::Mutex;
 use prometheus::{
@@ -48,7 +49,7 @@ use sui_json_rpc_types::{
 };
 use sui_macros::{fail_point, fail_point_async, nondeterministic};
 use sui_protocol_config::SupportedProtocolVersions;
-use sui_storage::indexes::{ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
+use sui_storage::indexes::{ObjectIndexChanges, OptionalIndex, MAX_GET_OWNED_OBJECT_SIZE};
 use sui_storage::write_ahead_log::WriteAheadLog;
 use sui_storage::{
     write_ahead_log::{DBTxGuard, TxGuard},
@@ -89,6 +90,7 @@ use sui_types::{
     object::{Object, ObjectFormatOptions, ObjectRead},
     SUI_FRAMEWORK_ADDRESS,
 };
+use typed_store::traits::RawTable;
 use typed_store::Map;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
@@ -481,6 +483,32 @@ impl AuthorityState {
         self.committee_store.clone()
     }
 
+    /// Returns the tables of all the stores of this node as `(store, table, handle)`, so that they
+    /// can be inspected and compacted while the node is running. The epoch tables are those of
+    /// `epoch_store`.
+    pub fn raw_tables<'a>(
+        &'a self,
+        epoch_store: &'a AuthorityPerEpochStore,
+    ) -> Vec<(&'static str, &'static str, &'a dyn RawTable)> {
+        let mut stores = vec![
+            ("perpetual", self.database.perpetual_tables.raw_tables()),
+            ("epoch", epoch_store.raw_tables()),
+            ("checkpoints", self.checkpoint_store.raw_tables()),
+            ("committee", self.committee_store.raw_tables()),
+        ];
+        if let Some(indexes) = &self.indexes {
+            stores.push(("indexes", indexes.raw_tables()));
+        }
+        stores
+            .into_iter()
+            .flat_map(|(store, tables)| {
+                tables
+                    .into_iter()
+                    .map(move |(table, handle)| (store, table, handle))
+            })
+            .collect()
+    }
+
     /// This is a private method and should be kept that way. It doesn't check whether
     /// the provided transaction is a system transaction, and hence can only be called internally.
     async fn handle_transaction_impl(
@@ -1202,9 +1230,15 @@ impl AuthorityState {
         timestamp_ms: u64,
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> SuiResult<u64> {
-        let changes = self
-            .process_object_index(effects, epoch_store)
-            .tap_err(|e| warn!("{e}"))?;
+        let config = indexes.config();
+        let changes = if config.is_enabled(OptionalIndex::OwnerIndex)
+            || config.is_enabled(OptionalIndex::DynamicFieldIndex)
+        {
+            self.process_object_index(effects, epoch_store)
+                .tap_err(|e| warn!("{e}"))?
+        } else {
+            ObjectIndexChanges::default()
+        };
 
         indexes.index_tx(
             cert.data().intent_message().value.sender(),
@@ -1337,68 +1371,7 @@ impl AuthorityState {
         o: &Object,
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> SuiResult<Option<DynamicFieldInfo>> {
-        // Skip if not a move object
-        let Some(move_object) =  o.data.try_as_move().cloned() else {
-            return Ok(None);
-        };
-        // We only index dynamic field objects
-        if !move_object.type_().is_dynamic_field() {
-            return Ok(None);
-        }
-        let move_struct = move_object.to_move_struct_with_resolver(
-            ObjectFormatOptions::default(),
-            epoch_store.module_cache().as_ref(),
-        )?;
-
-        let (name_value, type_, object_id) =
-            DynamicFieldInfo::parse_move_object(&move_struct).tap_err(|e| warn!("{e}"))?;
-
-        let name_type = move_object.type_().try_extract_field_name(&type_)?;
-
-        let bcs_name = bcs::to_bytes(&name_value.clone().undecorate()).map_err(|e| {
-            SuiError::ObjectSerializationError {
-                error: format!("{e}"),
-            }
-        })?;
-
-        let name = DynamicFieldName {
-            type_: name_type,
-            value: SuiMoveValue::from(name_value).to_json_value(),
-        };
-
-        Ok(Some(match type_ {
-            DynamicFieldType::DynamicObject => {
-                // Find the actual object from storage using the object id obtained from the wrapper.
-                let Some(object) = self.database.find_object_lt_or_eq_version(object_id, o.version()) else{
-                    return Err(UserInputError::ObjectNotFound {
-                        object_id,
-                        version: Some(o.version()),
-                    }.into())
-                };
-                let version = object.version();
-                let digest = object.digest();
-                let object_type = object.data.type_().unwrap();
-
-                DynamicFieldInfo {
-                    name,
-                    bcs_name,
-                    type_,
-                    object_type: object_type.to_string(),
-                    object_id,
-                    version,
-                    digest,
-                }
-            }
-            DynamicFieldType::DynamicField { .. } => DynamicFieldInfo {
-                name,
-                bcs_name,
-                type_,
-                object_type: move_object.into_type().into_type_params()[1].to_string(),
-                object_id: o.id(),
-                version: o.version(),
-                digest: o.digest(),
-            },
-        }))
+        try_create_dynamic_field_info(o, epoch_store.module_cache().as_ref(), &self.database)
     }
 
     #[instrument(level = "debug", skip_all, err)]
@@ -1617,6 +1590,7 @@ impl AuthorityState {
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
             store.objects_lock_table.clone(),
+            indexes.clone(),
             pruning_config,
             epoch_store.epoch_start_state().epoch_duration_ms(),
         );
@@ -1800,31 +1774,11 @@ impl AuthorityState {
             return Ok(());
         }
 
-        let mut new_owners = vec![];
-        let mut new_dynamic_fields = vec![];
-        for o in genesis_objects.iter() {
-            match o.owner {
-                Owner::AddressOwner(addr) => new_owners.push((
-                    (addr, o.id()),
-                    ObjectInfo::new(&o.compute_object_reference(), o),
-                )),
-                Owner::ObjectOwner(object_id) => {
-                    let id = o.id();
-                    let Some(info) = self.try_create_dynamic_field_info(o, epoch_store)? else{
-                        continue;
-                    };
-                    new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
-                }
-                _ => {}
-            }
-        }
-
-        index_store.insert_genesis_objects(ObjectIndexChanges {
-            deleted_owners: vec![],
-            deleted_dynamic_fields: vec![],
-            new_owners,
-            new_dynamic_fields,
-        })
+        index_store.insert_genesis_objects(object_index_changes_for_live_objects(
+            genesis_objects.iter(),
+            epoch_store.module_cache().as_ref(),
+            &self.database,
+        )?)
     }
 
     pub async fn reconfigure(
@@ -1935,6 +1889,11 @@ impl AuthorityState {
         self.epoch_store_for_testing().committee().clone()
     }
 
+    // Get the index store of a fullnode, should be used in tests only.
+    pub fn index_store_for_testing(&self) -> Option<&Arc<IndexStore>> {
+        self.indexes.as_ref()
+    }
+
     pub(crate) async fn get_object(
         &self,
         object_id: &ObjectID,
@@ -2366,6 +2325,17 @@ impl AuthorityState {
             .ok_or_else(|| anyhow!("Latest checkpoint sequence number not found"))
     }
 
+    /// Returns the oldest checkpoint whose transactions and events have not been pruned from the
+    /// indexes.
+    pub fn get_lowest_indexed_checkpoint_sequence_number(
+        &self,
+    ) -> SuiResult<CheckpointSequenceNumber> {
+        Ok(self
+            .get_indexes()?
+            .get_highest_pruned_checkpoint()?
+            .map_or(0, |pruned| pruned + 1))
+    }
+
     pub fn get_checkpoint_summary_by_sequence_number(
         &self,
         sequence_number: CheckpointSequenceNumber,
@@ -3384,6 +3354,108 @@ impl AuthorityState {
     }
 }
 
+/// Builds the dynamic field index entry for `o`, or returns `None` if `o` is not a dynamic field.
+pub fn try_create_dynamic_field_info(
+    o: &Object,
+    resolver: &impl GetModule,
+    store: &AuthorityStore,
+) -> SuiResult<Option<DynamicFieldInfo>> {
+    // Skip if not a move object
+    let Some(move_object) =  o.data.try_as_move().cloned() else {
+        return Ok(None);
+    };
+    // We only index dynamic field objects
+    if !move_object.type_().is_dynamic_field() {
+        return Ok(None);
+    }
+    let move_struct =
+        move_object.to_move_struct_with_resolver(ObjectFormatOptions::default(), resolver)?;
+
+    let (name_value, type_, object_id) =
+        DynamicFieldInfo::parse_move_object(&move_struct).tap_err(|e| warn!("{e}"))?;
+
+    let name_type = move_object.type_().try_extract_field_name(&type_)?;
+
+    let bcs_name = bcs::to_bytes(&name_value.clone().undecorate()).map_err(|e| {
+        SuiError::ObjectSerializationError {
+            error: format!("{e}"),
+        }
+    })?;
+
+    let name = DynamicFieldName {
+        type_: name_type,
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    Ok(Some(match type_ {
+        DynamicFieldType::DynamicObject => {
+            // Find the actual object from storage using the object id obtained from the wrapper.
+            let Some(object) = store.find_object_lt_or_eq_version(object_id, o.version()) else{
+                return Err(UserInputError::ObjectNotFound {
+                    object_id,
+                    version: Some(o.version()),
+                }.into())
+            };
+            let version = object.version();
+            let digest = object.digest();
+            let object_type = object.data.type_().unwrap();
+
+            DynamicFieldInfo {
+                name,
+                bcs_name,
+                type_,
+                object_type: object_type.to_string(),
+                object_id,
+                version,
+                digest,
+            }
+        }
+        DynamicFieldType::DynamicField { .. } => DynamicFieldInfo {
+            name,
+            bcs_name,
+            type_,
+            object_type: move_object.into_type().into_type_params()[1].to_string(),
+            object_id: o.id(),
+            version: o.version(),
+            digest: o.digest(),
+        },
+    }))
+}
+
+/// Builds the owner and dynamic field index entries for a set of live objects. Used to seed the
+/// index store from the genesis objects, and to rebuild the object indexes offline.
+pub fn object_index_changes_for_live_objects<'a>(
+    objects: impl Iterator<Item = &'a Object>,
+    resolver: &impl GetModule,
+    store: &AuthorityStore,
+) -> SuiResult<ObjectIndexChanges> {
+    let mut new_owners = vec![];
+    let mut new_dynamic_fields = vec![];
+    for o in objects {
+        match o.owner {
+            Owner::AddressOwner(addr) => new_owners.push((
+                (addr, o.id()),
+                ObjectInfo::new(&o.compute_object_reference(), o),
+            )),
+            Owner::ObjectOwner(object_id) => {
+                let id = o.id();
+                let Some(info) = try_create_dynamic_field_info(o, resolver, store)? else{
+                    continue;
+                };
+                new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ObjectIndexChanges {
+        deleted_owners: vec![],
+        deleted_dynamic_fields: vec![],
+        new_owners,
+        new_dynamic_fields,
+    })
+}
+
 fn calculate_checkpoint_numbers(
     // If `Some`, the query will start from the next item after the specified cursor
     cursor: Option<CheckpointSequenceNumber>,
@@ -31,7 +31,7 @@ use tracing::{debug, info, trace, warn};
 use typed_store::rocks::{
     point_lookup_db_options, DBBatch, DBMap, DBOptions, MetricConf, TypedStoreError,
 };
-use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::traits::{RawTable, TableSummary, TypedStoreDebug};
 
 use crate::authority::authority_notify_read::NotifyRead;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
@@ -84,6 +84,12 @@ const RECONFIG_STATE_INDEX: u64 = 0;
 const FINAL_EPOCH_CHECKPOINT_INDEX: u64 = 0;
 const OVERRIDE_PROTOCOL_UPGRADE_BUFFER_STAKE_INDEX: u64 = 0;
 pub const EPOCH_DB_PREFIX: &str = "epoch_";
+/// Name of the recovery log directory inside an epoch db directory.
+pub const RECOVERY_LOG_DIR: &str = "recovery_log";
+
+/// The write-ahead log of certificates being executed in an epoch.
+pub type ExecutionRecoveryLog =
+    DBWriteAheadLog<TrustedExecutableTransaction, (InnerTemporaryStore, TransactionEffects)>;
 
 pub struct CertLockGuard(MutexGuard);
 
@@ -141,9 +147,7 @@ pub struct AuthorityPerEpochStore {
     pending_consensus_certificates: Mutex<HashSet<TransactionDigest>>,
     /// A write-ahead/recovery log used to ensure we finish fully processing certs after errors or
     /// crashes.
-    wal: Arc<
-        DBWriteAheadLog<TrustedExecutableTransaction, (InnerTemporaryStore, TransactionEffects)>,
-    >,
+    wal: Arc<ExecutionRecoveryLog>,
 
     /// The moment when the current epoch started locally on this validator. Note that this
     /// value could be skewed if the node crashed and restarted in the middle of the epoch. That's
@@ -343,7 +347,7 @@ impl AuthorityPerEpochStore {
         let reconfig_state = tables
             .load_reconfig_state()
             .expect("Load reconfig state at initialization cannot fail");
-        let wal_path = AuthorityEpochTables::path(epoch_id, parent_path).join("recovery_log");
+        let wal_path = AuthorityEpochTables::path(epoch_id, parent_path).join(RECOVERY_LOG_DIR);
         let wal = Arc::new(DBWriteAheadLog::new(wal_path));
         let epoch_alive_notify = NotifyOnce::new();
         let pending_consensus_transactions = tables.get_all_pending_consensus_transactions();
@@ -434,14 +438,16 @@ impl AuthorityPerEpochStore {
         )
     }
 
-    pub fn wal(
-        &self,
-    ) -> &Arc<
-        DBWriteAheadLog<TrustedExecutableTransaction, (InnerTemporaryStore, TransactionEffects)>,
-    > {
+    pub fn wal(&self) -> &Arc<ExecutionRecoveryLog> {
         &self.wal
     }
 
+    /// Returns the tables of this epoch by name, for inspection and compaction while the node is
+    /// running.
+    pub fn raw_tables(&self) -> Vec<(&'static str, &dyn RawTable)> {
+        self.tables.raw_tables()
+    }
+
     pub fn committee(&self) -> &Committee {
         &self.committee
     }
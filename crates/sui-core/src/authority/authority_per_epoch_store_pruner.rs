@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::authority::authority_per_epoch_store::EPOCH_DB_PREFIX;
+use crate::authority::authority_per_epoch_store::{EPOCH_DB_PREFIX, RECOVERY_LOG_DIR};
 use itertools::Itertools;
 use std::fs;
 use std::path::PathBuf;
@@ -62,7 +62,7 @@ impl AuthorityPerEpochStorePruner {
             for (_, path) in candidates.into_iter().sorted().take(to_prune) {
                 info!("Dropping epoch directory {:?}", path);
                 pruned += 1;
-                gc_results.push(safe_drop_db(path.join("recovery_log")));
+                gc_results.push(safe_drop_db(path.join(RECOVERY_LOG_DIR)));
                 gc_results.push(safe_drop_db(path));
             }
         }
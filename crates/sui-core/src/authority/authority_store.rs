@@ -99,9 +99,9 @@ impl AuthorityStore {
             .get_committee(&cur_epoch)?
             .expect("Committee of the current epoch must exist");
         Self::open_inner(
-            genesis,
+            Some(genesis),
             perpetual_tables,
-            &committee,
+            committee.epoch,
             indirect_objects_threshold,
         )
         .await
@@ -119,22 +119,38 @@ impl AuthorityStore {
         assert_eq!(committee.epoch, 0);
         let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(path, db_options.clone()));
         Self::open_inner(
-            genesis,
+            Some(genesis),
             perpetual_tables,
-            committee,
+            committee.epoch,
             indirect_objects_threshold,
         )
         .await
     }
 
+    /// Open an existing, already initialized authority store without a genesis or committee.
+    /// This is meant for offline tools that read the store of a stopped node.
+    pub async fn open_existing(
+        path: &Path,
+        db_options: Option<Options>,
+        indirect_objects_threshold: usize,
+    ) -> SuiResult<Self> {
+        let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(path, db_options));
+        fp_ensure!(
+            !perpetual_tables.database_is_empty()?,
+            SuiError::FileIOError(format!("No authority store found at {}", path.display()))
+        );
+        let epoch = perpetual_tables.get_recovery_epoch_at_restart()?;
+        Self::open_inner(None, perpetual_tables, epoch, indirect_objects_threshold).await
+    }
+
+    /// An empty database is initialized from `genesis`, which is only optional for a database
+    /// that is known to be initialized already.
     async fn open_inner(
-        genesis: &Genesis,
+        genesis: Option<&Genesis>,
         perpetual_tables: Arc<AuthorityPerpetualTables>,
-        committee: &Committee,
+        epoch: EpochId,
         indirect_objects_threshold: usize,
     ) -> SuiResult<Self> {
-        let epoch = committee.epoch;
-
         let store = Self {
             mutex_table: MutexTable::new(NUM_SHARDS),
             perpetual_tables,
@@ -150,6 +166,9 @@ impl AuthorityStore {
             .database_is_empty()
             .expect("Database read should not fail at init.")
         {
+            let genesis = genesis.ok_or_else(|| {
+                SuiError::FileIOError("Cannot initialize an empty store without genesis".into())
+            })?;
             store
                 .bulk_object_insert(&genesis.objects().iter().collect::<Vec<_>>())
                 .await
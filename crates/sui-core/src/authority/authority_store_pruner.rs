@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use std::{sync::Arc, time::Duration};
 use sui_config::node::AuthorityStorePruningConfig;
 use sui_storage::mutex_table::RwLockTable;
+use sui_storage::IndexStore;
+use sui_types::base_types::TxSequenceNumber;
 use sui_types::messages::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::{
@@ -24,8 +26,15 @@ use super::authority_store_tables::AuthorityPerpetualTables;
 
 pub struct AuthorityStorePruner {
     _objects_pruner_cancel_handle: oneshot::Sender<()>,
+    _indexes_pruner_cancel_handle: Option<oneshot::Sender<()>>,
 }
 
+/// How often the index store is checked for entries that fell out of its retention window.
+const INDEXES_PRUNING_PERIOD: Duration = Duration::from_secs(600);
+
+/// Number of indexed transactions whose checkpoints are looked up at once.
+const INDEXES_PRUNING_BATCH_SIZE: usize = 1000;
+
 #[derive(Debug, Clone, Copy)]
 enum DeletionMethod {
     RangeDelete,
@@ -224,14 +233,140 @@ impl AuthorityStorePruner {
         });
         sender
     }
+    /// Returns the last checkpoint whose index entries fall out of the retention window of
+    /// `index_store`, if any.
+    fn indexes_pruning_target(
+        index_store: &IndexStore,
+        checkpoint_store: &CheckpointStore,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        let config = index_store.config();
+        let highest_executed = match checkpoint_store.get_highest_executed_checkpoint()? {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(None),
+        };
+        let by_checkpoints = config
+            .num_checkpoints_to_retain
+            .and_then(|retained| highest_executed.sequence_number().checked_sub(retained));
+        let by_epochs = match config
+            .num_epochs_to_retain
+            .and_then(|retained| highest_executed.epoch().checked_sub(retained))
+        {
+            Some(epoch) => checkpoint_store
+                .get_epoch_last_checkpoint(epoch)?
+                .map(|checkpoint| *checkpoint.sequence_number()),
+            None => None,
+        };
+        Ok(max(by_checkpoints, by_epochs))
+    }
+
+    /// Returns the transaction sequence number below which all index entries belong to
+    /// checkpoints up to `checkpoint_number`. The indexed transactions are walked in local
+    /// execution order from the previously pruned sequence number, and the walk stops at the
+    /// first one that is not in such a checkpoint. Local execution order is not checkpoint
+    /// order, so a transaction executed ahead of its checkpoint holds back pruning until its
+    /// checkpoint falls out of the retention window as well.
+    fn indexes_pruning_watermark(
+        index_store: &IndexStore,
+        perpetual_db: &AuthorityPerpetualTables,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> anyhow::Result<TxSequenceNumber> {
+        let mut watermark = index_store.get_pruned_sequence()?;
+        let mut transactions = index_store
+            .transactions_from_sequence(watermark)?
+            .peekable();
+        while transactions.peek().is_some() {
+            let chunk: Vec<_> = transactions
+                .by_ref()
+                .take(INDEXES_PRUNING_BATCH_SIZE)
+                .collect();
+            let checkpoints = perpetual_db
+                .executed_transactions_to_checkpoint
+                .multi_get(chunk.iter().map(|(_, digest)| digest))?;
+            for ((sequence, _), checkpoint) in chunk.iter().zip(checkpoints) {
+                match checkpoint {
+                    Some((_, checkpoint)) if checkpoint <= checkpoint_number => {
+                        watermark = sequence + 1
+                    }
+                    _ => return Ok(watermark),
+                }
+            }
+        }
+        Ok(watermark)
+    }
+
+    /// Prunes the transaction and event index entries of all checkpoints up to the one that
+    /// falls out of the index store's retention window.
+    fn prune_indexes(
+        index_store: &Arc<IndexStore>,
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("IndexesPruner");
+        let checkpoint_number = match Self::indexes_pruning_target(index_store, checkpoint_store)? {
+            Some(checkpoint_number) => checkpoint_number,
+            None => return Ok(()),
+        };
+        let watermark =
+            Self::indexes_pruning_watermark(index_store, perpetual_db, checkpoint_number)?;
+        debug!(
+            "Pruning indexes up to checkpoint {}, transaction sequence number {}",
+            checkpoint_number, watermark
+        );
+        // Called even if the watermark did not move, to continue the sweeps of the tables keyed
+        // by address or object.
+        index_store.prune(watermark, checkpoint_number)?;
+        Ok(())
+    }
+
+    fn setup_indexes_pruning(
+        index_store: Arc<IndexStore>,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        checkpoint_store: Arc<CheckpointStore>,
+    ) -> Sender<()> {
+        let (sender, mut recv) = tokio::sync::oneshot::channel();
+        debug!(
+            "Starting index pruning service with num_epochs_to_retain={:?}, num_checkpoints_to_retain={:?}",
+            index_store.config().num_epochs_to_retain,
+            index_store.config().num_checkpoints_to_retain
+        );
+        let mut prune_interval = tokio::time::interval_at(
+            Instant::now() + INDEXES_PRUNING_PERIOD,
+            INDEXES_PRUNING_PERIOD,
+        );
+
+        tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = prune_interval.tick() => {
+                        if let Err(err) = Self::prune_indexes(&index_store, &perpetual_db, &checkpoint_store) {
+                            error!("Failed to prune indexes: {:?}", err);
+                        }
+                    },
+                    _ = &mut recv => break,
+                }
+            }
+        });
+        sender
+    }
+
     pub fn new(
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
         objects_lock_table: Arc<RwLockTable<ObjectContentDigest>>,
+        indexes: Option<Arc<IndexStore>>,
         pruning_config: AuthorityStorePruningConfig,
         epoch_duration_ms: u64,
     ) -> Self {
         AuthorityStorePruner {
+            _indexes_pruner_cancel_handle: indexes
+                .filter(|index_store| index_store.config().is_pruning_enabled())
+                .map(|index_store| {
+                    Self::setup_indexes_pruning(
+                        index_store,
+                        perpetual_db.clone(),
+                        checkpoint_store.clone(),
+                    )
+                }),
             _objects_pruner_cancel_handle: Self::setup_objects_pruning(
                 pruning_config,
                 epoch_duration_ms,
@@ -259,9 +394,10 @@ mod tests {
     };
     #[cfg(not(target_env = "msvc"))]
     use pprof::Symbol;
+    use sui_storage::indexes::{IndexStore, ObjectIndexChanges};
     use sui_storage::mutex_table::RwLockTable;
-    use sui_types::base_types::{ObjectDigest, VersionNumber};
-    use sui_types::messages::{TransactionEffects, TransactionEffectsAPI};
+    use sui_types::base_types::{ObjectDigest, SuiAddress, TransactionDigest, VersionNumber};
+    use sui_types::messages::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
     use sui_types::{
         base_types::{ObjectID, SequenceNumber},
         object::Object,
@@ -597,4 +733,43 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn indexes_pruning_watermark_stops_at_transactions_of_later_checkpoints() {
+        let primary_path = tempfile::tempdir().unwrap().into_path();
+        let perpetual_db = AuthorityPerpetualTables::open(&primary_path, None);
+        let index_store = IndexStore::new(primary_path.join("indexes"));
+        // The transaction indexed second was executed ahead of its checkpoint.
+        for checkpoint in [1, 3, 2] {
+            let digest = TransactionDigest::random();
+            index_store
+                .index_tx(
+                    SuiAddress::random_for_testing_only(),
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    &TransactionEvents::default(),
+                    ObjectIndexChanges::default(),
+                    &digest,
+                    0,
+                )
+                .unwrap();
+            perpetual_db
+                .executed_transactions_to_checkpoint
+                .insert(&digest, &(0, checkpoint))
+                .unwrap();
+        }
+
+        let watermark = |checkpoint| {
+            AuthorityStorePruner::indexes_pruning_watermark(&index_store, &perpetual_db, checkpoint)
+                .unwrap()
+        };
+        assert_eq!(watermark(0), 0);
+        assert_eq!(watermark(2), 1);
+        assert_eq!(watermark(3), 3);
+
+        index_store.prune(1, 2).unwrap();
+        assert_eq!(watermark(2), 1);
+        assert_eq!(watermark(3), 3);
+    }
 }
@@ -10,7 +10,7 @@ use sui_types::base_types::ObjectID;
 use sui_types::committee::{Committee, EpochId};
 use sui_types::error::{SuiError, SuiResult};
 use typed_store::rocks::{point_lookup_db_options, DBMap, DBOptions, MetricConf};
-use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::traits::{RawTable, TableSummary, TypedStoreDebug};
 
 use typed_store::Map;
 use typed_store_derive::DBMapUtils;
@@ -125,6 +125,10 @@ impl CommitteeStore {
             .map_err(SuiError::StorageError)
     }
 
+    pub fn raw_tables(&self) -> Vec<(&'static str, &dyn RawTable)> {
+        self.tables.raw_tables()
+    }
+
     fn database_is_empty(&self) -> bool {
         self.tables.committee_map.iter().next().is_none()
     }
@@ -50,6 +50,9 @@ const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(5);
 
 const WAIT_FOR_FINALITY_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Name of the pending transaction log directory inside the fullnode's db directory.
+pub const PENDING_TRANSACTIONS_DIR: &str = "fullnode_pending_transactions";
+
 pub struct TransactiondOrchestrator<A> {
     quorum_driver_handler: Arc<QuorumDriverHandler<A>>,
     validator_state: Arc<AuthorityState>,
@@ -121,7 +124,7 @@ where
         let metrics = Arc::new(TransactionOrchestratorMetrics::new(prometheus_registry));
         let metrics_clone = metrics.clone();
         let pending_tx_log = Arc::new(WritePathPendingTransactionLog::new(
-            parent_path.join("fullnode_pending_transactions"),
+            parent_path.join(PENDING_TRANSACTIONS_DIR),
         ));
         let pending_tx_log_clone = pending_tx_log.clone();
         let _local_executor_handle = {
@@ -25,6 +25,24 @@ pub enum FaucetError {
     #[error("Timed out waiting for a coin from the gas coin pool")]
     NoGasCoinAvailable,
 
+    #[error("Coin type `{0}` is not dispensed by this faucet")]
+    UnsupportedCoinType(String),
+
+    #[error("Amount {0} is not within the allowed range [{1}, {2}]")]
+    InvalidAmount(u64, u64, u64),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Faucet request queue is full, please try again later")]
+    BatchQueueFull,
+
+    #[error("Unknown faucet request `{0}`")]
+    UnknownRequest(String),
+
+    #[error("Too many requests, retry after {0} seconds")]
+    TooManyRequests(u64),
+
     #[error("Wallet Error: `{0}`")]
     Wallet(String),
 
@@ -0,0 +1,125 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectID;
+use sui_types::parse_sui_struct_tag;
+
+use crate::FaucetError;
+
+/// A coin type other than SUI dispensed by the faucet, as listed in the YAML file passed with
+/// `--coin-types`:
+///
+/// ```yaml
+/// - coin-type: "0x6b1e::token::TOKEN"
+///   default-amount: 1000
+///   min-amount: 1
+///   max-amount: 100000
+///   treasury-cap: "0x4f2a..."
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct CoinTypeConfig {
+    pub coin_type: String,
+    /// Amount sent when the request does not pick one.
+    pub default_amount: u64,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    /// Mint the coins with this `TreasuryCap` owned by the faucet, instead of splitting them off
+    /// the coins of the type it owns.
+    #[serde(default)]
+    pub treasury_cap: Option<ObjectID>,
+}
+
+impl CoinTypeConfig {
+    pub fn load(path: &Path) -> Result<Vec<Self>, anyhow::Error> {
+        let reader = std::fs::File::open(path)?;
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    /// The amount to send for a request asking for `amount`, if it is within bounds.
+    pub fn amount(&self, amount: Option<u64>) -> Result<u64, FaucetError> {
+        let amount = amount.unwrap_or(self.default_amount);
+        if amount < self.min_amount || amount > self.max_amount {
+            return Err(FaucetError::InvalidAmount(
+                amount,
+                self.min_amount,
+                self.max_amount,
+            ));
+        }
+        Ok(amount)
+    }
+
+    pub(crate) fn struct_tag(&self) -> Result<StructTag, FaucetError> {
+        parse_coin_type(&self.coin_type)
+    }
+}
+
+pub(crate) fn parse_coin_type(coin_type: &str) -> Result<StructTag, FaucetError> {
+    parse_sui_struct_tag(coin_type)
+        .map_err(|e| FaucetError::InvalidRequest(format!("invalid coin type `{coin_type}`: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_coin_types() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("coin_types.yaml");
+        std::fs::write(
+            &path,
+            r#"
+- coin-type: "0x6b1e::token::TOKEN"
+  default-amount: 1000
+  min-amount: 1
+  max-amount: 100000
+  treasury-cap: "0x5"
+- coin-type: "0x6b1e::usdc::USDC"
+  default-amount: 10
+  min-amount: 10
+  max-amount: 10
+"#,
+        )
+        .unwrap();
+
+        let coin_types = CoinTypeConfig::load(&path).unwrap();
+        assert_eq!(coin_types.len(), 2);
+        assert_eq!(
+            coin_types[0].treasury_cap,
+            Some(ObjectID::from_single_byte(5))
+        );
+        assert_eq!(coin_types[1].treasury_cap, None);
+        assert_eq!(
+            coin_types[1].struct_tag().unwrap(),
+            parse_sui_struct_tag("0x6b1e::usdc::USDC").unwrap()
+        );
+    }
+
+    #[test]
+    fn amount_within_bounds() {
+        let coin_type = CoinTypeConfig {
+            coin_type: "0x6b1e::token::TOKEN".to_string(),
+            default_amount: 1000,
+            min_amount: 10,
+            max_amount: 100000,
+            treasury_cap: None,
+        };
+
+        assert_eq!(coin_type.amount(None), Ok(1000));
+        assert_eq!(coin_type.amount(Some(10)), Ok(10));
+        assert_eq!(coin_type.amount(Some(100000)), Ok(100000));
+        assert_eq!(
+            coin_type.amount(Some(9)),
+            Err(FaucetError::InvalidAmount(9, 10, 100000))
+        );
+        assert_eq!(
+            coin_type.amount(Some(100001)),
+            Err(FaucetError::InvalidAmount(100001, 10, 100000))
+        );
+    }
+}
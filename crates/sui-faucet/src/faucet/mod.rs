@@ -6,9 +6,13 @@ use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use uuid::Uuid;
 
+mod coin_types;
+mod request_queue;
 mod simple_faucet;
 mod write_ahead_log;
-pub use self::simple_faucet::SimpleFaucet;
+pub use self::coin_types::CoinTypeConfig;
+pub use self::request_queue::{BatchConfig, RequestQueue};
+pub use self::simple_faucet::{GasPoolConfig, SimpleFaucet};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -23,6 +27,34 @@ pub struct CoinInfo {
     pub transfer_tx_digest: TransactionDigest,
 }
 
+/// Progress of a request dispensed as part of a batch.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BatchSendStatus {
+    Queued,
+    InProgress,
+    Succeeded { receipt: FaucetReceipt },
+    Failed { error: String },
+}
+
+impl BatchSendStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Succeeded { .. } | Self::Failed { .. })
+    }
+}
+
+/// Remaining funds of the faucet, as of the last balance update.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPoolStatus {
+    /// Total SUI balance of the faucet, in MIST.
+    pub balance: u64,
+    /// Number of gas coins available to serve requests.
+    pub available_coins: u64,
+    /// Number of gas coins removed from the pool since the faucet started.
+    pub discarded_coins: u64,
+}
+
 #[async_trait]
 pub trait Faucet {
     /// Send `Coin<SUI>` of the specified amount to the recipient
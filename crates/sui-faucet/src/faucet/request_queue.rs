@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mysten_metrics::spawn_monitored_task;
+use sui_types::base_types::SuiAddress;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{Duration, Instant};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{BatchSendStatus, FaucetError, SimpleFaucet};
+
+/// How long the status of a dispensed request can be polled.
+const STATUS_RETENTION: Duration = Duration::from_secs(3600);
+
+#[derive(Clone, Copy, Debug)]
+pub struct BatchConfig {
+    /// Maximum number of requests dispensed by the same transaction.
+    pub max_batch_size: usize,
+    /// How long a batch waits for more requests after its first one.
+    pub batch_interval: Duration,
+    /// Maximum number of requests waiting to be batched, further requests are rejected.
+    pub queue_size: usize,
+}
+
+struct QueuedRequest {
+    id: Uuid,
+    recipient: SuiAddress,
+    amounts: Vec<u64>,
+}
+
+type Statuses = Arc<Mutex<HashMap<Uuid, (Instant, BatchSendStatus)>>>;
+
+/// Queue of faucet requests, flushed periodically into transactions each paying up to
+/// `max_batch_size` recipients. The status of each request is kept for polling.
+pub struct RequestQueue {
+    sender: mpsc::Sender<QueuedRequest>,
+    statuses: Statuses,
+}
+
+impl RequestQueue {
+    pub fn start(faucet: Arc<SimpleFaucet>, config: BatchConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_size);
+        let statuses = Statuses::default();
+        spawn_monitored_task!(Self::run(faucet, config, receiver, statuses.clone()));
+        Self { sender, statuses }
+    }
+
+    /// Queues the request `id` sending coins of `amounts` to `recipient`.
+    pub async fn enqueue(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: Vec<u64>,
+    ) -> Result<(), FaucetError> {
+        // The status is set before queueing the request, for it not to overwrite the status set
+        // once the request is dispensed.
+        self.statuses
+            .lock()
+            .await
+            .insert(id, (Instant::now(), BatchSendStatus::Queued));
+        let request = QueuedRequest {
+            id,
+            recipient,
+            amounts,
+        };
+        if self.sender.try_send(request).is_err() {
+            self.statuses.lock().await.remove(&id);
+            return Err(FaucetError::BatchQueueFull);
+        }
+        Ok(())
+    }
+
+    pub async fn status(&self, id: Uuid) -> Option<BatchSendStatus> {
+        self.statuses
+            .lock()
+            .await
+            .get(&id)
+            .map(|(_, status)| status.clone())
+    }
+
+    async fn run(
+        faucet: Arc<SimpleFaucet>,
+        config: BatchConfig,
+        mut receiver: mpsc::Receiver<QueuedRequest>,
+        statuses: Statuses,
+    ) {
+        while let Some(first) = receiver.recv().await {
+            let mut batch = vec![first];
+            let deadline = Instant::now() + config.batch_interval;
+            while batch.len() < config.max_batch_size {
+                match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                    Ok(Some(request)) => batch.push(request),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            {
+                let mut statuses = statuses.lock().await;
+                let now = Instant::now();
+                statuses.retain(|_, (updated, status)| {
+                    !status.is_finished() || now.duration_since(*updated) < STATUS_RETENTION
+                });
+                for request in &batch {
+                    statuses.insert(request.id, (now, BatchSendStatus::InProgress));
+                }
+            }
+
+            // Batches are dispensed concurrently, each with its own gas coin.
+            let faucet = faucet.clone();
+            let statuses = statuses.clone();
+            spawn_monitored_task!(Self::dispense(faucet, batch, statuses));
+        }
+    }
+
+    async fn dispense(faucet: Arc<SimpleFaucet>, batch: Vec<QueuedRequest>, statuses: Statuses) {
+        let batch_id = Uuid::new_v4();
+        let request_ids = batch.iter().map(|request| request.id).collect::<Vec<_>>();
+        info!(uuid = ?batch_id, ?request_ids, "Dispensing a batch of faucet requests");
+
+        let payments = batch
+            .iter()
+            .map(|request| (request.recipient, request.amounts.clone()))
+            .collect::<Vec<_>>();
+        // Sent from its own task, for a panic to fail the batch rather than leave its requests in
+        // progress forever.
+        let result =
+            spawn_monitored_task!(async move { faucet.batch_send(batch_id, &payments).await })
+                .await
+                .unwrap_or_else(|e| Err(FaucetError::internal(e)));
+
+        let mut statuses = statuses.lock().await;
+        let now = Instant::now();
+        match result {
+            Ok(receipts) => {
+                for (id, receipt) in request_ids.into_iter().zip(receipts) {
+                    statuses.insert(id, (now, BatchSendStatus::Succeeded { receipt }));
+                }
+            }
+            Err(e) => {
+                warn!(uuid = ?batch_id, "Failed to dispense batch: {:?}", e);
+                for id in request_ids {
+                    let error = e.to_string();
+                    statuses.insert(id, (now, BatchSendStatus::Failed { error }));
+                }
+            }
+        }
+    }
+}
@@ -7,11 +7,13 @@ use async_trait::async_trait;
 use prometheus::Registry;
 use tap::tap::TapFallible;
 
-#[cfg(test)]
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::path::Path;
 
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use shared_crypto::intent::Intent;
 use sui::client_commands::WalletContext;
 use sui_json_rpc_types::{
@@ -20,30 +22,60 @@ use sui_json_rpc_types::{
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
-    gas_coin::GasCoin,
-    messages::{ExecuteTransactionRequestType, Transaction, TransactionData, VerifiedTransaction},
+    base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest},
+    gas_coin::{GasCoin, GAS},
+    messages::{
+        Argument, Command, ExecuteTransactionRequestType, ObjectArg, Transaction, TransactionData,
+        VerifiedTransaction,
+    },
+    SUI_FRAMEWORK_OBJECT_ID,
 };
 use tokio::sync::{
-    mpsc::{self, Receiver, Sender},
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
     Mutex,
 };
 use tokio::time::{timeout, Duration};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::{CoinInfo, Faucet, FaucetError, FaucetReceipt};
+use crate::{CoinInfo, CoinTypeConfig, Faucet, FaucetError, FaucetReceipt, GasPoolStatus};
 
+use super::coin_types::parse_coin_type;
 use super::write_ahead_log::WriteAheadLog;
 
 pub struct SimpleFaucet {
     wallet: WalletContext,
     active_address: SuiAddress,
-    producer: Mutex<Sender<ObjectID>>,
-    consumer: Mutex<Receiver<ObjectID>>,
+    // The queue is unbounded for the gas pool to grow when it is replenished.
+    producer: Mutex<UnboundedSender<ObjectID>>,
+    consumer: Mutex<UnboundedReceiver<ObjectID>>,
     metrics: FaucetMetrics,
     wal: Mutex<WriteAheadLog>,
+    coin_types: HashMap<StructTag, CoinType>,
+    /// Coins removed from the pool for their insufficient balance, to be merged back in by
+    /// `rebalance_gas_pool`.
+    dust_coins: Mutex<HashSet<ObjectID>>,
+}
+
+/// Target of the replenishment of the gas pool by `SimpleFaucet::rebalance_gas_pool`.
+#[derive(Clone, Copy, Debug)]
+pub struct GasPoolConfig {
+    /// Number of coins the pool is replenished to.
+    pub target_size: usize,
+    /// Balance of the coins split to replenish the pool.
+    pub coin_balance: u64,
+    /// Coins with a balance below this are merged into the largest coin of the pool.
+    pub dust_balance: u64,
+}
+
+/// A coin type other than SUI dispensed by the faucet.
+struct CoinType {
+    config: CoinTypeConfig,
+    tag: StructTag,
+    /// The coins of the type or its treasury cap can only be used by one transaction at a time.
+    lock: Mutex<()>,
 }
 
 enum GasCoinResponse {
@@ -84,22 +116,21 @@ impl SimpleFaucet {
         let wal = WriteAheadLog::open(wal_path);
         let mut pending = vec![];
 
-        let (producer, consumer) = mpsc::channel(coins.len());
+        let (producer, consumer) = mpsc::unbounded_channel();
         for coin in &coins {
             let coin_id = *coin.id();
             if let Some(write_ahead_log::Entry {
                 uuid,
-                recipient,
+                recipients,
                 tx,
             }) = wal.reclaim(coin_id).map_err(FaucetError::internal)?
             {
                 let uuid = Uuid::from_bytes(uuid);
-                info!(?uuid, ?recipient, ?coin_id, "Retrying txn from WAL.");
-                pending.push((uuid, recipient, coin_id, tx));
+                info!(?uuid, ?recipients, ?coin_id, "Retrying txn from WAL.");
+                pending.push((uuid, recipients, coin_id, tx));
             } else {
                 producer
                     .send(coin_id)
-                    .await
                     .tap_ok(|_| {
                         info!(?coin_id, "Adding coin to gas pool");
                         metrics.total_available_coins.inc();
@@ -116,19 +147,49 @@ impl SimpleFaucet {
             consumer: Mutex::new(consumer),
             metrics,
             wal: Mutex::new(wal),
+            coin_types: HashMap::new(),
+            dust_coins: Mutex::new(HashSet::new()),
         };
 
         // Retrying all the pending transactions from the WAL, before continuing.  Ignore return
         // values -- if the executions failed, the pending coins will simply remain in the WAL, and
         // not recycled.
-        futures::future::join_all(pending.into_iter().map(|(uuid, recipient, coin_id, tx)| {
-            faucet.sign_and_execute_txn(uuid, recipient, coin_id, tx)
+        futures::future::join_all(pending.into_iter().map(|(uuid, recipients, coin_id, tx)| {
+            faucet.sign_and_execute_txn(uuid, recipients, coin_id, tx)
         }))
         .await;
 
         Ok(faucet)
     }
 
+    /// Also dispense the coin types of `coin_types`.
+    pub fn with_coin_types(mut self, coin_types: Vec<CoinTypeConfig>) -> Result<Self, FaucetError> {
+        for config in coin_types {
+            let tag = config.struct_tag()?;
+            info!(coin_type = %tag, "Dispensing coin type");
+            let coin_type = CoinType {
+                config,
+                tag: tag.clone(),
+                lock: Mutex::new(()),
+            };
+            self.coin_types.insert(tag, coin_type);
+        }
+        Ok(self)
+    }
+
+    /// The configuration of `coin_type`, or `None` if it is SUI. Fails if the faucet does not
+    /// dispense it.
+    pub fn coin_type(&self, coin_type: &str) -> Result<Option<&CoinTypeConfig>, FaucetError> {
+        let tag = parse_coin_type(coin_type)?;
+        if tag == GAS::type_() {
+            return Ok(None);
+        }
+        self.coin_types
+            .get(&tag)
+            .map(|coin_type| Some(&coin_type.config))
+            .ok_or_else(|| FaucetError::UnsupportedCoinType(coin_type.to_string()))
+    }
+
     /// Take the consumer lock and pull a Coin ID from the queue, without checking whether it is
     /// valid or not.
     async fn pop_gas_coin(&self, uuid: Uuid) -> Option<ObjectID> {
@@ -229,7 +290,7 @@ impl SimpleFaucet {
     async fn sign_and_execute_txn(
         &self,
         uuid: Uuid,
-        recipient: SuiAddress,
+        recipients: Vec<SuiAddress>,
         coin_id: ObjectID,
         tx_data: TransactionData,
     ) -> Result<SuiTransactionResponse, FaucetError> {
@@ -245,7 +306,7 @@ impl SimpleFaucet {
         let tx_digest = *tx.digest();
         info!(
             ?tx_digest,
-            ?recipient,
+            ?recipients,
             ?coin_id,
             ?uuid,
             "PaySui transaction in faucet."
@@ -253,13 +314,13 @@ impl SimpleFaucet {
 
         match timeout(
             Duration::from_secs(300),
-            self.execute_pay_sui_txn_with_retries(&tx, coin_id, recipient, uuid),
+            self.execute_pay_sui_txn_with_retries(&tx, coin_id, &recipients, uuid),
         )
         .await
         {
             Err(elapsed) => {
                 warn!(
-                    ?recipient,
+                    ?recipients,
                     ?coin_id,
                     ?uuid,
                     "Failed to execute PaySui transactions in faucet after {elapsed}. Coin will \
@@ -291,39 +352,58 @@ impl SimpleFaucet {
         }
     }
 
+    /// Splits the coins of all `payments` off a single gas coin and transfers them in one
+    /// transaction, returning the IDs of the coins sent by each payment, in order.
     async fn transfer_gases(
         &self,
-        amounts: &[u64],
-        recipient: SuiAddress,
+        payments: &[(SuiAddress, Vec<u64>)],
         uuid: Uuid,
-    ) -> Result<(TransactionDigest, Vec<ObjectID>), FaucetError> {
-        let number_of_coins = amounts.len();
-        let total_amount: u64 = amounts.iter().sum();
-        let gas_cost = self.get_gas_cost().await?;
+    ) -> Result<(TransactionDigest, Vec<Vec<ObjectID>>), FaucetError> {
+        if payments.is_empty() {
+            return Err(FaucetError::internal("no payment to transfer"));
+        }
+        let recipients = payments.iter().map(|(recipient, _)| *recipient).collect();
+        let total_amount: u64 = payments.iter().flat_map(|(_, amounts)| amounts).sum();
+        // Budget for the splits and transfers of each payment.
+        let gas_cost = self.get_gas_cost().await? * payments.len() as u64;
+
+        let coin_id = self.valid_gas_coin(total_amount + gas_cost, uuid).await?;
+        let tx_data = self
+            .build_pay_sui_txn(coin_id, self.active_address, payments, gas_cost)
+            .await
+            .map_err(FaucetError::internal)?;
 
-        let gas_coin_response = self.prepare_gas_coin(total_amount + gas_cost, uuid).await;
-        match gas_coin_response {
-            GasCoinResponse::ValidGasCoin(coin_id) => {
-                let tx_data = self
-                    .build_pay_sui_txn(coin_id, self.active_address, recipient, amounts, gas_cost)
-                    .await
-                    .map_err(FaucetError::internal)?;
+        let response = self
+            .reserve_sign_and_execute_txn(uuid, recipients, coin_id, tx_data)
+            .await?;
 
-                {
-                    // Register the intention to send this transaction before we send it, so that if
-                    // faucet fails or we give up before we get a definite response, we have a
-                    // chance to retry later.
-                    let mut wal = self.wal.lock().await;
-                    wal.reserve(uuid, coin_id, recipient, tx_data.clone())
-                        .map_err(FaucetError::internal)?;
-                }
-                let response = self
-                    .sign_and_execute_txn(uuid, recipient, coin_id, tx_data)
-                    .await?;
+        self.check_and_map_transfer_gas_result(response, payments)
+            .await
+    }
 
-                self.check_and_map_transfer_gas_result(response, number_of_coins, recipient)
-                    .await
-            }
+    /// Register the intention to send this transaction before we send it, so that if faucet fails
+    /// or we give up before we get a definite response, we have a chance to retry later.
+    async fn reserve_sign_and_execute_txn(
+        &self,
+        uuid: Uuid,
+        recipients: Vec<SuiAddress>,
+        coin_id: ObjectID,
+        tx_data: TransactionData,
+    ) -> Result<SuiTransactionResponse, FaucetError> {
+        {
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, coin_id, recipients.clone(), tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+        self.sign_and_execute_txn(uuid, recipients, coin_id, tx_data)
+            .await
+    }
+
+    /// Pulls a gas coin with a balance of at least `total_amount` from the queue.
+    async fn valid_gas_coin(&self, total_amount: u64, uuid: Uuid) -> Result<ObjectID, FaucetError> {
+        let gas_coin_response = self.prepare_gas_coin(total_amount, uuid).await;
+        match gas_coin_response {
+            GasCoinResponse::ValidGasCoin(coin_id) => Ok(coin_id),
 
             GasCoinResponse::UnknownGasCoin(coin_id) => {
                 self.recycle_gas_coin(coin_id, uuid).await;
@@ -335,6 +415,7 @@ impl SimpleFaucet {
             GasCoinResponse::GasCoinWithInsufficientBalance(coin_id) => {
                 warn!(?uuid, ?coin_id, "Insufficient balance, removing from pool");
                 self.metrics.total_discarded_coins.inc();
+                self.dust_coins.lock().await.insert(coin_id);
                 Err(FaucetError::GasCoinWithInsufficientBalance(
                     coin_id.to_hex_uncompressed(),
                 ))
@@ -353,13 +434,12 @@ impl SimpleFaucet {
 
     async fn recycle_gas_coin(&self, coin_id: ObjectID, uuid: Uuid) {
         // Once transactions are done, in despite of success or failure,
-        // we put back the coins. The producer should never wait indefinitely,
-        // in that the channel is initialized with big enough capacity.
+        // we put back the coins. The producer never waits, in that the channel is unbounded.
         let producer = self.producer.lock().await;
         info!(?uuid, ?coin_id, "Got producer lock and recycling coin");
         producer
-            .try_send(coin_id)
-            .expect("unexpected - queue is large enough to hold all coins");
+            .send(coin_id)
+            .expect("unexpected - the consumer is never dropped");
         self.metrics.total_available_coins.inc();
         info!(?uuid, ?coin_id, "Recycled coin");
     }
@@ -368,20 +448,22 @@ impl SimpleFaucet {
         &self,
         tx: &VerifiedTransaction,
         coin_id: ObjectID,
-        recipient: SuiAddress,
+        recipients: &[SuiAddress],
         uuid: Uuid,
     ) -> SuiTransactionResponse {
         let mut retry_delay = Duration::from_millis(500);
 
         loop {
-            let res = self.execute_pay_sui_txn(tx, coin_id, recipient, uuid).await;
+            let res = self
+                .execute_pay_sui_txn(tx, coin_id, recipients, uuid)
+                .await;
 
             if let Ok(res) = res {
                 return res;
             }
 
             info!(
-                ?recipient,
+                ?recipients,
                 ?coin_id,
                 ?uuid,
                 ?retry_delay,
@@ -398,7 +480,7 @@ impl SimpleFaucet {
         &self,
         tx: &VerifiedTransaction,
         coin_id: ObjectID,
-        recipient: SuiAddress,
+        recipients: &[SuiAddress],
         uuid: Uuid,
     ) -> Result<SuiTransactionResponse, anyhow::Error> {
         self.metrics.current_executions_in_flight.inc();
@@ -419,7 +501,7 @@ impl SimpleFaucet {
             .tap_err(|e| {
                 error!(
                     ?tx_digest,
-                    ?recipient,
+                    ?recipients,
                     ?coin_id,
                     ?uuid,
                     "Transfer Transaction failed: {:?}",
@@ -450,16 +532,17 @@ impl SimpleFaucet {
         &self,
         coin_id: ObjectID,
         signer: SuiAddress,
-        recipient: SuiAddress,
-        amounts: &[u64],
+        payments: &[(SuiAddress, Vec<u64>)],
         budget: u64,
     ) -> Result<TransactionData, anyhow::Error> {
-        let recipients: Vec<SuiAddress> =
-            std::iter::repeat(recipient).take(amounts.len()).collect();
+        let (recipients, amounts): (Vec<SuiAddress>, Vec<u64>) = payments
+            .iter()
+            .flat_map(|(recipient, amounts)| amounts.iter().map(|amount| (*recipient, *amount)))
+            .unzip();
         let client = self.wallet.get_client().await?;
         client
             .transaction_builder()
-            .pay_sui(signer, vec![coin_id], recipients, amounts.to_vec(), budget)
+            .pay_sui(signer, vec![coin_id], recipients, amounts, budget)
             .await
             .map_err(|e| {
                 anyhow::anyhow!(
@@ -473,9 +556,9 @@ impl SimpleFaucet {
     async fn check_and_map_transfer_gas_result(
         &self,
         res: SuiTransactionResponse,
-        number_of_coins: usize,
-        recipient: SuiAddress,
-    ) -> Result<(TransactionDigest, Vec<ObjectID>), FaucetError> {
+        payments: &[(SuiAddress, Vec<u64>)],
+    ) -> Result<(TransactionDigest, Vec<Vec<ObjectID>>), FaucetError> {
+        let number_of_coins: usize = payments.iter().map(|(_, amounts)| amounts.len()).sum();
         let created = res
             .effects
             .ok_or_else(|| {
@@ -487,19 +570,513 @@ impl SimpleFaucet {
             .created()
             .to_vec();
         if created.len() != number_of_coins {
-            panic!(
-                "PaySui Transaction should create exact {:?} new coins, but got {:?}",
-                number_of_coins, created
-            );
+            return Err(FaucetError::ParseTransactionResponseError(format!(
+                "PaySui Transaction {} should create exact {} new coins, but got {:?}",
+                res.digest, number_of_coins, created
+            )));
         }
-        assert!(created
+        let mut coins_by_recipient: HashMap<SuiAddress, VecDeque<ObjectID>> = HashMap::new();
+        for created_coin_owner_ref in created {
+            let Owner::AddressOwner(owner) = created_coin_owner_ref.owner else {
+                return Err(FaucetError::ParseTransactionResponseError(format!(
+                    "PaySui Transaction {} should only create address owned coins, but got {:?}",
+                    res.digest, created_coin_owner_ref
+                )));
+            };
+            coins_by_recipient
+                .entry(owner)
+                .or_default()
+                .push_back(created_coin_owner_ref.reference.object_id);
+        }
+        // Payments to the same recipient in a batch cannot be told apart, they are given its coins
+        // in order.
+        let coin_ids = payments
             .iter()
-            .all(|created_coin_owner_ref| created_coin_owner_ref.owner == recipient));
-        let coin_ids: Vec<ObjectID> = created
+            .map(|(recipient, amounts)| {
+                let coins = coins_by_recipient.entry(*recipient).or_default();
+                if coins.len() < amounts.len() {
+                    return Err(FaucetError::ParseTransactionResponseError(format!(
+                        "PaySui Transaction {} should create {} coins owned by {}",
+                        res.digest,
+                        amounts.len(),
+                        recipient
+                    )));
+                }
+                Ok(coins.drain(..amounts.len()).collect())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((res.digest, coin_ids))
+    }
+
+    async fn receipt(
+        &self,
+        uuid: Uuid,
+        recipient: SuiAddress,
+        digest: TransactionDigest,
+        coin_ids: Vec<ObjectID>,
+    ) -> FaucetReceipt {
+        let mut sent = Vec::with_capacity(coin_ids.len());
+        let coin_results =
+            futures::future::join_all(coin_ids.iter().map(|coin_id| self.get_coin(*coin_id))).await;
+        for (coin_id, res) in coin_ids.into_iter().zip(coin_results) {
+            let amount = if let Ok(Some((_, coin))) = res {
+                coin.value()
+            } else {
+                info!(
+                    ?recipient,
+                    ?coin_id,
+                    ?uuid,
+                    "Could not find coin after successful transaction, error: {:?}",
+                    &res,
+                );
+                0
+            };
+            sent.push(CoinInfo {
+                transfer_tx_digest: digest,
+                amount,
+                id: coin_id,
+            });
+        }
+        FaucetReceipt { sent }
+    }
+
+    /// Sends the coins of all `requests` in a single programmable transaction, splitting them off
+    /// the same gas coin, and returns the receipt of each request in order.
+    pub async fn batch_send(
+        &self,
+        id: Uuid,
+        requests: &[(SuiAddress, Vec<u64>)],
+    ) -> Result<Vec<FaucetReceipt>, FaucetError> {
+        info!(uuid = ?id, "Sending a batch of {} faucet requests", requests.len());
+
+        let (digest, coin_ids) = self.transfer_gases(requests, id).await?;
+
+        info!(uuid = ?id, ?digest, "Batched PaySui txn succeeded");
+        Ok(futures::future::join_all(
+            requests
+                .iter()
+                .zip(coin_ids)
+                .map(|((recipient, _), coin_ids)| self.receipt(id, *recipient, digest, coin_ids)),
+        )
+        .await)
+    }
+
+    /// Sends a coin of `amount` of `coin_type`, a coin type dispensed by the faucet other than SUI.
+    /// It is minted with the treasury cap of the type if the faucet has one, or split off the coins
+    /// of the type the faucet owns, and the gas is paid with a coin of the gas pool.
+    pub async fn send_coin(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        coin_type: &str,
+        amount: u64,
+    ) -> Result<FaucetReceipt, FaucetError> {
+        let tag = parse_coin_type(coin_type)?;
+        let coin_type = self
+            .coin_types
+            .get(&tag)
+            .ok_or_else(|| FaucetError::UnsupportedCoinType(coin_type.to_string()))?;
+        info!(?recipient, uuid = ?id, coin_type = %coin_type.tag, amount, "Sending coin");
+
+        let _guard = coin_type.lock.lock().await;
+        let gas_price = self.get_gas_price().await?;
+        let budget = gas_price * DEFAULT_GAS_COMPUTATION_BUCKET;
+        let coin_id = self.valid_gas_coin(budget, id).await?;
+        let tx_data = match self
+            .build_coin_txn(coin_id, recipient, coin_type, amount, budget, gas_price)
+            .await
+        {
+            Ok(tx_data) => tx_data,
+            Err(e) => {
+                self.recycle_gas_coin(coin_id, id).await;
+                return Err(e);
+            }
+        };
+
+        let response = self
+            .reserve_sign_and_execute_txn(id, vec![recipient], coin_id, tx_data)
+            .await?;
+        let effects = response.effects.ok_or_else(|| {
+            FaucetError::ParseTransactionResponseError(format!(
+                "effects field missing for txn {}",
+                response.digest
+            ))
+        })?;
+        let sent = effects
+            .created()
             .iter()
-            .map(|created_coin_owner_ref| created_coin_owner_ref.reference.object_id)
+            .filter(|created| created.owner == Owner::AddressOwner(recipient))
+            .map(|created| CoinInfo {
+                amount,
+                id: created.reference.object_id,
+                transfer_tx_digest: response.digest,
+            })
+            .collect::<Vec<_>>();
+        if sent.is_empty() {
+            return Err(FaucetError::Transfer(format!(
+                "transaction {} did not send a coin to {}: {:?}",
+                response.digest,
+                recipient,
+                effects.status()
+            )));
+        }
+        info!(uuid = ?id, ?recipient, digest = ?response.digest, "Coin transfer succeeded");
+        Ok(FaucetReceipt { sent })
+    }
+
+    async fn build_coin_txn(
+        &self,
+        gas_coin_id: ObjectID,
+        recipient: SuiAddress,
+        coin_type: &CoinType,
+        amount: u64,
+        budget: u64,
+        gas_price: u64,
+    ) -> Result<TransactionData, FaucetError> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        if let Some(treasury_cap) = coin_type.config.treasury_cap {
+            let treasury_cap = self
+                .get_object_ref(treasury_cap)
+                .await
+                .map_err(FaucetError::internal)?;
+            let arguments = vec![
+                builder
+                    .obj(ObjectArg::ImmOrOwnedObject(treasury_cap))
+                    .map_err(FaucetError::internal)?,
+                builder.pure(amount).map_err(FaucetError::internal)?,
+                builder.pure(recipient).map_err(FaucetError::internal)?,
+            ];
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_OBJECT_ID,
+                Identifier::new("coin").map_err(FaucetError::internal)?,
+                Identifier::new("mint_and_transfer").map_err(FaucetError::internal)?,
+                vec![TypeTag::Struct(Box::new(coin_type.tag.clone()))],
+                arguments,
+            );
+        } else {
+            let client = self
+                .wallet
+                .get_client()
+                .await
+                .map_err(|e| FaucetError::Wallet(format!("Unable to get client: {e:?}")))?;
+            let mut coins = client
+                .coin_read_api()
+                .get_coins(
+                    self.active_address,
+                    Some(coin_type.config.coin_type.clone()),
+                    None,
+                    None,
+                )
+                .await
+                .map_err(|e| FaucetError::FullnodeReadingError(format!("{e:?}")))?
+                .data;
+            // Merge the fewest coins covering the amount, the largest first.
+            coins.sort_by_key(|coin| std::cmp::Reverse(coin.balance));
+            let mut total = 0;
+            let coins = coins
+                .into_iter()
+                .take_while(|coin| {
+                    let needed = total < amount;
+                    total += coin.balance;
+                    needed
+                })
+                .map(|coin| coin.object_ref())
+                .collect::<Vec<_>>();
+            if total < amount {
+                return Err(FaucetError::InsuffientBalance);
+            }
+            builder
+                .pay(coins, vec![recipient], vec![amount])
+                .map_err(FaucetError::internal)?;
+        }
+
+        let gas = self
+            .get_object_ref(gas_coin_id)
+            .await
+            .map_err(FaucetError::internal)?;
+        Ok(TransactionData::new_programmable(
+            self.active_address,
+            vec![gas],
+            builder.finish(),
+            budget,
+            gas_price,
+        ))
+    }
+
+    async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
+        let client = self.wallet.get_client().await?;
+        match client
+            .read_api()
+            .get_object_with_options(object_id, SuiObjectDataOptions::new())
+            .await?
+        {
+            SuiObjectResponse::Exists(object) => Ok(object.object_ref()),
+            _ => Err(anyhow::anyhow!("Object {object_id} does not exist")),
+        }
+    }
+
+    /// Merges the dust coins of the gas pool into its largest coin, and splits coins of
+    /// `config.coin_balance` off it to bring the pool to `config.target_size` coins. Only the coins
+    /// merged or split are taken out of the pool, the other coins keep serving requests meanwhile,
+    /// and the coins in use by requests are left alone.
+    pub async fn rebalance_gas_pool(&self, config: &GasPoolConfig) -> Result<(), FaucetError> {
+        let uuid = Uuid::new_v4();
+        let balances: HashMap<ObjectID, u64> = self
+            .wallet
+            .gas_objects(self.active_address)
+            .await
+            .map_err(|e| FaucetError::FullnodeReadingError(format!("{e:?}")))?
+            .into_iter()
+            .map(|(balance, object)| (object.object_id, balance))
             .collect();
-        Ok((res.digest, coin_ids))
+
+        // Coins set aside for their low balance are merged if they are dust, and put back in the
+        // pool otherwise.
+        let set_aside = std::mem::take(&mut *self.dust_coins.lock().await);
+        let mut coins = vec![];
+        for coin_id in set_aside {
+            match balances.get(&coin_id) {
+                Some(balance) if *balance < config.dust_balance => coins.push((coin_id, *balance)),
+                Some(_) => self.recycle_gas_coin(coin_id, uuid).await,
+                None => {
+                    warn!(?uuid, ?coin_id, "Invalid, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+            }
+        }
+
+        // The largest coin of the pool pays for the gas and funds the new coins.
+        let taken = self
+            .take_available_coins(uuid, |available| {
+                let largest = available
+                    .iter()
+                    .filter(|coin_id| balances.contains_key(*coin_id))
+                    .max_by_key(|coin_id| balances[*coin_id]);
+                available
+                    .iter()
+                    .filter(|coin_id| {
+                        Some(*coin_id) == largest
+                            || balances
+                                .get(*coin_id)
+                                .map_or(false, |balance| *balance < config.dust_balance)
+                    })
+                    .copied()
+                    .collect()
+            })
+            .await;
+        coins.extend(
+            taken
+                .into_iter()
+                .map(|coin_id| (coin_id, balances[&coin_id])),
+        );
+
+        coins.sort_by_key(|(_, balance)| Reverse(*balance));
+        let Some(&(source, source_balance)) = coins.first() else {
+            return self.update_balance().await;
+        };
+        let merged = coins[1..].to_vec();
+        let merged_ids = merged
+            .iter()
+            .map(|(coin_id, _)| *coin_id)
+            .collect::<Vec<_>>();
+
+        let available = self.metrics.total_available_coins.get() as usize;
+        let deficit = config.target_size.saturating_sub(available + 1);
+        let tx_data = match self
+            .build_rebalance_txn(source, source_balance, &merged, deficit, config)
+            .await
+        {
+            Ok(Some(tx_data)) => tx_data,
+            Ok(None) => {
+                self.recycle_gas_coin(source, uuid).await;
+                return self.update_balance().await;
+            }
+            Err(e) => {
+                self.recycle_gas_coin(source, uuid).await;
+                self.recycle_gas_coins(&merged_ids, uuid).await;
+                return Err(e);
+            }
+        };
+
+        // The source coin is recycled once the transaction is executed. The merged coins are put
+        // back in the pool if the transaction did not go through, as they are then still owned by
+        // the faucet.
+        let created = match self.execute_rebalance_txn(uuid, source, tx_data).await {
+            Ok(created) => created,
+            Err(e) => {
+                self.recycle_gas_coins(&merged_ids, uuid).await;
+                return Err(e);
+            }
+        };
+        info!(
+            ?uuid,
+            merged = merged_ids.len(),
+            split = created.len(),
+            "Rebalanced gas pool"
+        );
+        self.recycle_gas_coins(&created, uuid).await;
+        self.update_balance().await
+    }
+
+    /// Executes the rebalancing transaction paid with the `source` coin, and returns the coins it
+    /// created.
+    async fn execute_rebalance_txn(
+        &self,
+        uuid: Uuid,
+        source: ObjectID,
+        tx_data: TransactionData,
+    ) -> Result<Vec<ObjectID>, FaucetError> {
+        let response = self
+            .reserve_sign_and_execute_txn(uuid, vec![self.active_address], source, tx_data)
+            .await?;
+        let effects = response.effects.ok_or_else(|| {
+            FaucetError::ParseTransactionResponseError(format!(
+                "effects field missing for txn {}",
+                response.digest
+            ))
+        })?;
+        if !effects.status().is_ok() {
+            return Err(FaucetError::Transfer(format!(
+                "gas pool rebalancing txn {} failed: {:?}",
+                response.digest,
+                effects.status()
+            )));
+        }
+        Ok(effects
+            .created()
+            .iter()
+            .filter(|created| created.owner == Owner::AddressOwner(self.active_address))
+            .map(|created| created.reference.object_id)
+            .collect())
+    }
+
+    async fn recycle_gas_coins(&self, coin_ids: &[ObjectID], uuid: Uuid) {
+        for coin_id in coin_ids {
+            self.recycle_gas_coin(*coin_id, uuid).await;
+        }
+    }
+
+    /// Builds the transaction merging the `merged` coins into the `source` coin and splitting up
+    /// to `deficit` coins off it, or returns `None` if there is nothing to do.
+    async fn build_rebalance_txn(
+        &self,
+        source: ObjectID,
+        source_balance: u64,
+        merged: &[(ObjectID, u64)],
+        deficit: usize,
+        config: &GasPoolConfig,
+    ) -> Result<Option<TransactionData>, FaucetError> {
+        let gas_price = self.get_gas_price().await?;
+        // The budget of a request for every 5 coins merged or split.
+        let budget =
+            gas_price * DEFAULT_GAS_COMPUTATION_BUCKET * (1 + (merged.len() + deficit) as u64 / 5);
+        let funds = merged
+            .iter()
+            .map(|(_, balance)| *balance)
+            .sum::<u64>()
+            .saturating_add(source_balance)
+            .saturating_sub(budget);
+        // The source coin keeps enough balance to stay in the pool.
+        let coin_balance = config.coin_balance.max(1);
+        let splits = deficit.min((funds / coin_balance).saturating_sub(1) as usize);
+        if merged.is_empty() && splits == 0 {
+            return Ok(None);
+        }
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        if !merged.is_empty() {
+            let mut arguments = vec![];
+            for (coin_id, _) in merged {
+                let coin = self
+                    .get_object_ref(*coin_id)
+                    .await
+                    .map_err(FaucetError::internal)?;
+                arguments.push(
+                    builder
+                        .obj(ObjectArg::ImmOrOwnedObject(coin))
+                        .map_err(FaucetError::internal)?,
+                );
+            }
+            builder.command(Command::MergeCoins(Argument::GasCoin, arguments));
+        }
+        if splits > 0 {
+            builder
+                .pay_sui(
+                    vec![self.active_address; splits],
+                    vec![coin_balance; splits],
+                )
+                .map_err(FaucetError::internal)?;
+        }
+
+        let gas = self
+            .get_object_ref(source)
+            .await
+            .map_err(FaucetError::internal)?;
+        Ok(Some(TransactionData::new_programmable(
+            self.active_address,
+            vec![gas],
+            builder.finish(),
+            budget,
+            gas_price,
+        )))
+    }
+
+    /// Takes the coins of the gas pool chosen by `select` among the available ones, and puts the
+    /// others back. The pool is only locked for as long as it takes to go through its coins.
+    async fn take_available_coins(
+        &self,
+        uuid: Uuid,
+        select: impl FnOnce(&[ObjectID]) -> HashSet<ObjectID>,
+    ) -> Vec<ObjectID> {
+        let Ok(mut consumer) = tokio::time::timeout(LOCK_TIMEOUT, self.consumer.lock()).await else {
+            error!(?uuid, "Timeout when getting consumer lock");
+            return vec![];
+        };
+        let mut available = vec![];
+        while let Ok(coin_id) = consumer.try_recv() {
+            available.push(coin_id);
+        }
+        let selected = select(&available);
+
+        let producer = self.producer.lock().await;
+        let mut taken = vec![];
+        for coin_id in available {
+            if selected.contains(&coin_id) {
+                self.metrics.total_available_coins.dec();
+                taken.push(coin_id);
+            } else {
+                producer
+                    .send(coin_id)
+                    .expect("unexpected - the consumer is never dropped");
+            }
+        }
+        taken
+    }
+
+    /// Refreshes the SUI balance of the faucet reported by `gas_pool_status`.
+    pub async fn update_balance(&self) -> Result<(), FaucetError> {
+        let client = self
+            .wallet
+            .get_client()
+            .await
+            .map_err(|e| FaucetError::Wallet(format!("Unable to get client: {e:?}")))?;
+        let balance = client
+            .coin_read_api()
+            .get_balance(self.active_address, None)
+            .await
+            .map_err(|e| FaucetError::FullnodeReadingError(format!("{e:?}")))?;
+        self.metrics
+            .total_balance
+            .set(i64::try_from(balance.total_balance).unwrap_or(i64::MAX));
+        Ok(())
+    }
+
+    pub fn gas_pool_status(&self) -> GasPoolStatus {
+        GasPoolStatus {
+            balance: self.metrics.total_balance.get() as u64,
+            available_coins: self.metrics.total_available_coins.get() as u64,
+            discarded_coins: self.metrics.total_discarded_coins.get() as u64,
+        }
     }
 
     #[cfg(test)]
@@ -538,32 +1115,14 @@ impl Faucet for SimpleFaucet {
     ) -> Result<FaucetReceipt, FaucetError> {
         info!(?recipient, uuid = ?id, "Getting faucet requests");
 
-        let (digest, coin_ids) = self.transfer_gases(amounts, recipient, id).await?;
+        let (digest, mut coin_ids) = self
+            .transfer_gases(&[(recipient, amounts.to_vec())], id)
+            .await?;
 
         info!(uuid = ?id, ?recipient, ?digest, "PaySui txn succeeded");
-        let mut sent = Vec::with_capacity(coin_ids.len());
-        let coin_results =
-            futures::future::join_all(coin_ids.iter().map(|coin_id| self.get_coin(*coin_id))).await;
-        for (coin_id, res) in coin_ids.into_iter().zip(coin_results) {
-            let amount = if let Ok(Some((_, coin))) = res {
-                coin.value()
-            } else {
-                info!(
-                    ?recipient,
-                    ?coin_id,
-                    uuid = ?id,
-                    "Could not find coin after successful transaction, error: {:?}",
-                    &res,
-                );
-                0
-            };
-            sent.push(CoinInfo {
-                transfer_tx_digest: digest,
-                amount,
-                id: coin_id,
-            });
-        }
-        Ok(FaucetReceipt { sent })
+        Ok(self
+            .receipt(id, recipient, digest, coin_ids.remove(0))
+            .await)
     }
 }
 
@@ -599,6 +1158,133 @@ mod tests {
         assert_eq!(discarded, faucet.metrics.total_discarded_coins.get());
     }
 
+    #[tokio::test]
+    async fn test_batch_send() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let faucet = SimpleFaucet::new(
+            test_cluster.wallet,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+        )
+        .await
+        .unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let requests = vec![
+            (recipient, vec![1, 2]),
+            (SuiAddress::random_for_testing_only(), vec![3]),
+            // A second request to the same recipient in the batch.
+            (recipient, vec![4]),
+        ];
+        let receipts = faucet.batch_send(Uuid::new_v4(), &requests).await.unwrap();
+
+        assert_eq!(receipts.len(), requests.len());
+        let digest = receipts[0].sent[0].transfer_tx_digest;
+        for (receipt, (_, amounts)) in receipts.iter().zip(&requests) {
+            assert_eq!(receipt.sent.len(), amounts.len());
+            assert!(receipt.sent.iter().all(|c| c.transfer_tx_digest == digest));
+        }
+        let mut actual_amounts: Vec<u64> = receipts
+            .iter()
+            .flat_map(|receipt| receipt.sent.iter().map(|c| c.amount))
+            .collect();
+        actual_amounts.sort_unstable();
+        assert_eq!(actual_amounts, vec![1, 2, 3, 4]);
+        assert_eq!(receipts[1].sent[0].amount, 3);
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_gas_pool() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let faucet = SimpleFaucet::new(
+            test_cluster.wallet,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+        )
+        .await
+        .unwrap();
+
+        let available = faucet.metrics.total_available_coins.get() as usize;
+        let config = GasPoolConfig {
+            target_size: available + 3,
+            coin_balance: 1_000_000_000,
+            dust_balance: 0,
+        };
+        faucet.rebalance_gas_pool(&config).await.unwrap();
+
+        let status = faucet.gas_pool_status();
+        assert_eq!(status.available_coins as usize, available + 3);
+        assert!(status.balance > 0);
+
+        // The pool is already at its target size.
+        faucet.rebalance_gas_pool(&config).await.unwrap();
+        assert_eq!(
+            faucet.metrics.total_available_coins.get() as usize,
+            available + 3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_gas_pool_merges_dust() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let gases = get_current_gases(address, &mut context).await;
+
+        // Split two dust coins off the largest coin, which stays the largest.
+        let source = gases.iter().max_by_key(|gas| gas.value()).unwrap();
+        let res = SuiClientCommands::SplitCoin {
+            coin_id: *source.id(),
+            amounts: Some(vec![1000, 1000]),
+            gas_budget: 50000,
+            gas: None,
+            count: None,
+        }
+        .execute(&mut context)
+        .await
+        .unwrap();
+        let SuiClientCommandResult::SplitCoin(resp) = res else {
+            panic!("split command did not return SuiClientCommandResult::SplitCoin");
+        };
+        let dust: HashSet<ObjectID> = resp
+            .effects
+            .unwrap()
+            .created()
+            .iter()
+            .map(|created| created.reference.object_id)
+            .collect();
+        assert_eq!(dust.len(), 2);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let mut faucet = SimpleFaucet::new(context, &prom_registry, &tmp.path().join("faucet.wal"))
+            .await
+            .unwrap();
+        let available = faucet.metrics.total_available_coins.get() as usize;
+
+        let config = GasPoolConfig {
+            target_size: 0,
+            coin_balance: 1_000_000_000,
+            dust_balance: 1_000_000,
+        };
+        faucet.rebalance_gas_pool(&config).await.unwrap();
+
+        // The dust coins are merged, every other coin is back in the pool.
+        let candidates = faucet.drain_gas_queue(available - 2).await;
+        assert!(candidates.is_disjoint(&dust));
+        let owned = get_current_gases(address, faucet.wallet_mut()).await;
+        assert!(owned.iter().all(|gas| !dust.contains(gas.id())));
+    }
+
     #[tokio::test]
     async fn test_init_gas_queue() {
         let test_cluster = TestClusterBuilder::new().build().await.unwrap();
@@ -22,16 +22,36 @@ use uuid::Uuid;
 /// it needs to confirm succeeded or failed.
 #[derive(DBMapUtils)]
 pub struct WriteAheadLog {
-    log: DBMap<ObjectID, Entry>,
+    /// Entries written by faucets sending each transaction to a single recipient, only read and
+    /// removed, for the transactions in flight when the faucet was upgraded to be recovered.
+    log: DBMap<ObjectID, LegacyEntry>,
+    entries: DBMap<ObjectID, Entry>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Entry {
     pub uuid: uuid::Bytes,
-    pub recipient: SuiAddress,
+    pub recipients: Vec<SuiAddress>,
     pub tx: TransactionData,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct LegacyEntry {
+    uuid: uuid::Bytes,
+    recipient: SuiAddress,
+    tx: TransactionData,
+}
+
+impl From<LegacyEntry> for Entry {
+    fn from(entry: LegacyEntry) -> Self {
+        Self {
+            uuid: entry.uuid,
+            recipients: vec![entry.recipient],
+            tx: entry.tx,
+        }
+    }
+}
+
 impl WriteAheadLog {
     pub(crate) fn open(path: &Path) -> Self {
         Self::open_tables_read_write(
@@ -42,16 +62,16 @@ impl WriteAheadLog {
         )
     }
 
-    /// Mark `coin` as reserved for transaction `tx` sending coin to `recipient`. Fails if `coin` is
-    /// already in the WAL pointing to an existing transaction.
+    /// Mark `coin` as reserved for transaction `tx` sending coins to `recipients`. Fails if `coin`
+    /// is already in the WAL pointing to an existing transaction.
     pub(crate) fn reserve(
         &mut self,
         uuid: Uuid,
         coin: ObjectID,
-        recipient: SuiAddress,
+        recipients: Vec<SuiAddress>,
         tx: TransactionData,
     ) -> Result<(), TypedStoreError> {
-        if self.log.contains_key(&coin)? {
+        if self.entries.contains_key(&coin)? || self.log.contains_key(&coin)? {
             // Don't permit multiple writes against the same coin
             // TODO: Use a better error type than `TypedStoreError`.
             return Err(TypedStoreError::SerializationError(format!(
@@ -60,11 +80,11 @@ impl WriteAheadLog {
         }
 
         let uuid = *uuid.as_bytes();
-        self.log.insert(
+        self.entries.insert(
             &coin,
             &Entry {
                 uuid,
-                recipient,
+                recipients,
                 tx,
             },
         )
@@ -74,12 +94,16 @@ impl WriteAheadLog {
     /// pending transaction exists, `Ok(None)` if not, and `Err(_)` if there was an internal error
     /// accessing the WAL.
     pub(crate) fn reclaim(&self, coin: ObjectID) -> Result<Option<Entry>, TypedStoreError> {
-        self.log.get(&coin)
+        if let Some(entry) = self.entries.get(&coin)? {
+            return Ok(Some(entry));
+        }
+        Ok(self.log.get(&coin)?.map(Entry::from))
     }
 
     /// Indicate that the transaction in flight for `coin` has landed, and the entry in the WAL can
     /// be removed.
     pub(crate) fn commit(&mut self, coin: ObjectID) -> Result<(), TypedStoreError> {
+        self.entries.remove(&coin)?;
         self.log.remove(&coin)
     }
 }
@@ -99,7 +123,7 @@ mod tests {
         let coin = random_object_ref();
         let (recv, tx) = random_request(coin);
 
-        assert!(wal.reserve(uuid, coin.0, recv, tx.clone()).is_ok());
+        assert!(wal.reserve(uuid, coin.0, recv.clone(), tx.clone()).is_ok());
 
         // Reclaim once
         let Some(entry) = wal.reclaim(coin.0).unwrap() else {
@@ -107,7 +131,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(recv, entry.recipients);
         assert_eq!(tx, entry.tx);
 
         // Reclaim again, should still be there.
@@ -116,7 +140,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(recv, entry.recipients);
         assert_eq!(tx, entry.tx);
     }
 
@@ -149,7 +173,7 @@ mod tests {
         let coin = random_object_ref();
         let (recv, tx) = random_request(coin);
 
-        wal.reserve(uuid, coin.0, recv, tx.clone()).unwrap();
+        wal.reserve(uuid, coin.0, recv.clone(), tx.clone()).unwrap();
 
         // Reclaim to show that the entry is there
         let Some(entry) = wal.reclaim(coin.0).unwrap() else {
@@ -157,7 +181,7 @@ mod tests {
         };
 
         assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
-        assert_eq!(recv, entry.recipient);
+        assert_eq!(recv, entry.recipients);
         assert_eq!(tx, entry.tx);
 
         // Commit the transaction, which removes it from the log.
@@ -187,11 +211,51 @@ mod tests {
         wal.reserve(uuid, coin.0, recv1, tx1).unwrap();
     }
 
-    fn random_request(coin: ObjectRef) -> (SuiAddress, TransactionData) {
+    #[tokio::test]
+    async fn reclaim_legacy_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("wal");
+
+        let uuid = Uuid::new_v4();
+        let coin = random_object_ref();
+        let (recv, tx) = random_request(coin);
+
+        // An entry left in flight by a faucet sending each transaction to a single recipient.
+        {
+            let wal = WriteAheadLog::open(&path);
+            let entry = LegacyEntry {
+                uuid: *uuid.as_bytes(),
+                recipient: recv[0],
+                tx: tx.clone(),
+            };
+            wal.log.insert(&coin.0, &entry).unwrap();
+        }
+
+        let mut wal = WriteAheadLog::open(&path);
+        let Some(entry) = wal.reclaim(coin.0).unwrap() else {
+            panic!("Entry not found for {}", coin.0);
+        };
+
+        assert_eq!(uuid, Uuid::from_bytes(entry.uuid));
+        assert_eq!(recv, entry.recipients);
+        assert_eq!(tx, entry.tx);
+
+        // The coin cannot be reserved again until the legacy entry is committed.
+        let (recv1, tx1) = random_request(coin);
+        assert!(wal
+            .reserve(uuid, coin.0, recv1.clone(), tx1.clone())
+            .is_err());
+
+        wal.commit(coin.0).unwrap();
+        assert_eq!(Ok(None), wal.reclaim(coin.0));
+        wal.reserve(uuid, coin.0, recv1, tx1).unwrap();
+    }
+
+    fn random_request(coin: ObjectRef) -> (Vec<SuiAddress>, TransactionData) {
         let send = SuiAddress::random_for_testing_only();
         let recv = SuiAddress::random_for_testing_only();
         (
-            recv,
+            vec![recv],
             TransactionData::new_pay_sui_with_dummy_gas_price(
                 send,
                 vec![coin],
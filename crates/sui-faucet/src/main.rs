@@ -3,16 +3,19 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{ConnectInfo, Path},
+    http::{header::RETRY_AFTER, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use clap::Parser;
 use http::Method;
 use mysten_metrics::spawn_monitored_task;
+use serde::Serialize;
 use std::{
     borrow::Cow,
+    collections::HashSet,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
     time::Duration,
@@ -20,13 +23,19 @@ use std::{
 use std::{env, path::PathBuf};
 use sui::client_commands::WalletContext;
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
-use sui_faucet::{Faucet, FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet};
+use sui_faucet::{
+    BatchConfig, BatchFaucetResponse, BatchStatusFaucetResponse, CoinRequest, CoinTypeConfig,
+    Faucet, FaucetError, FaucetRequest, FaucetResponse, GasPoolConfig, HealthResponse, Quota,
+    QuotaCharge, RequestMetricsLayer, RequestQueue, RequestQuotas, SimpleFaucet, API_KEY_HEADER,
+};
+use sui_types::base_types::SuiAddress;
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
 use uuid::Uuid;
 
 const CONCURRENCY_LIMIT: usize = 30;
+const QUOTA_PRUNE_INTERVAL: Duration = Duration::from_secs(600);
 
 #[derive(Parser)]
 #[clap(
@@ -58,10 +67,97 @@ struct FaucetConfig {
 
     #[clap(long)]
     write_ahead_log: PathBuf,
+
+    /// Maximum number of requests sending coins to the same address within
+    /// `--recipient-quota-window-secs`, unlimited if not set.
+    #[clap(long)]
+    max_requests_per_recipient: Option<usize>,
+
+    #[clap(long, default_value_t = 86400)]
+    recipient_quota_window_secs: u64,
+
+    /// Maximum number of requests from the same client IP within `--ip-quota-window-secs`,
+    /// unlimited if not set.
+    #[clap(long)]
+    max_requests_per_ip: Option<usize>,
+
+    #[clap(long, default_value_t = 86400)]
+    ip_quota_window_secs: u64,
+
+    /// File with one API key per line. Requests with one of them in the `X-Api-Key` header are not
+    /// subject to quotas.
+    #[clap(long)]
+    api_keys_file: Option<PathBuf>,
+
+    /// Number of proxies in front of the faucet that append the address they received a request
+    /// from to `X-Forwarded-For`. The client IP is the entry appended by the outermost of them,
+    /// entries to its left are set by the client and ignored. 0 uses the connection address.
+    #[clap(long, default_value_t = 0)]
+    trusted_proxies: usize,
+
+    /// Maximum number of requests to `/v1/gas` dispensed by the same transaction.
+    #[clap(long, default_value_t = 50)]
+    max_batch_size: usize,
+
+    /// How long a batch waits for more requests to `/v1/gas` after its first one.
+    #[clap(long, default_value_t = 1000)]
+    batch_interval_ms: u64,
+
+    /// Maximum number of requests to `/v1/gas` waiting to be batched.
+    #[clap(long, default_value_t = 10_000)]
+    batch_queue_size: usize,
+
+    /// YAML file listing the coin types other than SUI dispensed by the faucet, with their amounts
+    /// and how they are funded.
+    #[clap(long)]
+    coin_types: Option<PathBuf>,
+
+    /// Number of gas coins the pool is replenished to in the background, by splitting them off its
+    /// largest coin. The pool is not replenished if not set.
+    #[clap(long)]
+    gas_pool_target_size: Option<usize>,
+
+    /// Balance of the gas coins split to replenish the pool, enough for 10 requests by default.
+    #[clap(long)]
+    gas_pool_coin_balance: Option<u64>,
+
+    /// Gas coins with a balance below this are merged back into the largest coin of the pool,
+    /// enough for a request by default. The faucet reports itself unhealthy once its balance falls
+    /// below it.
+    #[clap(long)]
+    gas_pool_dust_balance: Option<u64>,
+
+    /// How often the gas pool is replenished and the balance of the faucet refreshed.
+    #[clap(long, default_value_t = 60)]
+    gas_pool_rebalance_interval_secs: u64,
+}
+
+impl FaucetConfig {
+    fn gas_pool_config(&self) -> Option<GasPoolConfig> {
+        Some(GasPoolConfig {
+            target_size: self.gas_pool_target_size?,
+            coin_balance: self
+                .gas_pool_coin_balance
+                .unwrap_or(self.request_amount() * 10),
+            dust_balance: self.dust_balance(),
+        })
+    }
+
+    fn dust_balance(&self) -> u64 {
+        self.gas_pool_dust_balance
+            .unwrap_or_else(|| self.request_amount())
+    }
+
+    /// Total amount sent by a request with the default amounts.
+    fn request_amount(&self) -> u64 {
+        self.amount * self.num_coins as u64
+    }
 }
 
 struct AppState<F = SimpleFaucet> {
-    faucet: F,
+    faucet: Arc<F>,
+    queue: RequestQueue,
+    quotas: RequestQuotas,
     config: FaucetConfig,
     // TODO: add counter
 }
@@ -99,13 +195,78 @@ async fn main() -> Result<(), anyhow::Error> {
     let registry_service = sui_node::metrics::start_prometheus_server(prom_binding);
     let prometheus_registry = registry_service.default_registry();
 
-    let app_state = Arc::new(AppState {
-        faucet: SimpleFaucet::new(context, &prometheus_registry, write_ahead_log)
+    // The quotas are kept next to the write ahead log.
+    let quotas = RequestQuotas::open(
+        &write_ahead_log.with_extension("quotas"),
+        config.max_requests_per_recipient.map(|max_requests| Quota {
+            max_requests,
+            window: Duration::from_secs(config.recipient_quota_window_secs),
+        }),
+        config.max_requests_per_ip.map(|max_requests| Quota {
+            max_requests,
+            window: Duration::from_secs(config.ip_quota_window_secs),
+        }),
+        read_api_keys(config.api_keys_file.as_deref())?,
+    );
+
+    let coin_types = match &config.coin_types {
+        Some(path) => CoinTypeConfig::load(path)?,
+        None => vec![],
+    };
+    let faucet = Arc::new(
+        SimpleFaucet::new(context, &prometheus_registry, write_ahead_log)
             .await
-            .unwrap(),
+            .unwrap()
+            .with_coin_types(coin_types)?,
+    );
+    let queue = RequestQueue::start(
+        faucet.clone(),
+        BatchConfig {
+            max_batch_size: config.max_batch_size,
+            batch_interval: Duration::from_millis(config.batch_interval_ms),
+            queue_size: config.batch_queue_size,
+        },
+    );
+
+    let app_state = Arc::new(AppState {
+        faucet,
+        queue,
+        quotas,
         config,
     });
 
+    let pruned_state = app_state.clone();
+    spawn_monitored_task!(async move {
+        let mut interval = tokio::time::interval(QUOTA_PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match pruned_state.quotas.prune().await {
+                Ok(pruned) => info!("Pruned quotas of {pruned} callers"),
+                Err(e) => warn!("Failed to prune quotas: {:?}", e),
+            }
+        }
+    });
+
+    let gas_pool_state = app_state.clone();
+    spawn_monitored_task!(async move {
+        let gas_pool_config = gas_pool_state.config.gas_pool_config();
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            gas_pool_state.config.gas_pool_rebalance_interval_secs,
+        ));
+        loop {
+            interval.tick().await;
+            let faucet = &gas_pool_state.faucet;
+            let result = match &gas_pool_config {
+                Some(gas_pool_config) => faucet.rebalance_gas_pool(gas_pool_config).await,
+                None => faucet.update_balance().await,
+            };
+            match result {
+                Ok(()) => info!("Gas pool status: {:?}", faucet.gas_pool_status()),
+                Err(e) => warn!("Failed to replenish the gas pool: {:?}", e),
+            }
+        }
+    });
+
     // TODO: restrict access if needed
     let cors = CorsLayer::new()
         .allow_methods(vec![Method::GET, Method::POST])
@@ -114,7 +275,10 @@ async fn main() -> Result<(), anyhow::Error> {
 
     let app = Router::new()
         .route("/", get(health))
+        .route("/health", get(gas_pool_health))
         .route("/gas", post(request_gas))
+        .route("/v1/gas", post(batch_request_gas))
+        .route("/v1/status/:task_id", get(request_status))
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(handle_error))
@@ -134,7 +298,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -144,45 +308,249 @@ async fn health() -> &'static str {
     "OK"
 }
 
+/// handler reporting the balance and the gas pool of the faucet, with a 503 once it cannot serve
+/// requests anymore
+async fn gas_pool_health(Extension(state): Extension<Arc<AppState>>) -> Response {
+    let gas_pool = state.faucet.gas_pool_status();
+    let healthy = gas_pool.available_coins > 0 && gas_pool.balance >= state.config.dust_balance();
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(HealthResponse { healthy, gas_pool })).into_response()
+}
+
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     // ID for traceability
     let id = Uuid::new_v4();
     info!(uuid = ?id, "Got new gas request.");
-    let result = match payload {
-        FaucetRequest::FixedAmountRequest(requests) => {
-            // We spawn a tokio task for this such that connection drop will not interrupt
-            // it and impact the reclycing of coins
-            spawn_monitored_task!(async move {
+    let recipient = payload.recipient();
+    let dispense = match check_request(&state, &payload) {
+        Ok(dispense) => dispense,
+        Err(e) => {
+            warn!(uuid = ?id, "Invalid gas request: {:?}", e);
+            return error_response::<FaucetResponse>(e);
+        }
+    };
+    let charge = match check_quotas(&state, &headers, addr, recipient).await {
+        Ok(charge) => charge,
+        Err(e) => {
+            warn!(uuid = ?id, "Rejected gas request: {:?}", e);
+            return error_response::<FaucetResponse>(e);
+        }
+    };
+
+    // We spawn a tokio task for this such that connection drop will not interrupt
+    // it and impact the reclycing of coins, nor keep the quotas of a failed request
+    let result = spawn_monitored_task!(async move {
+        let result = match dispense {
+            Dispense::Sui(amounts) => state.faucet.send(id, recipient, &amounts).await,
+            Dispense::Coin(coin_type, amount) => {
                 state
                     .faucet
-                    .send(
-                        id,
-                        requests.recipient,
-                        &vec![state.config.amount; state.config.num_coins],
-                    )
+                    .send_coin(id, recipient, &coin_type, amount)
                     .await
-            })
-            .await
-            .unwrap()
+            }
+        };
+        if result.is_err() {
+            release_quotas(&state, charge).await;
         }
-    };
+        result
+    })
+    .await
+    .unwrap();
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(FaucetResponse::from(v)),
+            error_response::<FaucetResponse>(v)
+        }
+    }
+}
+
+/// handler for the requests queued to be dispensed in batches, responds with the ID of the
+/// request to poll its status
+async fn batch_request_gas(
+    Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<FaucetRequest>,
+) -> Response {
+    let id = Uuid::new_v4();
+    info!(uuid = ?id, "Got new batched gas request.");
+    let recipient = payload.recipient();
+    let amounts = match check_request(&state, &payload) {
+        Ok(Dispense::Sui(amounts)) => amounts,
+        Ok(Dispense::Coin(..)) => {
+            let e = FaucetError::InvalidRequest("only SUI is dispensed in batches".to_string());
+            return error_response::<BatchFaucetResponse>(e);
+        }
+        Err(e) => {
+            warn!(uuid = ?id, "Invalid batched gas request: {:?}", e);
+            return error_response::<BatchFaucetResponse>(e);
+        }
+    };
+    let charge = match check_quotas(&state, &headers, addr, recipient).await {
+        Ok(charge) => charge,
+        Err(e) => {
+            warn!(uuid = ?id, "Rejected batched gas request: {:?}", e);
+            return error_response::<BatchFaucetResponse>(e);
+        }
+    };
+
+    match state.queue.enqueue(id, recipient, amounts).await {
+        Ok(()) => (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id))).into_response(),
+        Err(e) => {
+            warn!(uuid = ?id, "Failed to queue gas request: {:?}", e);
+            release_quotas(&state, charge).await;
+            error_response::<BatchFaucetResponse>(e)
+        }
+    }
+}
+
+/// handler for polling the status of a batched request
+async fn request_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Response {
+    let status = match Uuid::parse_str(&task_id) {
+        Ok(id) => state.queue.status(id).await,
+        Err(_) => None,
+    };
+    match status {
+        Some(status) => (
+            StatusCode::OK,
+            Json(BatchStatusFaucetResponse::from(status)),
+        )
+            .into_response(),
+        None => error_response::<BatchStatusFaucetResponse>(FaucetError::UnknownRequest(task_id)),
+    }
+}
+
+/// What a request is served with.
+enum Dispense {
+    /// SUI coins of these amounts.
+    Sui(Vec<u64>),
+    /// A coin of this type and amount.
+    Coin(String, u64),
+}
+
+fn check_request(state: &AppState, request: &FaucetRequest) -> Result<Dispense, FaucetError> {
+    let default_amounts = vec![state.config.amount; state.config.num_coins];
+    let FaucetRequest::CoinRequest(CoinRequest {
+        coin_type, amount, ..
+    }) = request
+    else {
+        return Ok(Dispense::Sui(default_amounts));
+    };
+    let coin_type = match coin_type {
+        Some(coin_type) => state.faucet.coin_type(coin_type)?,
+        None => None,
+    };
+    match (coin_type, amount) {
+        (Some(coin_type), _) => Ok(Dispense::Coin(
+            coin_type.coin_type.clone(),
+            coin_type.amount(*amount)?,
+        )),
+        (None, None) => Ok(Dispense::Sui(default_amounts)),
+        // A single SUI coin, of at most what a fixed amount request gets.
+        (None, Some(amount)) => {
+            let max_amount = default_amounts.iter().sum();
+            if *amount == 0 || *amount > max_amount {
+                return Err(FaucetError::InvalidAmount(*amount, 1, max_amount));
+            }
+            Ok(Dispense::Sui(vec![*amount]))
+        }
+    }
+}
+
+/// Fails with `FaucetError::TooManyRequests` if the recipient or the client are over their quota,
+/// unless the request carries an allowlisted API key. Returns what the request was charged, `None`
+/// if it is not subject to quotas.
+async fn check_quotas(
+    state: &AppState,
+    headers: &HeaderMap,
+    addr: SocketAddr,
+    recipient: SuiAddress,
+) -> Result<Option<QuotaCharge>, FaucetError> {
+    let api_key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok());
+    if state.quotas.is_allowlisted(api_key) {
+        return Ok(None);
+    }
+    let ip = client_ip(headers, addr, state.config.trusted_proxies);
+    state.quotas.acquire(recipient, ip).await.map(Some)
+}
+
+/// Gives the caller of a request that was not served back what it was charged.
+async fn release_quotas(state: &AppState, charge: Option<QuotaCharge>) {
+    let Some(charge) = charge else {
+        return;
+    };
+    if let Err(e) = state.quotas.release(charge).await {
+        warn!("Failed to release quotas: {:?}", e);
+    }
+}
+
+fn error_response<R: From<FaucetError> + Serialize>(error: FaucetError) -> Response {
+    let status = match error {
+        FaucetError::TooManyRequests(retry_after_secs) => {
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, retry_after_secs.to_string())],
+                Json(R::from(error)),
             )
+                .into_response();
         }
+        FaucetError::BatchQueueFull => StatusCode::SERVICE_UNAVAILABLE,
+        FaucetError::UnknownRequest(_) => StatusCode::NOT_FOUND,
+        FaucetError::UnsupportedCoinType(_)
+        | FaucetError::InvalidAmount(..)
+        | FaucetError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(R::from(error))).into_response()
+}
+
+/// The IP of the client, which is `trusted_proxies` entries from the end of `X-Forwarded-For` when
+/// the faucet runs behind proxies. Falls back to the connection address if the header has fewer
+/// entries than there are proxies.
+fn client_ip(headers: &HeaderMap, addr: SocketAddr, trusted_proxies: usize) -> IpAddr {
+    if trusted_proxies == 0 {
+        return addr.ip();
     }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|forwarded| forwarded.to_str().ok())
+        .and_then(|forwarded| {
+            let entries: Vec<&str> = forwarded.split(',').collect();
+            let index = entries.len().checked_sub(trusted_proxies)?;
+            entries[index].trim().parse().ok()
+        })
+        .unwrap_or_else(|| addr.ip())
+}
+
+fn read_api_keys(path: Option<&std::path::Path>) -> Result<HashSet<String>, anyhow::Error> {
+    let Some(path) = path else {
+        return Ok(HashSet::new());
+    };
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect())
 }
 
 async fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
@@ -204,3 +572,193 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
         Cow::from(format!("Unhandled internal error: {}", error)),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use prometheus::Registry;
+    use test_utils::network::TestClusterBuilder;
+
+    const CLIENT: &str = "1.1.1.1:5003";
+
+    fn forwarded_for(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_client_ip_ignores_spoofed_forwarded_for() {
+        let proxy: SocketAddr = "10.0.0.1:5003".parse().unwrap();
+        // The client sent its own `X-Forwarded-For: 1.1.1.1`, the proxy appended the client IP.
+        let headers = forwarded_for("1.1.1.1, 2.2.2.2");
+        assert_eq!(
+            client_ip(&headers, proxy, 1),
+            "2.2.2.2".parse::<IpAddr>().unwrap()
+        );
+        // Behind two proxies, the entry appended by the inner one is skipped.
+        let headers = forwarded_for("1.1.1.1, 2.2.2.2, 10.0.0.2");
+        assert_eq!(
+            client_ip(&headers, proxy, 2),
+            "2.2.2.2".parse::<IpAddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_client_ip_falls_back_to_connection_address() {
+        let addr: SocketAddr = "3.3.3.3:5003".parse().unwrap();
+        let headers = forwarded_for("1.1.1.1");
+        assert_eq!(client_ip(&headers, addr, 0), addr.ip());
+        assert_eq!(client_ip(&headers, addr, 2), addr.ip());
+        assert_eq!(client_ip(&HeaderMap::new(), addr, 1), addr.ip());
+        assert_eq!(client_ip(&forwarded_for("junk"), addr, 1), addr.ip());
+    }
+
+    /// State of a faucet serving requests with `args`, each recipient limited to one request.
+    async fn app_state(
+        wallet: WalletContext,
+        dir: &std::path::Path,
+        coin_types: Vec<CoinTypeConfig>,
+        args: &[&str],
+    ) -> Arc<AppState> {
+        let write_ahead_log = dir.join("faucet.wal");
+        let mut argv = vec![
+            "sui-faucet",
+            "--write-ahead-log",
+            write_ahead_log.to_str().unwrap(),
+            "--max-requests-per-recipient",
+            "1",
+        ];
+        argv.extend(args);
+        let config = FaucetConfig::parse_from(argv);
+        let faucet = Arc::new(
+            SimpleFaucet::new(wallet, &Registry::new(), &write_ahead_log)
+                .await
+                .unwrap()
+                .with_coin_types(coin_types)
+                .unwrap(),
+        );
+        let queue = RequestQueue::start(
+            faucet.clone(),
+            BatchConfig {
+                max_batch_size: config.max_batch_size,
+                batch_interval: Duration::from_millis(config.batch_interval_ms),
+                queue_size: config.batch_queue_size,
+            },
+        );
+        let quotas = RequestQuotas::open(
+            &dir.join("quotas"),
+            config.max_requests_per_recipient.map(|max_requests| Quota {
+                max_requests,
+                window: Duration::from_secs(config.recipient_quota_window_secs),
+            }),
+            None,
+            HashSet::new(),
+        );
+        Arc::new(AppState {
+            faucet,
+            queue,
+            quotas,
+            config,
+        })
+    }
+
+    async fn request(state: &Arc<AppState>, request: FaucetRequest) -> StatusCode {
+        request_gas(
+            Extension(state.clone()),
+            ConnectInfo(CLIENT.parse().unwrap()),
+            HeaderMap::new(),
+            Json(request),
+        )
+        .await
+        .status()
+    }
+
+    async fn batch_request(state: &Arc<AppState>, recipient: SuiAddress) -> StatusCode {
+        batch_request_gas(
+            Extension(state.clone()),
+            ConnectInfo(CLIENT.parse().unwrap()),
+            HeaderMap::new(),
+            Json(FaucetRequest::new_fixed_amount_request(recipient)),
+        )
+        .await
+        .status()
+    }
+
+    /// Whether `recipient` has its request left, taking it if so.
+    async fn has_quota(state: &AppState, recipient: SuiAddress) -> bool {
+        let ip = CLIENT.parse::<SocketAddr>().unwrap().ip();
+        state.quotas.acquire(recipient, ip).await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_releases_quotas() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        // More than any coin of the faucet holds.
+        let state = app_state(
+            test_cluster.wallet,
+            tmp.path(),
+            vec![],
+            &["--amount", "1000000000000000000", "--num-coins", "1"],
+        )
+        .await;
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let status = request(&state, FaucetRequest::new_fixed_amount_request(recipient)).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(has_quota(&state, recipient).await);
+        assert!(!has_quota(&state, recipient).await);
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_coin_releases_quotas() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        // The faucet owns no coin of the type, nor its treasury cap.
+        let coin_type = CoinTypeConfig {
+            coin_type: "0x6b1e::token::TOKEN".to_string(),
+            default_amount: 1,
+            min_amount: 1,
+            max_amount: 10,
+            treasury_cap: None,
+        };
+        let state = app_state(test_cluster.wallet, tmp.path(), vec![coin_type], &[]).await;
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let coin_request = FaucetRequest::new_coin_request(
+            recipient,
+            Some("0x6b1e::token::TOKEN".to_string()),
+            None,
+        );
+        let status = request(&state, coin_request).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(has_quota(&state, recipient).await);
+        assert!(!has_quota(&state, recipient).await);
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_queue_releases_quotas() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let state = app_state(
+            test_cluster.wallet,
+            tmp.path(),
+            vec![],
+            &["--batch-queue-size", "1", "--batch-interval-ms", "60000"],
+        )
+        .await;
+
+        // The queue is not drained before the test yields, the second request finds it full.
+        let queued = SuiAddress::random_for_testing_only();
+        let rejected = SuiAddress::random_for_testing_only();
+        assert_eq!(batch_request(&state, queued).await, StatusCode::ACCEPTED);
+        assert_eq!(
+            batch_request(&state, rejected).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert!(!has_quota(&state, queued).await);
+        assert!(has_quota(&state, rejected).await);
+    }
+}
@@ -27,6 +27,7 @@ pub struct FaucetMetrics {
     pub(crate) current_executions_in_flight: IntGauge,
     pub(crate) total_available_coins: IntGauge,
     pub(crate) total_discarded_coins: IntGauge,
+    pub(crate) total_balance: IntGauge,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -105,6 +106,12 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            total_balance: register_int_gauge_with_registry!(
+                "total_balance",
+                "Total SUI balance of the faucet, in MIST",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
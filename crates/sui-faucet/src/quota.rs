@@ -0,0 +1,358 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use tokio::sync::Mutex;
+use typed_store::rocks::DBMap;
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::Map;
+use typed_store_derive::DBMapUtils;
+
+use crate::FaucetError;
+
+/// Header carrying the API key of a caller that is not subject to quotas.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// At most `max_requests` requests are served to the same caller within any `window`.
+#[derive(Clone, Copy, Debug)]
+pub struct Quota {
+    pub max_requests: usize,
+    pub window: Duration,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaKey {
+    Recipient(SuiAddress),
+    Ip(IpAddr),
+}
+
+/// A request recorded against the quotas of its recipient and client IP, which can be released
+/// if the request ends up not being served.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotaCharge {
+    recipient: SuiAddress,
+    ip: IpAddr,
+    at: u64,
+}
+
+/// Persistent record of the requests served to each recipient and client IP, as the timestamps in
+/// milliseconds of the requests served within the window of its quota, oldest first.
+#[derive(DBMapUtils)]
+pub struct QuotaTable {
+    requests: DBMap<QuotaKey, Vec<u64>>,
+}
+
+/// Per-recipient and per-client-IP quotas over sliding windows, kept across restarts of the
+/// faucet. Callers presenting one of the allowlisted API keys are not limited.
+pub struct RequestQuotas {
+    per_recipient: Option<Quota>,
+    per_ip: Option<Quota>,
+    api_keys: HashSet<String>,
+    table: Mutex<QuotaTable>,
+}
+
+impl RequestQuotas {
+    pub fn open(
+        path: &Path,
+        per_recipient: Option<Quota>,
+        per_ip: Option<Quota>,
+        api_keys: HashSet<String>,
+    ) -> Self {
+        let table = QuotaTable::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::default(),
+            None,
+            None,
+        );
+        Self {
+            per_recipient,
+            per_ip,
+            api_keys,
+            table: Mutex::new(table),
+        }
+    }
+
+    pub fn is_allowlisted(&self, api_key: Option<&str>) -> bool {
+        api_key.map_or(false, |key| self.api_keys.contains(key))
+    }
+
+    /// Records a request sending coins to `recipient` on behalf of client `ip`, or fails with
+    /// `FaucetError::TooManyRequests` without recording it if either is over its quota.
+    pub async fn acquire(
+        &self,
+        recipient: SuiAddress,
+        ip: IpAddr,
+    ) -> Result<QuotaCharge, FaucetError> {
+        self.acquire_at(recipient, ip, now_ms()).await
+    }
+
+    async fn acquire_at(
+        &self,
+        recipient: SuiAddress,
+        ip: IpAddr,
+        now: u64,
+    ) -> Result<QuotaCharge, FaucetError> {
+        let table = self.table.lock().await;
+        let mut admitted = vec![];
+        let mut retry_after = None;
+        for key in [QuotaKey::Recipient(recipient), QuotaKey::Ip(ip)] {
+            let Some(quota) = self.quota(&key) else {
+                continue;
+            };
+            let window = quota.window.as_millis() as u64;
+            let mut requests = table
+                .requests
+                .get(&key)
+                .map_err(FaucetError::internal)?
+                .unwrap_or_default();
+            requests.retain(|t| t + window > now);
+            if requests.len() >= quota.max_requests {
+                // The caller can retry once enough of its requests left the window.
+                let expires = requests
+                    .get(requests.len() - quota.max_requests)
+                    .map_or(now, |t| t + window);
+                let secs = ((expires - now + 999) / 1000).max(1);
+                retry_after = retry_after.max(Some(secs));
+            } else {
+                requests.push(now);
+                admitted.push((key, requests));
+            }
+        }
+        if let Some(secs) = retry_after {
+            return Err(FaucetError::TooManyRequests(secs));
+        }
+        table
+            .requests
+            .multi_insert(admitted)
+            .map_err(FaucetError::internal)?;
+        Ok(QuotaCharge {
+            recipient,
+            ip,
+            at: now,
+        })
+    }
+
+    /// Forgets a request recorded by `acquire`, for a request that failed or could not be queued
+    /// not to count against the quotas of its caller.
+    pub async fn release(&self, charge: QuotaCharge) -> Result<(), FaucetError> {
+        let table = self.table.lock().await;
+        let mut released = vec![];
+        for key in [
+            QuotaKey::Recipient(charge.recipient),
+            QuotaKey::Ip(charge.ip),
+        ] {
+            let Some(mut requests) = table.requests.get(&key).map_err(FaucetError::internal)? else {
+                continue;
+            };
+            if let Some(index) = requests.iter().position(|t| *t == charge.at) {
+                requests.remove(index);
+                released.push((key, requests));
+            }
+        }
+        table
+            .requests
+            .multi_insert(released)
+            .map_err(FaucetError::internal)
+    }
+
+    /// Removes the callers with no request left in the window of their quota, returns how many.
+    pub async fn prune(&self) -> Result<usize, FaucetError> {
+        self.prune_at(now_ms()).await
+    }
+
+    async fn prune_at(&self, now: u64) -> Result<usize, FaucetError> {
+        let table = self.table.lock().await;
+        let expired = table
+            .requests
+            .iter()
+            .filter(|(key, requests)| {
+                self.quota(key).map_or(true, |quota| {
+                    requests
+                        .last()
+                        .map_or(true, |t| t + quota.window.as_millis() as u64 <= now)
+                })
+            })
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        table
+            .requests
+            .multi_remove(&expired)
+            .map_err(FaucetError::internal)?;
+        Ok(expired.len())
+    }
+
+    fn quota(&self, key: &QuotaKey) -> Option<Quota> {
+        match key {
+            QuotaKey::Recipient(_) => self.per_recipient,
+            QuotaKey::Ip(_) => self.per_ip,
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn quota(max_requests: usize) -> Option<Quota> {
+        Some(Quota {
+            max_requests,
+            window: HOUR,
+        })
+    }
+
+    #[tokio::test]
+    async fn recipient_over_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas =
+            RequestQuotas::open(&tmp.path().join("quotas"), quota(2), None, HashSet::new());
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        quotas.acquire_at(recipient, ip, 0).await.unwrap();
+        quotas.acquire_at(recipient, ip, 1_000).await.unwrap();
+
+        // The first request leaves the window an hour after it was served.
+        assert_eq!(
+            quotas.acquire_at(recipient, ip, 2_000).await,
+            Err(FaucetError::TooManyRequests(3598)),
+        );
+
+        // Other recipients are not limited.
+        let other = SuiAddress::random_for_testing_only();
+        quotas.acquire_at(other, ip, 2_000).await.unwrap();
+
+        // Rejected requests do not count, the window slides past the first request.
+        quotas.acquire_at(recipient, ip, 3_600_000).await.unwrap();
+        assert_eq!(
+            quotas.acquire_at(recipient, ip, 3_600_000).await,
+            Err(FaucetError::TooManyRequests(1)),
+        );
+    }
+
+    #[tokio::test]
+    async fn ip_over_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::open(
+            &tmp.path().join("quotas"),
+            quota(5),
+            quota(1),
+            HashSet::new(),
+        );
+
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        quotas
+            .acquire_at(SuiAddress::random_for_testing_only(), ip, 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            quotas
+                .acquire_at(SuiAddress::random_for_testing_only(), ip, 0)
+                .await,
+            Err(FaucetError::TooManyRequests(3600)),
+        );
+
+        let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        quotas
+            .acquire_at(SuiAddress::random_for_testing_only(), other_ip, 0)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn quotas_persist_across_restarts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("quotas");
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        {
+            let quotas = RequestQuotas::open(&path, quota(1), None, HashSet::new());
+            quotas.acquire_at(recipient, ip, 0).await.unwrap();
+        }
+
+        let quotas = RequestQuotas::open(&path, quota(1), None, HashSet::new());
+        assert!(quotas.acquire_at(recipient, ip, 1_000).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn prune_expired_callers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::open(
+            &tmp.path().join("quotas"),
+            quota(1),
+            quota(1),
+            HashSet::new(),
+        );
+
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let recipient = SuiAddress::random_for_testing_only();
+        quotas.acquire_at(recipient, ip, 0).await.unwrap();
+
+        // Both the recipient and the IP are still in their window.
+        assert_eq!(quotas.prune_at(1_000).await.unwrap(), 0);
+        assert_eq!(quotas.prune_at(3_600_000).await.unwrap(), 2);
+        quotas.acquire_at(recipient, ip, 3_600_000).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn released_requests_do_not_count() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::open(
+            &tmp.path().join("quotas"),
+            quota(1),
+            quota(2),
+            HashSet::new(),
+        );
+
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let recipient = SuiAddress::random_for_testing_only();
+        let charge = quotas.acquire_at(recipient, ip, 0).await.unwrap();
+        assert!(quotas.acquire_at(recipient, ip, 1_000).await.is_err());
+
+        // Both the recipient and the IP get their request back.
+        quotas.release(charge).await.unwrap();
+        quotas.acquire_at(recipient, ip, 1_000).await.unwrap();
+        quotas
+            .acquire_at(SuiAddress::random_for_testing_only(), ip, 1_000)
+            .await
+            .unwrap();
+        assert_eq!(
+            quotas
+                .acquire_at(SuiAddress::random_for_testing_only(), ip, 1_000)
+                .await,
+            Err(FaucetError::TooManyRequests(3600)),
+        );
+    }
+
+    #[test]
+    fn allowlisted_api_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::open(
+            &tmp.path().join("quotas"),
+            quota(1),
+            None,
+            HashSet::from(["secret".to_string()]),
+        );
+
+        assert!(quotas.is_allowlisted(Some("secret")));
+        assert!(!quotas.is_allowlisted(Some("guess")));
+        assert!(!quotas.is_allowlisted(None));
+    }
+}
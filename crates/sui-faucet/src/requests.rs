@@ -7,6 +7,7 @@ use sui_types::base_types::SuiAddress;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum FaucetRequest {
     FixedAmountRequest(FixedAmountRequest),
+    CoinRequest(CoinRequest),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,10 +15,43 @@ pub struct FixedAmountRequest {
     pub recipient: SuiAddress,
 }
 
+/// Request for a coin of a type and amount picked by the caller, within the bounds configured for
+/// the coin type.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinRequest {
+    pub recipient: SuiAddress,
+    /// SUI if not set.
+    #[serde(default)]
+    pub coin_type: Option<String>,
+    /// The default amount of the coin type if not set.
+    #[serde(default)]
+    pub amount: Option<u64>,
+}
+
 impl FaucetRequest {
     pub fn new_fixed_amount_request(recipient: impl Into<SuiAddress>) -> Self {
         Self::FixedAmountRequest(FixedAmountRequest {
             recipient: recipient.into(),
         })
     }
+
+    pub fn new_coin_request(
+        recipient: impl Into<SuiAddress>,
+        coin_type: Option<String>,
+        amount: Option<u64>,
+    ) -> Self {
+        Self::CoinRequest(CoinRequest {
+            recipient: recipient.into(),
+            coin_type,
+            amount,
+        })
+    }
+
+    pub fn recipient(&self) -> SuiAddress {
+        match self {
+            Self::FixedAmountRequest(request) => request.recipient,
+            Self::CoinRequest(request) => request.recipient,
+        }
+    }
 }
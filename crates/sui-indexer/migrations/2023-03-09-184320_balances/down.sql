@@ -0,0 +1,3 @@
+DROP MATERIALIZED VIEW IF EXISTS balances;
+DROP INDEX IF EXISTS objects_coin_owner;
+DROP FUNCTION IF EXISTS coin_balance(bcs_bytes[]);
@@ -0,0 +1,33 @@
+-- A coin is serialized as its 32 bytes UID followed by its balance as a little endian u64,
+-- decoded here as NUMERIC because balances can exceed the range of BIGINT.
+CREATE OR REPLACE FUNCTION coin_balance(bcs bcs_bytes[]) RETURNS NUMERIC AS
+$func$
+DECLARE
+    data    bytea   := (bcs[1]).data;
+    balance NUMERIC := 0;
+BEGIN
+    FOR i IN REVERSE 39..32
+        LOOP
+            balance := balance * 256 + get_byte(data, i);
+        END LOOP;
+    RETURN balance;
+END
+$func$
+    LANGUAGE plpgsql IMMUTABLE;
+
+CREATE INDEX objects_coin_owner ON objects (owner_address, object_type) WHERE object_type LIKE '0x2::coin::Coin<%';
+
+-- Balance of every coin type owned by an address, refreshed by the indexer every
+-- --balances-refresh-interval-ms, so it can lag the objects table by up to that interval.
+CREATE MATERIALIZED VIEW balances AS
+SELECT owner_address,
+       SUBSTRING(object_type FROM '^0x2::coin::Coin<(.+)>$') AS coin_type,
+       COUNT(*)                                             AS coin_num,
+       SUM(coin_balance(bcs))                               AS balance
+FROM objects
+WHERE owner_type = 'address_owner'
+  AND object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted')
+  AND object_type LIKE '0x2::coin::Coin<%'
+GROUP BY owner_address, coin_type;
+-- required to refresh the view concurrently
+CREATE UNIQUE INDEX balances_owner_coin_type ON balances (owner_address, coin_type);
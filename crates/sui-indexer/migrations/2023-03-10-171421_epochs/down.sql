@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS epochs;
+DROP TABLE IF EXISTS validators;
+DROP TABLE IF EXISTS committees;
+DROP TABLE IF EXISTS stake_events;
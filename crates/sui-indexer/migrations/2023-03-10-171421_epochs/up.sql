@@ -0,0 +1,70 @@
+-- One row per ended epoch, written at the last checkpoint of the epoch from the
+-- SystemEpochInfoEvent emitted by the epoch change transaction.
+CREATE TABLE epochs
+(
+    epoch                           BIGINT PRIMARY KEY,
+    first_checkpoint_id             BIGINT NOT NULL,
+    last_checkpoint_id              BIGINT NOT NULL,
+    epoch_start_timestamp           BIGINT NOT NULL,
+    epoch_end_timestamp             BIGINT NOT NULL,
+    epoch_total_transactions        BIGINT NOT NULL,
+    next_epoch_protocol_version     BIGINT NOT NULL,
+    next_epoch_reference_gas_price  BIGINT NOT NULL,
+    total_stake                     BIGINT NOT NULL,
+    storage_fund_reinvestment       BIGINT NOT NULL,
+    storage_charge                  BIGINT NOT NULL,
+    storage_rebate                  BIGINT NOT NULL,
+    storage_fund_balance            BIGINT NOT NULL,
+    stake_subsidy_amount            BIGINT NOT NULL,
+    total_gas_fees                  BIGINT NOT NULL,
+    total_stake_rewards_distributed BIGINT NOT NULL,
+    leftover_storage_fund_inflow    BIGINT NOT NULL
+);
+
+-- State of each validator at the end of an epoch, from the ValidatorEpochInfoEvent.
+CREATE TABLE validators
+(
+    epoch                           BIGINT  NOT NULL,
+    validator_address               address NOT NULL,
+    reference_gas_survey_quote      BIGINT  NOT NULL,
+    stake                           BIGINT  NOT NULL,
+    commission_rate                 BIGINT  NOT NULL,
+    pool_staking_reward             BIGINT  NOT NULL,
+    storage_fund_staking_reward     BIGINT  NOT NULL,
+    exchange_rate_sui_amount        BIGINT  NOT NULL,
+    exchange_rate_pool_token_amount BIGINT  NOT NULL,
+    tallying_rule_reporters         TEXT[]  NOT NULL,
+    tallying_rule_global_score      BIGINT  NOT NULL,
+    CONSTRAINT validators_pk PRIMARY KEY (epoch, validator_address)
+);
+CREATE INDEX validators_validator_address ON validators (validator_address);
+
+-- Committee of each epoch, as announced in the last checkpoint of the previous epoch.
+CREATE TABLE committees
+(
+    epoch          BIGINT  NOT NULL,
+    authority_name VARCHAR NOT NULL,
+    voting_power   BIGINT  NOT NULL,
+    CONSTRAINT committees_pk PRIMARY KEY (epoch, authority_name)
+);
+
+-- Staking and unstaking requests, unstaking_epoch is only set for the latter.
+CREATE TABLE stake_events
+(
+    id                     BIGSERIAL PRIMARY KEY,
+    transaction_digest     base58digest NOT NULL,
+    event_sequence         BIGINT       NOT NULL,
+    checkpoint             BIGINT       NOT NULL,
+    epoch                  BIGINT       NOT NULL,
+    pool_id                address      NOT NULL,
+    validator_address      address      NOT NULL,
+    staker_address         address      NOT NULL,
+    stake_activation_epoch BIGINT       NOT NULL,
+    unstaking_epoch        BIGINT,
+    principal_amount       BIGINT       NOT NULL,
+    reward_amount          BIGINT,
+    CONSTRAINT stake_events_event_id UNIQUE (transaction_digest, event_sequence)
+);
+CREATE INDEX stake_events_staker_address ON stake_events (staker_address);
+CREATE INDEX stake_events_validator_address ON stake_events (validator_address);
+CREATE INDEX stake_events_epoch ON stake_events (epoch);
@@ -0,0 +1,12 @@
+-- Last checkpoint written to each checkpoint sink.
+CREATE TABLE sink_watermarks
+(
+    sink_name                  VARCHAR(255) PRIMARY KEY,
+    checkpoint_sequence_number BIGINT NOT NULL
+);
+
+-- Checkpoints already in the database were written by the Postgres sink.
+INSERT INTO sink_watermarks (sink_name, checkpoint_sequence_number)
+SELECT 'postgres', MAX(sequence_number)
+FROM checkpoints
+HAVING MAX(sequence_number) IS NOT NULL;
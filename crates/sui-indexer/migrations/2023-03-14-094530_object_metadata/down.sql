@@ -0,0 +1,66 @@
+CREATE OR REPLACE FUNCTION objects_modified_func() RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF (TG_OP = 'UPDATE' OR TG_OP = 'INSERT') THEN
+        INSERT INTO objects_history
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address,
+                NEW.initial_shared_version,
+                NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+                NEW.storage_rebate, NEW.bcs);
+        RETURN NEW;
+    ELSIF (TG_OP = 'DELETE') THEN
+        -- object deleted from the main table, archive the history for that object
+        DELETE FROM objects_history WHERE object_id = old.object_id;
+        RETURN OLD;
+    ELSE
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - Other action occurred: %, at %',TG_OP,NOW();
+        RETURN NULL;
+    END IF;
+
+EXCEPTION
+    WHEN data_exception THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [DATA EXCEPTION] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN unique_violation THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [UNIQUE] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN OTHERS THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [OTHER] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+END;
+$body$
+    LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS objects_history ON objects;
+CREATE TRIGGER objects_history
+    AFTER INSERT OR UPDATE OR DELETE
+    ON objects
+    FOR EACH ROW
+EXECUTE PROCEDURE objects_modified_func();
+
+DROP TABLE IF EXISTS display_renders;
+DROP TABLE IF EXISTS display;
+DROP INDEX IF EXISTS objects_display;
+DROP INDEX IF EXISTS objects_df_parent;
+DROP INDEX IF EXISTS objects_type;
+
+ALTER TABLE objects_history
+    DROP COLUMN IF EXISTS df_kind,
+    DROP COLUMN IF EXISTS df_parent_id,
+    DROP COLUMN IF EXISTS df_name_type,
+    DROP COLUMN IF EXISTS df_name,
+    DROP COLUMN IF EXISTS df_bcs_name,
+    DROP COLUMN IF EXISTS df_object_type,
+    DROP COLUMN IF EXISTS df_object_id,
+    DROP COLUMN IF EXISTS display;
+
+ALTER TABLE objects
+    DROP COLUMN IF EXISTS df_kind,
+    DROP COLUMN IF EXISTS df_parent_id,
+    DROP COLUMN IF EXISTS df_name_type,
+    DROP COLUMN IF EXISTS df_name,
+    DROP COLUMN IF EXISTS df_bcs_name,
+    DROP COLUMN IF EXISTS df_object_type,
+    DROP COLUMN IF EXISTS df_object_id,
+    DROP COLUMN IF EXISTS display;
@@ -0,0 +1,94 @@
+-- Dynamic field columns, only set for `0x2::dynamic_field::Field` objects. For dynamic object fields,
+-- `df_object_id` is the ID of the child object and `df_object_type` is read from the child.
+-- `display` holds the object's fields rendered with the latest published `Display` of its type.
+ALTER TABLE objects
+    ADD COLUMN df_kind        VARCHAR,
+    ADD COLUMN df_parent_id   address,
+    ADD COLUMN df_name_type   VARCHAR,
+    ADD COLUMN df_name        JSONB,
+    ADD COLUMN df_bcs_name    bytea,
+    ADD COLUMN df_object_type VARCHAR,
+    ADD COLUMN df_object_id   address,
+    ADD COLUMN display        JSONB;
+
+ALTER TABLE objects_history
+    ADD COLUMN df_kind        VARCHAR,
+    ADD COLUMN df_parent_id   address,
+    ADD COLUMN df_name_type   VARCHAR,
+    ADD COLUMN df_name        JSONB,
+    ADD COLUMN df_bcs_name    bytea,
+    ADD COLUMN df_object_type VARCHAR,
+    ADD COLUMN df_object_id   address,
+    ADD COLUMN display        JSONB;
+
+CREATE INDEX objects_type ON objects (object_type, object_id);
+CREATE INDEX objects_df_parent ON objects (df_parent_id, object_id) WHERE df_parent_id IS NOT NULL;
+CREATE INDEX objects_display ON objects USING GIN (display jsonb_path_ops);
+
+-- `0x2::display::Display<T>` of each type T published by the latest `0x2::display::VersionUpdated<T>`
+-- event, `bcs` is the BCS of that event and `checkpoint` its checkpoint.
+CREATE TABLE display
+(
+    object_type VARCHAR PRIMARY KEY,
+    id          address NOT NULL,
+    version     INT     NOT NULL,
+    bcs         bytea   NOT NULL,
+    checkpoint  BIGINT  NOT NULL
+);
+
+-- Types whose objects are being re-rendered in the background with the display published in
+-- `checkpoint`, `cursor` is the last re-rendered object.
+CREATE TABLE display_renders
+(
+    object_type VARCHAR PRIMARY KEY,
+    checkpoint  BIGINT  NOT NULL,
+    cursor      address
+);
+
+CREATE OR REPLACE FUNCTION objects_modified_func() RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF (TG_OP = 'UPDATE' OR TG_OP = 'INSERT') THEN
+        INSERT INTO objects_history
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address,
+                NEW.initial_shared_version,
+                NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+                NEW.storage_rebate, NEW.bcs,
+                NEW.df_kind, NEW.df_parent_id, NEW.df_name_type, NEW.df_name, NEW.df_bcs_name,
+                NEW.df_object_type, NEW.df_object_id, NEW.display);
+        RETURN NEW;
+    ELSIF (TG_OP = 'DELETE') THEN
+        -- object deleted from the main table, archive the history for that object
+        DELETE FROM objects_history WHERE object_id = old.object_id;
+        RETURN OLD;
+    ELSE
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - Other action occurred: %, at %',TG_OP,NOW();
+        RETURN NULL;
+    END IF;
+
+EXCEPTION
+    WHEN data_exception THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [DATA EXCEPTION] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN unique_violation THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [UNIQUE] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN OTHERS THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [OTHER] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+END;
+$body$
+    LANGUAGE plpgsql;
+
+-- Re-rendering the display of an object does not create a new version of it, so updates of the
+-- `display` column alone are not recorded in the history.
+DROP TRIGGER IF EXISTS objects_history ON objects;
+CREATE TRIGGER objects_history
+    AFTER INSERT OR DELETE OR UPDATE OF epoch, checkpoint, object_id, version, object_digest, owner_type,
+    owner_address, initial_shared_version, previous_transaction, object_type, object_status,
+    has_public_transfer, storage_rebate, bcs, df_kind, df_parent_id, df_name_type, df_name, df_bcs_name,
+    df_object_type, df_object_id
+    ON objects
+    FOR EACH ROW
+EXECUTE PROCEDURE objects_modified_func();
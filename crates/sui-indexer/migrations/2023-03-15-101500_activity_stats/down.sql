@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS address_stats;
+DROP TABLE IF EXISTS address_counterparties;
+DROP TABLE IF EXISTS package_calls;
+DROP TABLE IF EXISTS network_activity;
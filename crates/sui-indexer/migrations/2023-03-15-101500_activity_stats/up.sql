@@ -0,0 +1,89 @@
+-- Aggregates of the indexed transactions, maintained by the indexer when it commits a checkpoint.
+
+-- Transactions sent or received by each address, a transaction is received by the owners of the
+-- objects it created, mutated or unwrapped, other than its sender.
+CREATE TABLE address_stats
+(
+    address                     address PRIMARY KEY,
+    transaction_count           BIGINT  NOT NULL,
+    sent_count                  BIGINT  NOT NULL,
+    received_count              BIGINT  NOT NULL,
+    first_activity_checkpoint   BIGINT  NOT NULL,
+    first_activity_timestamp_ms BIGINT  NOT NULL,
+    last_activity_checkpoint    BIGINT  NOT NULL,
+    last_activity_timestamp_ms  BIGINT  NOT NULL
+);
+
+-- Transactions between an address and each of its counterparties, recorded for both of them.
+CREATE TABLE address_counterparties
+(
+    address                    address NOT NULL,
+    counterparty               address NOT NULL,
+    transaction_count          BIGINT  NOT NULL,
+    last_activity_timestamp_ms BIGINT  NOT NULL,
+    PRIMARY KEY (address, counterparty)
+);
+CREATE INDEX address_counterparties_transaction_count ON address_counterparties (address, transaction_count DESC);
+
+-- Move calls to each package per epoch.
+CREATE TABLE package_calls
+(
+    epoch        BIGINT NOT NULL,
+    move_package TEXT   NOT NULL,
+    call_count   BIGINT NOT NULL,
+    PRIMARY KEY (epoch, move_package)
+);
+CREATE INDEX package_calls_call_count ON package_calls (epoch, call_count DESC);
+
+-- Transactions and checkpoints per minute, by checkpoint timestamp.
+CREATE TABLE network_activity
+(
+    bucket_start_ms   BIGINT PRIMARY KEY,
+    transaction_count BIGINT NOT NULL,
+    checkpoint_count  BIGINT NOT NULL
+);
+
+-- Seed the aggregates with the checkpoints already in the database.
+WITH activity AS (SELECT sender AS address, transaction_digest, checkpoint_sequence_number, timestamp_ms, TRUE AS sent
+                  FROM transactions
+                  UNION ALL
+                  SELECT r.recipient, r.transaction_digest, r.checkpoint_sequence_number, t.timestamp_ms, FALSE
+                  FROM recipients r
+                           JOIN transactions t ON t.transaction_digest = r.transaction_digest
+                  WHERE r.recipient <> t.sender)
+INSERT
+INTO address_stats
+SELECT address,
+       COUNT(DISTINCT transaction_digest),
+       COUNT(DISTINCT transaction_digest) FILTER (WHERE sent),
+       COUNT(DISTINCT transaction_digest) FILTER (WHERE NOT sent),
+       MIN(checkpoint_sequence_number),
+       MIN(timestamp_ms),
+       MAX(checkpoint_sequence_number),
+       MAX(timestamp_ms)
+FROM activity
+GROUP BY address;
+
+WITH pairs AS (SELECT DISTINCT t.sender, r.recipient, t.transaction_digest, t.timestamp_ms
+               FROM recipients r
+                        JOIN transactions t ON t.transaction_digest = r.transaction_digest
+               WHERE r.recipient <> t.sender)
+INSERT
+INTO address_counterparties
+SELECT address, counterparty, COUNT(*), MAX(timestamp_ms)
+FROM (SELECT sender AS address, recipient AS counterparty, timestamp_ms
+      FROM pairs
+      UNION ALL
+      SELECT recipient, sender, timestamp_ms
+      FROM pairs) counterparties
+GROUP BY address, counterparty;
+
+INSERT INTO package_calls
+SELECT epoch, move_package, COUNT(*)
+FROM move_calls
+GROUP BY epoch, move_package;
+
+INSERT INTO network_activity
+SELECT timestamp_ms / 60000 * 60000, SUM(total_transactions), COUNT(*)
+FROM checkpoints
+GROUP BY timestamp_ms / 60000 * 60000;
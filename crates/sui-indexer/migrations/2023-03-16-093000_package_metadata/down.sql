@@ -0,0 +1,9 @@
+DROP INDEX move_calls_function;
+DROP INDEX packages_original_package_id;
+
+ALTER TABLE packages
+    DROP COLUMN original_package_id,
+    DROP COLUMN upgrade_cap_id,
+    DROP COLUMN transaction_digest,
+    DROP COLUMN checkpoint_sequence_number,
+    DROP COLUMN modules;
@@ -0,0 +1,24 @@
+-- Upgrade lineage and normalized modules of each package version. `original_package_id` is the ID
+-- of the first version of the package, shared by all of its upgrades, and `upgrade_cap_id` is the
+-- `UpgradeCap` controlling the upgrades of the package, if any.
+ALTER TABLE packages
+    ADD COLUMN original_package_id        address,
+    ADD COLUMN upgrade_cap_id             address,
+    ADD COLUMN transaction_digest         base58digest,
+    ADD COLUMN checkpoint_sequence_number BIGINT,
+    ADD COLUMN modules                    JSONB;
+
+-- Rows indexed before this migration only get what can be derived in place, the lineage of upgrades,
+-- upgrade caps and modules are filled in by backfilling their checkpoints.
+UPDATE packages
+SET original_package_id = package_id
+WHERE version = 1;
+
+UPDATE packages
+SET transaction_digest         = objects.previous_transaction,
+    checkpoint_sequence_number = objects.checkpoint
+FROM objects
+WHERE objects.object_id = packages.package_id;
+
+CREATE INDEX packages_original_package_id ON packages (original_package_id, version);
+CREATE INDEX move_calls_function ON move_calls (move_package, move_module, move_function);
@@ -1,32 +1,66 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::IndexerError;
+use crate::store::IndexerStore;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::RpcModule;
-use sui_json_rpc::api::CoinReadApiClient;
-use sui_json_rpc::api::CoinReadApiServer;
+use sui_json_rpc::api::{cap_page_limit, CoinReadApiClient, CoinReadApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
 use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::gas_coin::GAS;
+use sui_types::parse_sui_struct_tag;
 
-pub(crate) struct CoinReadApi {
+pub(crate) struct CoinReadApi<S> {
     fullnode: HttpClient,
+    state: S,
+    method_to_be_forwarded: Vec<String>,
 }
 
-impl CoinReadApi {
-    pub fn new(fullnode_client: HttpClient) -> Self {
+impl<S: IndexerStore> CoinReadApi<S> {
+    pub fn new(state: S, fullnode_client: HttpClient) -> Self {
         Self {
+            state,
             fullnode: fullnode_client,
+            // TODO: read from config or env file
+            method_to_be_forwarded: vec![],
         }
     }
+
+    fn forward(&self, method: &str) -> bool {
+        self.method_to_be_forwarded.contains(&method.to_string())
+    }
+
+    /// Normalizes `coin_type` to the format the indexer stores types in, defaulting to SUI.
+    fn coin_type_or_sui(coin_type: Option<String>) -> Result<String, IndexerError> {
+        Ok(match coin_type {
+            Some(coin_type) => parse_sui_struct_tag(&coin_type)?.to_string(),
+            None => GAS::type_().to_string(),
+        })
+    }
+
+    fn get_coins_internal(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, IndexerError> {
+        self.state
+            .get_coins(owner, coin_type, cursor, cap_page_limit(limit))
+    }
 }
 
 #[async_trait]
-impl CoinReadApiServer for CoinReadApi {
+impl<S> CoinReadApiServer for CoinReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     async fn get_coins(
         &self,
         owner: SuiAddress,
@@ -34,9 +68,14 @@ impl CoinReadApiServer for CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<CoinPage> {
-        self.fullnode
-            .get_coins(owner, coin_type, cursor, limit)
-            .await
+        if self.forward("get_coins") {
+            return self
+                .fullnode
+                .get_coins(owner, coin_type, cursor, limit)
+                .await;
+        }
+        let coin_type = Self::coin_type_or_sui(coin_type)?;
+        Ok(self.get_coins_internal(owner, Some(coin_type), cursor, limit)?)
     }
 
     async fn get_all_coins(
@@ -45,19 +84,32 @@ impl CoinReadApiServer for CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<CoinPage> {
-        self.fullnode.get_all_coins(owner, cursor, limit).await
+        if self.forward("get_all_coins") {
+            return self.fullnode.get_all_coins(owner, cursor, limit).await;
+        }
+        Ok(self.get_coins_internal(owner, None, cursor, limit)?)
     }
 
+    /// Balances are read from the `balances` view, which is refreshed every
+    /// `--balances-refresh-interval-ms`, so they can lag `get_coins` by up to that interval.
     async fn get_balance(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> RpcResult<Balance> {
-        self.fullnode.get_balance(owner, coin_type).await
+        if self.forward("get_balance") {
+            return self.fullnode.get_balance(owner, coin_type).await;
+        }
+        let coin_type = Self::coin_type_or_sui(coin_type)?;
+        Ok(self.state.get_balance(owner, coin_type)?)
     }
 
+    /// Like `get_balance`, the balances can lag `get_all_coins` by up to the refresh interval.
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
-        self.fullnode.get_all_balances(owner).await
+        if self.forward("get_all_balances") {
+            return self.fullnode.get_all_balances(owner).await;
+        }
+        Ok(self.state.get_all_balances(owner)?)
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<SuiCoinMetadata> {
@@ -69,7 +121,10 @@ impl CoinReadApiServer for CoinReadApi {
     }
 }
 
-impl SuiRpcModule for CoinReadApi {
+impl<S> SuiRpcModule for CoinReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
     }
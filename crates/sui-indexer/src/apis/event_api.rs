@@ -1,15 +1,23 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
+use futures::StreamExt;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
+use mysten_metrics::spawn_monitored_task;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::warn;
 
-use sui_core::event_handler::EventHandler;
+use sui_core::event_handler::{EventHandler, EVENT_DISPATCH_BUFFER_SIZE};
 use sui_json_rpc::api::EventReadApiClient;
 use sui_json_rpc::api::EventReadApiServer;
 use sui_json_rpc::event_api::spawn_subscription;
@@ -22,6 +30,8 @@ use sui_types::event::EventID;
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
 
+const EVENT_REPLAY_PAGE_SIZE: usize = 100;
+
 pub(crate) struct EventReadApi<S> {
     state: S,
     fullnode: HttpClient,
@@ -29,7 +39,10 @@ pub(crate) struct EventReadApi<S> {
     method_to_be_forwarded: Vec<String>,
 }
 
-impl<S: IndexerStore> EventReadApi<S> {
+impl<S> EventReadApi<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
     pub fn new(state: S, fullnode_client: HttpClient, event_handler: Arc<EventHandler>) -> Self {
         Self {
             state,
@@ -40,6 +53,77 @@ impl<S: IndexerStore> EventReadApi<S> {
         }
     }
 
+    /// Streams the persisted events matching `filter` after the exclusive `cursor`, then the live
+    /// events, each event exactly once.
+    ///
+    /// The live subscription is opened before reading the first page, so that no event is missed
+    /// between the end of the replay and live delivery. Events are dispatched to live subscribers
+    /// only after their checkpoint has been persisted, so the live events that were replayed as
+    /// well all belong to the checkpoints from the latest persisted one at subscription on, and only
+    /// the replayed events of those checkpoints are remembered to be skipped.
+    fn replay_and_subscribe(
+        &self,
+        filter: EventFilter,
+        cursor: EventID,
+    ) -> Result<ReceiverStream<SuiEvent>, IndexerError> {
+        let latest_checkpoint = self.state.get_latest_checkpoint_sequence_number()?;
+        let overlap_start_ms = if latest_checkpoint < 0 {
+            0
+        } else {
+            self.state
+                .get_checkpoint((latest_checkpoint as u64).into())?
+                .timestamp_ms as u64
+        };
+        let mut live = self.event_handler.subscribe(filter.clone());
+        // Read the first page right away, to reject unknown cursors and unsupported filters.
+        let mut page = self.state.get_events(
+            filter.clone(),
+            Some(cursor),
+            Some(EVENT_REPLAY_PAGE_SIZE),
+            false,
+        )?;
+
+        let state = self.state.clone();
+        let (tx, rx) = mpsc::channel(EVENT_DISPATCH_BUFFER_SIZE);
+        spawn_monitored_task!(async move {
+            let mut replayed = HashSet::new();
+            loop {
+                for event in page.data {
+                    if event.timestamp_ms.map_or(true, |ts| ts >= overlap_start_ms) {
+                        replayed.insert(event.id.clone());
+                    }
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                if !page.has_next_page {
+                    break;
+                }
+                page = match state.get_events(
+                    filter.clone(),
+                    page.next_cursor,
+                    Some(EVENT_REPLAY_PAGE_SIZE),
+                    false,
+                ) {
+                    Ok(page) => page,
+                    Err(e) => {
+                        warn!("Failed to replay events with error: {:?}", e);
+                        return;
+                    }
+                };
+            }
+            while let Some(event) = live.next().await {
+                if replayed.remove(&event.id) {
+                    continue;
+                }
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(ReceiverStream::new(rx))
+    }
+
     pub fn get_events_internal(
         &self,
         query: EventFilter,
@@ -55,7 +139,7 @@ impl<S: IndexerStore> EventReadApi<S> {
 #[async_trait]
 impl<S> EventReadApiServer for EventReadApi<S>
 where
-    S: IndexerStore + Sync + Send + 'static,
+    S: IndexerStore + Clone + Sync + Send + 'static,
 {
     async fn query_events(
         &self,
@@ -77,8 +161,20 @@ where
         Ok(self.get_events_internal(query, cursor, limit, descending_order)?)
     }
 
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
-        spawn_subscription(sink, self.event_handler.subscribe(filter));
+    fn subscribe_event(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, self.event_handler.subscribe(filter));
+            return Ok(());
+        };
+        match self.replay_and_subscribe(filter, cursor) {
+            Ok(events) => spawn_subscription(sink, events),
+            Err(e) => sink.reject(CallError::InvalidParams(anyhow!(e)))?,
+        }
         Ok(())
     }
     async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
@@ -88,7 +184,7 @@ where
 
 impl<S> SuiRpcModule for EventReadApi<S>
 where
-    S: IndexerStore + Sync + Send + 'static,
+    S: IndexerStore + Clone + Sync + Send + 'static,
 {
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
@@ -0,0 +1,214 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::store::IndexerStore;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use std::collections::BTreeMap;
+use sui_json_rpc::api::{cap_page_limit, ExtendedApiServer};
+use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc_types::{
+    AddressCounterparty, AddressStats, EpochPage, MoveFunctionCaller, NetworkTps, ObjectsPage,
+    PackageCallCount, SuiMoveNormalizedModule, SuiObjectsQuery, SuiPackageVersion, ValidatorApy,
+    ValidatorApys, ValidatorEpochInfo,
+};
+use sui_open_rpc::Module;
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
+
+/// Number of past epochs the validator APYs are averaged over.
+const APY_EPOCH_WINDOW: usize = 30;
+const MILLIS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+const DEFAULT_TPS_WINDOW_MS: u64 = 60_000;
+
+pub(crate) struct ExtendedApi<S> {
+    state: S,
+}
+
+impl<S: IndexerStore> ExtendedApi<S> {
+    pub fn new(state: S) -> Self {
+        Self { state }
+    }
+}
+
+/// SUI per pool token of the validator's staking pool.
+fn exchange_rate(validator: &ValidatorEpochInfo) -> f64 {
+    if validator.exchange_rate_pool_token_amount == 0 {
+        1.0
+    } else {
+        validator.exchange_rate_sui_amount as f64 / validator.exchange_rate_pool_token_amount as f64
+    }
+}
+
+#[async_trait]
+impl<S> ExtendedApiServer for ExtendedApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    async fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage> {
+        let limit = cap_page_limit(limit);
+        let mut epochs =
+            self.state
+                .get_epochs(cursor, limit + 1, descending_order.unwrap_or_default())?;
+        let has_next_page = epochs.len() > limit;
+        epochs.truncate(limit);
+        let next_cursor = has_next_page
+            .then(|| epochs.last().map(|e| e.epoch))
+            .flatten();
+        Ok(EpochPage {
+            data: epochs,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
+        // Latest epoch first, plus one more epoch as the baseline of the window.
+        let epochs = self.state.get_epochs(None, APY_EPOCH_WINDOW + 1, true)?;
+        let Some(latest) = epochs.first() else {
+            return Ok(ValidatorApys {
+                apys: vec![],
+                epoch: 0,
+            });
+        };
+
+        // Oldest recorded state of each validator within the window.
+        let mut oldest = BTreeMap::new();
+        for epoch in &epochs {
+            for validator in &epoch.validators {
+                oldest.insert(
+                    validator.validator_address,
+                    (epoch.epoch_end_timestamp, validator),
+                );
+            }
+        }
+
+        let apys = latest
+            .validators
+            .iter()
+            .map(|validator| {
+                let apy = match oldest.get(&validator.validator_address) {
+                    Some((timestamp, baseline)) if *timestamp < latest.epoch_end_timestamp => {
+                        let elapsed = (latest.epoch_end_timestamp - timestamp) as f64;
+                        let growth = exchange_rate(validator) / exchange_rate(baseline);
+                        growth.powf(MILLIS_PER_YEAR / elapsed) - 1.0
+                    }
+                    _ => 0.0,
+                };
+                ValidatorApy {
+                    address: validator.validator_address,
+                    apy,
+                }
+            })
+            .collect();
+
+        Ok(ValidatorApys {
+            apys,
+            epoch: latest.epoch,
+        })
+    }
+
+    async fn query_objects(
+        &self,
+        query: SuiObjectsQuery,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let limit = cap_page_limit(limit);
+        let SuiObjectsQuery {
+            object_type,
+            display,
+            options,
+        } = query;
+        let mut objects = self
+            .state
+            .query_objects(object_type, display, cursor, limit + 1)?;
+        let has_next_page = objects.len() > limit;
+        objects.truncate(limit);
+        let next_cursor = objects
+            .last()
+            .map(|object| object.get_object_ref().map(|(object_id, _, _)| object_id))
+            .transpose()?;
+        let options = options.unwrap_or_default();
+        let data = objects
+            .into_iter()
+            .map(|object| {
+                object.try_into_object_response(options.clone(), self.state.module_cache())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ObjectsPage {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_address_stats(&self, address: SuiAddress) -> RpcResult<Option<AddressStats>> {
+        Ok(self.state.get_address_stats(address)?)
+    }
+
+    async fn get_address_counterparties(
+        &self,
+        address: SuiAddress,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<AddressCounterparty>> {
+        Ok(self
+            .state
+            .get_address_counterparties(address, cap_page_limit(limit))?)
+    }
+
+    async fn get_top_packages(
+        &self,
+        epoch: Option<EpochId>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageCallCount>> {
+        Ok(self.state.get_top_packages(epoch, cap_page_limit(limit))?)
+    }
+
+    async fn get_network_tps(&self, window_ms: Option<u64>) -> RpcResult<NetworkTps> {
+        Ok(self
+            .state
+            .get_network_tps(window_ms.unwrap_or(DEFAULT_TPS_WINDOW_MS))?)
+    }
+
+    async fn get_package_versions(&self, package: ObjectID) -> RpcResult<Vec<SuiPackageVersion>> {
+        Ok(self.state.get_package_versions(package)?)
+    }
+
+    async fn get_package_modules(
+        &self,
+        package: ObjectID,
+    ) -> RpcResult<Option<BTreeMap<String, SuiMoveNormalizedModule>>> {
+        Ok(self.state.get_package_modules(package)?)
+    }
+
+    async fn get_function_callers(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionCaller>> {
+        Ok(self
+            .state
+            .get_function_callers(package, &module, &function, cap_page_limit(limit))?)
+    }
+}
+
+impl<S> SuiRpcModule for ExtendedApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        sui_json_rpc::api::ExtendedApiOpenRpc::module_doc()
+    }
+}
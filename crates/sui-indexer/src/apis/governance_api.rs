@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::store::IndexerStore;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
@@ -13,20 +14,32 @@ use sui_open_rpc::Module;
 use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
-pub(crate) struct GovernanceReadApi {
+pub(crate) struct GovernanceReadApi<S> {
     fullnode: HttpClient,
+    state: S,
+    method_to_be_forwarded: Vec<String>,
 }
 
-impl GovernanceReadApi {
-    pub fn new(fullnode_client: HttpClient) -> Self {
+impl<S: IndexerStore> GovernanceReadApi<S> {
+    pub fn new(state: S, fullnode_client: HttpClient) -> Self {
         Self {
+            state,
             fullnode: fullnode_client,
+            // TODO: read from config or env file
+            method_to_be_forwarded: vec![],
         }
     }
+
+    fn forward(&self, method: &str) -> bool {
+        self.method_to_be_forwarded.contains(&method.to_string())
+    }
 }
 
 #[async_trait]
-impl GovernanceReadApiServer for GovernanceReadApi {
+impl<S> GovernanceReadApiServer for GovernanceReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     async fn get_stakes_by_ids(
         &self,
         staked_sui_id: Vec<ObjectID>,
@@ -38,7 +51,14 @@ impl GovernanceReadApiServer for GovernanceReadApi {
     }
 
     async fn get_committee_info(&self, epoch: Option<EpochId>) -> RpcResult<SuiCommittee> {
-        self.fullnode.get_committee_info(epoch).await
+        if self.forward("get_committee_info") {
+            return self.fullnode.get_committee_info(epoch).await;
+        }
+        // Committees are only indexed from the first epoch change the indexer has seen.
+        match self.state.get_committee(epoch)? {
+            Some(committee) => Ok(committee),
+            None => self.fullnode.get_committee_info(epoch).await,
+        }
     }
 
     async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
@@ -50,7 +70,10 @@ impl GovernanceReadApiServer for GovernanceReadApi {
     }
 }
 
-impl SuiRpcModule for GovernanceReadApi {
+impl<S> SuiRpcModule for GovernanceReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
     }
@@ -3,6 +3,7 @@
 
 mod coin_api;
 mod event_api;
+mod extended_api;
 mod governance_api;
 mod read_api;
 mod transaction_builder_api;
@@ -10,6 +11,7 @@ mod write_api;
 
 pub(crate) use coin_api::CoinReadApi;
 pub(crate) use event_api::EventReadApi;
+pub(crate) use extended_api::ExtendedApi;
 pub(crate) use governance_api::GovernanceReadApi;
 pub(crate) use read_api::ReadApi;
 pub(crate) use transaction_builder_api::TransactionBuilderApi;
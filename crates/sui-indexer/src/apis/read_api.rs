@@ -23,6 +23,7 @@ use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TxSequenceNumb
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::ObjectRead;
 use sui_types::query::TransactionFilter;
 
 pub(crate) struct ReadApi<S> {
@@ -204,8 +205,50 @@ impl<S: IndexerStore> ReadApi<S> {
         object_id: ObjectID,
         options: Option<SuiObjectDataOptions>,
     ) -> Result<SuiObjectResponse, IndexerError> {
-        let read = self.state.get_object(object_id, None)?;
-        Ok((read, options.unwrap_or_default()).try_into()?)
+        let options = options.unwrap_or_default();
+        match self.state.get_latest_object(object_id)? {
+            Some(object) => object.try_into_object_response(options, self.state.module_cache()),
+            None => Ok((ObjectRead::NotExists(object_id), options).try_into()?),
+        }
+    }
+
+    fn get_dynamic_fields_internal(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<DynamicFieldPage, IndexerError> {
+        let limit = cap_page_limit(limit);
+        let mut fields = self
+            .state
+            .get_dynamic_fields(parent_object_id, cursor, limit + 1)?;
+        let has_next_page = fields.len() > limit;
+        fields.truncate(limit);
+        let next_cursor = fields.last().map(|field| field.object_id).or(cursor);
+        Ok(Page {
+            data: fields,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_dynamic_field_object_internal(
+        &self,
+        parent_object_id: ObjectID,
+        name: DynamicFieldName,
+    ) -> Result<SuiObjectResponse, IndexerError> {
+        let object = self
+            .state
+            .get_dynamic_field_object(parent_object_id, &name)?
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Cannot find dynamic field [{name:?}] for object [{parent_object_id}]."
+                ))
+            })?;
+        object.try_into_object_response(
+            SuiObjectDataOptions::full_content(),
+            self.state.module_cache(),
+        )
     }
 
     fn get_latest_checkpoint_sequence_number_internal(&self) -> Result<u64, IndexerError> {
@@ -273,9 +316,16 @@ where
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<DynamicFieldPage> {
-        self.fullnode
-            .get_dynamic_fields(parent_object_id, cursor, limit)
-            .await
+        if self
+            .method_to_be_forwarded
+            .contains(&"get_dynamic_fields".to_string())
+        {
+            return self
+                .fullnode
+                .get_dynamic_fields(parent_object_id, cursor, limit)
+                .await;
+        }
+        Ok(self.get_dynamic_fields_internal(parent_object_id, cursor, limit)?)
     }
 
     async fn get_dynamic_field_object(
@@ -283,9 +333,16 @@ where
         parent_object_id: ObjectID,
         name: DynamicFieldName,
     ) -> RpcResult<SuiObjectResponse> {
-        self.fullnode
-            .get_dynamic_field_object(parent_object_id, name)
-            .await
+        if self
+            .method_to_be_forwarded
+            .contains(&"get_dynamic_field_object".to_string())
+        {
+            return self
+                .fullnode
+                .get_dynamic_field_object(parent_object_id, name)
+                .await;
+        }
+        Ok(self.get_dynamic_field_object_internal(parent_object_id, name)?)
     }
 
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
@@ -444,6 +501,14 @@ where
         Ok(self.get_latest_checkpoint_sequence_number_internal()?)
     }
 
+    async fn get_lowest_indexed_checkpoint_sequence_number(
+        &self,
+    ) -> RpcResult<CheckpointSequenceNumber> {
+        self.fullnode
+            .get_lowest_indexed_checkpoint_sequence_number()
+            .await
+    }
+
     async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
         if self
             .method_to_be_forwarded
@@ -1,91 +1,113 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use rand::Rng;
-use sui_indexer::new_rpc_client;
-use sui_json_rpc_types::{CheckpointId, SuiTransactionResponseOptions};
+use sui_indexer::consistency_checker::ConsistencyChecker;
+use sui_indexer::{new_pg_connection_pool, new_rpc_client};
+use sui_node::metrics::start_prometheus_server;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
     info!("Running correctness check for indexer...");
     let test_config = TestConfig::parse();
     let fn_rpc_client = new_rpc_client(&test_config.fn_rpc_client_url).await?;
     let indexer_rpc_client = new_rpc_client(&test_config.indexer_rpc_client_url).await?;
-
-    let latest_checkpoint = indexer_rpc_client
-        .read_api()
-        .get_latest_checkpoint_sequence_number()
-        .await?;
-
-    let num = rand::thread_rng().gen_range(10..100);
-
-    let target_checkpoint = if latest_checkpoint - num > 0 {
-        latest_checkpoint - num
-    } else {
-        1
+    let pg_pool = match &test_config.db_url {
+        Some(db_url) => Some(new_pg_connection_pool(db_url).await?),
+        None => None,
     };
+    let registry_service = start_prometheus_server(
+        format!(
+            "{}:{}",
+            test_config.client_metric_host, test_config.client_metric_port
+        )
+        .parse()?,
+    );
+    let checker = ConsistencyChecker::new(
+        fn_rpc_client,
+        indexer_rpc_client.clone(),
+        pg_pool,
+        &registry_service.default_registry(),
+    );
 
-    let fn_checkpoint = fn_rpc_client
-        .read_api()
-        .get_checkpoint(CheckpointId::SequenceNumber(target_checkpoint))
-        .await?;
+    if test_config.continuous {
+        checker
+            .run_forever(
+                test_config.window,
+                Duration::from_millis(test_config.interval_ms),
+            )
+            .await;
+        return Ok(());
+    }
 
-    let indexer_checkpoint = indexer_rpc_client
+    let latest_checkpoint = indexer_rpc_client
         .read_api()
-        .get_checkpoint(CheckpointId::SequenceNumber(target_checkpoint))
+        .get_latest_checkpoint_sequence_number()
         .await?;
 
-    assert_eq!(
-        fn_checkpoint.transactions.len(),
-        indexer_checkpoint.transactions.len(),
-        "Checkpoint number {} length is not the same for FN and Indexer",
-        target_checkpoint
-    );
-
-    let fn_checkpoint_transactions = fn_checkpoint.transactions;
-    let indexer_checkpoint_transactions = indexer_checkpoint.transactions;
-
-    for i in 0..fn_checkpoint_transactions.len() {
-        let fn_txn_digest = fn_checkpoint_transactions.get(i).cloned();
-        let idx_txn_digest = indexer_checkpoint_transactions.get(i).cloned();
-        assert_eq!(
-            fn_txn_digest, idx_txn_digest,
-            "Checkpoint transactions mismatch found in {}",
-            target_checkpoint
-        );
-
-        if let (Some(fn_txn_digest), Some(idx_txn_digest)) = (fn_txn_digest, idx_txn_digest) {
-            let fetch_options = SuiTransactionResponseOptions::new()
-                .with_events()
-                .with_effects()
-                .with_input();
-            let fn_sui_txn_response = fn_rpc_client
-                .read_api()
-                .get_transaction_with_options(fn_txn_digest, fetch_options.clone())
-                .await?;
-            let indexer_sui_txn_response = indexer_rpc_client
-                .read_api()
-                .get_transaction_with_options(idx_txn_digest, fetch_options)
-                .await?;
-            assert_eq!(
-                fn_sui_txn_response, indexer_sui_txn_response,
-                "Checkpoint transactions mismatch found in {}",
-                target_checkpoint
-            );
+    // Verify the given range, e.g. after a backfill, or a random recent checkpoint otherwise.
+    let target_checkpoints = match test_config.start_checkpoint {
+        Some(start_checkpoint) => {
+            start_checkpoint..=test_config.end_checkpoint.unwrap_or(latest_checkpoint)
         }
+        None => {
+            let num = rand::thread_rng().gen_range(10..100);
+            let target_checkpoint = if latest_checkpoint - num > 0 {
+                latest_checkpoint - num
+            } else {
+                1
+            };
+            target_checkpoint..=target_checkpoint
+        }
+    };
+    let mut mismatches = 0;
+    for target_checkpoint in target_checkpoints {
+        mismatches += checker.check_checkpoint(target_checkpoint).await?;
+    }
+    if mismatches > 0 {
+        return Err(anyhow!(
+            "Found {mismatches} mismatches between the fullnode and the indexer"
+        ));
     }
-
     Ok(())
 }
 
 #[derive(Parser)]
-#[clap(name = "Transactions Test")]
+#[clap(name = "Transactions Test", rename_all = "kebab-case")]
 pub struct TestConfig {
     #[clap(long)]
     pub fn_rpc_client_url: String,
     #[clap(long)]
     pub indexer_rpc_client_url: String,
+    /// Check all checkpoints from this one, instead of a random recent one.
+    #[clap(long)]
+    pub start_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Last checkpoint to check, inclusive. Defaults to the latest indexed checkpoint.
+    #[clap(long, requires = "start-checkpoint")]
+    pub end_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Keep checking random recent checkpoints instead of exiting after one check.
+    #[clap(long, conflicts_with = "start-checkpoint")]
+    pub continuous: bool,
+    /// Time between two checks in continuous mode.
+    #[clap(long, default_value = "1000")]
+    pub interval_ms: u64,
+    /// Number of latest indexed checkpoints continuous checks are sampled from.
+    #[clap(long, default_value = "1000")]
+    pub window: u64,
+    /// Indexer database to record mismatches in, in the `error_logs` table.
+    #[clap(long)]
+    pub db_url: Option<String>,
+    #[clap(long, default_value = "0.0.0.0")]
+    pub client_metric_host: String,
+    #[clap(long, default_value = "9185")]
+    pub client_metric_port: u16,
 }
@@ -0,0 +1,414 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use prometheus::Registry;
+use rand::Rng;
+use sui_json_rpc_types::{
+    Balance, CheckpointId, SuiObjectDataOptions, SuiPastObjectResponse, SuiTransactionDataAPI,
+    SuiTransactionEffectsAPI, SuiTransactionResponseOptions,
+};
+use sui_sdk::error::Error;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::{error, info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerConsistencyCheckerMetrics;
+use crate::utils::log_errors_to_pg;
+use crate::{get_pg_pool_connection, PgConnectionPool};
+
+const CHECKPOINT: &str = "checkpoint";
+const TRANSACTION: &str = "transaction";
+const OBJECT: &str = "object";
+const BALANCE: &str = "balance";
+
+/// How long to wait for the indexer to catch up with the fullnode before comparing balances.
+const CATCH_UP_TIMEOUT: Duration = Duration::from_secs(30);
+const CATCH_UP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Compares the answers of an indexer with those of a fullnode for checkpoints, their
+/// transactions, the objects these changed and the balances of their senders.
+///
+/// Comparisons are made at fixed points only, so that data changing while it is read is never
+/// reported: objects are compared at the version the indexer returns, and balances only if the
+/// fullnode returns the same balances before and after the indexer has caught up with it, and the
+/// balances of the indexer have been refreshed since.
+/// Mismatches are counted by kind of data and recorded in `error_logs` when a database is set,
+/// with the RPC call and both answers, so that they can be reproduced.
+pub struct ConsistencyChecker {
+    fullnode: SuiClient,
+    indexer: SuiClient,
+    pg_pool: Option<PgConnectionPool>,
+    metrics: IndexerConsistencyCheckerMetrics,
+}
+
+impl ConsistencyChecker {
+    pub fn new(
+        fullnode: SuiClient,
+        indexer: SuiClient,
+        pg_pool: Option<PgConnectionPool>,
+        prometheus_registry: &Registry,
+    ) -> Self {
+        Self {
+            fullnode,
+            indexer,
+            pg_pool,
+            metrics: IndexerConsistencyCheckerMetrics::new(prometheus_registry),
+        }
+    }
+
+    /// Checks a random checkpoint among the latest `window` indexed ones every `interval`.
+    pub async fn run_forever(&self, window: u64, interval: Duration) {
+        info!("Indexer consistency checker started...");
+        loop {
+            if let Err(e) = self.check_random_checkpoint(window).await {
+                self.metrics.total_check_errors.inc();
+                warn!("Consistency check failed with error: {:?}", e);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn check_random_checkpoint(&self, window: u64) -> Result<usize, IndexerError> {
+        let latest = self.indexer_latest_checkpoint().await?;
+        let target = rand::thread_rng().gen_range(latest.saturating_sub(window)..=latest);
+        self.check_checkpoint(target).await
+    }
+
+    /// Compares checkpoint `seq` and the data derived from it, and returns the number of
+    /// mismatches found.
+    pub async fn check_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<usize, IndexerError> {
+        info!("Checking checkpoint {seq}...");
+        let mut mismatches = vec![];
+
+        let fn_checkpoint = self
+            .fullnode
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(seq))
+            .await
+            .map_err(fullnode_error)?;
+        let indexer_checkpoint = self
+            .indexer
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(seq))
+            .await
+            .map_err(indexer_error)?;
+        self.metrics
+            .total_checks
+            .with_label_values(&[CHECKPOINT])
+            .inc();
+        if fn_checkpoint.transactions != indexer_checkpoint.transactions {
+            mismatches.push(self.mismatch(
+                CHECKPOINT,
+                format!(
+                    "sui_getCheckpoint(\"{seq}\"): fullnode transactions {:?}, indexer transactions {:?}",
+                    fn_checkpoint.transactions, indexer_checkpoint.transactions
+                ),
+            ));
+        }
+
+        let mut changed_objects = BTreeSet::new();
+        let mut senders = BTreeSet::new();
+        let options = SuiTransactionResponseOptions::new()
+            .with_events()
+            .with_effects()
+            .with_input();
+        for digest in fn_checkpoint.transactions {
+            let fn_transaction = self
+                .fullnode
+                .read_api()
+                .get_transaction_with_options(digest, options.clone())
+                .await
+                .map_err(fullnode_error)?;
+            let indexer_transaction = self
+                .indexer
+                .read_api()
+                .get_transaction_with_options(digest, options.clone())
+                .await
+                .map_err(indexer_error)?;
+            self.metrics
+                .total_checks
+                .with_label_values(&[TRANSACTION])
+                .inc();
+            if fn_transaction != indexer_transaction {
+                mismatches.push(self.mismatch(
+                    TRANSACTION,
+                    format!(
+                        "sui_getTransaction(\"{digest}\", {options:?}) of checkpoint {seq}: fullnode {:?}, indexer {:?}",
+                        fn_transaction, indexer_transaction
+                    ),
+                ));
+            }
+            if let Some(effects) = &fn_transaction.effects {
+                changed_objects.extend(
+                    effects
+                        .created()
+                        .iter()
+                        .chain(effects.mutated())
+                        .chain(effects.unwrapped())
+                        .map(|o| o.reference.object_id),
+                );
+            }
+            if let Some(transaction) = &fn_transaction.transaction {
+                senders.insert(*transaction.data.sender());
+            }
+        }
+
+        for object_id in changed_objects {
+            if let Some(mismatch) = self.check_object(object_id).await? {
+                mismatches.push(mismatch);
+            }
+        }
+        mismatches.extend(self.check_balances(&senders).await?);
+
+        self.metrics.latest_checked_checkpoint.set(seq as i64);
+        let count = mismatches.len();
+        self.report(mismatches);
+        Ok(count)
+    }
+
+    /// Compares the latest version of the object known to the indexer with the same version on
+    /// the fullnode. Checked objects were changed in recent checkpoints, so a version the fullnode
+    /// does not know is a mismatch.
+    async fn check_object(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Option<IndexerError>, IndexerError> {
+        let options = SuiObjectDataOptions::bcs_lossless();
+        let indexer_object = self
+            .indexer
+            .read_api()
+            .get_object_with_options(object_id, options.clone())
+            .await
+            .map_err(indexer_error)?;
+        // Deleted or wrapped since, nothing to compare at a fixed version.
+        let Ok(indexer_object) = indexer_object.into_object() else {
+            self.metrics.total_inconclusive_checks.with_label_values(&[OBJECT]).inc();
+            return Ok(None);
+        };
+        let version = indexer_object.version;
+        let fn_object = self
+            .fullnode
+            .read_api()
+            .try_get_parsed_past_object(object_id, version, options.clone())
+            .await
+            .map_err(fullnode_error)?;
+        self.metrics.total_checks.with_label_values(&[OBJECT]).inc();
+        match fn_object {
+            SuiPastObjectResponse::VersionFound(fn_object) if fn_object == indexer_object => {
+                Ok(None)
+            }
+            fn_object => Ok(Some(self.mismatch(
+                OBJECT,
+                format!(
+                    "sui_tryGetPastObject(\"{object_id}\", {version}, {options:?}): fullnode {:?}, indexer {:?}",
+                    fn_object, indexer_object
+                ),
+            ))),
+        }
+    }
+
+    /// Compares the balances of each of `addresses` that do not change on the fullnode while the
+    /// indexer catches up with it. The balances of all addresses are read concurrently, and the
+    /// indexer is waited for once.
+    async fn check_balances(
+        &self,
+        addresses: &BTreeSet<SuiAddress>,
+    ) -> Result<Vec<IndexerError>, IndexerError> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+        let fn_before = try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.fullnode_balances(*address)),
+        )
+        .await?;
+        let fn_checkpoint = self
+            .fullnode
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .map_err(fullnode_error)?;
+        if !self.wait_for_indexer(fn_checkpoint).await? {
+            self.metrics
+                .total_inconclusive_checks
+                .with_label_values(&[BALANCE])
+                .inc_by(addresses.len() as u64);
+            return Ok(vec![]);
+        }
+        let indexer_balances = try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.indexer_balances(*address)),
+        )
+        .await?;
+        let fn_after = try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.fullnode_balances(*address)),
+        )
+        .await?;
+
+        let mut mismatches = vec![];
+        for (((address, fn_before), fn_after), indexer_balances) in addresses
+            .iter()
+            .zip(fn_before)
+            .zip(fn_after)
+            .zip(indexer_balances)
+        {
+            // The balances of the indexer are refreshed on a timer, while its coins are up to
+            // date with the indexed checkpoints. Balances that disagree with the coins were not
+            // refreshed since the fullnode checkpoint yet, so they can't be compared.
+            let stale = fn_after != indexer_balances
+                && self.indexer_coin_balances(*address).await? != indexer_balances;
+            if fn_before != fn_after || stale {
+                self.metrics
+                    .total_inconclusive_checks
+                    .with_label_values(&[BALANCE])
+                    .inc();
+                continue;
+            }
+            self.metrics
+                .total_checks
+                .with_label_values(&[BALANCE])
+                .inc();
+            if fn_after != indexer_balances {
+                mismatches.push(self.mismatch(
+                    BALANCE,
+                    format!(
+                        "sui_getAllBalances(\"{address}\") at checkpoint {fn_checkpoint}: fullnode {:?}, indexer {:?}",
+                        fn_after, indexer_balances
+                    ),
+                ));
+            }
+        }
+        Ok(mismatches)
+    }
+
+    async fn fullnode_balances(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BTreeMap<String, (usize, u128)>, IndexerError> {
+        Ok(summarize_balances(
+            self.fullnode
+                .coin_read_api()
+                .get_all_balances(address)
+                .await
+                .map_err(fullnode_error)?,
+        ))
+    }
+
+    async fn indexer_balances(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BTreeMap<String, (usize, u128)>, IndexerError> {
+        Ok(summarize_balances(
+            self.indexer
+                .coin_read_api()
+                .get_all_balances(address)
+                .await
+                .map_err(indexer_error)?,
+        ))
+    }
+
+    /// Sums the coins of `address` served by the indexer, which unlike its balances are not
+    /// refreshed on a timer.
+    async fn indexer_coin_balances(
+        &self,
+        address: SuiAddress,
+    ) -> Result<BTreeMap<String, (usize, u128)>, IndexerError> {
+        let mut balances = BTreeMap::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .indexer
+                .coin_read_api()
+                .get_all_coins(address, cursor, None)
+                .await
+                .map_err(indexer_error)?;
+            for coin in page.data {
+                let (count, total) = balances.entry(coin.coin_type).or_insert((0, 0));
+                *count += 1;
+                *total += coin.balance as u128;
+            }
+            if !page.has_next_page {
+                return Ok(balances);
+            }
+            cursor = page.next_cursor;
+        }
+    }
+
+    async fn indexer_latest_checkpoint(&self) -> Result<CheckpointSequenceNumber, IndexerError> {
+        self.indexer
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .map_err(indexer_error)
+    }
+
+    /// Waits until the indexer has indexed `checkpoint`, returns false on timeout.
+    async fn wait_for_indexer(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<bool, IndexerError> {
+        let deadline = tokio::time::Instant::now() + CATCH_UP_TIMEOUT;
+        while self.indexer_latest_checkpoint().await? < checkpoint {
+            if tokio::time::Instant::now() > deadline {
+                return Ok(false);
+            }
+            tokio::time::sleep(CATCH_UP_POLL_INTERVAL).await;
+        }
+        Ok(true)
+    }
+
+    fn mismatch(&self, kind: &str, context: String) -> IndexerError {
+        self.metrics
+            .total_mismatches
+            .with_label_values(&[kind])
+            .inc();
+        IndexerError::DataMismatchError(format!("{kind} mismatch, {context}"))
+    }
+
+    fn report(&self, mismatches: Vec<IndexerError>) {
+        for mismatch in &mismatches {
+            error!("{}", mismatch);
+        }
+        let Some(pg_pool) = &self.pg_pool else {
+            return;
+        };
+        match get_pg_pool_connection(pg_pool) {
+            Ok(mut pg_pool_conn) => log_errors_to_pg(&mut pg_pool_conn, mismatches),
+            Err(e) => error!("Failed to record mismatches with error {:?}", e),
+        }
+    }
+}
+
+/// Coin object count and total balance by coin type.
+fn summarize_balances(balances: Vec<Balance>) -> BTreeMap<String, (usize, u128)> {
+    balances
+        .into_iter()
+        .map(|balance| {
+            (
+                balance.coin_type,
+                (balance.coin_object_count, balance.total_balance),
+            )
+        })
+        .collect()
+}
+
+fn fullnode_error(e: Error) -> IndexerError {
+    IndexerError::FullNodeReadingError(format!("{:?}", e))
+}
+
+fn indexer_error(e: Error) -> IndexerError {
+    IndexerError::UncategorizedError(anyhow::anyhow!("Failed to read the indexer: {:?}", e))
+}
@@ -9,6 +9,9 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum IndexerError {
+    #[error("Indexer failed to write to checkpoint sink `{0}` with error: `{1}`")]
+    CheckpointSinkError(String, String),
+
     #[error("Indexer failed to convert timestamp to NaiveDateTime with error: `{0}`")]
     DateTimeParsingError(String),
 
@@ -42,6 +45,20 @@ pub enum IndexerError {
     #[error(transparent)]
     PostgresError(#[from] diesel::result::Error),
 
+    #[error("Indexer failed to build SQLite connection pool with error: `{0}`")]
+    SqliteConnectionPoolInitError(String),
+
+    #[error(
+        "Indexer failed to get a pool connection from SQLite connection pool with error: `{0}`"
+    )]
+    SqlitePoolConnectionError(String),
+
+    #[error("Indexer failed to read SQLite database with error: `{0}`")]
+    SqliteReadError(String),
+
+    #[error("Indexer failed to commit changes to SQLite database with error: `{0}`")]
+    SqliteWriteError(String),
+
     #[error("Indexer failed to initialize fullnode RPC client with error: `{0}`")]
     RpcClientInitError(String),
 
@@ -62,11 +79,15 @@ pub enum IndexerError {
 
     #[error("Invalid argument with error: `{0}`")]
     InvalidArgumentError(String),
+
+    #[error("Indexer data does not match the fullnode: `{0}`")]
+    DataMismatchError(String),
 }
 
 impl IndexerError {
     pub fn name(&self) -> String {
         match self {
+            IndexerError::CheckpointSinkError(_, _) => "CheckpointSinkError".into(),
             IndexerError::FullNodeReadingError(_) => "FullNodeReadingError".into(),
             IndexerError::PostgresReadError(_) => "PostgresReadError".into(),
             IndexerError::PostgresWriteError(_) => "PostgresWriteError".into(),
@@ -81,10 +102,17 @@ impl IndexerError {
             IndexerError::SerdeError(_) => "SerdeError".into(),
             IndexerError::NotImplementedError(_) => "NotImplementedError".into(),
             IndexerError::PostgresError(_) => "PostgresError".into(),
+            IndexerError::SqliteConnectionPoolInitError(_) => {
+                "SqliteConnectionPoolInitError".into()
+            }
+            IndexerError::SqlitePoolConnectionError(_) => "SqlitePoolConnectionError".into(),
+            IndexerError::SqliteReadError(_) => "SqliteReadError".into(),
+            IndexerError::SqliteWriteError(_) => "SqliteWriteError".into(),
             IndexerError::UncategorizedError(_) => "UncategorizedError".into(),
             IndexerError::ObjectIdParseError(_) => "ObjectIdParseError".into(),
             IndexerError::SuiError(_) => "SuiError".into(),
             IndexerError::InvalidArgumentError(_) => "InvalidArgumentError".into(),
+            IndexerError::DataMismatchError(_) => "DataMismatchError".into(),
         }
     }
 }
@@ -0,0 +1,128 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures::future::join_all;
+use mysten_metrics::spawn_monitored_task;
+use prometheus::Registry;
+use sui_core::event_handler::EventHandler;
+use sui_sdk::SuiClient;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::handlers::checkpoint_handler::CheckpointHandler;
+use crate::store::IndexerStore;
+
+const BACKFILL_RETRY_INTERVAL_IN_SECS: u64 = 10;
+const MAX_BACKFILL_ATTEMPTS: usize = 5;
+
+/// Re-processes a range of already indexed checkpoints, e.g. after a schema change or a fix of
+/// the processing of checkpoints, overwriting the rows derived from them.
+///
+/// The range is split into contiguous shares re-indexed by parallel workers. Only checkpoints up
+/// to the latest one in the `checkpoints` table are backfilled, later checkpoints are left to the
+/// live tail, which can keep running against the same database.
+pub struct BackfillHandler<S> {
+    state: S,
+    handler: Arc<CheckpointHandler<S>>,
+}
+
+impl<S> BackfillHandler<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(state: S, rpc_client: SuiClient, prometheus_registry: &Registry) -> Self {
+        let handler = CheckpointHandler::new(
+            state.clone(),
+            rpc_client,
+            Arc::new(EventHandler::default()),
+            vec![],
+            prometheus_registry,
+        );
+        Self {
+            state,
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Re-indexes checkpoints `start` to `end` inclusive with `workers` parallel workers.
+    pub async fn backfill(
+        &self,
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+        workers: usize,
+    ) -> Result<(), IndexerError> {
+        let watermark = self.state.get_latest_checkpoint_sequence_number()?;
+        if watermark < 0 || start > watermark as u64 {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Cannot backfill from checkpoint {start}, the latest indexed checkpoint is {watermark}"
+            )));
+        }
+        let end = if end > watermark as u64 {
+            warn!(
+                "Backfilling up to the latest indexed checkpoint {watermark} instead of {end}, later checkpoints are indexed by the live tail"
+            );
+            watermark as u64
+        } else {
+            end
+        };
+        if start > end {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid backfill range from checkpoint {start} to {end}"
+            )));
+        }
+
+        let workers = workers.max(1) as u64;
+        let share = (end - start + workers) / workers;
+        info!("Backfilling checkpoints {start} to {end} with {workers} workers...");
+        let tasks = (0..workers)
+            .map(|worker| start + worker * share)
+            .take_while(|share_start| *share_start <= end)
+            .map(|share_start| {
+                let share_end = (share_start + share - 1).min(end);
+                let handler = self.handler.clone();
+                spawn_monitored_task!(backfill_range(handler, share_start, share_end))
+            })
+            .collect::<Vec<_>>();
+        for result in join_all(tasks).await {
+            result.map_err(|e| IndexerError::UncategorizedError(anyhow!(e)))??;
+        }
+
+        // Balances are derived from all objects, refresh them once the range is done.
+        self.state.refresh_balances()?;
+        info!("Backfilled checkpoints {start} to {end}");
+        Ok(())
+    }
+}
+
+async fn backfill_range<S>(
+    handler: Arc<CheckpointHandler<S>>,
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+) -> Result<(), IndexerError>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    for seq in start..=end {
+        let mut attempt = 1;
+        while let Err(e) = handler.reindex_checkpoint(seq).await {
+            if attempt == MAX_BACKFILL_ATTEMPTS {
+                return Err(e);
+            }
+            warn!(
+                "Failed to backfill checkpoint {seq} with error: {:?}, retrying after {:?} secs...",
+                e, BACKFILL_RETRY_INTERVAL_IN_SECS
+            );
+            tokio::time::sleep(std::time::Duration::from_secs(
+                BACKFILL_RETRY_INTERVAL_IN_SECS,
+            ))
+            .await;
+            attempt += 1;
+        }
+    }
+    info!("Backfilled checkpoints {start} to {end}");
+    Ok(())
+}
@@ -4,39 +4,69 @@
 use crate::errors::IndexerError;
 use crate::metrics::IndexerCheckpointHandlerMetrics;
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
+use crate::models::epoch::{
+    CommitteeMember, DBEpochInfo, DBValidatorEpochInfo, SystemEpochInfoEvent,
+    ValidatorEpochInfoEvent, SUI_SYSTEM_STATE_INNER_MODULE, SYSTEM_EPOCH_INFO_EVENT,
+    VALIDATOR_EPOCH_INFO_EVENT, VALIDATOR_SET_MODULE,
+};
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
+use crate::models::stake_events::{
+    StakeEvent, StakingRequestEvent, UnstakingRequestEvent, STAKING_REQUEST_EVENT,
+    UNSTAKING_REQUEST_EVENT, VALIDATOR_MODULE,
+};
 use crate::models::transactions::Transaction;
 use crate::multi_get_full_transactions;
+use crate::sinks::{CheckpointSink, IndexedCheckpoint};
 use crate::store::{
-    CheckpointData, IndexerStore, TemporaryCheckpointStore, TemporaryEpochStore,
-    TransactionObjectChanges,
+    CheckpointData, CheckpointModuleResolver, IndexerStore, TemporaryCheckpointStore,
+    TemporaryEpochStore, TransactionObjectChanges,
 };
 use crate::types::SuiTransactionFullResponse;
+use anyhow::anyhow;
 use futures::future::join_all;
 use futures::FutureExt;
 use mysten_metrics::spawn_monitored_task;
 use prometheus::Registry;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sui_core::event_handler::EventHandler;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiGetPastObjectRequest, SuiObjectData, SuiObjectDataOptions, SuiRawData,
-    SuiTransactionDataAPI, SuiTransactionEffectsAPI,
+    OwnedObjectRef, SuiEvent, SuiGetPastObjectRequest, SuiObjectData, SuiObjectDataOptions,
+    SuiRawData, SuiTransactionDataAPI, SuiTransactionEffectsAPI,
 };
 use sui_sdk::error::Error;
 use sui_sdk::SuiClient;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::move_package::UpgradeCap;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
 use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
 const HANDLER_RETRY_INTERVAL_IN_SECS: u64 = 10;
 const MULTI_GET_CHUNK_SIZE: usize = 500;
+/// Delay before the first retry of a sink that failed to write a checkpoint, doubled on every
+/// further failure up to `SINK_MAX_RETRY_INTERVAL_IN_SECS`.
+const SINK_RETRY_INTERVAL_IN_SECS: u64 = 1;
+const SINK_MAX_RETRY_INTERVAL_IN_SECS: u64 = 300;
+/// Maximum number of missed checkpoints written to a lagging sink before the next checkpoint is
+/// handled, so that a sink coming back after a long outage does not hold back the others.
+const SINK_MAX_CATCH_UP_CHECKPOINTS: u64 = 10;
+
+/// Retry state of a sink that failed to write a checkpoint. The sink is left behind while the
+/// others move on, and is retried from its watermark once `retry_at` is reached.
+struct SinkRetry {
+    failures: u32,
+    retry_at: Instant,
+}
 
 pub struct CheckpointHandler<S> {
     state: S,
     rpc_client: SuiClient,
     event_handler: Arc<EventHandler>,
+    sinks: Vec<Arc<dyn CheckpointSink>>,
     metrics: IndexerCheckpointHandlerMetrics,
 }
 
@@ -48,12 +78,14 @@ where
         state: S,
         rpc_client: SuiClient,
         event_handler: Arc<EventHandler>,
+        sinks: Vec<Arc<dyn CheckpointSink>>,
         prometheus_registry: &Registry,
     ) -> Self {
         Self {
             state,
             rpc_client,
             event_handler,
+            sinks,
             metrics: IndexerCheckpointHandlerMetrics::new(prometheus_registry),
         }
     }
@@ -77,18 +109,29 @@ where
 
     async fn start(&self) -> Result<(), IndexerError> {
         info!("Indexer checkpoint handler started...");
-        let mut next_cursor_sequence_number = self.state.get_latest_checkpoint_sequence_number()?;
-        if next_cursor_sequence_number > 0 {
-            info!("Resuming from checkpoint {next_cursor_sequence_number}");
-        }
-        next_cursor_sequence_number += 1;
+        // Sequence number of the next checkpoint each sink expects.
+        let mut sink_cursors = self
+            .sinks
+            .iter()
+            .map(|sink| {
+                let watermark = self.state.get_sink_watermark(sink.name())?;
+                if let Some(watermark) = watermark {
+                    info!("Sink {} resuming from checkpoint {watermark}", sink.name());
+                }
+                Ok(watermark.map_or(0, |watermark| watermark + 1))
+            })
+            .collect::<Result<Vec<_>, IndexerError>>()?;
+        let mut next_cursor_sequence_number = sink_cursors.iter().min().copied().unwrap_or(0);
+        // Checkpoints before this one have already been streamed to websocket subscribers.
+        let subscription_cursor = sink_cursors.iter().max().copied().unwrap_or(0);
+        let mut sink_retries: Vec<Option<SinkRetry>> = self.sinks.iter().map(|_| None).collect();
 
         loop {
             self.metrics.total_checkpoint_requested.inc();
             let request_guard = self.metrics.full_node_read_request_latency.start_timer();
 
             let checkpoint = self
-                .download_checkpoint_data(next_cursor_sequence_number as u64)
+                .download_checkpoint_data(next_cursor_sequence_number)
                 .await.map_err(|e| {
                     error!(
                         "Failed to download checkpoint data with checkpoint sequence number {} and error {:?}, retrying...",
@@ -102,36 +145,142 @@ where
             // Index checkpoint data
             // TODO: Metrics
             let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(&checkpoint)?;
-
-            // Write to DB
+            let indexed = IndexedCheckpoint {
+                data: &checkpoint,
+                checkpoint: &indexed_checkpoint,
+                epoch: indexed_epoch.as_ref(),
+            };
+
+            // Write to sinks that have not seen this checkpoint yet. A sink that fails keeps its
+            // watermark and is retried later, without holding back the other sinks.
             let db_guard = self.metrics.db_write_request_latency.start_timer();
-            let tx_count = indexed_checkpoint.transactions.len();
-            let object_count = indexed_checkpoint.objects_changes.len();
-
-            self.state.persist_checkpoint(&indexed_checkpoint)?;
-            info!(
-                "Checkpoint {} committed with {tx_count} transactions and {object_count} objects.",
-                next_cursor_sequence_number
-            );
+            for ((sink, cursor), retry) in self
+                .sinks
+                .iter()
+                .zip(sink_cursors.iter_mut())
+                .zip(sink_retries.iter_mut())
+            {
+                if *cursor > next_cursor_sequence_number
+                    || retry
+                        .as_ref()
+                        .map_or(false, |retry| retry.retry_at > Instant::now())
+                {
+                    continue;
+                }
+                let result = match self
+                    .catch_up_sink(sink.as_ref(), cursor, next_cursor_sequence_number)
+                    .await
+                {
+                    Ok(()) if *cursor < next_cursor_sequence_number => continue,
+                    Ok(()) => self
+                        .write_to_sink(sink.as_ref(), &indexed, next_cursor_sequence_number)
+                        .await
+                        .map(|()| *cursor = next_cursor_sequence_number + 1),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(()) => *retry = None,
+                    Err(e) => {
+                        self.metrics
+                            .sink_write_errors
+                            .with_label_values(&[sink.name()])
+                            .inc();
+                        let failures = retry.as_ref().map_or(0, |retry| retry.failures) + 1;
+                        let delay = Duration::from_secs(
+                            SINK_RETRY_INTERVAL_IN_SECS
+                                .saturating_mul(1 << (failures - 1).min(16))
+                                .min(SINK_MAX_RETRY_INTERVAL_IN_SECS),
+                        );
+                        error!(
+                            "Failed to write checkpoint {} to sink {} with error: {:?}, retrying after {:?}...",
+                            cursor, sink.name(), e, delay
+                        );
+                        *retry = Some(SinkRetry {
+                            failures,
+                            retry_at: Instant::now() + delay,
+                        });
+                    }
+                }
+            }
             self.metrics.total_checkpoint_processed.inc();
             db_guard.stop_and_record();
 
             // Process websocket subscription
-            let db_guard = self.metrics.db_write_request_latency.start_timer();
-            for tx in &checkpoint.transactions {
-                self.event_handler
-                    .process_events(&tx.effects, &tx.events)
-                    .await?;
-            }
-            db_guard.stop_and_record();
-
-            if let Some(indexed_epoch) = indexed_epoch {
-                self.state.persist_epoch(&indexed_epoch)?;
+            if next_cursor_sequence_number >= subscription_cursor {
+                let db_guard = self.metrics.db_write_request_latency.start_timer();
+                for tx in &checkpoint.transactions {
+                    self.event_handler
+                        .process_events(&tx.effects, &tx.events)
+                        .await?;
+                }
+                db_guard.stop_and_record();
             }
             next_cursor_sequence_number += 1;
         }
     }
 
+    /// Writes the checkpoints a sink missed because of earlier failures, from its `cursor` up to
+    /// at most `SINK_MAX_CATCH_UP_CHECKPOINTS` checkpoints before `next`. The missed checkpoints
+    /// are downloaded and indexed again.
+    async fn catch_up_sink(
+        &self,
+        sink: &dyn CheckpointSink,
+        cursor: &mut CheckpointSequenceNumber,
+        next: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        let end = next.min(*cursor + SINK_MAX_CATCH_UP_CHECKPOINTS);
+        while *cursor < end {
+            let checkpoint = self.download_checkpoint_data(*cursor).await?;
+            let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(&checkpoint)?;
+            let indexed = IndexedCheckpoint {
+                data: &checkpoint,
+                checkpoint: &indexed_checkpoint,
+                epoch: indexed_epoch.as_ref(),
+            };
+            self.write_to_sink(sink, &indexed, *cursor).await?;
+            *cursor += 1;
+        }
+        Ok(())
+    }
+
+    /// Writes checkpoint `seq` to `sink` and advances the sink's watermark.
+    async fn write_to_sink(
+        &self,
+        sink: &dyn CheckpointSink,
+        indexed: &IndexedCheckpoint<'_>,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        let sink_guard = self
+            .metrics
+            .sink_write_latency
+            .with_label_values(&[sink.name()])
+            .start_timer();
+        sink.write_checkpoint(indexed).await?;
+        sink_guard.stop_and_record();
+        self.state.persist_sink_watermark(sink.name(), seq)?;
+        self.metrics
+            .sink_watermark
+            .with_label_values(&[sink.name()])
+            .set(seq as i64);
+        Ok(())
+    }
+
+    /// Re-indexes the already persisted checkpoint `seq`, replacing the rows derived from it.
+    /// Sinks other than the store and websocket subscribers are not involved.
+    pub(crate) async fn reindex_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        let checkpoint = self.download_checkpoint_data(seq).await?;
+        let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(&checkpoint)?;
+        self.state.backfill_checkpoint(&indexed_checkpoint)?;
+        if let Some(indexed_epoch) = &indexed_epoch {
+            self.state.backfill_epoch(indexed_epoch)?;
+        }
+        self.metrics.total_checkpoint_processed.inc();
+        Ok(())
+    }
+
     /// Download all the data we need for one checkpoint.
     async fn download_checkpoint_data(
         &self,
@@ -285,7 +434,7 @@ where
                 acc
             });
 
-        let objects_changes = transactions
+        let mut objects_changes: Vec<TransactionObjectChanges> = transactions
             .iter()
             .map(|tx| {
                 let changed_objects = tx_objects
@@ -323,15 +472,54 @@ where
             })
             .collect();
 
+        // Index packages
+        let packages = Self::index_packages(transactions, changed_objects)?;
+
+        // Index dynamic fields and displays, modules of the packages published in this checkpoint
+        // are resolved from the checkpoint as they have not been persisted yet.
+        let module_resolver = CheckpointModuleResolver::new(&packages, self.state.module_cache())?;
+        let displays = Self::index_displays(transactions, checkpoint.sequence_number)?;
+        let mut stored_displays = BTreeMap::new();
+        for object in objects_changes
+            .iter_mut()
+            .flat_map(|changes| changes.mutated_objects.iter_mut())
+        {
+            if let Err(e) = object.index_dynamic_field(&&module_resolver) {
+                warn!(
+                    "Failed to index dynamic field {} in checkpoint {}: {:?}",
+                    object.object_id, checkpoint.sequence_number, e
+                );
+            }
+
+            let display = match displays.get(&object.object_type) {
+                Some(display) => Some(display),
+                None => {
+                    if !stored_displays.contains_key(&object.object_type) {
+                        let stored_display = self.state.get_display(&object.object_type)?;
+                        stored_displays.insert(object.object_type.clone(), stored_display);
+                    }
+                    stored_displays[&object.object_type].as_ref()
+                }
+            };
+            if let Some(display) = display {
+                object.display = display
+                    .render(object, &&module_resolver)
+                    .unwrap_or_else(|e| {
+                        warn!(
+                            "Failed to render display of object {} in checkpoint {}: {:?}",
+                            object.object_id, checkpoint.sequence_number, e
+                        );
+                        None
+                    });
+            }
+        }
+
         // Index addresses
         let addresses = db_transactions
             .iter()
             .map(|tx: &Transaction| tx.into())
             .collect();
 
-        // Index packages
-        let packages = Self::index_packages(transactions, changed_objects)?;
-
         // Store input objects, move calls and recipients separately for transaction query indexing.
         let input_objects = transactions
             .iter()
@@ -349,20 +537,120 @@ where
             .flat_map(|tx| tx.get_recipients(checkpoint.epoch, checkpoint.sequence_number))
             .collect();
 
+        // Index staking and unstaking requests
+        let stake_events = transactions
+            .iter()
+            .flat_map(|tx| tx.events.data.iter())
+            .filter_map(|event| {
+                if is_system_event(event, VALIDATOR_MODULE, STAKING_REQUEST_EVENT) {
+                    Some(
+                        bcs::from_bytes::<StakingRequestEvent>(&event.bcs).map(|request| {
+                            StakeEvent::staking_request(event, checkpoint.sequence_number, &request)
+                        }),
+                    )
+                } else if is_system_event(event, VALIDATOR_MODULE, UNSTAKING_REQUEST_EVENT) {
+                    Some(
+                        bcs::from_bytes::<UnstakingRequestEvent>(&event.bcs).map(|request| {
+                            StakeEvent::unstaking_request(
+                                event,
+                                checkpoint.sequence_number,
+                                checkpoint.epoch,
+                                &request,
+                            )
+                        }),
+                    )
+                } else {
+                    None
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to deserialize stake event in checkpoint {} with error {:?}",
+                    checkpoint.sequence_number, e
+                ))
+            })?;
+
+        let db_checkpoint = Checkpoint::from(checkpoint, &previous_cp)?;
+
         // Index epoch
         // TODO: Aggregate all object owner changes into owner index at epoch change.
-        let epoch_index =
-            checkpoint
-                .end_of_epoch_data
-                .as_ref()
-                .map(|_epoch_change| TemporaryEpochStore {
+        let epoch_index = match &checkpoint.end_of_epoch_data {
+            Some(end_of_epoch_data) => {
+                let epoch_events = transactions
+                    .iter()
+                    .flat_map(|tx| tx.events.data.iter())
+                    .collect::<Vec<_>>();
+                let system_epoch_info_event = epoch_events
+                    .iter()
+                    .find(|event| {
+                        is_system_event(
+                            event,
+                            SUI_SYSTEM_STATE_INNER_MODULE,
+                            SYSTEM_EPOCH_INFO_EVENT,
+                        )
+                    })
+                    .ok_or_else(|| {
+                        IndexerError::UncategorizedError(anyhow!(
+                            "Cannot find SystemEpochInfoEvent in last checkpoint {} of epoch {}",
+                            checkpoint.sequence_number,
+                            checkpoint.epoch
+                        ))
+                    })?;
+                let system_epoch_info_event: SystemEpochInfoEvent =
+                    bcs::from_bytes(&system_epoch_info_event.bcs).map_err(|e| {
+                        IndexerError::SerdeError(format!(
+                            "Failed to deserialize SystemEpochInfoEvent with error {:?}",
+                            e
+                        ))
+                    })?;
+                let validators = epoch_events
+                    .iter()
+                    .filter(|event| {
+                        is_system_event(event, VALIDATOR_SET_MODULE, VALIDATOR_EPOCH_INFO_EVENT)
+                    })
+                    .map(|event| {
+                        bcs::from_bytes::<ValidatorEpochInfoEvent>(&event.bcs)
+                            .map(|event| DBValidatorEpochInfo::new(checkpoint.epoch, &event))
+                            .map_err(|e| {
+                                IndexerError::SerdeError(format!(
+                                    "Failed to deserialize ValidatorEpochInfoEvent with error {:?}",
+                                    e
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // The epoch may have started before this indexer did, fall back to the
+                // current checkpoint when its first checkpoint has not been indexed.
+                let first_checkpoint = self
+                    .state
+                    .get_epoch_first_checkpoint(checkpoint.epoch)?
+                    .unwrap_or_else(|| db_checkpoint.clone());
+                let next_epoch_committee = end_of_epoch_data
+                    .next_epoch_committee
+                    .iter()
+                    .map(|(name, stake)| CommitteeMember::new(checkpoint.epoch + 1, name, *stake))
+                    .collect();
+
+                Some(TemporaryEpochStore {
                     owner_index: vec![],
                     epoch_id: checkpoint.epoch,
-                });
+                    epoch: DBEpochInfo::new(
+                        &first_checkpoint,
+                        &db_checkpoint,
+                        &system_epoch_info_event,
+                    ),
+                    validators,
+                    next_epoch_committee,
+                })
+            }
+            None => None,
+        };
 
         Ok((
             TemporaryCheckpointStore {
-                checkpoint: Checkpoint::from(checkpoint, &previous_cp)?,
+                checkpoint: db_checkpoint,
                 transactions: db_transactions,
                 events,
                 objects_changes,
@@ -371,11 +659,28 @@ where
                 input_objects,
                 move_calls,
                 recipients,
+                stake_events,
+                displays: displays.into_values().collect(),
             },
             epoch_index,
         ))
     }
 
+    /// The displays published in a checkpoint, by the type they display. Only the last one
+    /// published for each type is kept.
+    fn index_displays(
+        transactions: &[SuiTransactionFullResponse],
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<BTreeMap<String, StoredDisplay>, IndexerError> {
+        let mut displays = BTreeMap::new();
+        for event in transactions.iter().flat_map(|tx| tx.events.data.iter()) {
+            if let Some(display) = StoredDisplay::try_from_event(event, checkpoint)? {
+                displays.insert(display.object_type.clone(), display);
+            }
+        }
+        Ok(displays)
+    }
+
     fn index_packages(
         transactions: &[SuiTransactionFullResponse],
         changed_objects: &[(ObjectStatus, SuiObjectData)],
@@ -395,16 +700,47 @@ where
             })
             .collect::<BTreeMap<_, _>>();
 
+        // The `UpgradeCap` of each package published or upgraded in this checkpoint, a cap
+        // points to the latest version of the package it controls.
+        let upgrade_type = UpgradeCap::type_();
+        let mut upgrade_caps = BTreeMap::new();
+        for (_, o) in changed_objects {
+            if let Some(SuiRawData::MoveObject(move_object)) = &o.bcs {
+                if move_object.type_ == upgrade_type {
+                    let cap: UpgradeCap = bcs::from_bytes(&move_object.bcs_bytes).map_err(|e| {
+                        IndexerError::SerdeError(format!(
+                            "Failed to deserialize UpgradeCap {} with error {:?}",
+                            o.object_id, e
+                        ))
+                    })?;
+                    upgrade_caps.insert(cap.package.bytes, cap.id.id.bytes);
+                }
+            }
+        }
+
         transactions
             .iter()
             .flat_map(|tx| {
                 tx.effects.created().iter().map(|oref| {
-                    object_map
-                        .get(&oref.reference.object_id)
-                        .map(|o| Package::try_from(*tx.transaction.data.sender(), o))
+                    object_map.get(&oref.reference.object_id).map(|o| {
+                        Package::try_from(
+                            *tx.transaction.data.sender(),
+                            tx.digest,
+                            tx.checkpoint,
+                            o,
+                            upgrade_caps.get(&o.id).copied(),
+                        )
+                    })
                 })
             })
             .flatten()
             .collect()
     }
 }
+
+/// Whether `event` is the `name` event emitted by `module` of the Sui framework.
+fn is_system_event(event: &SuiEvent, module: &str, name: &str) -> bool {
+    event.type_.address == SUI_FRAMEWORK_ADDRESS
+        && event.type_.module.as_str() == module
+        && event.type_.name.as_str() == name
+}
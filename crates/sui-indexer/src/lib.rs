@@ -2,16 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use backoff::retry;
 use backoff::ExponentialBackoff;
 use clap::Parser;
+use diesel::connection::SimpleConnection;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
-use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+use diesel::r2d2::{ConnectionManager, CustomizeConnection, Pool, PooledConnection};
+use diesel::sqlite::SqliteConnection;
 use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClientBuilder};
 use prometheus::Registry;
 use tracing::{info, warn};
@@ -26,25 +30,40 @@ use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::base_types::TransactionDigest;
 
 use crate::apis::{
-    CoinReadApi, EventReadApi, GovernanceReadApi, ReadApi, TransactionBuilderApi, WriteApi,
+    CoinReadApi, EventReadApi, ExtendedApi, GovernanceReadApi, ReadApi, TransactionBuilderApi,
+    WriteApi,
 };
+use crate::handlers::backfill_handler::BackfillHandler;
 use crate::handlers::checkpoint_handler::CheckpointHandler;
+use crate::sinks::build_checkpoint_sinks;
 use crate::store::IndexerStore;
 use crate::types::SuiTransactionFullResponse;
 
 pub mod apis;
+pub mod consistency_checker;
 pub mod errors;
 mod handlers;
 pub mod metrics;
 pub mod models;
 pub mod processors;
 pub mod schema;
+pub mod sinks;
 pub mod store;
 pub mod types;
 pub mod utils;
 
 pub type PgConnectionPool = Pool<ConnectionManager<PgConnection>>;
 pub type PgPoolConnection = PooledConnection<ConnectionManager<PgConnection>>;
+pub type SqliteConnectionPool = Pool<ConnectionManager<SqliteConnection>>;
+pub type SqlitePoolConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+/// Prefix of the `--db-url` of a SQLite database, followed by the path of the database file, or
+/// by `:memory:` for a database that only lives as long as the indexer.
+pub const SQLITE_URL_PREFIX: &str = "sqlite://";
+const SQLITE_IN_MEMORY: &str = ":memory:";
+
+const DISPLAY_RENDER_BATCH_SIZE: usize = 1000;
+const DISPLAY_RENDER_IDLE_INTERVAL: Duration = Duration::from_secs(1);
 
 // TODO: placeholder, read from env or config file.
 pub const FAKE_PKG_VERSION: &str = "0.0.0";
@@ -56,6 +75,8 @@ pub const FAKE_PKG_VERSION: &str = "0.0.0";
     rename_all = "kebab-case"
 )]
 pub struct IndexerConfig {
+    /// Postgres URL, or `sqlite://<path>` (`sqlite://:memory:` for an in-memory database) for the
+    /// embedded SQLite store.
     #[clap(long)]
     pub db_url: String,
     #[clap(long)]
@@ -68,6 +89,28 @@ pub struct IndexerConfig {
     pub rpc_server_url: String,
     #[clap(long, default_value = "9000", global = true)]
     pub rpc_server_port: u16,
+    /// Also write each checkpoint as newline-delimited JSON into this directory.
+    #[clap(long)]
+    pub json_sink_dir: Option<PathBuf>,
+    /// Also write the transactions of each checkpoint as Parquet into this directory.
+    #[clap(long)]
+    pub parquet_sink_dir: Option<PathBuf>,
+    /// Also post each checkpoint as JSON to this URL.
+    #[clap(long)]
+    pub webhook_sink_url: Option<String>,
+    /// Re-index the already indexed checkpoints from this one to `--backfill-end`, instead of
+    /// following the chain.
+    #[clap(long, requires = "backfill-end")]
+    pub backfill_start: Option<u64>,
+    /// Last checkpoint to re-index, inclusive.
+    #[clap(long, requires = "backfill-start")]
+    pub backfill_end: Option<u64>,
+    /// Number of workers re-indexing shares of the backfill range in parallel.
+    #[clap(long, default_value = "4")]
+    pub backfill_workers: usize,
+    /// How often the balances served by the coin APIs are recomputed from the indexed objects.
+    #[clap(long, default_value = "10000")]
+    pub balances_refresh_interval_ms: u64,
 }
 
 impl IndexerConfig {
@@ -79,6 +122,13 @@ impl IndexerConfig {
             client_metric_port: 9184,
             rpc_server_url: "0.0.0.0".to_string(),
             rpc_server_port: 9000,
+            json_sink_dir: None,
+            parquet_sink_dir: None,
+            webhook_sink_url: None,
+            backfill_start: None,
+            backfill_end: None,
+            backfill_workers: 4,
+            balances_refresh_interval_ms: 10_000,
         }
     }
 }
@@ -98,7 +148,13 @@ impl Indexer {
         // let JSON RPC server run forever.
         spawn_monitored_task!(handle.stopped());
         info!("Sui indexer started...");
+        spawn_monitored_task!(refresh_balances_periodically(
+            store.clone(),
+            Duration::from_millis(config.balances_refresh_interval_ms),
+        ));
+        spawn_monitored_task!(render_displays(store.clone()));
 
+        let sinks = build_checkpoint_sinks(config, store.clone())?;
         backoff::future::retry(ExponentialBackoff::default(), || async {
             let event_handler_clone = event_handler.clone();
             let rpc_client = new_rpc_client(config.rpc_client_url.as_str()).await?;
@@ -108,6 +164,7 @@ impl Indexer {
                 store.clone(),
                 rpc_client.clone(),
                 event_handler_clone,
+                sinks.clone(),
                 registry,
             );
             cp.spawn()
@@ -117,6 +174,62 @@ impl Indexer {
         })
         .await
     }
+
+    /// Re-indexes the checkpoint range set in `config`, and returns once it is done.
+    pub async fn backfill<S: IndexerStore + Sync + Send + Clone + 'static>(
+        config: &IndexerConfig,
+        registry: &Registry,
+        store: S,
+    ) -> Result<(), IndexerError> {
+        let (Some(start), Some(end)) = (config.backfill_start, config.backfill_end) else {
+            return Err(IndexerError::InvalidArgumentError(
+                "Both --backfill-start and --backfill-end are required to backfill".to_string(),
+            ));
+        };
+        let rpc_client = new_rpc_client(config.rpc_client_url.as_str()).await?;
+        BackfillHandler::new(store, rpc_client, registry)
+            .backfill(start, end, config.backfill_workers)
+            .await
+    }
+}
+
+/// Recomputes the balances every `period`. Refreshing them after every checkpoint would
+/// recompute them from all objects several times a second.
+async fn refresh_balances_periodically<S: IndexerStore + Sync + Send + Clone + 'static>(
+    store: S,
+    period: Duration,
+) {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let store = store.clone();
+        match tokio::task::spawn_blocking(move || store.refresh_balances()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to refresh balances with error: {:?}", e),
+            Err(e) => warn!("Balances refresh task failed with error: {:?}", e),
+        }
+    }
+}
+
+/// Re-renders, in batches of `DISPLAY_RENDER_BATCH_SIZE`, the objects of the types whose display
+/// was published after they were indexed, and waits `DISPLAY_RENDER_IDLE_INTERVAL` once none are
+/// left or a batch fails.
+async fn render_displays<S: IndexerStore + Sync + Send + Clone + 'static>(store: S) {
+    loop {
+        let store = store.clone();
+        match tokio::task::spawn_blocking(move || {
+            store.render_pending_displays(DISPLAY_RENDER_BATCH_SIZE)
+        })
+        .await
+        {
+            Ok(Ok(0)) => {}
+            Ok(Ok(_)) => continue,
+            Ok(Err(e)) => warn!("Failed to render displays with error: {:?}", e),
+            Err(e) => warn!("Display render task failed with error: {:?}", e),
+        }
+        tokio::time::sleep(DISPLAY_RENDER_IDLE_INTERVAL).await;
+    }
 }
 
 pub async fn new_rpc_client(http_url: &str) -> Result<SuiClient, IndexerError> {
@@ -161,6 +274,59 @@ pub fn get_pg_pool_connection(pool: &PgConnectionPool) -> Result<PgPoolConnectio
     })
 }
 
+pub async fn new_sqlite_connection_pool(
+    db_url: &str,
+) -> Result<SqliteConnectionPool, IndexerError> {
+    let path = db_url.strip_prefix(SQLITE_URL_PREFIX).ok_or_else(|| {
+        IndexerError::SqliteConnectionPoolInitError(format!(
+            "{} is not a SQLite URL, expected {}<path>",
+            db_url, SQLITE_URL_PREFIX
+        ))
+    })?;
+    let builder = Pool::builder().connection_customizer(Box::new(SqliteConnectionOptions));
+    // Every connection to `:memory:` opens a new database, so a single connection is kept open
+    // for the lifetime of the pool.
+    let builder = if path == SQLITE_IN_MEMORY {
+        builder.max_size(1).idle_timeout(None).max_lifetime(None)
+    } else {
+        builder
+    };
+    builder
+        .build(ConnectionManager::<SqliteConnection>::new(path))
+        .map_err(|e| {
+            IndexerError::SqliteConnectionPoolInitError(format!(
+                "Failed to initialize connection pool with error: {:?}",
+                e
+            ))
+        })
+}
+
+pub fn get_sqlite_pool_connection(
+    pool: &SqliteConnectionPool,
+) -> Result<SqlitePoolConnection, IndexerError> {
+    retry(ExponentialBackoff::default(), || {
+        let pool_conn = pool.get()?;
+        Ok(pool_conn)
+    })
+    .map_err(|e| {
+        IndexerError::SqlitePoolConnectionError(format!(
+            "Failed to get pool connection from SQLite connection pool with error: {:?}",
+            e
+        ))
+    })
+}
+
+#[derive(Debug)]
+struct SqliteConnectionOptions;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for SqliteConnectionOptions {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        // Writers wait for each other instead of failing, and readers do not block writers.
+        conn.batch_execute("PRAGMA busy_timeout = 5000; PRAGMA journal_mode = WAL;")
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
 pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clone>(
     prometheus_registry: &Registry,
     state: S,
@@ -180,9 +346,10 @@ pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clo
         .map_err(|e| IndexerError::RpcClientInitError(e.to_string()))?;
 
     builder.register_module(ReadApi::new(state.clone(), http_client.clone()))?;
-    builder.register_module(CoinReadApi::new(http_client.clone()))?;
+    builder.register_module(CoinReadApi::new(state.clone(), http_client.clone()))?;
     builder.register_module(TransactionBuilderApi::new(http_client.clone()))?;
-    builder.register_module(GovernanceReadApi::new(http_client.clone()))?;
+    builder.register_module(GovernanceReadApi::new(state.clone(), http_client.clone()))?;
+    builder.register_module(ExtendedApi::new(state.clone()))?;
     builder.register_module(EventReadApi::new(state, http_client.clone(), event_handler))?;
     builder.register_module(WriteApi::new(http_client))?;
     let default_socket_addr = SocketAddr::new(
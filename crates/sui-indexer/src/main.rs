@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
+use prometheus::Registry;
 use sui_indexer::errors::IndexerError;
-use sui_indexer::store::PgIndexerStore;
-use sui_indexer::{new_pg_connection_pool, Indexer, IndexerConfig};
+use sui_indexer::store::{IndexerStore, PgIndexerStore, SqliteIndexerStore};
+use sui_indexer::{
+    new_pg_connection_pool, new_sqlite_connection_pool, Indexer, IndexerConfig, SQLITE_URL_PREFIX,
+};
 use sui_node::metrics::start_prometheus_server;
 
 #[tokio::main]
@@ -26,8 +29,25 @@ async fn main() -> Result<(), IndexerError> {
     );
 
     let registry = registry_service.default_registry();
-    let pg_connection_pool = new_pg_connection_pool(&indexer_config.db_url).await?;
-    let store = PgIndexerStore::new(pg_connection_pool);
+    if indexer_config.db_url.starts_with(SQLITE_URL_PREFIX) {
+        let sqlite_connection_pool = new_sqlite_connection_pool(&indexer_config.db_url).await?;
+        let store = SqliteIndexerStore::new(sqlite_connection_pool)?;
+        run(&indexer_config, &registry, store).await
+    } else {
+        let pg_connection_pool = new_pg_connection_pool(&indexer_config.db_url).await?;
+        let store = PgIndexerStore::new(pg_connection_pool);
+        run(&indexer_config, &registry, store).await
+    }
+}
 
-    Indexer::start(&indexer_config, &registry, store).await
+async fn run<S: IndexerStore + Sync + Send + Clone + 'static>(
+    indexer_config: &IndexerConfig,
+    registry: &Registry,
+    store: S,
+) -> Result<(), IndexerError> {
+    if indexer_config.backfill_start.is_some() {
+        Indexer::backfill(indexer_config, registry, store).await
+    } else {
+        Indexer::start(indexer_config, registry, store).await
+    }
 }
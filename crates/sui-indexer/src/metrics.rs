@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
-    Registry,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
+    HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
 };
 
 /// Prometheus metrics for sui-indexer.
@@ -255,6 +257,9 @@ pub struct IndexerCheckpointHandlerMetrics {
     pub db_write_request_latency: Histogram,
     pub full_node_read_request_latency: Histogram,
     pub subscription_process_latency: Histogram,
+    pub sink_watermark: IntGaugeVec,
+    pub sink_write_latency: HistogramVec,
+    pub sink_write_errors: IntCounterVec,
 }
 
 impl IndexerCheckpointHandlerMetrics {
@@ -305,6 +310,28 @@ impl IndexerCheckpointHandlerMetrics {
                 registry,
             )
             .unwrap(),
+            sink_watermark: register_int_gauge_vec_with_registry!(
+                "checkpoint_sink_watermark",
+                "Last checkpoint written to each checkpoint sink",
+                &["sink"],
+                registry,
+            )
+            .unwrap(),
+            sink_write_latency: register_histogram_vec_with_registry!(
+                "checkpoint_sink_write_latency",
+                "Time spent writing a checkpoint to each checkpoint sink",
+                &["sink"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            sink_write_errors: register_int_counter_vec_with_registry!(
+                "checkpoint_sink_write_errors",
+                "Number of failed checkpoint writes to each checkpoint sink",
+                &["sink"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -384,3 +411,52 @@ impl IndexerPackageProcessorMetrics {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct IndexerConsistencyCheckerMetrics {
+    pub total_checks: IntCounterVec,
+    pub total_mismatches: IntCounterVec,
+    pub total_inconclusive_checks: IntCounterVec,
+    pub total_check_errors: IntCounter,
+    pub latest_checked_checkpoint: IntGauge,
+}
+
+impl IndexerConsistencyCheckerMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            total_checks: register_int_counter_vec_with_registry!(
+                "consistency_checks",
+                "Total number of indexer responses compared with the fullnode, by kind of data",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_mismatches: register_int_counter_vec_with_registry!(
+                "consistency_mismatches",
+                "Total number of indexer responses not matching the fullnode, by kind of data",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_inconclusive_checks: register_int_counter_vec_with_registry!(
+                "consistency_inconclusive_checks",
+                "Total number of comparisons skipped as the data changed, was pruned or was not refreshed meanwhile",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_check_errors: register_int_counter_with_registry!(
+                "consistency_check_errors",
+                "Total number of checkpoint checks that failed to read the indexer or the fullnode",
+                registry,
+            )
+            .unwrap(),
+            latest_checked_checkpoint: register_int_gauge_with_registry!(
+                "consistency_latest_checked_checkpoint",
+                "Sequence number of the latest checked checkpoint",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
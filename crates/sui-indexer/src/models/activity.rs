@@ -0,0 +1,208 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use sui_json_rpc_types::{AddressCounterparty, AddressStats, PackageCallCount};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::transaction_index::{MoveCall, Recipient};
+use crate::models::transactions::Transaction;
+use crate::schema::{address_counterparties, address_stats, network_activity, package_calls};
+
+/// Width of the `network_activity` buckets.
+pub const NETWORK_ACTIVITY_BUCKET_MS: i64 = 60_000;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = address_stats)]
+pub struct DBAddressStats {
+    pub address: String,
+    pub transaction_count: i64,
+    pub sent_count: i64,
+    pub received_count: i64,
+    pub first_activity_checkpoint: i64,
+    pub first_activity_timestamp_ms: i64,
+    pub last_activity_checkpoint: i64,
+    pub last_activity_timestamp_ms: i64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = address_counterparties)]
+pub struct DBAddressCounterparty {
+    pub address: String,
+    pub counterparty: String,
+    pub transaction_count: i64,
+    pub last_activity_timestamp_ms: i64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = package_calls)]
+pub struct DBPackageCalls {
+    pub epoch: i64,
+    pub move_package: String,
+    pub call_count: i64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = network_activity)]
+pub struct DBNetworkActivity {
+    pub bucket_start_ms: i64,
+    pub transaction_count: i64,
+    pub checkpoint_count: i64,
+}
+
+/// The increments of the activity aggregates contributed by a single checkpoint.
+#[derive(Debug, Clone)]
+pub struct CheckpointActivity {
+    pub address_stats: Vec<DBAddressStats>,
+    pub counterparties: Vec<DBAddressCounterparty>,
+    pub package_calls: Vec<DBPackageCalls>,
+    pub network_activity: DBNetworkActivity,
+}
+
+impl CheckpointActivity {
+    pub fn new(
+        checkpoint: &Checkpoint,
+        transactions: &[Transaction],
+        recipients: &[Recipient],
+        move_calls: &[MoveCall],
+    ) -> Self {
+        // Recipients of each transaction other than its sender
+        let mut received = BTreeMap::<&str, BTreeSet<&str>>::new();
+        for recipient in recipients {
+            received
+                .entry(recipient.transaction_digest.as_str())
+                .or_default()
+                .insert(recipient.recipient.as_str());
+        }
+
+        let mut address_stats = BTreeMap::<&str, DBAddressStats>::new();
+        let mut counterparties = BTreeMap::<(&str, &str), DBAddressCounterparty>::new();
+        for tx in transactions {
+            let sender = tx.sender.as_str();
+            let tx_recipients = received
+                .get(tx.transaction_digest.as_str())
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|recipient| *recipient != sender)
+                .collect::<Vec<_>>();
+            record_activity(&mut address_stats, sender, tx, true);
+            for recipient in &tx_recipients {
+                record_activity(&mut address_stats, *recipient, tx, false);
+            }
+
+            for recipient in tx_recipients {
+                for (address, counterparty) in [(sender, recipient), (recipient, sender)] {
+                    let entry = counterparties
+                        .entry((address, counterparty))
+                        .or_insert_with(|| DBAddressCounterparty {
+                            address: address.to_string(),
+                            counterparty: counterparty.to_string(),
+                            transaction_count: 0,
+                            last_activity_timestamp_ms: tx.timestamp_ms,
+                        });
+                    entry.transaction_count += 1;
+                    entry.last_activity_timestamp_ms = tx.timestamp_ms;
+                }
+            }
+        }
+
+        let mut package_calls = BTreeMap::<(i64, &str), DBPackageCalls>::new();
+        for move_call in move_calls {
+            package_calls
+                .entry((move_call.epoch, move_call.move_package.as_str()))
+                .or_insert_with(|| DBPackageCalls {
+                    epoch: move_call.epoch,
+                    move_package: move_call.move_package.clone(),
+                    call_count: 0,
+                })
+                .call_count += 1;
+        }
+
+        Self {
+            address_stats: address_stats.into_values().collect(),
+            counterparties: counterparties.into_values().collect(),
+            package_calls: package_calls.into_values().collect(),
+            network_activity: DBNetworkActivity {
+                bucket_start_ms: checkpoint.timestamp_ms / NETWORK_ACTIVITY_BUCKET_MS
+                    * NETWORK_ACTIVITY_BUCKET_MS,
+                transaction_count: checkpoint.total_transactions,
+                checkpoint_count: 1,
+            },
+        }
+    }
+}
+
+fn record_activity<'a>(
+    address_stats: &mut BTreeMap<&'a str, DBAddressStats>,
+    address: &'a str,
+    tx: &Transaction,
+    sent: bool,
+) {
+    let stats = address_stats
+        .entry(address)
+        .or_insert_with(|| DBAddressStats {
+            address: address.to_string(),
+            transaction_count: 0,
+            sent_count: 0,
+            received_count: 0,
+            first_activity_checkpoint: tx.checkpoint_sequence_number,
+            first_activity_timestamp_ms: tx.timestamp_ms,
+            last_activity_checkpoint: tx.checkpoint_sequence_number,
+            last_activity_timestamp_ms: tx.timestamp_ms,
+        });
+    stats.transaction_count += 1;
+    if sent {
+        stats.sent_count += 1;
+    } else {
+        stats.received_count += 1;
+    }
+    stats.last_activity_checkpoint = tx.checkpoint_sequence_number;
+    stats.last_activity_timestamp_ms = tx.timestamp_ms;
+}
+
+impl TryFrom<DBAddressStats> for AddressStats {
+    type Error = IndexerError;
+
+    fn try_from(stats: DBAddressStats) -> Result<Self, Self::Error> {
+        Ok(AddressStats {
+            address: SuiAddress::from_str(&stats.address)?,
+            transaction_count: stats.transaction_count as u64,
+            sent_count: stats.sent_count as u64,
+            received_count: stats.received_count as u64,
+            first_activity_checkpoint: stats.first_activity_checkpoint as u64,
+            first_activity_timestamp_ms: stats.first_activity_timestamp_ms as u64,
+            last_activity_checkpoint: stats.last_activity_checkpoint as u64,
+            last_activity_timestamp_ms: stats.last_activity_timestamp_ms as u64,
+        })
+    }
+}
+
+impl TryFrom<DBAddressCounterparty> for AddressCounterparty {
+    type Error = IndexerError;
+
+    fn try_from(counterparty: DBAddressCounterparty) -> Result<Self, Self::Error> {
+        Ok(AddressCounterparty {
+            counterparty: SuiAddress::from_str(&counterparty.counterparty)?,
+            transaction_count: counterparty.transaction_count as u64,
+            last_activity_timestamp_ms: counterparty.last_activity_timestamp_ms as u64,
+        })
+    }
+}
+
+impl TryFrom<DBPackageCalls> for PackageCallCount {
+    type Error = IndexerError;
+
+    fn try_from(calls: DBPackageCalls) -> Result<Self, Self::Error> {
+        Ok(PackageCallCount {
+            package: ObjectID::from_str(&calls.move_package)?,
+            epoch: calls.epoch as u64,
+            call_count: calls.call_count as u64,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::IndexerError;
+use diesel::sql_types::{BigInt, VarChar};
+use diesel::QueryableByName;
+use sui_json_rpc_types::Balance;
+
+/// A row of the `balances` materialized view, the total balance of one coin type owned by an
+/// address.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct AddressBalance {
+    #[diesel(sql_type = VarChar)]
+    pub coin_type: String,
+    #[diesel(sql_type = BigInt)]
+    pub coin_num: i64,
+    // NUMERIC in the view, read back as text as it does not fit any integer SQL type.
+    #[diesel(sql_type = VarChar)]
+    pub balance: String,
+}
+
+impl TryFrom<AddressBalance> for Balance {
+    type Error = IndexerError;
+
+    fn try_from(b: AddressBalance) -> Result<Self, Self::Error> {
+        let total_balance = b.balance.parse().map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to parse balance {} of coin type {}, error: {}",
+                b.balance, b.coin_type, e
+            ))
+        })?;
+        Ok(Balance {
+            coin_type: b.coin_type,
+            coin_object_count: b.coin_num as usize,
+            total_balance,
+            // locked coins are not indexed
+            locked_balance: Default::default(),
+        })
+    }
+}
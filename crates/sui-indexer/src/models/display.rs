@@ -0,0 +1,107 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::IndexerError;
+use crate::models::objects::Object;
+use crate::schema::display;
+use diesel::prelude::*;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::TypeTag;
+use serde::Deserialize;
+use serde_json::Value;
+use sui_json_rpc::read_api::get_rendered_fields;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::collection_types::VecMap;
+use sui_types::display::DISPLAY_MODULE_NAME;
+use sui_types::id::ID;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+const VERSION_UPDATED_EVENT_NAME: &str = "VersionUpdated";
+
+/// Rust representation of the Move `0x2::display::VersionUpdated<T>` event, emitted when a
+/// `Display<T>` is published with `display::update_version`.
+#[derive(Deserialize, Debug)]
+pub struct DisplayVersionUpdatedEvent {
+    pub id: ID,
+    pub version: u16,
+    pub fields: VecMap<String, String>,
+}
+
+/// The `Display<T>` published by the latest `VersionUpdated<T>` event of type `object_type`.
+/// Several `Display<T>` objects can exist for the same type, the one published last applies.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = display)]
+pub struct StoredDisplay {
+    pub object_type: String,
+    pub id: String,
+    pub version: i32,
+    /// BCS of the `VersionUpdated` event.
+    pub bcs: Vec<u8>,
+    /// Checkpoint of the `VersionUpdated` event.
+    pub checkpoint: i64,
+}
+
+impl StoredDisplay {
+    /// Reads `event` as the publication of a display if it is a `VersionUpdated` event.
+    pub fn try_from_event(
+        event: &SuiEvent,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Option<Self>, IndexerError> {
+        let event_type = &event.type_;
+        let display_type = match event_type.type_params.first() {
+            Some(TypeTag::Struct(display_type))
+                if event_type.address == SUI_FRAMEWORK_ADDRESS
+                    && event_type.module.as_ident_str() == DISPLAY_MODULE_NAME
+                    && event_type.name.as_str() == VERSION_UPDATED_EVENT_NAME =>
+            {
+                display_type.to_string()
+            }
+            _ => return Ok(None),
+        };
+        let version_updated = decode_version_updated(&event.bcs)?;
+        Ok(Some(Self {
+            object_type: display_type,
+            id: version_updated.id.bytes.to_string(),
+            version: version_updated.version as i32,
+            bcs: event.bcs.clone(),
+            checkpoint: checkpoint as i64,
+        }))
+    }
+
+    pub fn fields(&self) -> Result<VecMap<String, String>, IndexerError> {
+        Ok(decode_version_updated(&self.bcs)?.fields)
+    }
+
+    /// Renders the fields of `object` with this display, `object` must be of `object_type`.
+    pub fn render(
+        &self,
+        object: &Object,
+        module_cache: &impl GetModule,
+    ) -> Result<Option<Value>, IndexerError> {
+        let Some((_, move_struct)) = object.to_move_struct(module_cache)? else {
+            return Ok(None);
+        };
+        let fields = get_rendered_fields(self.fields()?, &move_struct).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to render display of object {} with error {:?}",
+                object.object_id, e
+            ))
+        })?;
+        Ok(Some(serde_json::to_value(fields).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to serialize display of object {} with error {:?}",
+                object.object_id, e
+            ))
+        })?))
+    }
+}
+
+fn decode_version_updated(bcs: &[u8]) -> Result<DisplayVersionUpdatedEvent, IndexerError> {
+    bcs::from_bytes(bcs).map_err(|e| {
+        IndexerError::SerdeError(format!(
+            "Failed to deserialize display VersionUpdated event with error {:?}",
+            e
+        ))
+    })
+}
@@ -0,0 +1,252 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::schema::{committees, epochs, validators};
+use diesel::prelude::*;
+use fastcrypto::encoding::{Encoding, Hex};
+use serde::Deserialize;
+use std::str::FromStr;
+use sui_json_rpc_types::{EndOfEpochInfo, EpochInfo, SuiCommittee, ValidatorEpochInfo};
+use sui_types::base_types::{AuthorityName, EpochId, SuiAddress};
+use sui_types::committee::StakeUnit;
+
+pub const SUI_SYSTEM_STATE_INNER_MODULE: &str = "sui_system_state_inner";
+pub const SYSTEM_EPOCH_INFO_EVENT: &str = "SystemEpochInfoEvent";
+pub const VALIDATOR_SET_MODULE: &str = "validator_set";
+pub const VALIDATOR_EPOCH_INFO_EVENT: &str = "ValidatorEpochInfoEvent";
+
+/// Rust representation of the Move `sui::sui_system_state_inner::SystemEpochInfoEvent`, emitted
+/// once by the epoch change transaction.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SystemEpochInfoEvent {
+    pub epoch: u64,
+    pub protocol_version: u64,
+    pub reference_gas_price: u64,
+    pub total_stake: u64,
+    pub storage_fund_reinvestment: u64,
+    pub storage_charge: u64,
+    pub storage_rebate: u64,
+    pub storage_fund_balance: u64,
+    pub stake_subsidy_amount: u64,
+    pub total_gas_fees: u64,
+    pub total_stake_rewards_distributed: u64,
+    pub leftover_storage_fund_inflow: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PoolTokenExchangeRate {
+    pub sui_amount: u64,
+    pub pool_token_amount: u64,
+}
+
+/// Rust representation of the Move `sui::validator_set::ValidatorEpochInfoEvent`, emitted for
+/// every active validator by the epoch change transaction.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ValidatorEpochInfoEvent {
+    pub epoch: u64,
+    pub validator_address: SuiAddress,
+    pub reference_gas_survey_quote: u64,
+    pub stake: u64,
+    pub commission_rate: u64,
+    pub pool_staking_reward: u64,
+    pub storage_fund_staking_reward: u64,
+    pub pool_token_exchange_rate: PoolTokenExchangeRate,
+    pub tallying_rule_reporters: Vec<SuiAddress>,
+    pub tallying_rule_global_score: u64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = epochs)]
+pub struct DBEpochInfo {
+    pub epoch: i64,
+    pub first_checkpoint_id: i64,
+    pub last_checkpoint_id: i64,
+    pub epoch_start_timestamp: i64,
+    pub epoch_end_timestamp: i64,
+    pub epoch_total_transactions: i64,
+    pub next_epoch_protocol_version: i64,
+    pub next_epoch_reference_gas_price: i64,
+    pub total_stake: i64,
+    pub storage_fund_reinvestment: i64,
+    pub storage_charge: i64,
+    pub storage_rebate: i64,
+    pub storage_fund_balance: i64,
+    pub stake_subsidy_amount: i64,
+    pub total_gas_fees: i64,
+    pub total_stake_rewards_distributed: i64,
+    pub leftover_storage_fund_inflow: i64,
+}
+
+impl DBEpochInfo {
+    /// Summary of the epoch that starts at `first_checkpoint` and ends at `last_checkpoint`.
+    pub fn new(
+        first_checkpoint: &Checkpoint,
+        last_checkpoint: &Checkpoint,
+        event: &SystemEpochInfoEvent,
+    ) -> Self {
+        Self {
+            epoch: last_checkpoint.epoch,
+            first_checkpoint_id: first_checkpoint.sequence_number,
+            last_checkpoint_id: last_checkpoint.sequence_number,
+            epoch_start_timestamp: first_checkpoint.timestamp_ms,
+            epoch_end_timestamp: last_checkpoint.timestamp_ms,
+            epoch_total_transactions: last_checkpoint.total_transactions_current_epoch,
+            next_epoch_protocol_version: event.protocol_version as i64,
+            next_epoch_reference_gas_price: event.reference_gas_price as i64,
+            total_stake: event.total_stake as i64,
+            storage_fund_reinvestment: event.storage_fund_reinvestment as i64,
+            storage_charge: event.storage_charge as i64,
+            storage_rebate: event.storage_rebate as i64,
+            storage_fund_balance: event.storage_fund_balance as i64,
+            stake_subsidy_amount: event.stake_subsidy_amount as i64,
+            total_gas_fees: event.total_gas_fees as i64,
+            total_stake_rewards_distributed: event.total_stake_rewards_distributed as i64,
+            leftover_storage_fund_inflow: event.leftover_storage_fund_inflow as i64,
+        }
+    }
+
+    pub fn into_epoch_info(
+        self,
+        validators: Vec<DBValidatorEpochInfo>,
+    ) -> Result<EpochInfo, IndexerError> {
+        Ok(EpochInfo {
+            epoch: self.epoch as EpochId,
+            first_checkpoint_id: self.first_checkpoint_id as u64,
+            last_checkpoint_id: self.last_checkpoint_id as u64,
+            epoch_start_timestamp: self.epoch_start_timestamp as u64,
+            epoch_end_timestamp: self.epoch_end_timestamp as u64,
+            epoch_total_transactions: self.epoch_total_transactions as u64,
+            end_of_epoch_info: EndOfEpochInfo {
+                next_epoch_protocol_version: self.next_epoch_protocol_version as u64,
+                next_epoch_reference_gas_price: self.next_epoch_reference_gas_price as u64,
+                total_stake: self.total_stake as u64,
+                storage_fund_reinvestment: self.storage_fund_reinvestment as u64,
+                storage_charge: self.storage_charge as u64,
+                storage_rebate: self.storage_rebate as u64,
+                storage_fund_balance: self.storage_fund_balance as u64,
+                stake_subsidy_amount: self.stake_subsidy_amount as u64,
+                total_gas_fees: self.total_gas_fees as u64,
+                total_stake_rewards_distributed: self.total_stake_rewards_distributed as u64,
+                leftover_storage_fund_inflow: self.leftover_storage_fund_inflow as u64,
+            },
+            validators: validators
+                .into_iter()
+                .map(ValidatorEpochInfo::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// State of a validator at the end of `epoch`.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = validators)]
+pub struct DBValidatorEpochInfo {
+    pub epoch: i64,
+    pub validator_address: String,
+    pub reference_gas_survey_quote: i64,
+    pub stake: i64,
+    pub commission_rate: i64,
+    pub pool_staking_reward: i64,
+    pub storage_fund_staking_reward: i64,
+    pub exchange_rate_sui_amount: i64,
+    pub exchange_rate_pool_token_amount: i64,
+    pub tallying_rule_reporters: Vec<Option<String>>,
+    pub tallying_rule_global_score: i64,
+}
+
+impl DBValidatorEpochInfo {
+    pub fn new(epoch: EpochId, event: &ValidatorEpochInfoEvent) -> Self {
+        Self {
+            epoch: epoch as i64,
+            validator_address: event.validator_address.to_string(),
+            reference_gas_survey_quote: event.reference_gas_survey_quote as i64,
+            stake: event.stake as i64,
+            commission_rate: event.commission_rate as i64,
+            pool_staking_reward: event.pool_staking_reward as i64,
+            storage_fund_staking_reward: event.storage_fund_staking_reward as i64,
+            exchange_rate_sui_amount: event.pool_token_exchange_rate.sui_amount as i64,
+            exchange_rate_pool_token_amount: event.pool_token_exchange_rate.pool_token_amount
+                as i64,
+            tallying_rule_reporters: event
+                .tallying_rule_reporters
+                .iter()
+                .map(|reporter| Some(reporter.to_string()))
+                .collect(),
+            tallying_rule_global_score: event.tallying_rule_global_score as i64,
+        }
+    }
+
+    /// SUI per pool token of the validator's staking pool at the end of the epoch.
+    pub fn exchange_rate(&self) -> f64 {
+        if self.exchange_rate_pool_token_amount == 0 {
+            1.0
+        } else {
+            self.exchange_rate_sui_amount as f64 / self.exchange_rate_pool_token_amount as f64
+        }
+    }
+}
+
+impl TryFrom<DBValidatorEpochInfo> for ValidatorEpochInfo {
+    type Error = IndexerError;
+
+    fn try_from(v: DBValidatorEpochInfo) -> Result<Self, Self::Error> {
+        Ok(ValidatorEpochInfo {
+            validator_address: SuiAddress::from_str(&v.validator_address)?,
+            reference_gas_survey_quote: v.reference_gas_survey_quote as u64,
+            stake: v.stake as u64,
+            commission_rate: v.commission_rate as u64,
+            pool_staking_reward: v.pool_staking_reward as u64,
+            storage_fund_staking_reward: v.storage_fund_staking_reward as u64,
+            exchange_rate_sui_amount: v.exchange_rate_sui_amount as u64,
+            exchange_rate_pool_token_amount: v.exchange_rate_pool_token_amount as u64,
+            tallying_rule_reporters: v
+                .tallying_rule_reporters
+                .into_iter()
+                .flatten()
+                .map(|reporter| SuiAddress::from_str(&reporter))
+                .collect::<Result<_, _>>()?,
+            tallying_rule_global_score: v.tallying_rule_global_score as u64,
+        })
+    }
+}
+
+/// Voting power of one authority in the committee of `epoch`.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = committees)]
+pub struct CommitteeMember {
+    pub epoch: i64,
+    pub authority_name: String,
+    pub voting_power: i64,
+}
+
+impl CommitteeMember {
+    pub fn new(epoch: EpochId, name: &AuthorityName, voting_power: StakeUnit) -> Self {
+        Self {
+            epoch: epoch as i64,
+            authority_name: Hex::encode(name),
+            voting_power: voting_power as i64,
+        }
+    }
+}
+
+/// Builds the committee of an epoch from all its members.
+pub fn committee_from_members(
+    epoch: EpochId,
+    members: Vec<CommitteeMember>,
+) -> Result<SuiCommittee, IndexerError> {
+    let validators = members
+        .into_iter()
+        .map(|member| {
+            let name = AuthorityName::from_str(&member.authority_name).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to parse authority name {}, error: {}",
+                    member.authority_name, e
+                ))
+            })?;
+            Ok((name, member.voting_power as StakeUnit))
+        })
+        .collect::<Result<_, IndexerError>>()?;
+    Ok(SuiCommittee { epoch, validators })
+}
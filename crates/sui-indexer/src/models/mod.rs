@@ -1,12 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod activity;
 pub mod addresses;
+pub mod balances;
 pub mod checkpoints;
+pub mod display;
+pub mod epoch;
 pub mod error_logs;
 pub mod events;
 pub mod objects;
 pub mod owners;
 pub mod packages;
+pub mod stake_events;
 pub mod transaction_index;
 pub mod transactions;
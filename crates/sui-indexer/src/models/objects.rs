@@ -3,8 +3,8 @@
 
 use crate::errors::IndexerError;
 use crate::models::owners::OwnerType;
-use crate::schema::objects;
 use crate::schema::sql_types::BcsBytes;
+use crate::schema::{objects, objects_history};
 use diesel::deserialize::FromSql;
 use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
@@ -13,15 +13,34 @@ use diesel::sql_types::{Bytea, Nullable, Record, VarChar};
 use diesel::SqlType;
 use diesel_derive_enum::DbEnum;
 use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::StructTag;
+use move_core_types::value::MoveStruct;
+use serde::Serialize;
+use serde_json::Value;
 use std::{collections::BTreeMap, str::FromStr};
-use sui_json_rpc_types::{SuiObjectData, SuiObjectRef, SuiRawData};
+use sui_json_rpc_types::{
+    Coin, SuiMoveValue, SuiObjectData, SuiObjectDataOptions, SuiObjectRef, SuiObjectResponse,
+    SuiRawData,
+};
 use sui_types::base_types::{EpochId, ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress};
 use sui_types::digests::TransactionDigest;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Data, MoveObject, ObjectFormatOptions, ObjectRead, Owner};
+use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
 
 const OBJECT: &str = "object";
+const DYNAMIC_FIELD_TYPE_PREFIX: &str = "0x2::dynamic_field::Field<";
+pub const DYNAMIC_FIELD_KIND: &str = "dynamic_field";
+pub const DYNAMIC_OBJECT_KIND: &str = "dynamic_object";
+
+fn dynamic_field_kind(type_: &DynamicFieldType) -> &'static str {
+    match type_ {
+        DynamicFieldType::DynamicField => DYNAMIC_FIELD_KIND,
+        DynamicFieldType::DynamicObject => DYNAMIC_OBJECT_KIND,
+    }
+}
 
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone)]
 #[diesel(table_name = objects, primary_key(object_id))]
@@ -42,7 +61,75 @@ pub struct Object {
     pub has_public_transfer: bool,
     pub storage_rebate: i64,
     pub bcs: Vec<NamedBcsBytes>,
+    // dynamic field related, only non-null for `0x2::dynamic_field::Field` objects
+    pub df_kind: Option<String>,
+    pub df_parent_id: Option<String>,
+    pub df_name_type: Option<String>,
+    pub df_name: Option<Value>,
+    pub df_bcs_name: Option<Vec<u8>>,
+    pub df_object_type: Option<String>,
+    pub df_object_id: Option<String>,
+    // fields rendered with the `Display` of the object type, if it has one
+    pub display: Option<Value>,
+}
+
+/// A version of an object kept in `objects_history`, which has the same columns as `objects`.
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = objects_history)]
+pub struct ObjectHistory {
+    pub epoch: i64,
+    pub checkpoint: i64,
+    pub object_id: String,
+    pub version: i64,
+    pub object_digest: String,
+    pub owner_type: OwnerType,
+    pub owner_address: Option<String>,
+    pub initial_shared_version: Option<i64>,
+    pub previous_transaction: String,
+    pub object_type: String,
+    pub object_status: ObjectStatus,
+    pub has_public_transfer: bool,
+    pub storage_rebate: i64,
+    pub bcs: Vec<NamedBcsBytes>,
+    pub df_kind: Option<String>,
+    pub df_parent_id: Option<String>,
+    pub df_name_type: Option<String>,
+    pub df_name: Option<Value>,
+    pub df_bcs_name: Option<Vec<u8>>,
+    pub df_object_type: Option<String>,
+    pub df_object_id: Option<String>,
+    pub display: Option<Value>,
+}
+
+impl From<Object> for ObjectHistory {
+    fn from(o: Object) -> Self {
+        ObjectHistory {
+            epoch: o.epoch,
+            checkpoint: o.checkpoint,
+            object_id: o.object_id,
+            version: o.version,
+            object_digest: o.object_digest,
+            owner_type: o.owner_type,
+            owner_address: o.owner_address,
+            initial_shared_version: o.initial_shared_version,
+            previous_transaction: o.previous_transaction,
+            object_type: o.object_type,
+            object_status: o.object_status,
+            has_public_transfer: o.has_public_transfer,
+            storage_rebate: o.storage_rebate,
+            bcs: o.bcs,
+            df_kind: o.df_kind,
+            df_parent_id: o.df_parent_id,
+            df_name_type: o.df_name_type,
+            df_name: o.df_name,
+            df_bcs_name: o.df_bcs_name,
+            df_object_type: o.df_object_type,
+            df_object_id: o.df_object_id,
+            display: o.display,
+        }
+    }
 }
+
 #[derive(SqlType, Debug, Clone)]
 #[diesel(sql_type = crate::schema::sql_types::BcsBytes)]
 pub struct NamedBcsBytes(pub String, pub Vec<u8>);
@@ -93,12 +180,21 @@ impl From<DeletedObject> for Object {
             has_public_transfer: o.has_public_transfer,
             storage_rebate: 0,
             bcs: vec![],
+            df_kind: None,
+            df_parent_id: None,
+            df_name_type: None,
+            df_name: None,
+            df_bcs_name: None,
+            df_object_type: None,
+            df_object_id: None,
+            display: None,
         }
     }
 }
 
-#[derive(DbEnum, Debug, Clone, Copy)]
+#[derive(DbEnum, Debug, Clone, Copy, Serialize)]
 #[ExistingTypePath = "crate::schema::sql_types::ObjectStatus"]
+#[serde(rename_all = "snake_case")]
 pub enum ObjectStatus {
     Created,
     Mutated,
@@ -155,7 +251,163 @@ impl Object {
             has_public_transfer,
             storage_rebate: o.storage_rebate.unwrap_or_default() as i64,
             bcs,
+            df_kind: None,
+            df_parent_id: None,
+            df_name_type: None,
+            df_name: None,
+            df_bcs_name: None,
+            df_object_type: None,
+            df_object_id: None,
+            display: None,
+        }
+    }
+
+    /// The Move struct of a Move object, None for packages.
+    pub fn to_move_struct(
+        &self,
+        module_cache: &impl GetModule,
+    ) -> Result<Option<(StructTag, MoveStruct)>, IndexerError> {
+        if self.object_type == ObjectType::Package.to_string() {
+            return Ok(None);
+        }
+        let object_type = parse_sui_struct_tag(&self.object_type)?;
+        let layout = MoveObject::get_layout_from_struct_tag(
+            object_type.clone(),
+            ObjectFormatOptions::default(),
+            module_cache,
+        )?;
+        let contents = &self
+            .bcs
+            .first()
+            .ok_or_else(|| {
+                IndexerError::SerdeError(format!("Object {} has no BCS content", self.object_id))
+            })?
+            .1;
+        let move_struct = MoveStruct::simple_deserialize(contents, &layout).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to deserialize object {} with error {:?}",
+                self.object_id, e
+            ))
+        })?;
+        Ok(Some((object_type, move_struct)))
+    }
+
+    /// Fills in the dynamic field columns if this is a `0x2::dynamic_field::Field` object.
+    pub fn index_dynamic_field(
+        &mut self,
+        module_cache: &impl GetModule,
+    ) -> Result<(), IndexerError> {
+        if !self.object_type.starts_with(DYNAMIC_FIELD_TYPE_PREFIX) {
+            return Ok(());
         }
+        let Some((object_type, move_struct)) = self.to_move_struct(module_cache)? else {
+            return Ok(());
+        };
+        if !DynamicFieldInfo::is_dynamic_field(&object_type) {
+            return Ok(());
+        }
+
+        let (name_value, df_type, object_id) = DynamicFieldInfo::parse_move_object(&move_struct)?;
+        let name_type = DynamicFieldInfo::try_extract_field_name(&object_type, &df_type)?;
+        let bcs_name = bcs::to_bytes(&name_value.clone().undecorate()).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to serialize name of dynamic field {} with error {:?}",
+                self.object_id, e
+            ))
+        })?;
+
+        self.df_object_type = match df_type {
+            // the type of a dynamic object is read from the child object itself
+            DynamicFieldType::DynamicObject => None,
+            DynamicFieldType::DynamicField => object_type.type_params.get(1).map(|t| t.to_string()),
+        };
+        self.df_kind = Some(dynamic_field_kind(&df_type).to_string());
+        self.df_parent_id = self.owner_address.clone();
+        self.df_name_type = Some(name_type.to_string());
+        self.df_name = Some(SuiMoveValue::from(name_value).to_json_value());
+        self.df_bcs_name = Some(bcs_name);
+        self.df_object_id = Some(object_id.to_string());
+        Ok(())
+    }
+
+    /// The dynamic field this `Field` object represents. `child` is the object a dynamic object
+    /// field points to, unused for other dynamic fields.
+    pub fn try_into_dynamic_field_info(
+        self,
+        child: Option<&Object>,
+    ) -> Result<DynamicFieldInfo, IndexerError> {
+        let missing = |column: &str| {
+            IndexerError::PostgresReadError(format!(
+                "Dynamic field {} has no {column}",
+                self.object_id
+            ))
+        };
+        let type_ = match self.df_kind.as_deref() {
+            Some(DYNAMIC_FIELD_KIND) => DynamicFieldType::DynamicField,
+            Some(DYNAMIC_OBJECT_KIND) => DynamicFieldType::DynamicObject,
+            _ => return Err(missing("df_kind")),
+        };
+        let name = DynamicFieldName {
+            type_: parse_sui_type_tag(
+                self.df_name_type
+                    .as_ref()
+                    .ok_or_else(|| missing("df_name_type"))?,
+            )?,
+            value: self.df_name.clone().ok_or_else(|| missing("df_name"))?,
+        };
+        let bcs_name = self
+            .df_bcs_name
+            .clone()
+            .ok_or_else(|| missing("df_bcs_name"))?;
+        let (object_type, (object_id, version, digest)) = match type_ {
+            DynamicFieldType::DynamicField => (
+                self.df_object_type
+                    .clone()
+                    .ok_or_else(|| missing("df_object_type"))?,
+                self.get_object_ref()?,
+            ),
+            DynamicFieldType::DynamicObject => {
+                let child = child.ok_or_else(|| missing("child object"))?;
+                (child.object_type.clone(), child.get_object_ref()?)
+            }
+        };
+        Ok(DynamicFieldInfo {
+            name,
+            bcs_name,
+            type_,
+            object_type,
+            object_id,
+            version,
+            digest,
+        })
+    }
+
+    /// The rendered `Display` fields stored with the object.
+    pub fn display_fields(&self) -> Result<Option<BTreeMap<String, String>>, IndexerError> {
+        self.display
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to deserialize display of object {} with error {:?}",
+                    self.object_id, e
+                ))
+            })
+    }
+
+    pub fn try_into_object_response(
+        self,
+        options: SuiObjectDataOptions,
+        module_cache: &impl GetModule,
+    ) -> Result<SuiObjectResponse, IndexerError> {
+        let display = self.display_fields()?;
+        Ok(match self.try_into_object_read(module_cache)? {
+            ObjectRead::Exists(object_ref, object, layout) => SuiObjectResponse::Exists(
+                (object_ref, object, layout, options, display).try_into()?,
+            ),
+            read => (read, options).try_into()?,
+        })
     }
 
     pub fn try_into_object_read(
@@ -268,6 +520,43 @@ impl TryFrom<Object> for sui_types::object::Object {
     }
 }
 
+impl TryFrom<Object> for Coin {
+    type Error = IndexerError;
+
+    fn try_from(o: Object) -> Result<Self, Self::Error> {
+        let (coin_object_id, version, digest) = o.get_object_ref()?;
+        let coin_type = parse_sui_struct_tag(&o.object_type)?
+            .type_params
+            .first()
+            .ok_or_else(|| {
+                IndexerError::SerdeError(format!("{} is not a coin type", o.object_type))
+            })?
+            .to_string();
+        let bcs_bytes = &o
+            .bcs
+            .first()
+            .ok_or_else(|| {
+                IndexerError::SerdeError(format!("Coin {} has no BCS content", o.object_id))
+            })?
+            .1;
+        let coin: sui_types::coin::Coin = bcs::from_bytes(bcs_bytes).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to deserialize coin {}, error: {}",
+                o.object_id, e
+            ))
+        })?;
+        Ok(Coin {
+            coin_type,
+            coin_object_id,
+            version,
+            digest,
+            balance: coin.balance.value(),
+            locked_until_epoch: None,
+            previous_transaction: TransactionDigest::from_str(&o.previous_transaction)?,
+        })
+    }
+}
+
 impl DeletedObject {
     pub fn from(
         epoch: &EpochId,
@@ -1,14 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
 use crate::errors::IndexerError;
 use crate::schema::packages;
 
 use diesel::prelude::*;
+use serde_json::Value;
 
 use crate::models::objects::NamedBcsBytes;
-use sui_json_rpc_types::SuiRawMovePackage;
-use sui_types::base_types::SuiAddress;
+use sui_json_rpc_types::{SuiMoveNormalizedModule, SuiPackageVersion, SuiRawMovePackage};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::move_package::normalize_modules;
 
 #[derive(Queryable, Insertable, Debug, Identifiable)]
 #[diesel(table_name = packages, primary_key(package_id, version))]
@@ -17,20 +23,100 @@ pub struct Package {
     pub version: i64,
     pub author: String,
     pub data: Vec<NamedBcsBytes>,
+    // null for packages indexed before this metadata was added, until their checkpoint is backfilled
+    pub original_package_id: Option<String>,
+    pub upgrade_cap_id: Option<String>,
+    pub transaction_digest: Option<String>,
+    pub checkpoint_sequence_number: Option<i64>,
+    pub modules: Option<Value>,
 }
 
 impl Package {
-    pub fn try_from(sender: SuiAddress, package: &SuiRawMovePackage) -> Result<Self, IndexerError> {
+    pub fn try_from(
+        sender: SuiAddress,
+        transaction_digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+        package: &SuiRawMovePackage,
+        upgrade_cap_id: Option<ObjectID>,
+    ) -> Result<Self, IndexerError> {
+        let data: Vec<NamedBcsBytes> = package
+            .module_map
+            .clone()
+            .into_iter()
+            .map(|(k, v)| NamedBcsBytes(k, v))
+            .collect();
+        let normalized_modules = normalize_modules(package.module_map.values())?;
+        // Modules keep the address of the first version of the package across upgrades.
+        let original_package_id = normalized_modules
+            .values()
+            .next()
+            .map(|module| ObjectID::from(module.address).to_string());
+        let modules: BTreeMap<String, SuiMoveNormalizedModule> = normalized_modules
+            .into_iter()
+            .map(|(name, module)| (name, module.into()))
+            .collect();
         Ok(Self {
             package_id: package.id.to_string(),
             version: package.version.value() as i64,
             author: sender.to_string(),
-            data: package
-                .module_map
-                .clone()
+            data,
+            original_package_id,
+            upgrade_cap_id: upgrade_cap_id.map(|id| id.to_string()),
+            transaction_digest: Some(transaction_digest.base58_encode()),
+            checkpoint_sequence_number: Some(checkpoint as i64),
+            modules: Some(serde_json::to_value(modules).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to serialize modules of package {} with error {:?}",
+                    package.id, e
+                ))
+            })?),
+        })
+    }
+
+    /// The normalized modules of the package, by module name.
+    pub fn normalized_modules(
+        &self,
+    ) -> Result<BTreeMap<String, SuiMoveNormalizedModule>, IndexerError> {
+        match &self.modules {
+            Some(modules) => serde_json::from_value(modules.clone()).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to deserialize modules of package {} with error {:?}",
+                    self.package_id, e
+                ))
+            }),
+            // Not indexed yet, normalize the module bytes instead
+            None => Ok(normalize_modules(self.data.iter().map(|module| &module.1))?
                 .into_iter()
-                .map(|(k, v)| NamedBcsBytes(k, v))
-                .collect(),
+                .map(|(name, module)| (name, module.into()))
+                .collect()),
+        }
+    }
+}
+
+impl TryFrom<Package> for SuiPackageVersion {
+    type Error = IndexerError;
+
+    fn try_from(package: Package) -> Result<Self, Self::Error> {
+        Ok(SuiPackageVersion {
+            package_id: ObjectID::from_str(&package.package_id)?,
+            original_package_id: package
+                .original_package_id
+                .as_deref()
+                .map(ObjectID::from_str)
+                .transpose()?,
+            version: SequenceNumber::from_u64(package.version as u64),
+            publisher: SuiAddress::from_str(&package.author)?,
+            upgrade_cap_id: package
+                .upgrade_cap_id
+                .as_deref()
+                .map(ObjectID::from_str)
+                .transpose()?,
+            transaction_digest: package
+                .transaction_digest
+                .as_deref()
+                .map(TransactionDigest::from_str)
+                .transpose()?,
+            checkpoint: package.checkpoint_sequence_number.map(|seq| seq as u64),
         })
     }
 }
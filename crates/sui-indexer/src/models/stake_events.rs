@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::stake_events;
+use diesel::prelude::*;
+use serde::Deserialize;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+pub const VALIDATOR_MODULE: &str = "validator";
+pub const STAKING_REQUEST_EVENT: &str = "StakingRequestEvent";
+pub const UNSTAKING_REQUEST_EVENT: &str = "UnstakingRequestEvent";
+
+/// Rust representation of the Move `sui::validator::StakingRequestEvent`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct StakingRequestEvent {
+    pub pool_id: ObjectID,
+    pub validator_address: SuiAddress,
+    pub staker_address: SuiAddress,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+/// Rust representation of the Move `sui::validator::UnstakingRequestEvent`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct UnstakingRequestEvent {
+    pub pool_id: ObjectID,
+    pub validator_address: SuiAddress,
+    pub staker_address: SuiAddress,
+    pub stake_activation_epoch: u64,
+    pub unstaking_epoch: u64,
+    pub principal_amount: u64,
+    pub reward_amount: u64,
+}
+
+/// A staking or unstaking request, `unstaking_epoch` and `reward_amount` are only set for the
+/// latter.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = stake_events)]
+pub struct StakeEvent {
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub event_sequence: i64,
+    pub checkpoint: i64,
+    pub epoch: i64,
+    pub pool_id: String,
+    pub validator_address: String,
+    pub staker_address: String,
+    pub stake_activation_epoch: i64,
+    pub unstaking_epoch: Option<i64>,
+    pub principal_amount: i64,
+    pub reward_amount: Option<i64>,
+}
+
+impl StakeEvent {
+    pub fn staking_request(
+        event: &SuiEvent,
+        checkpoint: CheckpointSequenceNumber,
+        request: &StakingRequestEvent,
+    ) -> Self {
+        Self {
+            id: None,
+            transaction_digest: event.id.tx_digest.base58_encode(),
+            event_sequence: event.id.event_seq as i64,
+            checkpoint: checkpoint as i64,
+            epoch: request.epoch as i64,
+            pool_id: request.pool_id.to_string(),
+            validator_address: request.validator_address.to_string(),
+            staker_address: request.staker_address.to_string(),
+            // stakes become active at the epoch following the request
+            stake_activation_epoch: request.epoch as i64 + 1,
+            unstaking_epoch: None,
+            principal_amount: request.amount as i64,
+            reward_amount: None,
+        }
+    }
+
+    pub fn unstaking_request(
+        event: &SuiEvent,
+        checkpoint: CheckpointSequenceNumber,
+        epoch: EpochId,
+        request: &UnstakingRequestEvent,
+    ) -> Self {
+        Self {
+            id: None,
+            transaction_digest: event.id.tx_digest.base58_encode(),
+            event_sequence: event.id.event_seq as i64,
+            checkpoint: checkpoint as i64,
+            epoch: epoch as i64,
+            pool_id: request.pool_id.to_string(),
+            validator_address: request.validator_address.to_string(),
+            staker_address: request.staker_address.to_string(),
+            stake_activation_epoch: request.stake_activation_epoch as i64,
+            unstaking_epoch: Some(request.unstaking_epoch as i64),
+            principal_amount: request.principal_amount as i64,
+            reward_amount: Some(request.reward_amount as i64),
+        }
+    }
+}
@@ -16,6 +16,28 @@ pub mod sql_types {
     pub struct OwnerType;
 }
 
+diesel::table! {
+    address_counterparties (address, counterparty) {
+        address -> Varchar,
+        counterparty -> Varchar,
+        transaction_count -> Int8,
+        last_activity_timestamp_ms -> Int8,
+    }
+}
+
+diesel::table! {
+    address_stats (address) {
+        address -> Varchar,
+        transaction_count -> Int8,
+        sent_count -> Int8,
+        received_count -> Int8,
+        first_activity_checkpoint -> Int8,
+        first_activity_timestamp_ms -> Int8,
+        last_activity_checkpoint -> Int8,
+        last_activity_timestamp_ms -> Int8,
+    }
+}
+
 diesel::table! {
     addresses (account_address) {
         account_address -> Varchar,
@@ -47,6 +69,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    committees (epoch, authority_name) {
+        epoch -> Int8,
+        authority_name -> Varchar,
+        voting_power -> Int8,
+    }
+}
+
+diesel::table! {
+    display (object_type) {
+        object_type -> Varchar,
+        id -> Varchar,
+        version -> Int4,
+        bcs -> Bytea,
+        checkpoint -> Int8,
+    }
+}
+
+diesel::table! {
+    display_renders (object_type) {
+        object_type -> Varchar,
+        checkpoint -> Int8,
+        cursor -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    epochs (epoch) {
+        epoch -> Int8,
+        first_checkpoint_id -> Int8,
+        last_checkpoint_id -> Int8,
+        epoch_start_timestamp -> Int8,
+        epoch_end_timestamp -> Int8,
+        epoch_total_transactions -> Int8,
+        next_epoch_protocol_version -> Int8,
+        next_epoch_reference_gas_price -> Int8,
+        total_stake -> Int8,
+        storage_fund_reinvestment -> Int8,
+        storage_charge -> Int8,
+        storage_rebate -> Int8,
+        storage_fund_balance -> Int8,
+        stake_subsidy_amount -> Int8,
+        total_gas_fees -> Int8,
+        total_stake_rewards_distributed -> Int8,
+        leftover_storage_fund_inflow -> Int8,
+    }
+}
+
 diesel::table! {
     error_logs (id) {
         id -> Int8,
@@ -95,6 +165,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    network_activity (bucket_start_ms) {
+        bucket_start_ms -> Int8,
+        transaction_count -> Int8,
+        checkpoint_count -> Int8,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::OwnerType;
@@ -116,6 +194,14 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        df_kind -> Nullable<Varchar>,
+        df_parent_id -> Nullable<Varchar>,
+        df_name_type -> Nullable<Varchar>,
+        df_name -> Nullable<Jsonb>,
+        df_bcs_name -> Nullable<Bytea>,
+        df_object_type -> Nullable<Varchar>,
+        df_object_id -> Nullable<Varchar>,
+        display -> Nullable<Jsonb>,
     }
 }
 
@@ -140,6 +226,14 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        df_kind -> Nullable<Varchar>,
+        df_parent_id -> Nullable<Varchar>,
+        df_name_type -> Nullable<Varchar>,
+        df_name -> Nullable<Jsonb>,
+        df_bcs_name -> Nullable<Bytea>,
+        df_object_type -> Nullable<Varchar>,
+        df_object_id -> Nullable<Varchar>,
+        display -> Nullable<Jsonb>,
     }
 }
 
@@ -179,6 +273,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    package_calls (epoch, move_package) {
+        epoch -> Int8,
+        move_package -> Text,
+        call_count -> Int8,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::BcsBytes;
@@ -188,6 +290,11 @@ diesel::table! {
         version -> Int8,
         author -> Varchar,
         data -> Array<Nullable<BcsBytes>>,
+        original_package_id -> Nullable<Varchar>,
+        upgrade_cap_id -> Nullable<Varchar>,
+        transaction_digest -> Nullable<Varchar>,
+        checkpoint_sequence_number -> Nullable<Int8>,
+        modules -> Nullable<Jsonb>,
     }
 }
 
@@ -201,6 +308,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sink_watermarks (sink_name) {
+        sink_name -> Varchar,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    stake_events (id) {
+        id -> Int8,
+        transaction_digest -> Varchar,
+        event_sequence -> Int8,
+        checkpoint -> Int8,
+        epoch -> Int8,
+        pool_id -> Varchar,
+        validator_address -> Varchar,
+        staker_address -> Varchar,
+        stake_activation_epoch -> Int8,
+        unstaking_epoch -> Nullable<Int8>,
+        principal_amount -> Int8,
+        reward_amount -> Nullable<Int8>,
+    }
+}
+
 diesel::table! {
     transactions (id) {
         id -> Int8,
@@ -232,18 +363,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    validators (epoch, validator_address) {
+        epoch -> Int8,
+        validator_address -> Varchar,
+        reference_gas_survey_quote -> Int8,
+        stake -> Int8,
+        commission_rate -> Int8,
+        pool_staking_reward -> Int8,
+        storage_fund_staking_reward -> Int8,
+        exchange_rate_sui_amount -> Int8,
+        exchange_rate_pool_token_amount -> Int8,
+        tallying_rule_reporters -> Array<Nullable<Text>>,
+        tallying_rule_global_score -> Int8,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
+    address_counterparties,
+    address_stats,
     addresses,
     checkpoints,
+    committees,
+    display,
+    display_renders,
+    epochs,
     error_logs,
     events,
     input_objects,
     move_calls,
+    network_activity,
     objects,
     objects_history,
     owner,
     owner_history,
+    package_calls,
     packages,
     recipients,
+    sink_watermarks,
+    stake_events,
     transactions,
+    validators,
 );
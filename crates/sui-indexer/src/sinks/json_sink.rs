@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use sui_json_rpc_types::{Checkpoint as RpcCheckpoint, SuiObjectData};
+
+use crate::errors::IndexerError;
+use crate::models::objects::ObjectStatus;
+use crate::sinks::{create_sink_dir, write_file_atomically, CheckpointSink, IndexedCheckpoint};
+use crate::types::SuiTransactionFullResponse;
+
+pub const JSON_SINK_NAME: &str = "json";
+
+/// One line of a checkpoint file.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JsonRecord<'a> {
+    Checkpoint(&'a RpcCheckpoint),
+    Transaction(&'a SuiTransactionFullResponse),
+    Object {
+        status: ObjectStatus,
+        object: &'a SuiObjectData,
+    },
+}
+
+/// Writes each checkpoint to `<dir>/<sequence number>.jsonl`, as newline-delimited JSON records
+/// tagged by `kind`: the checkpoint summary first, then its transactions and changed objects.
+pub struct JsonFileSink {
+    dir: PathBuf,
+}
+
+impl JsonFileSink {
+    pub fn new(dir: PathBuf) -> Result<Self, IndexerError> {
+        Ok(Self {
+            dir: create_sink_dir(JSON_SINK_NAME, &dir)?,
+        })
+    }
+}
+
+#[async_trait]
+impl CheckpointSink for JsonFileSink {
+    fn name(&self) -> &str {
+        JSON_SINK_NAME
+    }
+
+    async fn write_checkpoint(
+        &self,
+        checkpoint: &IndexedCheckpoint<'_>,
+    ) -> Result<(), IndexerError> {
+        let data = checkpoint.data;
+        let records = std::iter::once(JsonRecord::Checkpoint(&data.checkpoint))
+            .chain(data.transactions.iter().map(JsonRecord::Transaction))
+            .chain(
+                data.changed_objects
+                    .iter()
+                    .map(|(status, object)| JsonRecord::Object {
+                        status: *status,
+                        object,
+                    }),
+            );
+
+        let mut bytes = vec![];
+        for record in records {
+            serde_json::to_writer(&mut bytes, &record).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed to serialize checkpoint {} to JSON with error {:?}",
+                    data.checkpoint.sequence_number, e
+                ))
+            })?;
+            bytes.push(b'\n');
+        }
+
+        let path = self
+            .dir
+            .join(format!("{}.jsonl", data.checkpoint.sequence_number));
+        write_file_atomically(JSON_SINK_NAME, &path, &bytes)
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Destinations of the checkpoint stream processed by the checkpoint handler.
+//!
+//! Every checkpoint is delivered to each sink in order, and the handler records a per-sink
+//! watermark once a sink has written it. Sinks resume from their own watermark on restart, so a
+//! sink added to an existing indexer catches up from genesis without affecting the others.
+//! Delivery is at-least-once: a checkpoint written right before a crash may be delivered again.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::errors::IndexerError;
+use crate::store::{CheckpointData, IndexerStore, TemporaryCheckpointStore, TemporaryEpochStore};
+use crate::IndexerConfig;
+
+pub use json_sink::JsonFileSink;
+pub use parquet_sink::ParquetFileSink;
+pub use store_sink::StoreSink;
+pub use webhook_sink::WebhookSink;
+
+mod json_sink;
+mod parquet_sink;
+mod store_sink;
+mod webhook_sink;
+
+/// A checkpoint as downloaded from the fullnode, along with the rows indexed from it.
+pub struct IndexedCheckpoint<'a> {
+    pub data: &'a CheckpointData,
+    pub checkpoint: &'a TemporaryCheckpointStore,
+    /// Set for the last checkpoint of an epoch.
+    pub epoch: Option<&'a TemporaryEpochStore>,
+}
+
+#[async_trait]
+pub trait CheckpointSink: Send + Sync {
+    /// Unique name of the sink, its watermark is stored under this name.
+    fn name(&self) -> &str;
+
+    /// Writes `checkpoint` to the sink. Checkpoints are delivered in order, but may be delivered
+    /// more than once, so writes must be idempotent.
+    async fn write_checkpoint(
+        &self,
+        checkpoint: &IndexedCheckpoint<'_>,
+    ) -> Result<(), IndexerError>;
+}
+
+/// The sinks enabled by `config`, the store itself is always the first one.
+pub fn build_checkpoint_sinks<S>(
+    config: &IndexerConfig,
+    store: S,
+) -> Result<Vec<Arc<dyn CheckpointSink>>, IndexerError>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    let mut sinks: Vec<Arc<dyn CheckpointSink>> = vec![Arc::new(StoreSink::new(store))];
+    if let Some(dir) = &config.json_sink_dir {
+        sinks.push(Arc::new(JsonFileSink::new(dir.clone())?));
+    }
+    if let Some(dir) = &config.parquet_sink_dir {
+        sinks.push(Arc::new(ParquetFileSink::new(dir.clone())?));
+    }
+    if let Some(url) = &config.webhook_sink_url {
+        sinks.push(Arc::new(WebhookSink::new(url)?));
+    }
+    Ok(sinks)
+}
+
+/// Creates `dir` if needed, for sinks writing files into it.
+fn create_sink_dir(name: &str, dir: &Path) -> Result<PathBuf, IndexerError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        IndexerError::CheckpointSinkError(
+            name.to_string(),
+            format!("Failed to create directory {}: {:?}", dir.display(), e),
+        )
+    })?;
+    Ok(dir.to_path_buf())
+}
+
+/// Writes `bytes` to `path` through a temporary file, so that readers never observe a partially
+/// written file and rewriting a checkpoint replaces it.
+fn write_file_atomically(name: &str, path: &Path, bytes: &[u8]) -> Result<(), IndexerError> {
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, bytes)
+        .and_then(|_| std::fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            IndexerError::CheckpointSinkError(
+                name.to_string(),
+                format!("Failed to write {}: {:?}", path.display(), e),
+            )
+        })
+}
@@ -0,0 +1,108 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use parquet::arrow::ArrowWriter;
+
+use crate::errors::IndexerError;
+use crate::models::transactions::Transaction;
+use crate::sinks::{create_sink_dir, write_file_atomically, CheckpointSink, IndexedCheckpoint};
+
+pub const PARQUET_SINK_NAME: &str = "parquet";
+
+/// Writes the transactions of each checkpoint to `<dir>/<sequence number>.parquet`, one row per
+/// transaction with the columns of the indexed `transactions` table.
+pub struct ParquetFileSink {
+    dir: PathBuf,
+    schema: SchemaRef,
+}
+
+impl ParquetFileSink {
+    pub fn new(dir: PathBuf) -> Result<Self, IndexerError> {
+        let int64 = |name| Field::new(name, DataType::Int64, false);
+        let utf8 = |name| Field::new(name, DataType::Utf8, false);
+        let schema = Schema::new(vec![
+            int64("checkpoint_sequence_number"),
+            int64("timestamp_ms"),
+            utf8("transaction_digest"),
+            utf8("sender"),
+            utf8("transaction_kind"),
+            utf8("gas_object_id"),
+            int64("gas_budget"),
+            int64("gas_price"),
+            int64("total_gas_cost"),
+            int64("computation_cost"),
+            int64("storage_cost"),
+            int64("storage_rebate"),
+            utf8("transaction_content"),
+            utf8("transaction_effects_content"),
+        ]);
+        Ok(Self {
+            dir: create_sink_dir(PARQUET_SINK_NAME, &dir)?,
+            schema: Arc::new(schema),
+        })
+    }
+
+    fn record_batch(&self, transactions: &[Transaction]) -> Result<RecordBatch, IndexerError> {
+        let int64 = |f: fn(&Transaction) -> i64| -> ArrayRef {
+            Arc::new(Int64Array::from_iter_values(transactions.iter().map(f)))
+        };
+        let utf8 = |f: fn(&Transaction) -> &str| -> ArrayRef {
+            Arc::new(StringArray::from_iter_values(transactions.iter().map(f)))
+        };
+        RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                int64(|tx| tx.checkpoint_sequence_number),
+                int64(|tx| tx.timestamp_ms),
+                utf8(|tx| tx.transaction_digest.as_str()),
+                utf8(|tx| tx.sender.as_str()),
+                utf8(|tx| tx.transaction_kind.as_str()),
+                utf8(|tx| tx.gas_object_id.as_str()),
+                int64(|tx| tx.gas_budget),
+                int64(|tx| tx.gas_price),
+                int64(|tx| tx.total_gas_cost),
+                int64(|tx| tx.computation_cost),
+                int64(|tx| tx.storage_cost),
+                int64(|tx| tx.storage_rebate),
+                utf8(|tx| tx.transaction_content.as_str()),
+                utf8(|tx| tx.transaction_effects_content.as_str()),
+            ],
+        )
+        .map_err(|e| IndexerError::CheckpointSinkError(PARQUET_SINK_NAME.into(), e.to_string()))
+    }
+}
+
+#[async_trait]
+impl CheckpointSink for ParquetFileSink {
+    fn name(&self) -> &str {
+        PARQUET_SINK_NAME
+    }
+
+    async fn write_checkpoint(
+        &self,
+        checkpoint: &IndexedCheckpoint<'_>,
+    ) -> Result<(), IndexerError> {
+        let batch = self.record_batch(&checkpoint.checkpoint.transactions)?;
+        let to_sink_error = |e: parquet::errors::ParquetError| {
+            IndexerError::CheckpointSinkError(PARQUET_SINK_NAME.into(), e.to_string())
+        };
+
+        let mut bytes = vec![];
+        let mut writer =
+            ArrowWriter::try_new(&mut bytes, self.schema.clone(), None).map_err(to_sink_error)?;
+        writer.write(&batch).map_err(to_sink_error)?;
+        writer.close().map_err(to_sink_error)?;
+
+        let path = self.dir.join(format!(
+            "{}.parquet",
+            checkpoint.checkpoint.checkpoint.sequence_number
+        ));
+        write_file_atomically(PARQUET_SINK_NAME, &path, &bytes)
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::errors::IndexerError;
+use crate::sinks::{CheckpointSink, IndexedCheckpoint};
+use crate::store::IndexerStore;
+
+pub const STORE_SINK_NAME: &str = "postgres";
+
+/// Persists checkpoints to the indexer store the JSON RPC server reads from.
+pub struct StoreSink<S> {
+    state: S,
+}
+
+impl<S> StoreSink<S> {
+    pub fn new(state: S) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl<S> CheckpointSink for StoreSink<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    fn name(&self) -> &str {
+        STORE_SINK_NAME
+    }
+
+    async fn write_checkpoint(
+        &self,
+        checkpoint: &IndexedCheckpoint<'_>,
+    ) -> Result<(), IndexerError> {
+        let indexed_checkpoint = checkpoint.checkpoint;
+        let tx_count = indexed_checkpoint.transactions.len();
+        let object_count = indexed_checkpoint.objects_changes.len();
+
+        self.state.persist_checkpoint(indexed_checkpoint)?;
+        info!(
+            "Checkpoint {} committed with {tx_count} transactions and {object_count} objects.",
+            indexed_checkpoint.checkpoint.sequence_number
+        );
+
+        if let Some(indexed_epoch) = checkpoint.epoch {
+            self.state.persist_epoch(indexed_epoch)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+
+use crate::errors::IndexerError;
+use crate::sinks::{CheckpointSink, IndexedCheckpoint};
+
+pub const WEBHOOK_SINK_NAME: &str = "webhook";
+/// Header carrying the sequence number of the posted checkpoint, so that receivers can drop
+/// checkpoints delivered twice without parsing the body.
+pub const CHECKPOINT_SEQUENCE_NUMBER_HEADER: &str = "x-sui-checkpoint-sequence-number";
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Posts each checkpoint, with its transactions and changed objects, as a JSON body to a URL.
+/// Any response other than a success status fails the write, and the checkpoint is retried.
+pub struct WebhookSink {
+    client: Client,
+    url: Url,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Result<Self, IndexerError> {
+        let url = Url::parse(url).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!("Invalid webhook URL {url}: {e}"))
+        })?;
+        let client = Client::builder()
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                IndexerError::CheckpointSinkError(WEBHOOK_SINK_NAME.into(), e.to_string())
+            })?;
+        Ok(Self { client, url })
+    }
+}
+
+#[async_trait]
+impl CheckpointSink for WebhookSink {
+    fn name(&self) -> &str {
+        WEBHOOK_SINK_NAME
+    }
+
+    async fn write_checkpoint(
+        &self,
+        checkpoint: &IndexedCheckpoint<'_>,
+    ) -> Result<(), IndexerError> {
+        let sequence_number = checkpoint.data.checkpoint.sequence_number;
+        self.client
+            .post(self.url.clone())
+            .header(CHECKPOINT_SEQUENCE_NUMBER_HEADER, sequence_number)
+            .json(checkpoint.data)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                IndexerError::CheckpointSinkError(
+                    WEBHOOK_SINK_NAME.into(),
+                    format!("Failed to post checkpoint {sequence_number}: {e}"),
+                )
+            })?;
+        Ok(())
+    }
+}
@@ -4,27 +4,53 @@
 use crate::errors::IndexerError;
 use crate::models::addresses::Address;
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
+use crate::models::epoch::{CommitteeMember, DBEpochInfo, DBValidatorEpochInfo};
 use crate::models::events::Event;
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::owners::ObjectOwner;
 use crate::models::packages::Package;
+use crate::models::stake_events::StakeEvent;
 use crate::models::transaction_index::{InputObject, MoveCall, Recipient};
 use crate::models::transactions::Transaction;
 use crate::types::SuiTransactionFullResponse;
 use async_trait::async_trait;
+use move_bytecode_utils::module_cache::GetModule;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use sui_json_rpc_types::{
-    Checkpoint as RpcCheckpoint, CheckpointId, EventFilter, EventPage, SuiObjectData,
+    AddressCounterparty, AddressStats, Balance, Checkpoint as RpcCheckpoint, CheckpointId,
+    CoinPage, EpochInfo, EventFilter, EventPage, MoveFunctionCaller, NetworkTps, PackageCallCount,
+    SuiCommittee, SuiMoveNormalizedModule, SuiObjectData, SuiPackageVersion,
 };
-use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::event::EventID;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::ObjectRead;
 
 #[async_trait]
 pub trait IndexerStore {
-    type ModuleCache;
+    type ModuleCache: GetModule;
 
     fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError>;
     fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint, IndexerError>;
+    /// The first checkpoint of `epoch` that has been persisted, if any.
+    fn get_epoch_first_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> Result<Option<Checkpoint>, IndexerError>;
+
+    /// Ended epochs with the state of their validators, starting after `cursor`.
+    fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<EpochInfo>, IndexerError>;
+
+    /// The committee of `epoch`, or of the latest indexed epoch if unset.
+    fn get_committee(&self, epoch: Option<EpochId>) -> Result<Option<SuiCommittee>, IndexerError>;
 
     fn get_event(&self, id: EventID) -> Result<Event, IndexerError>;
     fn get_events(
@@ -40,6 +66,86 @@ pub trait IndexerStore {
         object_id: ObjectID,
         version: Option<SequenceNumber>,
     ) -> Result<ObjectRead, IndexerError>;
+    /// The latest indexed version of `object_id`, along with its dynamic field and display columns.
+    fn get_latest_object(&self, object_id: ObjectID) -> Result<Option<Object>, IndexerError>;
+
+    /// Live objects of `object_type` whose rendered `Display` contains all the `display` fields,
+    /// in ascending object ID order, starting after `cursor`.
+    fn query_objects(
+        &self,
+        object_type: Option<String>,
+        display: BTreeMap<String, String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<Object>, IndexerError>;
+
+    /// Dynamic fields of `parent_object_id` in ascending field object ID order, starting after
+    /// `cursor`.
+    fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<DynamicFieldInfo>, IndexerError>;
+    /// The value of the dynamic field `name` of `parent_object_id`, that is the `Field` object for
+    /// dynamic fields and the child object for dynamic object fields.
+    fn get_dynamic_field_object(
+        &self,
+        parent_object_id: ObjectID,
+        name: &DynamicFieldName,
+    ) -> Result<Option<Object>, IndexerError>;
+
+    /// The `Display` of `object_type` published last, if one has been published.
+    fn get_display(&self, object_type: &str) -> Result<Option<StoredDisplay>, IndexerError>;
+
+    /// Coins owned by `owner` in ascending object ID order, starting after `cursor`. Only coins of
+    /// `coin_type` (e.g. `0x2::sui::SUI`) are returned if it is set.
+    fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<CoinPage, IndexerError>;
+    fn get_balance(&self, owner: SuiAddress, coin_type: String) -> Result<Balance, IndexerError>;
+    fn get_all_balances(&self, owner: SuiAddress) -> Result<Vec<Balance>, IndexerError>;
+
+    /// Aggregated activity of `address`, None if it has not been involved in any transaction.
+    fn get_address_stats(&self, address: SuiAddress) -> Result<Option<AddressStats>, IndexerError>;
+    /// The `limit` counterparties `address` has the most transactions with.
+    fn get_address_counterparties(
+        &self,
+        address: SuiAddress,
+        limit: usize,
+    ) -> Result<Vec<AddressCounterparty>, IndexerError>;
+    /// The `limit` most called packages in `epoch`, or in the latest epoch with calls if unset.
+    fn get_top_packages(
+        &self,
+        epoch: Option<EpochId>,
+        limit: usize,
+    ) -> Result<Vec<PackageCallCount>, IndexerError>;
+    /// Transactions per second over the latest `window_ms`, at the granularity of the
+    /// `network_activity` buckets.
+    fn get_network_tps(&self, window_ms: u64) -> Result<NetworkTps, IndexerError>;
+
+    /// All versions of the package `package_id` is a version of, oldest first.
+    fn get_package_versions(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<SuiPackageVersion>, IndexerError>;
+    /// The normalized modules of a package by module name, None if the package is not indexed.
+    fn get_package_modules(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Option<BTreeMap<String, SuiMoveNormalizedModule>>, IndexerError>;
+    /// The `limit` addresses that called a Move function the most.
+    fn get_function_callers(
+        &self,
+        package_id: ObjectID,
+        module: &str,
+        function: &str,
+        limit: usize,
+    ) -> Result<Vec<MoveFunctionCaller>, IndexerError>;
 
     fn get_total_transaction_number(&self) -> Result<i64, IndexerError>;
 
@@ -132,13 +238,38 @@ pub trait IndexerStore {
 
     fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError>;
     fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError>;
+    /// Replaces the rows derived from an already persisted checkpoint with `data`, including the
+    /// object history and the contribution of the checkpoint to the activity aggregates. Objects
+    /// are only overwritten if the persisted version is not newer, and addresses keep their
+    /// earliest appearance.
+    fn backfill_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError>;
+    /// Replaces the rows of an already persisted ended epoch with `data`.
+    fn backfill_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError>;
+    /// Brings the balances served by `get_balance` and `get_all_balances` up to date with the
+    /// persisted objects.
+    fn refresh_balances(&self) -> Result<(), IndexerError>;
+    /// Re-renders the display of at most `limit` live objects of a type whose `Display` was
+    /// published after they were indexed, and returns the number of objects re-rendered. Returns
+    /// 0 once all objects are rendered with the latest `Display` of their type.
+    fn render_pending_displays(&self, limit: usize) -> Result<usize, IndexerError>;
+
+    /// The last checkpoint written to the checkpoint sink `sink_name`, if any.
+    fn get_sink_watermark(
+        &self,
+        sink_name: &str,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError>;
+    fn persist_sink_watermark(
+        &self,
+        sink_name: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError>;
 
     fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError>;
 
     fn module_cache(&self) -> &Self::ModuleCache;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CheckpointData {
     pub checkpoint: RpcCheckpoint,
     pub transactions: Vec<SuiTransactionFullResponse>,
@@ -156,6 +287,9 @@ pub struct TemporaryCheckpointStore {
     pub input_objects: Vec<InputObject>,
     pub move_calls: Vec<MoveCall>,
     pub recipients: Vec<Recipient>,
+    pub stake_events: Vec<StakeEvent>,
+    /// The displays published in the checkpoint, at most one per type.
+    pub displays: Vec<StoredDisplay>,
 }
 
 #[derive(Debug)]
@@ -168,4 +302,8 @@ pub struct TransactionObjectChanges {
 pub struct TemporaryEpochStore {
     pub owner_index: Vec<ObjectOwner>,
     pub epoch_id: u64,
+    pub epoch: DBEpochInfo,
+    pub validators: Vec<DBValidatorEpochInfo>,
+    /// Committee of the next epoch.
+    pub next_epoch_committee: Vec<CommitteeMember>,
 }
@@ -2,8 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub use indexer_store::*;
+pub use module_resolver::{CheckpointModuleResolver, SqliteModuleResolver};
 pub use pg_indexer_store::PgIndexerStore;
+pub use sqlite_indexer_store::SqliteIndexerStore;
 
 mod indexer_store;
 mod module_resolver;
 mod pg_indexer_store;
+mod sqlite_indexer_store;
+mod sqlite_models;
+mod sqlite_schema;
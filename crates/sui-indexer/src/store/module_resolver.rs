@@ -2,13 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
-use crate::{get_pg_pool_connection, PgConnectionPool};
+use crate::models::packages::Package;
+use crate::store::sqlite_models::PackageRow;
+use crate::store::sqlite_schema::packages;
+use crate::{
+    get_pg_pool_connection, get_sqlite_pool_connection, PgConnectionPool, SqliteConnectionPool,
+};
 use diesel::pg::sql_types::Bytea;
 use diesel::sql_types::Text;
 use diesel::QueryableByName;
-use diesel::RunQueryDsl;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use sui_types::base_types::ObjectID;
 
 pub struct IndexerModuleResolver {
@@ -57,3 +66,95 @@ impl ModuleResolver for IndexerModuleResolver {
         Ok(Some(module_bytes.data))
     }
 }
+
+pub struct SqliteModuleResolver {
+    cp: SqliteConnectionPool,
+}
+
+impl SqliteModuleResolver {
+    pub fn new(cp: SqliteConnectionPool) -> Self {
+        Self { cp }
+    }
+}
+
+impl ModuleResolver for SqliteModuleResolver {
+    type Error = IndexerError;
+
+    fn get_module(&self, id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
+        let package_id = ObjectID::from(*id.address()).to_string();
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        let package = packages::table
+            .filter(packages::package_id.eq(&package_id))
+            .order(packages::version.desc())
+            .first::<PackageRow>(&mut sqlite_pool_conn)
+            .optional()
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading package {} with error {:?}",
+                    package_id, e
+                ))
+            })?;
+        let Some(package) = package else {
+            return Ok(None);
+        };
+        let module_name = id.name().as_str();
+        Ok(Package::try_from(package)?
+            .data
+            .into_iter()
+            .find(|module| module.0 == module_name)
+            .map(|module| module.1))
+    }
+}
+
+/// Resolves modules of the packages published in a checkpoint that has not been persisted yet,
+/// and all other modules from the `store` module cache.
+pub struct CheckpointModuleResolver<'a, M> {
+    modules: HashMap<ModuleId, CompiledModule>,
+    store: &'a M,
+}
+
+impl<'a, M: GetModule> CheckpointModuleResolver<'a, M> {
+    pub fn new(packages: &[Package], store: &'a M) -> Result<Self, IndexerError> {
+        let modules = packages
+            .iter()
+            .flat_map(|package| package.data.iter())
+            .map(|module| {
+                CompiledModule::deserialize(&module.1)
+                    .map(|module| (module.self_id(), module))
+                    .map_err(|e| {
+                        IndexerError::SerdeError(format!(
+                            "Failed to deserialize module {} with error {:?}",
+                            module.0, e
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { modules, store })
+    }
+}
+
+pub enum ResolvedModule<'a, T> {
+    Checkpoint(&'a CompiledModule),
+    Store(T),
+}
+
+impl<T: Borrow<CompiledModule>> Borrow<CompiledModule> for ResolvedModule<'_, T> {
+    fn borrow(&self) -> &CompiledModule {
+        match self {
+            ResolvedModule::Checkpoint(module) => module,
+            ResolvedModule::Store(module) => module.borrow(),
+        }
+    }
+}
+
+impl<'a, M: GetModule> GetModule for &'a CheckpointModuleResolver<'a, M> {
+    type Error = M::Error;
+    type Item = ResolvedModule<'a, M::Item>;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(module) = self.modules.get(id) {
+            return Ok(Some(ResolvedModule::Checkpoint(module)));
+        }
+        Ok(self.store.get_module_by_id(id)?.map(ResolvedModule::Store))
+    }
+}
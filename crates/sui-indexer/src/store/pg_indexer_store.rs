@@ -1,38 +1,61 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use diesel::dsl::{count, max};
-use diesel::sql_types::VarChar;
+use diesel::dsl::{count, count_star, max, sql};
+use diesel::pg::PgConnection;
+use diesel::sql_types::{Array, BigInt, Bool, Jsonb, Nullable, Text, VarChar};
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgArrayExpressionMethods};
-use diesel::{OptionalExtension, QueryableByName};
+use diesel::{OptionalExtension, QueryResult, QueryableByName};
 use diesel::{QueryDsl, RunQueryDsl};
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use sui_json_rpc_types::{CheckpointId, EventFilter, EventPage, SuiEvent};
-use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_json_rpc_types::{
+    AddressCounterparty, AddressStats, Balance, CheckpointId, Coin, CoinPage, EpochInfo,
+    EventFilter, EventPage, MoveFunctionCaller, NetworkTps, PackageCallCount, SuiCommittee,
+    SuiEvent, SuiMoveNormalizedModule, SuiPackageVersion,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::committee::EpochId;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::event::EventID;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::ObjectRead;
+use sui_types::parse_sui_struct_tag;
 
 use crate::errors::IndexerError;
+use crate::models::activity::{
+    CheckpointActivity, DBAddressCounterparty, DBAddressStats, DBNetworkActivity, DBPackageCalls,
+    NETWORK_ACTIVITY_BUCKET_MS,
+};
+use crate::models::addresses::Address;
+use crate::models::balances::AddressBalance;
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
+use crate::models::epoch::{
+    committee_from_members, CommitteeMember, DBEpochInfo, DBValidatorEpochInfo,
+};
 use crate::models::error_logs::commit_error_logs;
 use crate::models::events::Event;
-use crate::models::objects::Object;
+use crate::models::objects::{Object, ObjectHistory, ObjectStatus, DYNAMIC_OBJECT_KIND};
+use crate::models::owners::OwnerType;
+use crate::models::packages::Package;
+use crate::models::transaction_index::{MoveCall, Recipient};
 use crate::models::transactions::Transaction;
 use crate::schema::{
-    addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, events, input_objects,
-    input_objects::dsl as input_objects_dsl, move_calls, move_calls::dsl as move_calls_dsl,
-    objects, objects::dsl as objects_dsl, objects_history, packages, recipients,
-    recipients::dsl as recipients_dsl, transactions, transactions::dsl as transactions_dsl,
+    address_counterparties, address_stats, addresses, checkpoints,
+    checkpoints::dsl as checkpoints_dsl, committees, display, display_renders, epochs, events,
+    input_objects, input_objects::dsl as input_objects_dsl, move_calls,
+    move_calls::dsl as move_calls_dsl, network_activity, objects, objects::dsl as objects_dsl,
+    objects_history, package_calls, packages, recipients, recipients::dsl as recipients_dsl,
+    sink_watermarks, stake_events, transactions, transactions::dsl as transactions_dsl, validators,
 };
 use crate::store::indexer_store::TemporaryCheckpointStore;
 use crate::store::module_resolver::IndexerModuleResolver;
@@ -42,6 +65,23 @@ use crate::{get_pg_pool_connection, PgConnectionPool};
 const MAX_EVENT_PAGE_SIZE: usize = 1000;
 const PG_COMMIT_CHUNK_SIZE: usize = 1000;
 
+/// Prefix of the type of all coin objects, followed by the coin type and `>`.
+const COIN_TYPE_PREFIX: &str = "0x2::coin::Coin<";
+
+const GET_BALANCES_SQL: &str = r#"
+SELECT coin_type, coin_num, balance::TEXT AS balance
+FROM balances
+WHERE owner_address = $1"#;
+
+/// Writes the rendered `display` of the given object versions. The history trigger of `objects`
+/// does not fire on `display` updates, so that re-rendering does not rewrite `objects_history`.
+const RENDER_DISPLAYS_SQL: &str = r#"
+UPDATE objects
+SET display = rendered.display
+FROM UNNEST($1::VARCHAR[], $2::BIGINT[], $3::JSONB[]) AS rendered(object_id, version, display)
+WHERE objects.object_id = rendered.object_id
+  AND objects.version = rendered.version"#;
+
 const GET_PARTITION_SQL: &str = r#"
 SELECT parent.relname                           AS table_name,
        MAX(SUBSTRING(child.relname FROM '\d$')) AS last_partition
@@ -78,6 +118,84 @@ impl PgIndexerStore {
     }
 }
 
+impl PgIndexerStore {
+    /// Persists the displays published in a checkpoint and queues the re-rendering of the objects
+    /// of their types. A display published in a later checkpoint is never replaced by an earlier
+    /// one, so that re-indexing a checkpoint does not roll displays back.
+    fn persist_displays(&self, displays: &[StoredDisplay]) -> Result<(), IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        for displays_chunk in displays.chunks(PG_COMMIT_CHUNK_SIZE) {
+            pg_pool_conn
+                .build_transaction()
+                .serializable()
+                .read_write()
+                .run(|conn| {
+                    let stored_checkpoints: HashMap<String, i64> = display::table
+                        .filter(
+                            display::object_type
+                                .eq_any(displays_chunk.iter().map(|d| d.object_type.clone())),
+                        )
+                        .select((display::object_type, display::checkpoint))
+                        .load::<(String, i64)>(conn)?
+                        .into_iter()
+                        .collect();
+                    let newer_displays: Vec<StoredDisplay> = displays_chunk
+                        .iter()
+                        .filter(|d| {
+                            stored_checkpoints
+                                .get(&d.object_type)
+                                .map_or(true, |checkpoint| *checkpoint <= d.checkpoint)
+                        })
+                        .cloned()
+                        .collect();
+                    if newer_displays.is_empty() {
+                        return Ok(());
+                    }
+
+                    diesel::insert_into(display::table)
+                        .values(&newer_displays)
+                        .on_conflict(display::object_type)
+                        .do_update()
+                        .set((
+                            display::id.eq(excluded(display::id)),
+                            display::version.eq(excluded(display::version)),
+                            display::bcs.eq(excluded(display::bcs)),
+                            display::checkpoint.eq(excluded(display::checkpoint)),
+                        ))
+                        .execute(conn)?;
+                    // Restart the rendering of the objects of these types from the beginning.
+                    diesel::insert_into(display_renders::table)
+                        .values(
+                            newer_displays
+                                .iter()
+                                .map(|d| {
+                                    (
+                                        display_renders::object_type.eq(d.object_type.clone()),
+                                        display_renders::checkpoint.eq(d.checkpoint),
+                                    )
+                                })
+                                .collect::<Vec<_>>(),
+                        )
+                        .on_conflict(display_renders::object_type)
+                        .do_update()
+                        .set((
+                            display_renders::checkpoint.eq(excluded(display_renders::checkpoint)),
+                            display_renders::cursor.eq(None::<String>),
+                        ))
+                        .execute(conn)?;
+                    Ok::<_, diesel::result::Error>(())
+                })
+                .map_err(|e| {
+                    IndexerError::PostgresWriteError(format!(
+                        "Failed writing displays to PostgresDB with error: {:?}",
+                        e
+                    ))
+                })?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl IndexerStore for PgIndexerStore {
     type ModuleCache = SyncModuleCache<IndexerModuleResolver>;
@@ -125,6 +243,118 @@ impl IndexerStore for PgIndexerStore {
             })
     }
 
+    fn get_epoch_first_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> Result<Option<Checkpoint>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                checkpoints_dsl::checkpoints
+                    .filter(checkpoints::epoch.eq(epoch as i64))
+                    .order(checkpoints::sequence_number.asc())
+                    .first::<Checkpoint>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading first checkpoint of epoch {} with error {:?}",
+                    epoch, e
+                ))
+            })
+    }
+
+    fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<EpochInfo>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let (epochs, validators) = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let mut query = epochs::table.into_boxed();
+                if let Some(cursor) = cursor {
+                    query = if descending_order {
+                        query.filter(epochs::epoch.lt(cursor as i64))
+                    } else {
+                        query.filter(epochs::epoch.gt(cursor as i64))
+                    };
+                }
+                query = if descending_order {
+                    query.order(epochs::epoch.desc())
+                } else {
+                    query.order(epochs::epoch.asc())
+                };
+                let epochs = query.limit(limit as i64).load::<DBEpochInfo>(conn)?;
+                let validators = validators::table
+                    .filter(validators::epoch.eq_any(epochs.iter().map(|e| e.epoch)))
+                    .order(validators::validator_address.asc())
+                    .load::<DBValidatorEpochInfo>(conn)?;
+                Ok::<_, diesel::result::Error>((epochs, validators))
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading epochs with cursor {:?} and limit {} and error {:?}",
+                    cursor, limit, e
+                ))
+            })?;
+
+        let mut validators_by_epoch =
+            validators
+                .into_iter()
+                .fold(BTreeMap::<i64, Vec<_>>::new(), |mut acc, validator| {
+                    acc.entry(validator.epoch).or_default().push(validator);
+                    acc
+                });
+        epochs
+            .into_iter()
+            .map(|epoch| {
+                let validators = validators_by_epoch.remove(&epoch.epoch).unwrap_or_default();
+                epoch.into_epoch_info(validators)
+            })
+            .collect()
+    }
+
+    fn get_committee(&self, epoch: Option<EpochId>) -> Result<Option<SuiCommittee>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let members = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let epoch = match epoch {
+                    Some(epoch) => Some(epoch as i64),
+                    None => committees::table
+                        .select(max(committees::epoch))
+                        .first::<Option<i64>>(conn)?,
+                };
+                match epoch {
+                    Some(epoch) => committees::table
+                        .filter(committees::epoch.eq(epoch))
+                        .load::<CommitteeMember>(conn),
+                    None => Ok(vec![]),
+                }
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading committee of epoch {:?} with error {:?}",
+                    epoch, e
+                ))
+            })?;
+
+        match members.first() {
+            Some(member) => Ok(Some(committee_from_members(
+                member.epoch as EpochId,
+                members,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
     fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
         pg_pool_conn
@@ -245,6 +475,236 @@ impl IndexerStore for PgIndexerStore {
         })
     }
 
+    fn get_address_stats(&self, address: SuiAddress) -> Result<Option<AddressStats>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let stats = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                address_stats::table
+                    .filter(address_stats::address.eq(address.to_string()))
+                    .first::<DBAddressStats>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading stats of address {} with error {:?}",
+                    address, e
+                ))
+            })?;
+        stats.map(AddressStats::try_from).transpose()
+    }
+
+    fn get_address_counterparties(
+        &self,
+        address: SuiAddress,
+        limit: usize,
+    ) -> Result<Vec<AddressCounterparty>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                address_counterparties::table
+                    .filter(address_counterparties::address.eq(address.to_string()))
+                    .order((
+                        address_counterparties::transaction_count.desc(),
+                        address_counterparties::counterparty.asc(),
+                    ))
+                    .limit(limit as i64)
+                    .load::<DBAddressCounterparty>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading counterparties of address {} with error {:?}",
+                    address, e
+                ))
+            })?
+            .into_iter()
+            .map(AddressCounterparty::try_from)
+            .collect()
+    }
+
+    fn get_top_packages(
+        &self,
+        epoch: Option<EpochId>,
+        limit: usize,
+    ) -> Result<Vec<PackageCallCount>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let epoch = match epoch {
+                    Some(epoch) => Some(epoch as i64),
+                    None => package_calls::table
+                        .select(max(package_calls::epoch))
+                        .first::<Option<i64>>(conn)?,
+                };
+                let Some(epoch) = epoch else {
+                    return Ok(vec![]);
+                };
+                package_calls::table
+                    .filter(package_calls::epoch.eq(epoch))
+                    .order((
+                        package_calls::call_count.desc(),
+                        package_calls::move_package.asc(),
+                    ))
+                    .limit(limit as i64)
+                    .load::<DBPackageCalls>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading top packages of epoch {:?} with error {:?}",
+                    epoch, e
+                ))
+            })?
+            .into_iter()
+            .map(PackageCallCount::try_from)
+            .collect()
+    }
+
+    fn get_network_tps(&self, window_ms: u64) -> Result<NetworkTps, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let window_ms = i64::try_from(window_ms)
+            .unwrap_or(i64::MAX)
+            .max(NETWORK_ACTIVITY_BUCKET_MS);
+        let (window_end_ms, buckets) = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let Some(latest_bucket) = network_activity::table
+                    .select(max(network_activity::bucket_start_ms))
+                    .first::<Option<i64>>(conn)? else {
+                    return Ok((0, vec![]));
+                };
+                let window_end_ms = latest_bucket + NETWORK_ACTIVITY_BUCKET_MS;
+                let buckets = network_activity::table
+                    .filter(network_activity::bucket_start_ms.ge(window_end_ms - window_ms))
+                    .load::<DBNetworkActivity>(conn)?;
+                Ok::<_, diesel::result::Error>((window_end_ms, buckets))
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading network activity with error {:?}",
+                    e
+                ))
+            })?;
+        let transaction_count: i64 = buckets.iter().map(|b| b.transaction_count).sum();
+        let checkpoint_count: i64 = buckets.iter().map(|b| b.checkpoint_count).sum();
+        Ok(NetworkTps {
+            window_start_ms: (window_end_ms - window_ms).max(0) as u64,
+            window_end_ms: window_end_ms as u64,
+            transaction_count: transaction_count as u64,
+            checkpoint_count: checkpoint_count as u64,
+            tps: transaction_count as f64 * 1000.0 / window_ms as f64,
+        })
+    }
+
+    fn get_package_versions(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<SuiPackageVersion>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let Some(package) = packages::table
+                    .filter(packages::package_id.eq(package_id.to_string()))
+                    .first::<Package>(conn)
+                    .optional()? else {
+                    return Ok(vec![]);
+                };
+                match package.original_package_id {
+                    Some(original_package_id) => packages::table
+                        .filter(packages::original_package_id.eq(original_package_id))
+                        .order(packages::version.asc())
+                        .load::<Package>(conn),
+                    // Lineage unknown until the checkpoint of the package is backfilled
+                    None => Ok(vec![package]),
+                }
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading versions of package {} with error {:?}",
+                    package_id, e
+                ))
+            })?
+            .into_iter()
+            .map(SuiPackageVersion::try_from)
+            .collect()
+    }
+
+    fn get_package_modules(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Option<BTreeMap<String, SuiMoveNormalizedModule>>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let package = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                packages::table
+                    .filter(packages::package_id.eq(package_id.to_string()))
+                    .first::<Package>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading package {} with error {:?}",
+                    package_id, e
+                ))
+            })?;
+        package
+            .map(|package| package.normalized_modules())
+            .transpose()
+    }
+
+    fn get_function_callers(
+        &self,
+        package_id: ObjectID,
+        module: &str,
+        function: &str,
+        limit: usize,
+    ) -> Result<Vec<MoveFunctionCaller>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let callers = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                move_calls::table
+                    .filter(move_calls::move_package.eq(package_id.to_string()))
+                    .filter(move_calls::move_module.eq(module))
+                    .filter(move_calls::move_function.eq(function))
+                    .group_by(move_calls::sender)
+                    .select((
+                        move_calls::sender,
+                        count_star(),
+                        max(move_calls::checkpoint_sequence_number),
+                    ))
+                    .order((count_star().desc(), move_calls::sender.asc()))
+                    .limit(limit as i64)
+                    .load::<(String, i64, Option<i64>)>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading callers of {}::{}::{} with error {:?}",
+                    package_id, module, function, e
+                ))
+            })?;
+        callers
+            .into_iter()
+            .map(|(caller, call_count, last_call_checkpoint)| {
+                Ok(MoveFunctionCaller {
+                    caller: SuiAddress::from_str(&caller)?,
+                    call_count: call_count as u64,
+                    last_call_checkpoint: last_call_checkpoint.unwrap_or_default() as u64,
+                })
+            })
+            .collect()
+    }
+
     fn get_total_transaction_number(&self) -> Result<i64, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
         pg_pool_conn
@@ -371,6 +831,304 @@ impl IndexerStore for PgIndexerStore {
         }
     }
 
+    fn get_latest_object(&self, object_id: ObjectID) -> Result<Option<Object>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                objects_dsl::objects
+                    .filter(objects_dsl::object_id.eq(object_id.to_string()))
+                    .first::<Object>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading object with id {} and err: {:?}",
+                    object_id, e
+                ))
+            })
+    }
+
+    fn query_objects(
+        &self,
+        object_type: Option<String>,
+        display: BTreeMap<String, String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<Object>, IndexerError> {
+        let object_type = object_type
+            .map(|object_type| {
+                Ok::<_, IndexerError>(parse_sui_struct_tag(&object_type)?.to_string())
+            })
+            .transpose()?;
+        let display_filter = (!display.is_empty())
+            .then(|| serde_json::to_value(&display))
+            .transpose()
+            .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let mut query = objects_dsl::objects
+                    .filter(objects_dsl::object_status.ne_all(vec![
+                        ObjectStatus::Deleted,
+                        ObjectStatus::Wrapped,
+                        ObjectStatus::UnwrappedThenDeleted,
+                    ]))
+                    .into_boxed();
+                if let Some(object_type) = &object_type {
+                    query = query.filter(objects_dsl::object_type.eq(object_type));
+                }
+                if let Some(display_filter) = &display_filter {
+                    query = query.filter(
+                        sql::<Bool>("display @> ").bind::<Jsonb, _>(display_filter.clone()),
+                    );
+                }
+                if let Some(cursor) = cursor {
+                    query = query.filter(objects_dsl::object_id.gt(cursor.to_string()));
+                }
+                query
+                    .order(objects_dsl::object_id.asc())
+                    .limit(limit as i64)
+                    .load::<Object>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading objects of type {:?} and display {:?} with cursor {:?} and err: {:?}",
+                    object_type, display_filter, cursor, e
+                ))
+            })
+    }
+
+    fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<DynamicFieldInfo>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let (fields, children) = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let mut query = objects_dsl::objects
+                    .filter(objects_dsl::df_parent_id.eq(parent_object_id.to_string()))
+                    .filter(objects_dsl::df_kind.is_not_null())
+                    .filter(objects_dsl::object_status.ne_all(vec![
+                        ObjectStatus::Deleted,
+                        ObjectStatus::Wrapped,
+                        ObjectStatus::UnwrappedThenDeleted,
+                    ]))
+                    .into_boxed();
+                if let Some(cursor) = cursor {
+                    query = query.filter(objects_dsl::object_id.gt(cursor.to_string()));
+                }
+                let fields = query
+                    .order(objects_dsl::object_id.asc())
+                    .limit(limit as i64)
+                    .load::<Object>(conn)?;
+                let child_ids: Vec<String> = fields
+                    .iter()
+                    .filter(|field| field.df_kind.as_deref() == Some(DYNAMIC_OBJECT_KIND))
+                    .filter_map(|field| field.df_object_id.clone())
+                    .collect();
+                let children = objects_dsl::objects
+                    .filter(objects_dsl::object_id.eq_any(child_ids))
+                    .load::<Object>(conn)?;
+                Ok::<_, diesel::result::Error>((fields, children))
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading dynamic fields of {} with cursor {:?} and err: {:?}",
+                    parent_object_id, cursor, e
+                ))
+            })?;
+
+        let children: HashMap<String, Object> = children
+            .into_iter()
+            .map(|child| (child.object_id.clone(), child))
+            .collect();
+        fields
+            .into_iter()
+            .map(|field| {
+                let child = field
+                    .df_object_id
+                    .as_ref()
+                    .and_then(|child_id| children.get(child_id));
+                field.try_into_dynamic_field_info(child)
+            })
+            .collect()
+    }
+
+    fn get_dynamic_field_object(
+        &self,
+        parent_object_id: ObjectID,
+        name: &DynamicFieldName,
+    ) -> Result<Option<Object>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let field = objects_dsl::objects
+                    .filter(objects_dsl::df_parent_id.eq(parent_object_id.to_string()))
+                    .filter(objects_dsl::df_name_type.eq(name.type_.to_string()))
+                    .filter(objects_dsl::df_name.eq(&name.value))
+                    .filter(objects_dsl::object_status.ne_all(vec![
+                        ObjectStatus::Deleted,
+                        ObjectStatus::Wrapped,
+                        ObjectStatus::UnwrappedThenDeleted,
+                    ]))
+                    .first::<Object>(conn)
+                    .optional()?;
+                match field {
+                    Some(field) if field.df_kind.as_deref() == Some(DYNAMIC_OBJECT_KIND) => {
+                        objects_dsl::objects
+                            .filter(
+                                objects_dsl::object_id.eq(field.df_object_id.unwrap_or_default()),
+                            )
+                            .first::<Object>(conn)
+                            .optional()
+                    }
+                    field => Ok(field),
+                }
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading dynamic field {:?} of {} with err: {:?}",
+                    name, parent_object_id, e
+                ))
+            })
+    }
+
+    fn get_display(&self, object_type: &str) -> Result<Option<StoredDisplay>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                display::table
+                    .filter(display::object_type.eq(object_type))
+                    .first::<StoredDisplay>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading display of type {} with err: {:?}",
+                    object_type, e
+                ))
+            })
+    }
+
+    fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<CoinPage, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let mut coins = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let mut query = objects_dsl::objects
+                    .filter(objects_dsl::owner_type.eq(OwnerType::AddressOwner))
+                    .filter(objects_dsl::owner_address.eq(owner.to_string()))
+                    .filter(objects_dsl::object_status.ne_all(vec![
+                        ObjectStatus::Deleted,
+                        ObjectStatus::Wrapped,
+                        ObjectStatus::UnwrappedThenDeleted,
+                    ]))
+                    .into_boxed();
+                query = match &coin_type {
+                    Some(coin_type) => query
+                        .filter(objects_dsl::object_type.eq(format!("{COIN_TYPE_PREFIX}{coin_type}>"))),
+                    None => query.filter(objects_dsl::object_type.like(format!("{COIN_TYPE_PREFIX}%"))),
+                };
+                if let Some(cursor) = cursor {
+                    query = query.filter(objects_dsl::object_id.gt(cursor.to_string()));
+                }
+                query
+                    .order(objects_dsl::object_id.asc())
+                    .limit(limit as i64 + 1)
+                    .load::<Object>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading coins of owner {} and coin type {:?} with cursor {:?} and err: {:?}",
+                    owner, coin_type, cursor, e
+                ))
+            })?;
+
+        let has_next_page = coins.len() > limit;
+        coins.truncate(limit);
+        let data = coins
+            .into_iter()
+            .map(Coin::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let next_cursor = data.last().map(|coin| coin.coin_object_id).or(cursor);
+        Ok(CoinPage {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_balance(&self, owner: SuiAddress, coin_type: String) -> Result<Balance, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let balance: Option<AddressBalance> = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                diesel::sql_query(format!("{GET_BALANCES_SQL} AND coin_type = $2"))
+                    .bind::<VarChar, _>(owner.to_string())
+                    .bind::<VarChar, _>(&coin_type)
+                    .get_result(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading balance of owner {} and coin type {} with err: {:?}",
+                    owner, coin_type, e
+                ))
+            })?;
+
+        match balance {
+            Some(balance) => balance.try_into(),
+            None => Ok(Balance {
+                coin_type,
+                coin_object_count: 0,
+                total_balance: 0,
+                locked_balance: Default::default(),
+            }),
+        }
+    }
+
+    fn get_all_balances(&self, owner: SuiAddress) -> Result<Vec<Balance>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                diesel::sql_query(GET_BALANCES_SQL)
+                    .bind::<VarChar, _>(owner.to_string())
+                    .load::<AddressBalance>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading balances of owner {} with err: {:?}",
+                    owner, e
+                ))
+            })?
+            .into_iter()
+            .map(Balance::try_from)
+            .collect()
+    }
+
     fn get_move_call_sequence_by_digest(
         &self,
         txn_digest: Option<String>,
@@ -776,6 +1534,8 @@ impl IndexerStore for PgIndexerStore {
             input_objects,
             move_calls,
             recipients,
+            stake_events,
+            displays,
         } = data;
 
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
@@ -827,23 +1587,7 @@ impl IndexerStore for PgIndexerStore {
                     .build_transaction()
                     .serializable()
                     .read_write()
-                    .run(|conn| {
-                        diesel::insert_into(objects::table)
-                            .values(mutated_object_change_chunk)
-                            .on_conflict(objects::object_id)
-                            .do_update()
-                            .set((
-                                objects::epoch.eq(excluded(objects::epoch)),
-                                objects::checkpoint.eq(excluded(objects::checkpoint)),
-                                objects::version.eq(excluded(objects::version)),
-                                objects::object_digest.eq(excluded(objects::object_digest)),
-                                objects::owner_address.eq(excluded(objects::owner_address)),
-                                objects::previous_transaction
-                                    .eq(excluded(objects::previous_transaction)),
-                                objects::object_status.eq(excluded(objects::object_status)),
-                            ))
-                            .execute(conn)
-                    })
+                    .run(|conn| upsert_objects(conn, mutated_object_change_chunk))
                     .map_err(|e| {
                         IndexerError::PostgresWriteError(format!(
                             "Failed writing updated objects to PostgresDB with chunk: {:?} error: {:?}",
@@ -862,21 +1606,7 @@ impl IndexerStore for PgIndexerStore {
                     .build_transaction()
                     .serializable()
                     .read_write()
-                    .run(|conn| {
-                        diesel::insert_into(objects::table)
-                            .values(deleted_object_change_chunk)
-                            .on_conflict(objects::object_id)
-                            .do_update()
-                            .set((
-                                objects::epoch.eq(excluded(objects::epoch)),
-                                objects::checkpoint.eq(excluded(objects::checkpoint)),
-                                objects::version.eq(excluded(objects::version)),
-                                objects::previous_transaction
-                                    .eq(excluded(objects::previous_transaction)),
-                                objects::object_status.eq(excluded(objects::object_status)),
-                            ))
-                            .execute(conn)
-                    })
+                    .run(|conn| upsert_deleted_objects(conn, deleted_object_change_chunk))
                     .map_err(|e| {
                         IndexerError::PostgresWriteError(format!(
                             "Failed writing deleted objects to PostgresDB with chunk: {:?} error: {:?}",
@@ -927,6 +1657,9 @@ impl IndexerStore for PgIndexerStore {
                 })?;
         }
 
+        // Commit updated displays, and re-render the objects of their types
+        self.persist_displays(displays)?;
+
         // Commit indexed move calls
         for move_calls_chunk in move_calls.chunks(PG_COMMIT_CHUNK_SIZE) {
             pg_pool_conn
@@ -987,17 +1720,48 @@ impl IndexerStore for PgIndexerStore {
                 })?;
         }
 
+        // Commit indexed stake events
+        for stake_events_chunk in stake_events.chunks(PG_COMMIT_CHUNK_SIZE) {
+            pg_pool_conn
+                .build_transaction()
+                .serializable()
+                .read_write()
+                .run(|conn| {
+                    diesel::insert_into(stake_events::table)
+                        .values(stake_events_chunk)
+                        .on_conflict((
+                            stake_events::transaction_digest,
+                            stake_events::event_sequence,
+                        ))
+                        .do_nothing()
+                        .execute(conn)
+                })
+                .map_err(|e| {
+                    IndexerError::PostgresWriteError(format!(
+                        "Failed writing stake_events to PostgresDB with error: {:?}",
+                        e
+                    ))
+                })?;
+        }
+
         // Commit indexed checkpoint last, so that if the checkpoint is committed,
         // all related data have been committed as well.
+        // The activity aggregates are incremented in the same transaction, and only if the
+        // checkpoint was not committed before, so that a checkpoint is never counted twice.
+        let activity = CheckpointActivity::new(checkpoint, transactions, recipients, move_calls);
         pg_pool_conn
             .build_transaction()
             .serializable()
             .read_write()
             .run(|conn| {
-                diesel::insert_into(checkpoints::table)
+                let inserted = diesel::insert_into(checkpoints::table)
                     .values(checkpoint)
                     .on_conflict_do_nothing()
-                    .execute(conn)
+                    .execute(conn)?;
+                if inserted > 0 {
+                    upsert_activity(conn, &activity)?;
+                }
+                Ok::<_, diesel::result::Error>(inserted)
             })
             .map_err(|e| {
                 IndexerError::PostgresWriteError(format!(
@@ -1008,10 +1772,463 @@ impl IndexerStore for PgIndexerStore {
     }
 
     fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                diesel::insert_into(epochs::table)
+                    .values(&data.epoch)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                diesel::insert_into(validators::table)
+                    .values(&data.validators)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                diesel::insert_into(committees::table)
+                    .values(&data.next_epoch_committee)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed writing epoch {} to PostgresDB with error: {:?}",
+                    data.epoch_id, e
+                ))
+            })?;
+
         // TODO: create new partition on epoch change
         self.partition_manager.advance_epoch(data.epoch_id + 1)
     }
 
+    fn backfill_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+        let TemporaryCheckpointStore {
+            checkpoint,
+            transactions,
+            events,
+            objects_changes,
+            addresses,
+            packages,
+            input_objects,
+            move_calls,
+            recipients,
+            stake_events,
+            displays,
+        } = data;
+        let sequence_number = checkpoint.sequence_number;
+
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        // Replace all rows of the checkpoint in a single transaction, so that readers and the live
+        // tail never observe the checkpoint partially written.
+        let committed = pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                // The activity aggregates are corrected by the difference between the persisted
+                // and the backfilled checkpoint.
+                subtract_persisted_activity(conn, sequence_number)?;
+                let transaction_digests: Vec<&String> = transactions
+                    .iter()
+                    .map(|transaction| &transaction.transaction_digest)
+                    .collect();
+                diesel::delete(
+                    events::table.filter(events::transaction_digest.eq_any(&transaction_digests)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    transactions::table
+                        .filter(transactions::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    move_calls::table
+                        .filter(move_calls::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    input_objects::table
+                        .filter(input_objects::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    recipients::table
+                        .filter(recipients::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    stake_events::table.filter(stake_events::checkpoint.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                for package in packages {
+                    diesel::delete(
+                        packages::table
+                            .filter(packages::package_id.eq(&package.package_id))
+                            .filter(packages::version.eq(package.version)),
+                    )
+                    .execute(conn)?;
+                }
+                diesel::delete(
+                    checkpoints::table.filter(checkpoints::sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+
+                // The history of the checkpoint is replaced as a whole, objects changed again after
+                // this checkpoint keep their latest state.
+                diesel::delete(
+                    objects_history::table
+                        .filter(objects_history::epoch.eq(checkpoint.epoch))
+                        .filter(objects_history::checkpoint.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                let changed_object_ids: Vec<&String> = objects_changes
+                    .iter()
+                    .flat_map(|changes| {
+                        changes
+                            .mutated_objects
+                            .iter()
+                            .map(|object| &object.object_id)
+                            .chain(
+                                changes
+                                    .deleted_objects
+                                    .iter()
+                                    .map(|object| &object.object_id),
+                            )
+                    })
+                    .collect();
+                let persisted_versions: HashMap<String, i64> = objects::table
+                    .select((objects::object_id, objects::version))
+                    .filter(objects::object_id.eq_any(&changed_object_ids))
+                    .load::<(String, i64)>(conn)?
+                    .into_iter()
+                    .collect();
+                let is_latest = |object_id: &String, version: i64| {
+                    persisted_versions
+                        .get(object_id)
+                        .map_or(true, |persisted| *persisted <= version)
+                };
+                for changes in objects_changes {
+                    let mutated_objects: Vec<Object> = changes
+                        .mutated_objects
+                        .iter()
+                        .filter(|object| is_latest(&object.object_id, object.version))
+                        .cloned()
+                        .collect();
+                    let deleted_objects: Vec<Object> = changes
+                        .deleted_objects
+                        .iter()
+                        .filter(|object| is_latest(&object.object_id, object.version))
+                        .map(|object| object.clone().into())
+                        .collect();
+                    for chunk in mutated_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
+                        upsert_objects(conn, chunk)?;
+                    }
+                    for chunk in deleted_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
+                        upsert_deleted_objects(conn, chunk)?;
+                    }
+                }
+                // The trigger already wrote the history of the objects upserted above.
+                let history: Vec<ObjectHistory> = objects_changes
+                    .iter()
+                    .flat_map(|changes| {
+                        changes
+                            .mutated_objects
+                            .iter()
+                            .cloned()
+                            .chain(changes.deleted_objects.iter().map(|o| o.clone().into()))
+                    })
+                    .map(ObjectHistory::from)
+                    .collect();
+                for chunk in history.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(objects_history::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+
+                for chunk in transactions.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(transactions::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                for chunk in events.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(events::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                // An address keeps its earliest appearance, which is in this checkpoint if it was
+                // first indexed from a later one.
+                let mut first_appearances: BTreeMap<&String, &Address> = BTreeMap::new();
+                for address in addresses {
+                    first_appearances
+                        .entry(&address.account_address)
+                        .or_insert(address);
+                }
+                let first_appearances: Vec<&Address> = first_appearances.into_values().collect();
+                for chunk in first_appearances.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    let stored_appearances: HashMap<String, i64> = addresses::table
+                        .select((addresses::account_address, addresses::first_appearance_time))
+                        .filter(
+                            addresses::account_address
+                                .eq_any(chunk.iter().map(|address| &address.account_address)),
+                        )
+                        .load::<(String, i64)>(conn)?
+                        .into_iter()
+                        .collect();
+                    let earlier_appearances: Vec<_> = chunk
+                        .iter()
+                        .filter(|address| {
+                            stored_appearances
+                                .get(&address.account_address)
+                                .map_or(true, |time| *time > address.first_appearance_time)
+                        })
+                        .map(|address| {
+                            (
+                                addresses::account_address.eq(&address.account_address),
+                                addresses::first_appearance_tx.eq(&address.first_appearance_tx),
+                                addresses::first_appearance_time.eq(address.first_appearance_time),
+                            )
+                        })
+                        .collect();
+                    diesel::insert_into(addresses::table)
+                        .values(earlier_appearances)
+                        .on_conflict(addresses::account_address)
+                        .do_update()
+                        .set((
+                            addresses::first_appearance_tx
+                                .eq(excluded(addresses::first_appearance_tx)),
+                            addresses::first_appearance_time
+                                .eq(excluded(addresses::first_appearance_time)),
+                        ))
+                        .execute(conn)?;
+                }
+                for chunk in packages.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(packages::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                for chunk in move_calls.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(move_calls::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                for chunk in input_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(input_objects::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                for chunk in recipients.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(recipients::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                for chunk in stake_events.chunks(PG_COMMIT_CHUNK_SIZE) {
+                    diesel::insert_into(stake_events::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                upsert_activity(
+                    conn,
+                    &CheckpointActivity::new(checkpoint, transactions, recipients, move_calls),
+                )?;
+                diesel::insert_into(checkpoints::table)
+                    .values(checkpoint)
+                    .execute(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed backfilling checkpoint {} to PostgresDB with error: {:?}",
+                    sequence_number, e
+                ))
+            })?;
+
+        self.persist_displays(displays)?;
+        Ok(committed)
+    }
+
+    fn backfill_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError> {
+        let epoch = data.epoch_id as i64;
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                diesel::delete(epochs::table.filter(epochs::epoch.eq(epoch))).execute(conn)?;
+                diesel::delete(validators::table.filter(validators::epoch.eq(epoch)))
+                    .execute(conn)?;
+                diesel::delete(committees::table.filter(committees::epoch.eq(epoch + 1)))
+                    .execute(conn)?;
+                diesel::insert_into(epochs::table)
+                    .values(&data.epoch)
+                    .execute(conn)?;
+                diesel::insert_into(validators::table)
+                    .values(&data.validators)
+                    .execute(conn)?;
+                diesel::insert_into(committees::table)
+                    .values(&data.next_epoch_committee)
+                    .execute(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed backfilling epoch {} to PostgresDB with error: {:?}",
+                    data.epoch_id, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    fn refresh_balances(&self) -> Result<(), IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        // Refreshing concurrently keeps the view readable while it is recomputed.
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY balances")
+            .execute(&mut pg_pool_conn)
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed refreshing balances with error: {:?}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+
+    fn render_pending_displays(&self, limit: usize) -> Result<usize, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let pending = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                display_renders::table
+                    .order(display_renders::object_type)
+                    .first::<(String, i64, Option<String>)>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading pending display renders with error: {:?}",
+                    e
+                ))
+            })?;
+        let Some((object_type, checkpoint, cursor)) = pending else {
+            return Ok(0);
+        };
+        let stored_display = match self.get_display(&object_type)? {
+            Some(stored_display) if stored_display.checkpoint == checkpoint => stored_display,
+            // The display was published again since, its render is restarted.
+            _ => return Ok(0),
+        };
+
+        let cursor = cursor.map(|c| ObjectID::from_str(&c)).transpose()?;
+        let objects =
+            self.query_objects(Some(object_type.clone()), BTreeMap::new(), cursor, limit)?;
+        let mut object_ids = Vec::with_capacity(objects.len());
+        let mut versions = Vec::with_capacity(objects.len());
+        let mut displays = Vec::with_capacity(objects.len());
+        for object in &objects {
+            let display = stored_display
+                .render(object, self.module_cache.as_ref())
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to render display of object {}: {:?}",
+                        object.object_id, e
+                    );
+                    None
+                });
+            object_ids.push(object.object_id.clone());
+            versions.push(object.version);
+            displays.push(display);
+        }
+
+        pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                let pending_render = display_renders::table
+                    .filter(display_renders::object_type.eq(&object_type))
+                    .filter(display_renders::checkpoint.eq(checkpoint));
+                let updated = match objects.last() {
+                    Some(last) if objects.len() == limit => diesel::update(pending_render)
+                        .set(display_renders::cursor.eq(&last.object_id))
+                        .execute(conn)?,
+                    _ => diesel::delete(pending_render).execute(conn)?,
+                };
+                // A display published meanwhile restarted the render, drop this batch.
+                if updated == 0 {
+                    return Ok(0);
+                }
+                diesel::sql_query(RENDER_DISPLAYS_SQL)
+                    .bind::<Array<Text>, _>(&object_ids)
+                    .bind::<Array<BigInt>, _>(&versions)
+                    .bind::<Array<Nullable<Jsonb>>, _>(&displays)
+                    .execute(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed writing display of type {} to PostgresDB with error: {:?}",
+                    object_type, e
+                ))
+            })
+    }
+
+    fn get_sink_watermark(
+        &self,
+        sink_name: &str,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                sink_watermarks::table
+                    .select(sink_watermarks::checkpoint_sequence_number)
+                    .filter(sink_watermarks::sink_name.eq(sink_name))
+                    .first::<i64>(conn)
+                    .optional()
+            })
+            .map(|watermark| watermark.map(|seq| seq as CheckpointSequenceNumber))
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading watermark of sink {} with error {:?}",
+                    sink_name, e
+                ))
+            })
+    }
+
+    fn persist_sink_watermark(
+        &self,
+        sink_name: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                diesel::insert_into(sink_watermarks::table)
+                    .values((
+                        sink_watermarks::sink_name.eq(sink_name),
+                        sink_watermarks::checkpoint_sequence_number.eq(checkpoint as i64),
+                    ))
+                    .on_conflict(sink_watermarks::sink_name)
+                    .do_update()
+                    .set(
+                        sink_watermarks::checkpoint_sequence_number
+                            .eq(excluded(sink_watermarks::checkpoint_sequence_number)),
+                    )
+                    .execute(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed writing watermark {} of sink {} with error {:?}",
+                    checkpoint, sink_name, e
+                ))
+            })?;
+        Ok(())
+    }
+
     fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError> {
         if !errors.is_empty() {
             let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
@@ -1028,6 +2245,210 @@ impl IndexerStore for PgIndexerStore {
     }
 }
 
+fn upsert_objects(conn: &mut PgConnection, objects: &[Object]) -> QueryResult<usize> {
+    diesel::insert_into(objects::table)
+        .values(objects)
+        .on_conflict(objects::object_id)
+        .do_update()
+        .set((
+            objects::epoch.eq(excluded(objects::epoch)),
+            objects::checkpoint.eq(excluded(objects::checkpoint)),
+            objects::version.eq(excluded(objects::version)),
+            objects::object_digest.eq(excluded(objects::object_digest)),
+            objects::owner_type.eq(excluded(objects::owner_type)),
+            objects::owner_address.eq(excluded(objects::owner_address)),
+            objects::initial_shared_version.eq(excluded(objects::initial_shared_version)),
+            objects::previous_transaction.eq(excluded(objects::previous_transaction)),
+            objects::object_status.eq(excluded(objects::object_status)),
+            objects::has_public_transfer.eq(excluded(objects::has_public_transfer)),
+            objects::storage_rebate.eq(excluded(objects::storage_rebate)),
+            objects::bcs.eq(excluded(objects::bcs)),
+            objects::df_kind.eq(excluded(objects::df_kind)),
+            objects::df_parent_id.eq(excluded(objects::df_parent_id)),
+            objects::df_name_type.eq(excluded(objects::df_name_type)),
+            objects::df_name.eq(excluded(objects::df_name)),
+            objects::df_bcs_name.eq(excluded(objects::df_bcs_name)),
+            objects::df_object_type.eq(excluded(objects::df_object_type)),
+            objects::df_object_id.eq(excluded(objects::df_object_id)),
+            objects::display.eq(excluded(objects::display)),
+        ))
+        .execute(conn)
+}
+
+fn upsert_deleted_objects(conn: &mut PgConnection, objects: &[Object]) -> QueryResult<usize> {
+    diesel::insert_into(objects::table)
+        .values(objects)
+        .on_conflict(objects::object_id)
+        .do_update()
+        .set((
+            objects::epoch.eq(excluded(objects::epoch)),
+            objects::checkpoint.eq(excluded(objects::checkpoint)),
+            objects::version.eq(excluded(objects::version)),
+            objects::previous_transaction.eq(excluded(objects::previous_transaction)),
+            objects::object_status.eq(excluded(objects::object_status)),
+        ))
+        .execute(conn)
+}
+
+/// Adds `activity` to the activity aggregates. The first and last activity of addresses do not
+/// depend on the order in which checkpoints are added, so that backfilled checkpoints are added
+/// like the live ones.
+fn upsert_activity(conn: &mut PgConnection, activity: &CheckpointActivity) -> QueryResult<()> {
+    for address_stats_chunk in activity.address_stats.chunks(PG_COMMIT_CHUNK_SIZE) {
+        diesel::insert_into(address_stats::table)
+            .values(address_stats_chunk)
+            .on_conflict(address_stats::address)
+            .do_update()
+            .set((
+                address_stats::transaction_count
+                    .eq(address_stats::transaction_count
+                        + excluded(address_stats::transaction_count)),
+                address_stats::sent_count
+                    .eq(address_stats::sent_count + excluded(address_stats::sent_count)),
+                address_stats::received_count
+                    .eq(address_stats::received_count + excluded(address_stats::received_count)),
+                address_stats::first_activity_checkpoint.eq(sql::<BigInt>(
+                    "LEAST(address_stats.first_activity_checkpoint, \
+                     EXCLUDED.first_activity_checkpoint)",
+                )),
+                address_stats::first_activity_timestamp_ms.eq(sql::<BigInt>(
+                    "LEAST(address_stats.first_activity_timestamp_ms, \
+                     EXCLUDED.first_activity_timestamp_ms)",
+                )),
+                address_stats::last_activity_checkpoint.eq(sql::<BigInt>(
+                    "GREATEST(address_stats.last_activity_checkpoint, \
+                     EXCLUDED.last_activity_checkpoint)",
+                )),
+                address_stats::last_activity_timestamp_ms.eq(sql::<BigInt>(
+                    "GREATEST(address_stats.last_activity_timestamp_ms, \
+                     EXCLUDED.last_activity_timestamp_ms)",
+                )),
+            ))
+            .execute(conn)?;
+    }
+    for counterparties_chunk in activity.counterparties.chunks(PG_COMMIT_CHUNK_SIZE) {
+        diesel::insert_into(address_counterparties::table)
+            .values(counterparties_chunk)
+            .on_conflict((
+                address_counterparties::address,
+                address_counterparties::counterparty,
+            ))
+            .do_update()
+            .set((
+                address_counterparties::transaction_count
+                    .eq(address_counterparties::transaction_count
+                        + excluded(address_counterparties::transaction_count)),
+                address_counterparties::last_activity_timestamp_ms.eq(sql::<BigInt>(
+                    "GREATEST(address_counterparties.last_activity_timestamp_ms, \
+                     EXCLUDED.last_activity_timestamp_ms)",
+                )),
+            ))
+            .execute(conn)?;
+    }
+    for package_calls_chunk in activity.package_calls.chunks(PG_COMMIT_CHUNK_SIZE) {
+        diesel::insert_into(package_calls::table)
+            .values(package_calls_chunk)
+            .on_conflict((package_calls::epoch, package_calls::move_package))
+            .do_update()
+            .set(
+                package_calls::call_count
+                    .eq(package_calls::call_count + excluded(package_calls::call_count)),
+            )
+            .execute(conn)?;
+    }
+    diesel::insert_into(network_activity::table)
+        .values(&activity.network_activity)
+        .on_conflict(network_activity::bucket_start_ms)
+        .do_update()
+        .set((
+            network_activity::transaction_count
+                .eq(network_activity::transaction_count
+                    + excluded(network_activity::transaction_count)),
+            network_activity::checkpoint_count
+                .eq(network_activity::checkpoint_count
+                    + excluded(network_activity::checkpoint_count)),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Removes the contribution of the persisted checkpoint `sequence_number` from the activity
+/// aggregates, and returns whether it was persisted. Aggregates left without any activity are
+/// deleted, the first and last activity of the remaining addresses are left as is.
+fn subtract_persisted_activity(conn: &mut PgConnection, sequence_number: i64) -> QueryResult<bool> {
+    let Some(checkpoint) = checkpoints::table
+        .filter(checkpoints::sequence_number.eq(sequence_number))
+        .first::<Checkpoint>(conn)
+        .optional()? else {
+        return Ok(false);
+    };
+    let transactions = transactions::table
+        .filter(transactions::checkpoint_sequence_number.eq(sequence_number))
+        .load::<Transaction>(conn)?;
+    let recipients = recipients::table
+        .filter(recipients::checkpoint_sequence_number.eq(sequence_number))
+        .load::<Recipient>(conn)?;
+    let move_calls = move_calls::table
+        .filter(move_calls::checkpoint_sequence_number.eq(sequence_number))
+        .load::<MoveCall>(conn)?;
+    let activity = CheckpointActivity::new(&checkpoint, &transactions, &recipients, &move_calls);
+
+    for stats in &activity.address_stats {
+        let address_stats = address_stats::table.filter(address_stats::address.eq(&stats.address));
+        diesel::update(address_stats)
+            .set((
+                address_stats::transaction_count
+                    .eq(address_stats::transaction_count - stats.transaction_count),
+                address_stats::sent_count.eq(address_stats::sent_count - stats.sent_count),
+                address_stats::received_count
+                    .eq(address_stats::received_count - stats.received_count),
+            ))
+            .execute(conn)?;
+        diesel::delete(address_stats.filter(address_stats::transaction_count.le(0)))
+            .execute(conn)?;
+    }
+    for counterparty in &activity.counterparties {
+        let address_counterparty = address_counterparties::table
+            .filter(address_counterparties::address.eq(&counterparty.address))
+            .filter(address_counterparties::counterparty.eq(&counterparty.counterparty));
+        diesel::update(address_counterparty)
+            .set(
+                address_counterparties::transaction_count
+                    .eq(address_counterparties::transaction_count - counterparty.transaction_count),
+            )
+            .execute(conn)?;
+        diesel::delete(
+            address_counterparty.filter(address_counterparties::transaction_count.le(0)),
+        )
+        .execute(conn)?;
+    }
+    for calls in &activity.package_calls {
+        let package_calls = package_calls::table
+            .filter(package_calls::epoch.eq(calls.epoch))
+            .filter(package_calls::move_package.eq(&calls.move_package));
+        diesel::update(package_calls)
+            .set(package_calls::call_count.eq(package_calls::call_count - calls.call_count))
+            .execute(conn)?;
+        diesel::delete(package_calls.filter(package_calls::call_count.le(0))).execute(conn)?;
+    }
+    let network_activity = network_activity::table
+        .filter(network_activity::bucket_start_ms.eq(activity.network_activity.bucket_start_ms));
+    diesel::update(network_activity)
+        .set(
+            (
+                network_activity::transaction_count.eq(network_activity::transaction_count
+                    - activity.network_activity.transaction_count),
+                network_activity::checkpoint_count
+                    .eq(network_activity::checkpoint_count
+                        - activity.network_activity.checkpoint_count),
+            ),
+        )
+        .execute(conn)?;
+    diesel::delete(network_activity.filter(network_activity::checkpoint_count.le(0)))
+        .execute(conn)?;
+    Ok(true)
+}
+
 #[derive(Clone)]
 struct PartitionManager {
     cp: PgConnectionPool,
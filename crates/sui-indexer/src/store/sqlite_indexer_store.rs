@@ -0,0 +1,1697 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::connection::SimpleConnection;
+use diesel::dsl::{count, count_star, max, sql};
+use diesel::sql_types::{Bool, Text};
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, QueryResult, QueryableByName};
+use diesel::{RunQueryDsl, TextExpressionMethods};
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use tracing::{error, warn};
+
+use sui_json_rpc_types::{
+    AddressCounterparty, AddressStats, Balance, CheckpointId, Coin, CoinPage, EpochInfo,
+    EventFilter, EventPage, MoveFunctionCaller, NetworkTps, PackageCallCount, SuiCommittee,
+    SuiEvent, SuiMoveNormalizedModule, SuiPackageVersion,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_types::committee::EpochId;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
+use sui_types::event::EventID;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::ObjectRead;
+use sui_types::parse_sui_struct_tag;
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
+use crate::models::error_logs::ErrorLog;
+use crate::models::events::Event;
+use crate::models::objects::{Object, ObjectStatus, DYNAMIC_OBJECT_KIND};
+use crate::models::owners::OwnerType;
+use crate::models::packages::Package;
+use crate::models::transactions::Transaction;
+use crate::store::indexer_store::TemporaryCheckpointStore;
+use crate::store::module_resolver::SqliteModuleResolver;
+use crate::store::sqlite_models::{
+    object_status_name, owner_type_name, to_json, CheckpointRow, EventRow, ObjectRow, PackageRow,
+    TransactionRow,
+};
+use crate::store::sqlite_schema::{
+    addresses, checkpoints, display, display_renders, error_logs, events, input_objects,
+    move_calls, objects, objects_history, packages, recipients, sink_watermarks, transactions,
+};
+use crate::store::{IndexerStore, TemporaryEpochStore};
+use crate::{get_sqlite_pool_connection, SqliteConnectionPool};
+
+const SQLITE_SCHEMA: &str = include_str!("sqlite_schema.sql");
+
+const MAX_EVENT_PAGE_SIZE: usize = 1000;
+
+/// Prefix of the type of all coin objects, followed by the coin type and `>`.
+const COIN_TYPE_PREFIX: &str = "0x2::coin::Coin<";
+
+#[derive(QueryableByName, Debug, Clone)]
+struct DigestRow {
+    #[diesel(sql_type = Text)]
+    digest_name: String,
+}
+
+/// An `IndexerStore` backed by an embedded SQLite database, for local development and tests.
+///
+/// It keeps the data served by the read, coin, event and package APIs. Epochs, committees,
+/// validators, stake events and activity aggregates are not kept, and the queries of these fail
+/// with `IndexerError::NotImplementedError`, except committees, which are left to the fullnode.
+#[derive(Clone)]
+pub struct SqliteIndexerStore {
+    cp: SqliteConnectionPool,
+    module_cache: Arc<SyncModuleCache<SqliteModuleResolver>>,
+}
+
+impl SqliteIndexerStore {
+    /// Creates the tables of the store if the database does not have them yet.
+    pub fn new(cp: SqliteConnectionPool) -> Result<Self, IndexerError> {
+        get_sqlite_pool_connection(&cp)?
+            .batch_execute(SQLITE_SCHEMA)
+            .map_err(|e| {
+                IndexerError::SqliteWriteError(format!(
+                    "Failed creating SQLite schema with error: {:?}",
+                    e
+                ))
+            })?;
+        let module_cache = Arc::new(SyncModuleCache::new(SqliteModuleResolver::new(cp.clone())));
+        Ok(Self { cp, module_cache })
+    }
+
+    fn read<T>(
+        &self,
+        what: impl std::fmt::Display,
+        query: impl FnOnce(&mut SqliteConnection) -> QueryResult<T>,
+    ) -> Result<T, IndexerError> {
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        sqlite_pool_conn.transaction(query).map_err(|e| {
+            IndexerError::SqliteReadError(format!("Failed reading {} with error {:?}", what, e))
+        })
+    }
+
+    /// Runs `query` in a transaction that takes the write lock upfront, SQLite fails transactions
+    /// that would need to upgrade a read lock while another connection writes.
+    fn write<T>(
+        &self,
+        what: impl std::fmt::Display,
+        query: impl FnOnce(&mut SqliteConnection) -> QueryResult<T>,
+    ) -> Result<T, IndexerError> {
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        sqlite_pool_conn.immediate_transaction(query).map_err(|e| {
+            IndexerError::SqliteWriteError(format!("Failed writing {} with error {:?}", what, e))
+        })
+    }
+
+    /// Persists the displays published in a checkpoint, unless a display of the same type was
+    /// published in a later checkpoint, and queues the re-rendering of the objects of their types.
+    fn persist_displays(&self, displays: &[StoredDisplay]) -> Result<(), IndexerError> {
+        for stored_display in displays {
+            self.write(
+                format_args!("display of type {}", stored_display.object_type),
+                |conn| {
+                    let stored_checkpoint = display::table
+                        .filter(display::object_type.eq(&stored_display.object_type))
+                        .select(display::checkpoint)
+                        .first::<i64>(conn)
+                        .optional()?;
+                    if stored_checkpoint.map_or(false, |c| c > stored_display.checkpoint) {
+                        return Ok(());
+                    }
+                    diesel::replace_into(display::table)
+                        .values((
+                            display::object_type.eq(&stored_display.object_type),
+                            display::id.eq(&stored_display.id),
+                            display::version.eq(stored_display.version),
+                            display::bcs.eq(&stored_display.bcs),
+                            display::checkpoint.eq(stored_display.checkpoint),
+                        ))
+                        .execute(conn)?;
+                    diesel::replace_into(display_renders::table)
+                        .values((
+                            display_renders::object_type.eq(&stored_display.object_type),
+                            display_renders::checkpoint.eq(stored_display.checkpoint),
+                            display_renders::cursor.eq(None::<String>),
+                        ))
+                        .execute(conn)?;
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Coin object count and total balance by coin type of the coins owned by `owner`.
+    fn get_balances(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> Result<BTreeMap<String, (usize, u128)>, IndexerError> {
+        let coins = self.read(format_args!("coins of owner {}", owner), |conn| {
+            coins_query(owner, coin_type.as_deref()).load::<ObjectRow>(conn)
+        })?;
+        let mut balances = BTreeMap::new();
+        for coin in coins {
+            let coin = Coin::try_from(Object::try_from(coin)?)?;
+            let (count, total) = balances.entry(coin.coin_type).or_insert((0, 0));
+            *count += 1;
+            *total += coin.balance as u128;
+        }
+        Ok(balances)
+    }
+
+    /// Transaction digests of the rows of an index table matching `conditions`, in the order of
+    /// the latest matching row of each transaction. `conditions` are SQL conditions on the table
+    /// with a `?` placeholder for each of the `binds`.
+    fn get_transaction_digest_page_by_index(
+        &self,
+        table: &str,
+        conditions: Vec<String>,
+        binds: Vec<String>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conditions = conditions;
+        if let Some(start_sequence) = start_sequence {
+            conditions.push(if is_descending {
+                format!("id < {start_sequence}")
+            } else {
+                format!("id > {start_sequence}")
+            });
+        }
+        let sql_query = format!(
+            "SELECT transaction_digest AS digest_name FROM {table}
+            WHERE {}
+            GROUP BY transaction_digest
+            ORDER BY MAX(id) {} LIMIT {limit}",
+            conditions.join(" AND "),
+            if is_descending { "DESC" } else { "ASC" },
+        );
+        self.read(
+            format_args!("transaction digests of {} with {:?}", table, binds),
+            |conn| {
+                let mut query = diesel::sql_query(&sql_query).into_boxed::<Sqlite>();
+                for bind in &binds {
+                    query = query.bind::<Text, _>(bind.clone());
+                }
+                query.load::<DigestRow>(conn)
+            },
+        )
+        .map(|rows| rows.into_iter().map(|row| row.digest_name).collect())
+    }
+}
+
+#[async_trait]
+impl IndexerStore for SqliteIndexerStore {
+    type ModuleCache = SyncModuleCache<SqliteModuleResolver>;
+
+    fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
+        self.read("latest checkpoint sequence number", |conn| {
+            checkpoints::table
+                .select(max(checkpoints::sequence_number))
+                .first::<Option<i64>>(conn)
+                // -1 to differentiate between no checkpoints and the first checkpoint
+                .map(|o| o.unwrap_or(-1))
+        })
+    }
+
+    fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint, IndexerError> {
+        self.read(format_args!("checkpoint {:?}", id), |conn| match id {
+            CheckpointId::SequenceNumber(seq) => checkpoints::table
+                .filter(checkpoints::sequence_number.eq(seq as i64))
+                .first::<CheckpointRow>(conn),
+            CheckpointId::Digest(digest) => checkpoints::table
+                .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
+                .first::<CheckpointRow>(conn),
+        })?
+        .try_into()
+    }
+
+    fn get_epoch_first_checkpoint(
+        &self,
+        epoch: EpochId,
+    ) -> Result<Option<Checkpoint>, IndexerError> {
+        self.read(
+            format_args!("first checkpoint of epoch {}", epoch),
+            |conn| {
+                checkpoints::table
+                    .filter(checkpoints::epoch.eq(epoch as i64))
+                    .order(checkpoints::sequence_number.asc())
+                    .first::<CheckpointRow>(conn)
+                    .optional()
+            },
+        )?
+        .map(Checkpoint::try_from)
+        .transpose()
+    }
+
+    fn get_epochs(
+        &self,
+        _cursor: Option<EpochId>,
+        _limit: usize,
+        _descending_order: bool,
+    ) -> Result<Vec<EpochInfo>, IndexerError> {
+        Err(not_stored("Epochs"))
+    }
+
+    fn get_committee(&self, _epoch: Option<EpochId>) -> Result<Option<SuiCommittee>, IndexerError> {
+        // Committees are not stored, the governance API reads them from the fullnode instead.
+        Ok(None)
+    }
+
+    fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
+        self.read(format_args!("event {:?}", id), |conn| {
+            events::table
+                .filter(events::transaction_digest.eq(id.tx_digest.base58_encode()))
+                .filter(events::event_sequence.eq(id.event_seq as i64))
+                .first::<EventRow>(conn)
+        })?
+        .try_into()
+    }
+
+    fn get_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        let mut boxed_query = events::table.into_boxed();
+        match query {
+            EventFilter::All(..) => {}
+            EventFilter::Transaction(digest) => {
+                boxed_query =
+                    boxed_query.filter(events::transaction_digest.eq(digest.base58_encode()));
+            }
+            EventFilter::MoveModule { package, module } => {
+                boxed_query = boxed_query
+                    .filter(events::package.eq(package.to_string()))
+                    .filter(events::module.eq(module.to_string()));
+            }
+            EventFilter::MoveEventType(struct_name) => {
+                boxed_query = boxed_query.filter(events::event_type.eq(struct_name.to_string()));
+            }
+            EventFilter::Sender(sender) => {
+                boxed_query = boxed_query.filter(events::sender.eq(sender.to_string()));
+            }
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                boxed_query = boxed_query
+                    .filter(events::event_time_ms.ge(start_time as i64))
+                    .filter(events::event_time_ms.lt(end_time as i64));
+            }
+            _ => {
+                return Err(IndexerError::NotImplementedError(format!(
+                    "Filter type [{query:?}] not supported by the Indexer."
+                )))
+            }
+        }
+
+        let page_limit = limit.unwrap_or(MAX_EVENT_PAGE_SIZE);
+        if page_limit > MAX_EVENT_PAGE_SIZE {
+            Err(IndexerError::InvalidArgumentError(format!(
+                "Limit {} exceeds the maximum page size {}",
+                page_limit, MAX_EVENT_PAGE_SIZE
+            )))?;
+        }
+
+        let row_cursor = cursor
+            .map(|c| {
+                self.get_event(c)?
+                    .id
+                    .ok_or_else(|| IndexerError::SqliteReadError("Event ID is None".to_string()))
+            })
+            .transpose()?;
+        if let Some(row_cursor) = row_cursor {
+            boxed_query = if descending_order {
+                boxed_query.filter(events::id.lt(row_cursor))
+            } else {
+                boxed_query.filter(events::id.gt(row_cursor))
+            };
+        }
+        boxed_query = if descending_order {
+            boxed_query.order(events::id.desc())
+        } else {
+            boxed_query.order(events::id.asc())
+        };
+        // fetch one more item to tell if there is next page
+        let rows = self.read("events", |conn| {
+            boxed_query
+                .limit(page_limit as i64 + 1)
+                .load::<EventRow>(conn)
+        })?;
+
+        let mut sui_event_vec = rows
+            .into_iter()
+            .map(|row| Event::try_from(row)?.try_into())
+            .collect::<Result<Vec<SuiEvent>, _>>()?;
+        let has_next_page = sui_event_vec.len() > page_limit;
+        sui_event_vec.truncate(page_limit);
+        let next_cursor = sui_event_vec.last().map(|e| e.id.clone());
+        Ok(EventPage {
+            data: sui_event_vec,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_object(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<ObjectRead, IndexerError> {
+        let object = self.read(format_args!("object {}", object_id), |conn| {
+            if let Some(version) = version {
+                objects_history::table
+                    .filter(objects_history::object_id.eq(object_id.to_string()))
+                    .filter(objects_history::version.eq(version.value() as i64))
+                    .first::<ObjectRow>(conn)
+                    .optional()
+            } else {
+                objects::table
+                    .filter(objects::object_id.eq(object_id.to_string()))
+                    .first::<ObjectRow>(conn)
+                    .optional()
+            }
+        })?;
+
+        match object {
+            None => Ok(ObjectRead::NotExists(object_id)),
+            Some(o) => Object::try_from(o)?.try_into_object_read(&self.module_cache),
+        }
+    }
+
+    fn get_latest_object(&self, object_id: ObjectID) -> Result<Option<Object>, IndexerError> {
+        self.read(format_args!("object {}", object_id), |conn| {
+            objects::table
+                .filter(objects::object_id.eq(object_id.to_string()))
+                .first::<ObjectRow>(conn)
+                .optional()
+        })?
+        .map(Object::try_from)
+        .transpose()
+    }
+
+    fn query_objects(
+        &self,
+        object_type: Option<String>,
+        display: BTreeMap<String, String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<Object>, IndexerError> {
+        let object_type = object_type
+            .map(|object_type| {
+                Ok::<_, IndexerError>(parse_sui_struct_tag(&object_type)?.to_string())
+            })
+            .transpose()?;
+
+        let mut query = objects::table
+            .filter(objects::object_status.ne_all(gone_statuses()))
+            .into_boxed();
+        if let Some(object_type) = &object_type {
+            query = query.filter(objects::object_type.eq(object_type.clone()));
+        }
+        for (field, value) in &display {
+            query = query.filter(
+                sql::<Bool>("json_extract(display, ")
+                    .bind::<Text, _>(format!("$.\"{field}\""))
+                    .sql(") = ")
+                    .bind::<Text, _>(value.clone()),
+            );
+        }
+        if let Some(cursor) = cursor {
+            query = query.filter(objects::object_id.gt(cursor.to_string()));
+        }
+        self.read(
+            format_args!(
+                "objects of type {:?} and display {:?} with cursor {:?}",
+                object_type, display, cursor
+            ),
+            |conn| {
+                query
+                    .order(objects::object_id.asc())
+                    .limit(limit as i64)
+                    .load::<ObjectRow>(conn)
+            },
+        )?
+        .into_iter()
+        .map(Object::try_from)
+        .collect()
+    }
+
+    fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<DynamicFieldInfo>, IndexerError> {
+        let (fields, children) = self.read(
+            format_args!(
+                "dynamic fields of {} with cursor {:?}",
+                parent_object_id, cursor
+            ),
+            |conn| {
+                let mut query = objects::table
+                    .filter(objects::df_parent_id.eq(parent_object_id.to_string()))
+                    .filter(objects::df_kind.is_not_null())
+                    .filter(objects::object_status.ne_all(gone_statuses()))
+                    .into_boxed();
+                if let Some(cursor) = cursor {
+                    query = query.filter(objects::object_id.gt(cursor.to_string()));
+                }
+                let fields = query
+                    .order(objects::object_id.asc())
+                    .limit(limit as i64)
+                    .load::<ObjectRow>(conn)?;
+                let child_ids: Vec<String> = fields
+                    .iter()
+                    .filter(|field| field.df_kind.as_deref() == Some(DYNAMIC_OBJECT_KIND))
+                    .filter_map(|field| field.df_object_id.clone())
+                    .collect();
+                let children = objects::table
+                    .filter(objects::object_id.eq_any(child_ids))
+                    .load::<ObjectRow>(conn)?;
+                Ok((fields, children))
+            },
+        )?;
+
+        let children = children
+            .into_iter()
+            .map(|child| Ok((child.object_id.clone(), Object::try_from(child)?)))
+            .collect::<Result<HashMap<String, Object>, IndexerError>>()?;
+        fields
+            .into_iter()
+            .map(|field| {
+                let field = Object::try_from(field)?;
+                let child = field
+                    .df_object_id
+                    .as_ref()
+                    .and_then(|child_id| children.get(child_id));
+                field.try_into_dynamic_field_info(child)
+            })
+            .collect()
+    }
+
+    fn get_dynamic_field_object(
+        &self,
+        parent_object_id: ObjectID,
+        name: &DynamicFieldName,
+    ) -> Result<Option<Object>, IndexerError> {
+        let name_value = to_json(&name.value)?;
+        self.read(
+            format_args!("dynamic field {:?} of {}", name, parent_object_id),
+            |conn| {
+                let field = objects::table
+                    .filter(objects::df_parent_id.eq(parent_object_id.to_string()))
+                    .filter(objects::df_name_type.eq(name.type_.to_string()))
+                    .filter(objects::df_name.eq(&name_value))
+                    .filter(objects::object_status.ne_all(gone_statuses()))
+                    .first::<ObjectRow>(conn)
+                    .optional()?;
+                match field {
+                    Some(field) if field.df_kind.as_deref() == Some(DYNAMIC_OBJECT_KIND) => {
+                        objects::table
+                            .filter(objects::object_id.eq(field.df_object_id.unwrap_or_default()))
+                            .first::<ObjectRow>(conn)
+                            .optional()
+                    }
+                    field => Ok(field),
+                }
+            },
+        )?
+        .map(Object::try_from)
+        .transpose()
+    }
+
+    fn get_display(&self, object_type: &str) -> Result<Option<StoredDisplay>, IndexerError> {
+        self.read(format_args!("display of type {}", object_type), |conn| {
+            display::table
+                .filter(display::object_type.eq(object_type))
+                .first::<(String, String, i32, Vec<u8>, i64)>(conn)
+                .optional()
+        })
+        .map(|display| {
+            display.map(
+                |(object_type, id, version, bcs, checkpoint)| StoredDisplay {
+                    object_type,
+                    id,
+                    version,
+                    bcs,
+                    checkpoint,
+                },
+            )
+        })
+    }
+
+    fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<CoinPage, IndexerError> {
+        let mut query = coins_query(owner, coin_type.as_deref());
+        if let Some(cursor) = cursor {
+            query = query.filter(objects::object_id.gt(cursor.to_string()));
+        }
+        let mut coins = self.read(
+            format_args!(
+                "coins of owner {} and coin type {:?} with cursor {:?}",
+                owner, coin_type, cursor
+            ),
+            |conn| {
+                query
+                    .order(objects::object_id.asc())
+                    .limit(limit as i64 + 1)
+                    .load::<ObjectRow>(conn)
+            },
+        )?;
+
+        let has_next_page = coins.len() > limit;
+        coins.truncate(limit);
+        let data = coins
+            .into_iter()
+            .map(|coin| Coin::try_from(Object::try_from(coin)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        let next_cursor = data.last().map(|coin| coin.coin_object_id).or(cursor);
+        Ok(CoinPage {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_balance(&self, owner: SuiAddress, coin_type: String) -> Result<Balance, IndexerError> {
+        let (coin_object_count, total_balance) = self
+            .get_balances(owner, Some(coin_type.clone()))?
+            .remove(&coin_type)
+            .unwrap_or_default();
+        Ok(Balance {
+            coin_type,
+            coin_object_count,
+            total_balance,
+            // locked coins are not indexed
+            locked_balance: Default::default(),
+        })
+    }
+
+    fn get_all_balances(&self, owner: SuiAddress) -> Result<Vec<Balance>, IndexerError> {
+        Ok(self
+            .get_balances(owner, None)?
+            .into_iter()
+            .map(|(coin_type, (coin_object_count, total_balance))| Balance {
+                coin_type,
+                coin_object_count,
+                total_balance,
+                locked_balance: Default::default(),
+            })
+            .collect())
+    }
+
+    fn get_address_stats(
+        &self,
+        _address: SuiAddress,
+    ) -> Result<Option<AddressStats>, IndexerError> {
+        Err(not_stored("Address activity"))
+    }
+
+    fn get_address_counterparties(
+        &self,
+        _address: SuiAddress,
+        _limit: usize,
+    ) -> Result<Vec<AddressCounterparty>, IndexerError> {
+        Err(not_stored("Address activity"))
+    }
+
+    fn get_top_packages(
+        &self,
+        _epoch: Option<EpochId>,
+        _limit: usize,
+    ) -> Result<Vec<PackageCallCount>, IndexerError> {
+        Err(not_stored("Package activity"))
+    }
+
+    fn get_network_tps(&self, _window_ms: u64) -> Result<NetworkTps, IndexerError> {
+        Err(not_stored("Network activity"))
+    }
+
+    fn get_package_versions(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<SuiPackageVersion>, IndexerError> {
+        self.read(format_args!("versions of package {}", package_id), |conn| {
+            let Some(package) = packages::table
+                .filter(packages::package_id.eq(package_id.to_string()))
+                .first::<PackageRow>(conn)
+                .optional()? else {
+                return Ok(vec![]);
+            };
+            match package.original_package_id {
+                Some(original_package_id) => packages::table
+                    .filter(packages::original_package_id.eq(original_package_id))
+                    .order(packages::version.asc())
+                    .load::<PackageRow>(conn),
+                // Lineage unknown until the checkpoint of the package is backfilled
+                None => Ok(vec![package]),
+            }
+        })?
+        .into_iter()
+        .map(|package| SuiPackageVersion::try_from(Package::try_from(package)?))
+        .collect()
+    }
+
+    fn get_package_modules(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Option<BTreeMap<String, SuiMoveNormalizedModule>>, IndexerError> {
+        self.read(format_args!("package {}", package_id), |conn| {
+            packages::table
+                .filter(packages::package_id.eq(package_id.to_string()))
+                .first::<PackageRow>(conn)
+                .optional()
+        })?
+        .map(|package| Package::try_from(package)?.normalized_modules())
+        .transpose()
+    }
+
+    fn get_function_callers(
+        &self,
+        package_id: ObjectID,
+        module: &str,
+        function: &str,
+        limit: usize,
+    ) -> Result<Vec<MoveFunctionCaller>, IndexerError> {
+        self.read(
+            format_args!("callers of {}::{}::{}", package_id, module, function),
+            |conn| {
+                move_calls::table
+                    .filter(move_calls::move_package.eq(package_id.to_string()))
+                    .filter(move_calls::move_module.eq(module))
+                    .filter(move_calls::move_function.eq(function))
+                    .group_by(move_calls::sender)
+                    .select((
+                        move_calls::sender,
+                        count_star(),
+                        max(move_calls::checkpoint_sequence_number),
+                    ))
+                    .order((count_star().desc(), move_calls::sender.asc()))
+                    .limit(limit as i64)
+                    .load::<(String, i64, Option<i64>)>(conn)
+            },
+        )?
+        .into_iter()
+        .map(|(caller, call_count, last_call_checkpoint)| {
+            Ok(MoveFunctionCaller {
+                caller: SuiAddress::from_str(&caller)?,
+                call_count: call_count as u64,
+                last_call_checkpoint: last_call_checkpoint.unwrap_or_default() as u64,
+            })
+        })
+        .collect()
+    }
+
+    fn get_total_transaction_number(&self) -> Result<i64, IndexerError> {
+        self.read("total transaction number", |conn| {
+            transactions::table
+                .select(count(transactions::id))
+                .first::<i64>(conn)
+        })
+    }
+
+    fn get_transaction_by_digest(&self, txn_digest: &str) -> Result<Transaction, IndexerError> {
+        self.read(format_args!("transaction {}", txn_digest), |conn| {
+            transactions::table
+                .filter(transactions::transaction_digest.eq(txn_digest))
+                .first::<TransactionRow>(conn)
+        })?
+        .try_into()
+    }
+
+    fn multi_get_transactions_by_digests(
+        &self,
+        txn_digests: &[String],
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        self.read(format_args!("transactions {:?}", txn_digests), |conn| {
+            transactions::table
+                .filter(transactions::transaction_digest.eq_any(txn_digests))
+                .load::<TransactionRow>(conn)
+        })?
+        .into_iter()
+        .map(Transaction::try_from)
+        .collect()
+    }
+
+    fn get_all_transaction_digest_page(
+        &self,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut query = transactions::table.into_boxed();
+        if let Some(start_sequence) = start_sequence {
+            query = if is_descending {
+                query.filter(transactions::id.lt(start_sequence))
+            } else {
+                query.filter(transactions::id.gt(start_sequence))
+            };
+        }
+        query = if is_descending {
+            query.order(transactions::id.desc())
+        } else {
+            query.order(transactions::id.asc())
+        };
+        self.read(
+            format_args!("transaction digests from {:?}", start_sequence),
+            |conn| {
+                query
+                    .limit(limit as i64)
+                    .select(transactions::transaction_digest)
+                    .load::<String>(conn)
+            },
+        )
+    }
+
+    fn get_transaction_digest_page_by_mutated_object(
+        &self,
+        object_id: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut query = transactions::table
+            .filter(
+                sql::<Bool>("(EXISTS (SELECT 1 FROM json_each(created) WHERE value = ")
+                    .bind::<Text, _>(object_id.clone())
+                    .sql(") OR EXISTS (SELECT 1 FROM json_each(mutated) WHERE value = ")
+                    .bind::<Text, _>(object_id.clone())
+                    .sql(") OR EXISTS (SELECT 1 FROM json_each(unwrapped) WHERE value = ")
+                    .bind::<Text, _>(object_id.clone())
+                    .sql("))"),
+            )
+            .into_boxed();
+        if let Some(start_sequence) = start_sequence {
+            query = if is_descending {
+                query.filter(transactions::id.lt(start_sequence))
+            } else {
+                query.filter(transactions::id.gt(start_sequence))
+            };
+        }
+        query = if is_descending {
+            query.order(transactions::id.desc())
+        } else {
+            query.order(transactions::id.asc())
+        };
+        self.read(
+            format_args!(
+                "transaction digests by mutated object {} from {:?}",
+                object_id, start_sequence
+            ),
+            |conn| {
+                query
+                    .limit(limit as i64)
+                    .select(transactions::transaction_digest)
+                    .load::<String>(conn)
+            },
+        )
+    }
+
+    fn get_transaction_digest_page_by_sender_address(
+        &self,
+        sender_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut query = transactions::table
+            .filter(transactions::sender.eq(sender_address.clone()))
+            .into_boxed();
+        if let Some(start_sequence) = start_sequence {
+            query = if is_descending {
+                query.filter(transactions::id.lt(start_sequence))
+            } else {
+                query.filter(transactions::id.gt(start_sequence))
+            };
+        }
+        query = if is_descending {
+            query.order(transactions::id.desc())
+        } else {
+            query.order(transactions::id.asc())
+        };
+        self.read(
+            format_args!(
+                "transaction digests by sender {} from {:?}",
+                sender_address, start_sequence
+            ),
+            |conn| {
+                query
+                    .limit(limit as i64)
+                    .select(transactions::transaction_digest)
+                    .load::<String>(conn)
+            },
+        )
+    }
+
+    fn get_transaction_digest_page_by_recipient_address(
+        &self,
+        recipient_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        self.get_transaction_digest_page_by_index(
+            "recipients",
+            vec!["recipient = ?".to_string()],
+            vec![recipient_address],
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_transaction_digest_page_by_input_object(
+        &self,
+        object_id: String,
+        version: Option<i64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conditions = vec!["object_id = ?".to_string()];
+        if let Some(version) = version {
+            conditions.push(format!("object_version = {version}"));
+        }
+        self.get_transaction_digest_page_by_index(
+            "input_objects",
+            conditions,
+            vec![object_id],
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_transaction_digest_page_by_move_call(
+        &self,
+        package: String,
+        module: Option<String>,
+        function: Option<String>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conditions = vec!["move_package = ?".to_string()];
+        let mut binds = vec![package];
+        if let Some(module) = module {
+            conditions.push("move_module = ?".to_string());
+            binds.push(module);
+        }
+        if let Some(function) = function {
+            conditions.push("move_function = ?".to_string());
+            binds.push(function);
+        }
+        self.get_transaction_digest_page_by_index(
+            "move_calls",
+            conditions,
+            binds,
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_transaction_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        _is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                self.read(format_args!("sequence of transaction {}", digest), |conn| {
+                    transactions::table
+                        .filter(transactions::transaction_digest.eq(&digest))
+                        .select(transactions::id)
+                        .first::<i64>(conn)
+                })
+            })
+            .transpose()
+    }
+
+    fn get_move_call_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                self.read(
+                    format_args!("move call sequence of transaction {}", digest),
+                    |conn| {
+                        let query = move_calls::table
+                            .filter(move_calls::transaction_digest.eq(&digest))
+                            .select(move_calls::id);
+                        if is_descending {
+                            query.order(move_calls::id.desc()).first::<i64>(conn)
+                        } else {
+                            query.order(move_calls::id.asc()).first::<i64>(conn)
+                        }
+                    },
+                )
+            })
+            .transpose()
+    }
+
+    fn get_input_object_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                self.read(
+                    format_args!("input object sequence of transaction {}", digest),
+                    |conn| {
+                        let query = input_objects::table
+                            .filter(input_objects::transaction_digest.eq(&digest))
+                            .select(input_objects::id);
+                        if is_descending {
+                            query.order(input_objects::id.desc()).first::<i64>(conn)
+                        } else {
+                            query.order(input_objects::id.asc()).first::<i64>(conn)
+                        }
+                    },
+                )
+            })
+            .transpose()
+    }
+
+    fn get_recipient_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                self.read(
+                    format_args!("recipient sequence of transaction {}", digest),
+                    |conn| {
+                        let query = recipients::table
+                            .filter(recipients::transaction_digest.eq(&digest))
+                            .select(recipients::id);
+                        if is_descending {
+                            query.order(recipients::id.desc()).first::<i64>(conn)
+                        } else {
+                            query.order(recipients::id.asc()).first::<i64>(conn)
+                        }
+                    },
+                )
+            })
+            .transpose()
+    }
+
+    fn read_transactions(
+        &self,
+        last_processed_id: i64,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        self.read(
+            format_args!("transactions after {}", last_processed_id),
+            |conn| {
+                transactions::table
+                    .filter(transactions::id.gt(last_processed_id))
+                    .order(transactions::id.asc())
+                    .limit(limit as i64)
+                    .load::<TransactionRow>(conn)
+            },
+        )?
+        .into_iter()
+        .map(Transaction::try_from)
+        .collect()
+    }
+
+    fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+        let rows = CheckpointRows::try_from(data)?;
+        let sequence_number = data.checkpoint.sequence_number;
+        // The whole checkpoint is committed in a single transaction, a checkpoint that is already
+        // persisted is left as is.
+        let committed = self.write(format_args!("checkpoint {}", sequence_number), |conn| {
+            let persisted = checkpoints::table
+                .filter(checkpoints::sequence_number.eq(sequence_number))
+                .count()
+                .get_result::<i64>(conn)?;
+            if persisted > 0 {
+                return Ok(0);
+            }
+            for object in &rows.mutated_objects {
+                upsert_object(conn, object)?;
+            }
+            for object in &rows.deleted_objects {
+                upsert_deleted_object(conn, object)?;
+            }
+            rows.insert(conn, data)
+        })?;
+        if committed > 0 {
+            self.persist_displays(&data.displays)?;
+        }
+        Ok(committed)
+    }
+
+    fn persist_epoch(&self, _data: &TemporaryEpochStore) -> Result<(), IndexerError> {
+        // Epochs, validators and committees are not stored.
+        Ok(())
+    }
+
+    fn backfill_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+        let rows = CheckpointRows::try_from(data)?;
+        let sequence_number = data.checkpoint.sequence_number;
+        let transaction_digests: Vec<&String> = data
+            .transactions
+            .iter()
+            .map(|transaction| &transaction.transaction_digest)
+            .collect();
+        let committed = self.write(
+            format_args!("backfilled checkpoint {}", sequence_number),
+            |conn| {
+                diesel::delete(
+                    events::table.filter(events::transaction_digest.eq_any(&transaction_digests)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    transactions::table
+                        .filter(transactions::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    move_calls::table
+                        .filter(move_calls::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    input_objects::table
+                        .filter(input_objects::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    recipients::table
+                        .filter(recipients::checkpoint_sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+                for package in &rows.packages {
+                    diesel::delete(
+                        packages::table
+                            .filter(packages::package_id.eq(&package.package_id))
+                            .filter(packages::version.eq(package.version)),
+                    )
+                    .execute(conn)?;
+                }
+                diesel::delete(
+                    checkpoints::table.filter(checkpoints::sequence_number.eq(sequence_number)),
+                )
+                .execute(conn)?;
+
+                // Objects changed again after this checkpoint keep their latest state, the history
+                // rows of the versions that are written again are replaced by the triggers.
+                let changed_object_ids: Vec<&String> = rows
+                    .mutated_objects
+                    .iter()
+                    .chain(rows.deleted_objects.iter())
+                    .map(|object| &object.object_id)
+                    .collect();
+                let persisted_versions: HashMap<String, i64> = objects::table
+                    .select((objects::object_id, objects::version))
+                    .filter(objects::object_id.eq_any(&changed_object_ids))
+                    .load::<(String, i64)>(conn)?
+                    .into_iter()
+                    .collect();
+                let is_latest = |object: &&ObjectRow| {
+                    persisted_versions
+                        .get(&object.object_id)
+                        .map_or(true, |persisted| *persisted <= object.version)
+                };
+                for object in rows.mutated_objects.iter().filter(is_latest) {
+                    upsert_object(conn, object)?;
+                }
+                for object in rows.deleted_objects.iter().filter(is_latest) {
+                    upsert_deleted_object(conn, object)?;
+                }
+                rows.insert(conn, data)
+            },
+        )?;
+        self.persist_displays(&data.displays)?;
+        Ok(committed)
+    }
+
+    fn backfill_epoch(&self, _data: &TemporaryEpochStore) -> Result<(), IndexerError> {
+        // Epochs, validators and committees are not stored.
+        Ok(())
+    }
+
+    fn refresh_balances(&self) -> Result<(), IndexerError> {
+        // Balances are summed from the coin objects when they are read.
+        Ok(())
+    }
+
+    fn render_pending_displays(&self, limit: usize) -> Result<usize, IndexerError> {
+        let pending = self.read("pending display renders", |conn| {
+            display_renders::table
+                .order(display_renders::object_type)
+                .first::<(String, i64, Option<String>)>(conn)
+                .optional()
+        })?;
+        let Some((object_type, checkpoint, cursor)) = pending else {
+            return Ok(0);
+        };
+        let stored_display = match self.get_display(&object_type)? {
+            Some(stored_display) if stored_display.checkpoint == checkpoint => stored_display,
+            // The display was published again since, its render is restarted.
+            _ => return Ok(0),
+        };
+
+        let cursor = cursor.map(|c| ObjectID::from_str(&c)).transpose()?;
+        let objects =
+            self.query_objects(Some(object_type.clone()), BTreeMap::new(), cursor, limit)?;
+        let rendered = objects
+            .iter()
+            .map(|object| {
+                let display = stored_display
+                    .render(object, self.module_cache.as_ref())
+                    .unwrap_or_else(|e| {
+                        warn!(
+                            "Failed to render display of object {}: {:?}",
+                            object.object_id, e
+                        );
+                        None
+                    });
+                Ok((
+                    object.object_id.clone(),
+                    object.version,
+                    display.as_ref().map(to_json).transpose()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, IndexerError>>()?;
+
+        self.write(
+            format_args!("display of objects of type {}", object_type),
+            |conn| {
+                let pending_render = display_renders::table
+                    .filter(display_renders::object_type.eq(&object_type))
+                    .filter(display_renders::checkpoint.eq(checkpoint));
+                let updated = match objects.last() {
+                    Some(last) if objects.len() == limit => diesel::update(pending_render)
+                        .set(display_renders::cursor.eq(&last.object_id))
+                        .execute(conn)?,
+                    _ => diesel::delete(pending_render).execute(conn)?,
+                };
+                // A display published meanwhile restarted the render, drop this batch.
+                if updated == 0 {
+                    return Ok(0);
+                }
+                for (object_id, version, display) in &rendered {
+                    diesel::update(
+                        objects::table
+                            .filter(objects::object_id.eq(object_id))
+                            .filter(objects::version.eq(version)),
+                    )
+                    .set(objects::display.eq(display))
+                    .execute(conn)?;
+                }
+                Ok(rendered.len())
+            },
+        )
+    }
+
+    fn get_sink_watermark(
+        &self,
+        sink_name: &str,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError> {
+        self.read(format_args!("watermark of sink {}", sink_name), |conn| {
+            sink_watermarks::table
+                .select(sink_watermarks::checkpoint_sequence_number)
+                .filter(sink_watermarks::sink_name.eq(sink_name))
+                .first::<i64>(conn)
+                .optional()
+        })
+        .map(|watermark| watermark.map(|seq| seq as CheckpointSequenceNumber))
+    }
+
+    fn persist_sink_watermark(
+        &self,
+        sink_name: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        self.write(
+            format_args!("watermark {} of sink {}", checkpoint, sink_name),
+            |conn| {
+                diesel::replace_into(sink_watermarks::table)
+                    .values((
+                        sink_watermarks::sink_name.eq(sink_name),
+                        sink_watermarks::checkpoint_sequence_number.eq(checkpoint as i64),
+                    ))
+                    .execute(conn)
+            },
+        )?;
+        Ok(())
+    }
+
+    fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError> {
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let error_logs: Vec<ErrorLog> = errors.into_iter().map(|e| e.into()).collect();
+        let result = self.write("error logs", |conn| {
+            for error_log in &error_logs {
+                diesel::insert_into(error_logs::table)
+                    .values((
+                        error_logs::error_type.eq(&error_log.error_type),
+                        error_logs::error.eq(&error_log.error),
+                        error_logs::error_time.eq(error_log.error_time),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            error!("Failed writing error logs with error {:?}", e);
+        }
+        Ok(())
+    }
+
+    fn module_cache(&self) -> &Self::ModuleCache {
+        &self.module_cache
+    }
+}
+
+/// The rows of a checkpoint, converted before the write transaction starts.
+struct CheckpointRows {
+    checkpoint: CheckpointRow,
+    transactions: Vec<TransactionRow>,
+    events: Vec<EventRow>,
+    mutated_objects: Vec<ObjectRow>,
+    deleted_objects: Vec<ObjectRow>,
+    packages: Vec<PackageRow>,
+}
+
+impl TryFrom<&TemporaryCheckpointStore> for CheckpointRows {
+    type Error = IndexerError;
+
+    fn try_from(data: &TemporaryCheckpointStore) -> Result<Self, Self::Error> {
+        Ok(Self {
+            checkpoint: CheckpointRow::try_from(&data.checkpoint)?,
+            transactions: data
+                .transactions
+                .iter()
+                .map(TransactionRow::try_from)
+                .collect::<Result<_, _>>()?,
+            events: data
+                .events
+                .iter()
+                .map(EventRow::try_from)
+                .collect::<Result<_, _>>()?,
+            mutated_objects: data
+                .objects_changes
+                .iter()
+                .flat_map(|changes| changes.mutated_objects.iter())
+                .map(ObjectRow::try_from)
+                .collect::<Result<_, _>>()?,
+            deleted_objects: data
+                .objects_changes
+                .iter()
+                .flat_map(|changes| changes.deleted_objects.iter())
+                .map(|object| ObjectRow::try_from(&Object::from(object.clone())))
+                .collect::<Result<_, _>>()?,
+            packages: data
+                .packages
+                .iter()
+                .map(PackageRow::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl CheckpointRows {
+    /// Inserts all rows of the checkpoint but the objects, the checkpoint last.
+    fn insert(
+        &self,
+        conn: &mut SqliteConnection,
+        data: &TemporaryCheckpointStore,
+    ) -> QueryResult<usize> {
+        for transaction in &self.transactions {
+            diesel::insert_or_ignore_into(transactions::table)
+                .values(transaction)
+                .execute(conn)?;
+        }
+        for event in &self.events {
+            diesel::insert_or_ignore_into(events::table)
+                .values(event)
+                .execute(conn)?;
+        }
+        for address in &data.addresses {
+            diesel::insert_or_ignore_into(addresses::table)
+                .values((
+                    addresses::account_address.eq(&address.account_address),
+                    addresses::first_appearance_tx.eq(&address.first_appearance_tx),
+                    addresses::first_appearance_time.eq(address.first_appearance_time),
+                ))
+                .execute(conn)?;
+        }
+        for package in &self.packages {
+            diesel::insert_or_ignore_into(packages::table)
+                .values(package)
+                .execute(conn)?;
+        }
+        for move_call in &data.move_calls {
+            diesel::insert_into(move_calls::table)
+                .values((
+                    move_calls::transaction_digest.eq(&move_call.transaction_digest),
+                    move_calls::checkpoint_sequence_number.eq(move_call.checkpoint_sequence_number),
+                    move_calls::epoch.eq(move_call.epoch),
+                    move_calls::sender.eq(&move_call.sender),
+                    move_calls::move_package.eq(&move_call.move_package),
+                    move_calls::move_module.eq(&move_call.move_module),
+                    move_calls::move_function.eq(&move_call.move_function),
+                ))
+                .execute(conn)?;
+        }
+        for input_object in &data.input_objects {
+            diesel::insert_into(input_objects::table)
+                .values((
+                    input_objects::transaction_digest.eq(&input_object.transaction_digest),
+                    input_objects::checkpoint_sequence_number
+                        .eq(input_object.checkpoint_sequence_number),
+                    input_objects::epoch.eq(input_object.epoch),
+                    input_objects::object_id.eq(&input_object.object_id),
+                    input_objects::object_version.eq(input_object.object_version),
+                ))
+                .execute(conn)?;
+        }
+        for recipient in &data.recipients {
+            diesel::insert_into(recipients::table)
+                .values((
+                    recipients::transaction_digest.eq(&recipient.transaction_digest),
+                    recipients::checkpoint_sequence_number.eq(recipient.checkpoint_sequence_number),
+                    recipients::epoch.eq(recipient.epoch),
+                    recipients::recipient.eq(&recipient.recipient),
+                ))
+                .execute(conn)?;
+        }
+        diesel::insert_into(checkpoints::table)
+            .values(&self.checkpoint)
+            .execute(conn)
+    }
+}
+
+fn upsert_object(conn: &mut SqliteConnection, object: &ObjectRow) -> QueryResult<()> {
+    let updated = diesel::update(objects::table.filter(objects::object_id.eq(&object.object_id)))
+        .set(object)
+        .execute(conn)?;
+    if updated == 0 {
+        diesel::insert_into(objects::table)
+            .values(object)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Same as `upsert_object`, but the content of an already persisted object is kept.
+fn upsert_deleted_object(conn: &mut SqliteConnection, object: &ObjectRow) -> QueryResult<()> {
+    let updated = diesel::update(objects::table.filter(objects::object_id.eq(&object.object_id)))
+        .set((
+            objects::epoch.eq(object.epoch),
+            objects::checkpoint.eq(object.checkpoint),
+            objects::version.eq(object.version),
+            objects::previous_transaction.eq(&object.previous_transaction),
+            objects::object_status.eq(&object.object_status),
+        ))
+        .execute(conn)?;
+    if updated == 0 {
+        diesel::insert_into(objects::table)
+            .values(object)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Live coins owned by `owner`, only of `coin_type` if it is set.
+fn coins_query(owner: SuiAddress, coin_type: Option<&str>) -> objects::BoxedQuery<'static, Sqlite> {
+    let query = objects::table
+        .filter(objects::owner_type.eq(owner_type_name(&OwnerType::AddressOwner)))
+        .filter(objects::owner_address.eq(owner.to_string()))
+        .filter(objects::object_status.ne_all(gone_statuses()))
+        .into_boxed();
+    match coin_type {
+        Some(coin_type) => {
+            query.filter(objects::object_type.eq(format!("{COIN_TYPE_PREFIX}{coin_type}>")))
+        }
+        None => query.filter(objects::object_type.like(format!("{COIN_TYPE_PREFIX}%"))),
+    }
+}
+
+/// Statuses of the objects that no longer exist as such.
+fn gone_statuses() -> [&'static str; 3] {
+    [
+        object_status_name(&ObjectStatus::Deleted),
+        object_status_name(&ObjectStatus::Wrapped),
+        object_status_name(&ObjectStatus::UnwrappedThenDeleted),
+    ]
+}
+
+fn not_stored(data: &str) -> IndexerError {
+    IndexerError::NotImplementedError(format!("{data} is not stored by the SQLite indexer store"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use sui_types::digests::{CheckpointDigest, TransactionDigest};
+
+    use super::*;
+    use crate::models::objects::NamedBcsBytes;
+    use crate::new_sqlite_connection_pool;
+    use crate::store::TransactionObjectChanges;
+
+    #[derive(QueryableByName)]
+    struct ColumnInfo {
+        #[diesel(sql_type = Text)]
+        name: String,
+    }
+
+    async fn in_memory_store() -> SqliteIndexerStore {
+        let cp = new_sqlite_connection_pool("sqlite://:memory:")
+            .await
+            .unwrap();
+        SqliteIndexerStore::new(cp).unwrap()
+    }
+
+    /// Columns of each table declared with `diesel::table!` in `schema`, in declaration order.
+    fn table_columns(schema: &str) -> BTreeMap<String, Vec<String>> {
+        let mut tables = BTreeMap::new();
+        let mut table: Option<(String, Vec<String>)> = None;
+        for line in schema.lines().map(str::trim) {
+            match &mut table {
+                Some((_, columns)) if line != "}" => {
+                    if let Some((column, _)) = line.split_once(" -> ") {
+                        columns.push(column.to_string());
+                    }
+                }
+                Some(_) => {
+                    let (name, columns) = table.take().unwrap();
+                    tables.insert(name, columns);
+                }
+                None if line.ends_with(") {") => {
+                    let (name, _) = line.split_once(" (").unwrap();
+                    table = Some((name.to_string(), vec![]));
+                }
+                None => {}
+            }
+        }
+        tables
+    }
+
+    // The SQLite rows convert from and to the Postgres models field by field, so a column missing
+    // from either schema would not compile; these tests catch the columns added to only one.
+    #[test]
+    fn test_sqlite_schema_matches_postgres_schema() {
+        let pg_tables = table_columns(include_str!("../schema.rs"));
+        let sqlite_tables = table_columns(include_str!("sqlite_schema.rs"));
+        assert!(sqlite_tables.contains_key("objects"));
+        for (table, columns) in &sqlite_tables {
+            if let Some(pg_columns) = pg_tables.get(table) {
+                assert_eq!(columns, pg_columns, "columns of table {table}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_schema_sql_matches_diesel_schema() {
+        let store = in_memory_store().await;
+        let mut conn = get_sqlite_pool_connection(&store.cp).unwrap();
+        for (table, columns) in table_columns(include_str!("sqlite_schema.rs")) {
+            let created_columns: Vec<String> =
+                diesel::sql_query(format!("PRAGMA table_info({table})"))
+                    .load::<ColumnInfo>(&mut conn)
+                    .unwrap()
+                    .into_iter()
+                    .map(|column| column.name)
+                    .collect();
+            assert_eq!(created_columns, columns, "columns of table {table}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persisted_checkpoint_is_read_back() -> Result<(), IndexerError> {
+        let store = in_memory_store().await;
+        let digest = TransactionDigest::random();
+        let sender = SuiAddress::random_for_testing_only().to_string();
+        let object_id = ObjectID::random();
+        let checkpoint = Checkpoint {
+            sequence_number: 7,
+            checkpoint_digest: CheckpointDigest::random().base58_encode(),
+            transactions: vec![Some(digest.base58_encode())],
+            total_transactions: 1,
+            ..Default::default()
+        };
+        let transaction = Transaction {
+            id: None,
+            transaction_digest: digest.base58_encode(),
+            sender: sender.clone(),
+            recipients: vec![Some(sender.clone())],
+            checkpoint_sequence_number: 7,
+            timestamp_ms: 1_000,
+            transaction_kind: "ProgrammableTransaction".to_string(),
+            created: vec![Some(object_id.to_string())],
+            mutated: vec![],
+            deleted: vec![],
+            unwrapped: vec![],
+            wrapped: vec![],
+            move_calls: vec![],
+            gas_object_id: ObjectID::random().to_string(),
+            gas_object_sequence: 1,
+            gas_object_digest: "gas".to_string(),
+            gas_budget: 1_000,
+            total_gas_cost: 10,
+            computation_cost: 10,
+            storage_cost: 0,
+            storage_rebate: 0,
+            gas_price: 1,
+            raw_transaction: vec![0, 1],
+            transaction_content: "{}".to_string(),
+            transaction_effects_content: "{}".to_string(),
+            confirmed_local_execution: Some(true),
+        };
+        let event = Event {
+            id: None,
+            transaction_digest: digest.base58_encode(),
+            event_sequence: 0,
+            sender: sender.clone(),
+            package: "0x2".to_string(),
+            module: "devnet_nft".to_string(),
+            event_type: "0x2::devnet_nft::MintNFTEvent".to_string(),
+            event_time_ms: Some(1_000),
+            parsed_json: json!({ "name": "nft" }),
+            event_bcs: vec![1, 2, 3],
+        };
+        let object = Object {
+            epoch: 0,
+            checkpoint: 7,
+            object_id: object_id.to_string(),
+            version: 1,
+            object_digest: "digest".to_string(),
+            owner_type: OwnerType::AddressOwner,
+            owner_address: Some(sender.clone()),
+            initial_shared_version: None,
+            previous_transaction: digest.base58_encode(),
+            object_type: "0x2::devnet_nft::DevNetNFT".to_string(),
+            object_status: ObjectStatus::Created,
+            has_public_transfer: true,
+            storage_rebate: 0,
+            bcs: vec![NamedBcsBytes("object".to_string(), vec![4, 5, 6])],
+            df_kind: None,
+            df_parent_id: None,
+            df_name_type: None,
+            df_name: None,
+            df_bcs_name: None,
+            df_object_type: None,
+            df_object_id: None,
+            display: Some(json!({ "name": "nft" })),
+        };
+        let data = TemporaryCheckpointStore {
+            checkpoint: checkpoint.clone(),
+            transactions: vec![transaction],
+            events: vec![event],
+            objects_changes: vec![TransactionObjectChanges {
+                mutated_objects: vec![object],
+                deleted_objects: vec![],
+            }],
+            addresses: vec![],
+            packages: vec![],
+            input_objects: vec![],
+            move_calls: vec![],
+            recipients: vec![],
+            stake_events: vec![],
+            displays: vec![],
+        };
+        assert_eq!(store.persist_checkpoint(&data)?, 1);
+        // Persisting a checkpoint again leaves it as is
+        assert_eq!(store.persist_checkpoint(&data)?, 0);
+
+        assert_eq!(store.get_latest_checkpoint_sequence_number()?, 7);
+        let stored_checkpoint = store.get_checkpoint(CheckpointId::SequenceNumber(7))?;
+        assert_eq!(
+            stored_checkpoint.checkpoint_digest,
+            checkpoint.checkpoint_digest
+        );
+        assert_eq!(stored_checkpoint.transactions, checkpoint.transactions);
+
+        let stored_transaction = store.get_transaction_by_digest(&digest.base58_encode())?;
+        assert_eq!(stored_transaction.sender, sender);
+        assert_eq!(stored_transaction.recipients, vec![Some(sender.clone())]);
+        assert_eq!(
+            stored_transaction.created,
+            vec![Some(object_id.to_string())]
+        );
+        assert_eq!(store.get_total_transaction_number()?, 1);
+
+        let stored_event = store.get_event(EventID {
+            tx_digest: digest,
+            event_seq: 0,
+        })?;
+        assert_eq!(stored_event.parsed_json, json!({ "name": "nft" }));
+        assert_eq!(stored_event.event_bcs, vec![1, 2, 3]);
+
+        let stored_object = store
+            .get_latest_object(object_id)?
+            .expect("Object should be persisted");
+        assert_eq!(stored_object.version, 1);
+        assert_eq!(stored_object.owner_address, Some(sender));
+        assert_eq!(stored_object.bcs[0].1, vec![4, 5, 6]);
+        assert_eq!(stored_object.display, Some(json!({ "name": "nft" })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_data_not_stored_fails_explicitly() {
+        let store = in_memory_store().await;
+        assert!(matches!(
+            store.get_epochs(None, 10, false),
+            Err(IndexerError::NotImplementedError(_))
+        ));
+        assert!(matches!(
+            store.get_network_tps(60_000),
+            Err(IndexerError::NotImplementedError(_))
+        ));
+        // Missing committees fall back to the fullnode
+        assert!(matches!(store.get_committee(None), Ok(None)));
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rows of the SQLite store, the indexer models with the Postgres specific columns encoded as
+//! JSON text or BCS.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::events::Event;
+use crate::models::objects::{NamedBcsBytes, Object, ObjectStatus};
+use crate::models::owners::OwnerType;
+use crate::models::packages::Package;
+use crate::models::transactions::Transaction;
+use crate::store::sqlite_schema::{checkpoints, events, objects, packages, transactions};
+
+#[derive(Queryable, Insertable, Debug)]
+#[diesel(table_name = checkpoints)]
+pub(crate) struct CheckpointRow {
+    pub sequence_number: i64,
+    pub checkpoint_digest: String,
+    pub epoch: i64,
+    pub transactions: String,
+    pub previous_checkpoint_digest: Option<String>,
+    pub next_epoch_committee: Option<String>,
+    pub next_epoch_protocol_version: Option<i64>,
+    pub end_of_epoch_data: Option<String>,
+    pub total_gas_cost: i64,
+    pub total_computation_cost: i64,
+    pub total_storage_cost: i64,
+    pub total_storage_rebate: i64,
+    pub total_transactions: i64,
+    pub total_transactions_current_epoch: i64,
+    pub total_transactions_from_genesis: i64,
+    pub timestamp_ms: i64,
+    pub timestamp_ms_str: NaiveDateTime,
+    pub checkpoint_tps: f32,
+}
+
+impl TryFrom<&Checkpoint> for CheckpointRow {
+    type Error = IndexerError;
+
+    fn try_from(c: &Checkpoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence_number: c.sequence_number,
+            checkpoint_digest: c.checkpoint_digest.clone(),
+            epoch: c.epoch,
+            transactions: to_json(&c.transactions)?,
+            previous_checkpoint_digest: c.previous_checkpoint_digest.clone(),
+            next_epoch_committee: c.next_epoch_committee.clone(),
+            next_epoch_protocol_version: c.next_epoch_protocol_version,
+            end_of_epoch_data: c.end_of_epoch_data.clone(),
+            total_gas_cost: c.total_gas_cost,
+            total_computation_cost: c.total_computation_cost,
+            total_storage_cost: c.total_storage_cost,
+            total_storage_rebate: c.total_storage_rebate,
+            total_transactions: c.total_transactions,
+            total_transactions_current_epoch: c.total_transactions_current_epoch,
+            total_transactions_from_genesis: c.total_transactions_from_genesis,
+            timestamp_ms: c.timestamp_ms,
+            timestamp_ms_str: c.timestamp_ms_str,
+            checkpoint_tps: c.checkpoint_tps,
+        })
+    }
+}
+
+impl TryFrom<CheckpointRow> for Checkpoint {
+    type Error = IndexerError;
+
+    fn try_from(c: CheckpointRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence_number: c.sequence_number,
+            checkpoint_digest: c.checkpoint_digest,
+            epoch: c.epoch,
+            transactions: from_json(&c.transactions)?,
+            previous_checkpoint_digest: c.previous_checkpoint_digest,
+            next_epoch_committee: c.next_epoch_committee,
+            next_epoch_protocol_version: c.next_epoch_protocol_version,
+            end_of_epoch_data: c.end_of_epoch_data,
+            total_gas_cost: c.total_gas_cost,
+            total_computation_cost: c.total_computation_cost,
+            total_storage_cost: c.total_storage_cost,
+            total_storage_rebate: c.total_storage_rebate,
+            total_transactions: c.total_transactions,
+            total_transactions_current_epoch: c.total_transactions_current_epoch,
+            total_transactions_from_genesis: c.total_transactions_from_genesis,
+            timestamp_ms: c.timestamp_ms,
+            timestamp_ms_str: c.timestamp_ms_str,
+            checkpoint_tps: c.checkpoint_tps,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug)]
+#[diesel(table_name = transactions)]
+pub(crate) struct TransactionRow {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub sender: String,
+    pub recipients: String,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub transaction_kind: String,
+    pub created: String,
+    pub mutated: String,
+    pub deleted: String,
+    pub unwrapped: String,
+    pub wrapped: String,
+    pub move_calls: String,
+    pub gas_object_id: String,
+    pub gas_object_sequence: i64,
+    pub gas_object_digest: String,
+    pub gas_budget: i64,
+    pub total_gas_cost: i64,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+    pub gas_price: i64,
+    pub raw_transaction: Vec<u8>,
+    pub transaction_content: String,
+    pub transaction_effects_content: String,
+    pub confirmed_local_execution: Option<bool>,
+}
+
+impl TryFrom<&Transaction> for TransactionRow {
+    type Error = IndexerError;
+
+    fn try_from(t: &Transaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: None,
+            transaction_digest: t.transaction_digest.clone(),
+            sender: t.sender.clone(),
+            recipients: to_json(&t.recipients)?,
+            checkpoint_sequence_number: t.checkpoint_sequence_number,
+            timestamp_ms: t.timestamp_ms,
+            transaction_kind: t.transaction_kind.clone(),
+            created: to_json(&t.created)?,
+            mutated: to_json(&t.mutated)?,
+            deleted: to_json(&t.deleted)?,
+            unwrapped: to_json(&t.unwrapped)?,
+            wrapped: to_json(&t.wrapped)?,
+            move_calls: to_json(&t.move_calls)?,
+            gas_object_id: t.gas_object_id.clone(),
+            gas_object_sequence: t.gas_object_sequence,
+            gas_object_digest: t.gas_object_digest.clone(),
+            gas_budget: t.gas_budget,
+            total_gas_cost: t.total_gas_cost,
+            computation_cost: t.computation_cost,
+            storage_cost: t.storage_cost,
+            storage_rebate: t.storage_rebate,
+            gas_price: t.gas_price,
+            raw_transaction: t.raw_transaction.clone(),
+            transaction_content: t.transaction_content.clone(),
+            transaction_effects_content: t.transaction_effects_content.clone(),
+            confirmed_local_execution: t.confirmed_local_execution,
+        })
+    }
+}
+
+impl TryFrom<TransactionRow> for Transaction {
+    type Error = IndexerError;
+
+    fn try_from(t: TransactionRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: t.id,
+            transaction_digest: t.transaction_digest,
+            sender: t.sender,
+            recipients: from_json(&t.recipients)?,
+            checkpoint_sequence_number: t.checkpoint_sequence_number,
+            timestamp_ms: t.timestamp_ms,
+            transaction_kind: t.transaction_kind,
+            created: from_json(&t.created)?,
+            mutated: from_json(&t.mutated)?,
+            deleted: from_json(&t.deleted)?,
+            unwrapped: from_json(&t.unwrapped)?,
+            wrapped: from_json(&t.wrapped)?,
+            move_calls: from_json(&t.move_calls)?,
+            gas_object_id: t.gas_object_id,
+            gas_object_sequence: t.gas_object_sequence,
+            gas_object_digest: t.gas_object_digest,
+            gas_budget: t.gas_budget,
+            total_gas_cost: t.total_gas_cost,
+            computation_cost: t.computation_cost,
+            storage_cost: t.storage_cost,
+            storage_rebate: t.storage_rebate,
+            gas_price: t.gas_price,
+            raw_transaction: t.raw_transaction,
+            transaction_content: t.transaction_content,
+            transaction_effects_content: t.transaction_effects_content,
+            confirmed_local_execution: t.confirmed_local_execution,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug)]
+#[diesel(table_name = events)]
+pub(crate) struct EventRow {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub event_sequence: i64,
+    pub sender: String,
+    pub package: String,
+    pub module: String,
+    pub event_type: String,
+    pub event_time_ms: Option<i64>,
+    pub parsed_json: String,
+    pub event_bcs: Vec<u8>,
+}
+
+impl TryFrom<&Event> for EventRow {
+    type Error = IndexerError;
+
+    fn try_from(e: &Event) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: None,
+            transaction_digest: e.transaction_digest.clone(),
+            event_sequence: e.event_sequence,
+            sender: e.sender.clone(),
+            package: e.package.clone(),
+            module: e.module.clone(),
+            event_type: e.event_type.clone(),
+            event_time_ms: e.event_time_ms,
+            parsed_json: to_json(&e.parsed_json)?,
+            event_bcs: e.event_bcs.clone(),
+        })
+    }
+}
+
+impl TryFrom<EventRow> for Event {
+    type Error = IndexerError;
+
+    fn try_from(e: EventRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: e.id,
+            transaction_digest: e.transaction_digest,
+            event_sequence: e.event_sequence,
+            sender: e.sender,
+            package: e.package,
+            module: e.module,
+            event_type: e.event_type,
+            event_time_ms: e.event_time_ms,
+            parsed_json: from_json(&e.parsed_json)?,
+            event_bcs: e.event_bcs,
+        })
+    }
+}
+
+/// A row of `objects` or `objects_history`, which have the same columns.
+#[derive(Queryable, Insertable, AsChangeset, Debug)]
+#[diesel(table_name = objects, treat_none_as_null = true)]
+pub(crate) struct ObjectRow {
+    pub epoch: i64,
+    pub checkpoint: i64,
+    pub object_id: String,
+    pub version: i64,
+    pub object_digest: String,
+    pub owner_type: String,
+    pub owner_address: Option<String>,
+    pub initial_shared_version: Option<i64>,
+    pub previous_transaction: String,
+    pub object_type: String,
+    pub object_status: String,
+    pub has_public_transfer: bool,
+    pub storage_rebate: i64,
+    pub bcs: Vec<u8>,
+    pub df_kind: Option<String>,
+    pub df_parent_id: Option<String>,
+    pub df_name_type: Option<String>,
+    pub df_name: Option<String>,
+    pub df_bcs_name: Option<Vec<u8>>,
+    pub df_object_type: Option<String>,
+    pub df_object_id: Option<String>,
+    pub display: Option<String>,
+}
+
+impl TryFrom<&Object> for ObjectRow {
+    type Error = IndexerError;
+
+    fn try_from(o: &Object) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: o.epoch,
+            checkpoint: o.checkpoint,
+            object_id: o.object_id.clone(),
+            version: o.version,
+            object_digest: o.object_digest.clone(),
+            owner_type: owner_type_name(&o.owner_type).to_string(),
+            owner_address: o.owner_address.clone(),
+            initial_shared_version: o.initial_shared_version,
+            previous_transaction: o.previous_transaction.clone(),
+            object_type: o.object_type.clone(),
+            object_status: object_status_name(&o.object_status).to_string(),
+            has_public_transfer: o.has_public_transfer,
+            storage_rebate: o.storage_rebate,
+            bcs: to_named_bcs(&o.bcs)?,
+            df_kind: o.df_kind.clone(),
+            df_parent_id: o.df_parent_id.clone(),
+            df_name_type: o.df_name_type.clone(),
+            df_name: o.df_name.as_ref().map(to_json).transpose()?,
+            df_bcs_name: o.df_bcs_name.clone(),
+            df_object_type: o.df_object_type.clone(),
+            df_object_id: o.df_object_id.clone(),
+            display: o.display.as_ref().map(to_json).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<ObjectRow> for Object {
+    type Error = IndexerError;
+
+    fn try_from(o: ObjectRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: o.epoch,
+            checkpoint: o.checkpoint,
+            object_id: o.object_id,
+            version: o.version,
+            object_digest: o.object_digest,
+            owner_type: parse_owner_type(&o.owner_type)?,
+            owner_address: o.owner_address,
+            initial_shared_version: o.initial_shared_version,
+            previous_transaction: o.previous_transaction,
+            object_type: o.object_type,
+            object_status: parse_object_status(&o.object_status)?,
+            has_public_transfer: o.has_public_transfer,
+            storage_rebate: o.storage_rebate,
+            bcs: from_named_bcs(&o.bcs)?,
+            df_kind: o.df_kind,
+            df_parent_id: o.df_parent_id,
+            df_name_type: o.df_name_type,
+            df_name: o.df_name.as_deref().map(from_json).transpose()?,
+            df_bcs_name: o.df_bcs_name,
+            df_object_type: o.df_object_type,
+            df_object_id: o.df_object_id,
+            display: o.display.as_deref().map(from_json).transpose()?,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug)]
+#[diesel(table_name = packages)]
+pub(crate) struct PackageRow {
+    pub package_id: String,
+    pub version: i64,
+    pub author: String,
+    pub data: Vec<u8>,
+    pub original_package_id: Option<String>,
+    pub upgrade_cap_id: Option<String>,
+    pub transaction_digest: Option<String>,
+    pub checkpoint_sequence_number: Option<i64>,
+    pub modules: Option<String>,
+}
+
+impl TryFrom<&Package> for PackageRow {
+    type Error = IndexerError;
+
+    fn try_from(p: &Package) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package_id: p.package_id.clone(),
+            version: p.version,
+            author: p.author.clone(),
+            data: to_named_bcs(&p.data)?,
+            original_package_id: p.original_package_id.clone(),
+            upgrade_cap_id: p.upgrade_cap_id.clone(),
+            transaction_digest: p.transaction_digest.clone(),
+            checkpoint_sequence_number: p.checkpoint_sequence_number,
+            modules: p.modules.as_ref().map(to_json).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<PackageRow> for Package {
+    type Error = IndexerError;
+
+    fn try_from(p: PackageRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package_id: p.package_id,
+            version: p.version,
+            author: p.author,
+            data: from_named_bcs(&p.data)?,
+            original_package_id: p.original_package_id,
+            upgrade_cap_id: p.upgrade_cap_id,
+            transaction_digest: p.transaction_digest,
+            checkpoint_sequence_number: p.checkpoint_sequence_number,
+            modules: p.modules.as_deref().map(from_json).transpose()?,
+        })
+    }
+}
+
+/// The name of `owner_type` in the `owner_type` enum of the Postgres schema.
+pub(crate) fn owner_type_name(owner_type: &OwnerType) -> &'static str {
+    match owner_type {
+        OwnerType::AddressOwner => "address_owner",
+        OwnerType::ObjectOwner => "object_owner",
+        OwnerType::Shared => "shared",
+        OwnerType::Immutable => "immutable",
+    }
+}
+
+fn parse_owner_type(name: &str) -> Result<OwnerType, IndexerError> {
+    Ok(match name {
+        "address_owner" => OwnerType::AddressOwner,
+        "object_owner" => OwnerType::ObjectOwner,
+        "shared" => OwnerType::Shared,
+        "immutable" => OwnerType::Immutable,
+        _ => {
+            return Err(IndexerError::SerdeError(format!(
+                "Unknown owner type {name}"
+            )))
+        }
+    })
+}
+
+/// The name of `object_status` in the `object_status` enum of the Postgres schema.
+pub(crate) fn object_status_name(object_status: &ObjectStatus) -> &'static str {
+    match object_status {
+        ObjectStatus::Created => "created",
+        ObjectStatus::Mutated => "mutated",
+        ObjectStatus::Deleted => "deleted",
+        ObjectStatus::Wrapped => "wrapped",
+        ObjectStatus::Unwrapped => "unwrapped",
+        ObjectStatus::UnwrappedThenDeleted => "unwrapped_then_deleted",
+    }
+}
+
+fn parse_object_status(name: &str) -> Result<ObjectStatus, IndexerError> {
+    Ok(match name {
+        "created" => ObjectStatus::Created,
+        "mutated" => ObjectStatus::Mutated,
+        "deleted" => ObjectStatus::Deleted,
+        "wrapped" => ObjectStatus::Wrapped,
+        "unwrapped" => ObjectStatus::Unwrapped,
+        "unwrapped_then_deleted" => ObjectStatus::UnwrappedThenDeleted,
+        _ => {
+            return Err(IndexerError::SerdeError(format!(
+                "Unknown object status {name}"
+            )))
+        }
+    })
+}
+
+pub(crate) fn to_json<T: Serialize>(value: &T) -> Result<String, IndexerError> {
+    serde_json::to_string(value).map_err(|e| {
+        IndexerError::SerdeError(format!("Failed to serialize column with error {:?}", e))
+    })
+}
+
+fn from_json<T: DeserializeOwned>(value: &str) -> Result<T, IndexerError> {
+    serde_json::from_str(value).map_err(|e| {
+        IndexerError::SerdeError(format!(
+            "Failed to deserialize column {} with error {:?}",
+            value, e
+        ))
+    })
+}
+
+fn to_named_bcs(bytes: &[NamedBcsBytes]) -> Result<Vec<u8>, IndexerError> {
+    let pairs: Vec<(&String, &Vec<u8>)> = bytes.iter().map(|b| (&b.0, &b.1)).collect();
+    bcs::to_bytes(&pairs).map_err(|e| {
+        IndexerError::SerdeError(format!("Failed to serialize BCS bytes with error {:?}", e))
+    })
+}
+
+fn from_named_bcs(bytes: &[u8]) -> Result<Vec<NamedBcsBytes>, IndexerError> {
+    let pairs: Vec<(String, Vec<u8>)> = bcs::from_bytes(bytes).map_err(|e| {
+        IndexerError::SerdeError(format!(
+            "Failed to deserialize BCS bytes with error {:?}",
+            e
+        ))
+    })?;
+    Ok(pairs
+        .into_iter()
+        .map(|(name, data)| NamedBcsBytes(name, data))
+        .collect())
+}
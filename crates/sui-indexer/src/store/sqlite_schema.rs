@@ -0,0 +1,234 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Diesel tables of the SQLite store, created from `sqlite_schema.sql`.
+
+diesel::table! {
+    addresses (account_address) {
+        account_address -> Text,
+        first_appearance_tx -> Text,
+        first_appearance_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    checkpoints (sequence_number) {
+        sequence_number -> BigInt,
+        checkpoint_digest -> Text,
+        epoch -> BigInt,
+        transactions -> Text,
+        previous_checkpoint_digest -> Nullable<Text>,
+        next_epoch_committee -> Nullable<Text>,
+        next_epoch_protocol_version -> Nullable<BigInt>,
+        end_of_epoch_data -> Nullable<Text>,
+        total_gas_cost -> BigInt,
+        total_computation_cost -> BigInt,
+        total_storage_cost -> BigInt,
+        total_storage_rebate -> BigInt,
+        total_transactions -> BigInt,
+        total_transactions_current_epoch -> BigInt,
+        total_transactions_from_genesis -> BigInt,
+        timestamp_ms -> BigInt,
+        timestamp_ms_str -> Timestamp,
+        checkpoint_tps -> Float,
+    }
+}
+
+diesel::table! {
+    display (object_type) {
+        object_type -> Text,
+        id -> Text,
+        version -> Integer,
+        bcs -> Binary,
+        checkpoint -> BigInt,
+    }
+}
+
+diesel::table! {
+    display_renders (object_type) {
+        object_type -> Text,
+        checkpoint -> BigInt,
+        cursor -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    error_logs (id) {
+        id -> BigInt,
+        error_type -> Text,
+        error -> Text,
+        error_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    events (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        event_sequence -> BigInt,
+        sender -> Text,
+        package -> Text,
+        module -> Text,
+        event_type -> Text,
+        event_time_ms -> Nullable<BigInt>,
+        parsed_json -> Text,
+        event_bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    input_objects (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        checkpoint_sequence_number -> BigInt,
+        epoch -> BigInt,
+        object_id -> Text,
+        object_version -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    move_calls (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        checkpoint_sequence_number -> BigInt,
+        epoch -> BigInt,
+        sender -> Text,
+        move_package -> Text,
+        move_module -> Text,
+        move_function -> Text,
+    }
+}
+
+diesel::table! {
+    objects (object_id) {
+        epoch -> BigInt,
+        checkpoint -> BigInt,
+        object_id -> Text,
+        version -> BigInt,
+        object_digest -> Text,
+        owner_type -> Text,
+        owner_address -> Nullable<Text>,
+        initial_shared_version -> Nullable<BigInt>,
+        previous_transaction -> Text,
+        object_type -> Text,
+        object_status -> Text,
+        has_public_transfer -> Bool,
+        storage_rebate -> BigInt,
+        bcs -> Binary,
+        df_kind -> Nullable<Text>,
+        df_parent_id -> Nullable<Text>,
+        df_name_type -> Nullable<Text>,
+        df_name -> Nullable<Text>,
+        df_bcs_name -> Nullable<Binary>,
+        df_object_type -> Nullable<Text>,
+        df_object_id -> Nullable<Text>,
+        display -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    objects_history (object_id, version) {
+        epoch -> BigInt,
+        checkpoint -> BigInt,
+        object_id -> Text,
+        version -> BigInt,
+        object_digest -> Text,
+        owner_type -> Text,
+        owner_address -> Nullable<Text>,
+        initial_shared_version -> Nullable<BigInt>,
+        previous_transaction -> Text,
+        object_type -> Text,
+        object_status -> Text,
+        has_public_transfer -> Bool,
+        storage_rebate -> BigInt,
+        bcs -> Binary,
+        df_kind -> Nullable<Text>,
+        df_parent_id -> Nullable<Text>,
+        df_name_type -> Nullable<Text>,
+        df_name -> Nullable<Text>,
+        df_bcs_name -> Nullable<Binary>,
+        df_object_type -> Nullable<Text>,
+        df_object_id -> Nullable<Text>,
+        display -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    packages (package_id, version) {
+        package_id -> Text,
+        version -> BigInt,
+        author -> Text,
+        data -> Binary,
+        original_package_id -> Nullable<Text>,
+        upgrade_cap_id -> Nullable<Text>,
+        transaction_digest -> Nullable<Text>,
+        checkpoint_sequence_number -> Nullable<BigInt>,
+        modules -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    recipients (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        checkpoint_sequence_number -> BigInt,
+        epoch -> BigInt,
+        recipient -> Text,
+    }
+}
+
+diesel::table! {
+    sink_watermarks (sink_name) {
+        sink_name -> Text,
+        checkpoint_sequence_number -> BigInt,
+    }
+}
+
+diesel::table! {
+    transactions (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        sender -> Text,
+        recipients -> Text,
+        checkpoint_sequence_number -> BigInt,
+        timestamp_ms -> BigInt,
+        transaction_kind -> Text,
+        created -> Text,
+        mutated -> Text,
+        deleted -> Text,
+        unwrapped -> Text,
+        wrapped -> Text,
+        move_calls -> Text,
+        gas_object_id -> Text,
+        gas_object_sequence -> BigInt,
+        gas_object_digest -> Text,
+        gas_budget -> BigInt,
+        total_gas_cost -> BigInt,
+        computation_cost -> BigInt,
+        storage_cost -> BigInt,
+        storage_rebate -> BigInt,
+        gas_price -> BigInt,
+        raw_transaction -> Binary,
+        transaction_content -> Text,
+        transaction_effects_content -> Text,
+        confirmed_local_execution -> Nullable<Bool>,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    addresses,
+    checkpoints,
+    display,
+    display_renders,
+    error_logs,
+    events,
+    input_objects,
+    move_calls,
+    objects,
+    objects_history,
+    packages,
+    recipients,
+    sink_watermarks,
+    transactions,
+);
@@ -0,0 +1,256 @@
+-- Schema of the SQLite store, the subset of the Postgres schema needed to serve the read APIs.
+-- Postgres arrays and JSONB are stored as JSON text, and `bcs_bytes[]` as the BCS of the list of
+-- (name, bytes) pairs. Epochs, validators, stake events and activity aggregates are not stored.
+
+CREATE TABLE IF NOT EXISTS checkpoints
+(
+    sequence_number                  BIGINT PRIMARY KEY,
+    checkpoint_digest                TEXT      NOT NULL,
+    epoch                            BIGINT    NOT NULL,
+    transactions                     TEXT      NOT NULL,
+    previous_checkpoint_digest       TEXT,
+    next_epoch_committee             TEXT,
+    next_epoch_protocol_version      BIGINT,
+    end_of_epoch_data                TEXT,
+    total_gas_cost                   BIGINT    NOT NULL,
+    total_computation_cost           BIGINT    NOT NULL,
+    total_storage_cost               BIGINT    NOT NULL,
+    total_storage_rebate             BIGINT    NOT NULL,
+    total_transactions               BIGINT    NOT NULL,
+    total_transactions_current_epoch BIGINT    NOT NULL,
+    total_transactions_from_genesis  BIGINT    NOT NULL,
+    timestamp_ms                     BIGINT    NOT NULL,
+    timestamp_ms_str                 TIMESTAMP NOT NULL,
+    checkpoint_tps                   REAL      NOT NULL
+);
+CREATE INDEX IF NOT EXISTS checkpoints_digest ON checkpoints (checkpoint_digest);
+CREATE INDEX IF NOT EXISTS checkpoints_epoch ON checkpoints (epoch, sequence_number);
+
+CREATE TABLE IF NOT EXISTS transactions
+(
+    id                          INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest          TEXT    NOT NULL UNIQUE,
+    sender                      TEXT    NOT NULL,
+    recipients                  TEXT    NOT NULL,
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    transaction_kind            TEXT    NOT NULL,
+    created                     TEXT    NOT NULL,
+    mutated                     TEXT    NOT NULL,
+    deleted                     TEXT    NOT NULL,
+    unwrapped                   TEXT    NOT NULL,
+    wrapped                     TEXT    NOT NULL,
+    move_calls                  TEXT    NOT NULL,
+    gas_object_id               TEXT    NOT NULL,
+    gas_object_sequence         BIGINT  NOT NULL,
+    gas_object_digest           TEXT    NOT NULL,
+    gas_budget                  BIGINT  NOT NULL,
+    total_gas_cost              BIGINT  NOT NULL,
+    computation_cost            BIGINT  NOT NULL,
+    storage_cost                BIGINT  NOT NULL,
+    storage_rebate              BIGINT  NOT NULL,
+    gas_price                   BIGINT  NOT NULL,
+    raw_transaction             BLOB    NOT NULL,
+    transaction_content         TEXT    NOT NULL,
+    transaction_effects_content TEXT    NOT NULL,
+    confirmed_local_execution   BOOLEAN
+);
+CREATE INDEX IF NOT EXISTS transactions_sender ON transactions (sender);
+CREATE INDEX IF NOT EXISTS transactions_checkpoint_sequence_number ON transactions (checkpoint_sequence_number);
+
+CREATE TABLE IF NOT EXISTS events
+(
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest TEXT   NOT NULL,
+    event_sequence     BIGINT NOT NULL,
+    sender             TEXT   NOT NULL,
+    package            TEXT   NOT NULL,
+    module             TEXT   NOT NULL,
+    event_type         TEXT   NOT NULL,
+    event_time_ms      BIGINT,
+    parsed_json        TEXT   NOT NULL,
+    event_bcs          BLOB   NOT NULL,
+    UNIQUE (transaction_digest, event_sequence)
+);
+CREATE INDEX IF NOT EXISTS events_sender ON events (sender);
+CREATE INDEX IF NOT EXISTS events_package_module ON events (package, module);
+CREATE INDEX IF NOT EXISTS events_event_type ON events (event_type);
+CREATE INDEX IF NOT EXISTS events_event_time_ms ON events (event_time_ms);
+
+CREATE TABLE IF NOT EXISTS objects
+(
+    epoch                  BIGINT  NOT NULL,
+    checkpoint             BIGINT  NOT NULL,
+    object_id              TEXT PRIMARY KEY,
+    version                BIGINT  NOT NULL,
+    object_digest          TEXT    NOT NULL,
+    owner_type             TEXT    NOT NULL,
+    owner_address          TEXT,
+    initial_shared_version BIGINT,
+    previous_transaction   TEXT    NOT NULL,
+    object_type            TEXT    NOT NULL,
+    object_status          TEXT    NOT NULL,
+    has_public_transfer    BOOLEAN NOT NULL,
+    storage_rebate         BIGINT  NOT NULL,
+    bcs                    BLOB    NOT NULL,
+    df_kind                TEXT,
+    df_parent_id           TEXT,
+    df_name_type           TEXT,
+    df_name                TEXT,
+    df_bcs_name            BLOB,
+    df_object_type         TEXT,
+    df_object_id           TEXT,
+    display                TEXT
+);
+CREATE INDEX IF NOT EXISTS objects_owner_address ON objects (owner_address, object_type);
+CREATE INDEX IF NOT EXISTS objects_type ON objects (object_type, object_id);
+CREATE INDEX IF NOT EXISTS objects_df_parent ON objects (df_parent_id, object_id) WHERE df_parent_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS objects_history
+(
+    epoch                  BIGINT  NOT NULL,
+    checkpoint             BIGINT  NOT NULL,
+    object_id              TEXT    NOT NULL,
+    version                BIGINT  NOT NULL,
+    object_digest          TEXT    NOT NULL,
+    owner_type             TEXT    NOT NULL,
+    owner_address          TEXT,
+    initial_shared_version BIGINT,
+    previous_transaction   TEXT    NOT NULL,
+    object_type            TEXT    NOT NULL,
+    object_status          TEXT    NOT NULL,
+    has_public_transfer    BOOLEAN NOT NULL,
+    storage_rebate         BIGINT  NOT NULL,
+    bcs                    BLOB    NOT NULL,
+    df_kind                TEXT,
+    df_parent_id           TEXT,
+    df_name_type           TEXT,
+    df_name                TEXT,
+    df_bcs_name            BLOB,
+    df_object_type         TEXT,
+    df_object_id           TEXT,
+    display                TEXT,
+    PRIMARY KEY (object_id, version)
+);
+
+-- Same as the `objects_history` trigger of the Postgres schema, every written version of an object
+-- is kept in `objects_history`.
+CREATE TRIGGER IF NOT EXISTS objects_history_insert
+    AFTER INSERT
+    ON objects
+BEGIN
+    INSERT OR REPLACE INTO objects_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address, NEW.initial_shared_version, NEW.previous_transaction, NEW.object_type,
+            NEW.object_status, NEW.has_public_transfer, NEW.storage_rebate, NEW.bcs, NEW.df_kind,
+            NEW.df_parent_id, NEW.df_name_type, NEW.df_name, NEW.df_bcs_name, NEW.df_object_type,
+            NEW.df_object_id, NEW.display);
+END;
+
+-- Updates of the `display` column alone are not recorded, re-rendering does not create a version.
+CREATE TRIGGER IF NOT EXISTS objects_history_update
+    AFTER UPDATE OF epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address,
+    initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer,
+    storage_rebate, bcs, df_kind, df_parent_id, df_name_type, df_name, df_bcs_name, df_object_type,
+    df_object_id
+    ON objects
+BEGIN
+    INSERT OR REPLACE INTO objects_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address, NEW.initial_shared_version, NEW.previous_transaction, NEW.object_type,
+            NEW.object_status, NEW.has_public_transfer, NEW.storage_rebate, NEW.bcs, NEW.df_kind,
+            NEW.df_parent_id, NEW.df_name_type, NEW.df_name, NEW.df_bcs_name, NEW.df_object_type,
+            NEW.df_object_id, NEW.display);
+END;
+
+CREATE TABLE IF NOT EXISTS addresses
+(
+    account_address       TEXT PRIMARY KEY,
+    first_appearance_tx   TEXT   NOT NULL,
+    first_appearance_time BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS packages
+(
+    package_id                 TEXT   NOT NULL,
+    version                    BIGINT NOT NULL,
+    author                     TEXT   NOT NULL,
+    data                       BLOB   NOT NULL,
+    original_package_id        TEXT,
+    upgrade_cap_id             TEXT,
+    transaction_digest         TEXT,
+    checkpoint_sequence_number BIGINT,
+    modules                    TEXT,
+    PRIMARY KEY (package_id, version)
+);
+CREATE INDEX IF NOT EXISTS packages_original_package_id ON packages (original_package_id, version);
+
+CREATE TABLE IF NOT EXISTS display
+(
+    object_type TEXT PRIMARY KEY,
+    id          TEXT    NOT NULL,
+    version     INTEGER NOT NULL,
+    bcs         BLOB    NOT NULL,
+    checkpoint  BIGINT  NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS display_renders
+(
+    object_type TEXT PRIMARY KEY,
+    checkpoint  BIGINT NOT NULL,
+    cursor      TEXT
+);
+
+CREATE TABLE IF NOT EXISTS move_calls
+(
+    id                         INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest         TEXT   NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    epoch                      BIGINT NOT NULL,
+    sender                     TEXT   NOT NULL,
+    move_package               TEXT   NOT NULL,
+    move_module                TEXT   NOT NULL,
+    move_function              TEXT   NOT NULL
+);
+CREATE INDEX IF NOT EXISTS move_calls_transaction_digest ON move_calls (transaction_digest);
+CREATE INDEX IF NOT EXISTS move_calls_checkpoint_sequence_number ON move_calls (checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS move_calls_function ON move_calls (move_package, move_module, move_function);
+
+CREATE TABLE IF NOT EXISTS input_objects
+(
+    id                         INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest         TEXT   NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    epoch                      BIGINT NOT NULL,
+    object_id                  TEXT   NOT NULL,
+    object_version             BIGINT
+);
+CREATE INDEX IF NOT EXISTS input_objects_transaction_digest ON input_objects (transaction_digest);
+CREATE INDEX IF NOT EXISTS input_objects_checkpoint_sequence_number ON input_objects (checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS input_objects_object_id ON input_objects (object_id, object_version);
+
+CREATE TABLE IF NOT EXISTS recipients
+(
+    id                         INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest         TEXT   NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    epoch                      BIGINT NOT NULL,
+    recipient                  TEXT   NOT NULL
+);
+CREATE INDEX IF NOT EXISTS recipients_transaction_digest ON recipients (transaction_digest);
+CREATE INDEX IF NOT EXISTS recipients_checkpoint_sequence_number ON recipients (checkpoint_sequence_number);
+CREATE INDEX IF NOT EXISTS recipients_recipient ON recipients (recipient);
+
+CREATE TABLE IF NOT EXISTS sink_watermarks
+(
+    sink_name                  TEXT PRIMARY KEY,
+    checkpoint_sequence_number BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS error_logs
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    error_type TEXT      NOT NULL,
+    error      TEXT      NOT NULL,
+    error_time TIMESTAMP NOT NULL
+);
@@ -3,6 +3,9 @@
 
 use crate::errors::IndexerError;
 use crate::models::transaction_index::{InputObject, MoveCall, Recipient};
+use fastcrypto::encoding::Base64;
+use serde::Serialize;
+use serde_with::serde_as;
 use sui_json_rpc_types::{
     BalanceChange, ObjectChange, SuiCommand, SuiTransaction, SuiTransactionDataAPI,
     SuiTransactionEffects, SuiTransactionEffectsAPI, SuiTransactionEvents, SuiTransactionKind,
@@ -13,11 +16,14 @@ use sui_types::messages::{SenderSignedData, TransactionDataAPI};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 
-#[derive(Debug, Clone)]
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SuiTransactionFullResponse {
     pub digest: TransactionDigest,
     /// Transaction input data
     pub transaction: SuiTransaction,
+    #[serde_as(as = "Base64")]
     pub raw_transaction: Vec<u8>,
     pub effects: SuiTransactionEffects,
     pub events: SuiTransactionEvents,
@@ -0,0 +1,47 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module display_test::boars {
+    use std::string::{utf8, String};
+    use std::vector;
+    use sui::display::{Self, Display};
+    use sui::dynamic_field;
+    use sui::dynamic_object_field;
+    use sui::object::{Self, UID};
+    use sui::package;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// One-time witness of the module.
+    struct BOARS has drop {}
+
+    struct Boar has key, store {
+        id: UID,
+        name: String,
+    }
+
+    fun init(otw: BOARS, ctx: &mut TxContext) {
+        let publisher = package::claim(otw, ctx);
+        let fields = vector::singleton(utf8(b"name"));
+        let values = vector::singleton(utf8(b"{name}"));
+        let display = display::new_with_fields<Boar>(&publisher, fields, values, ctx);
+        display::update_version(&mut display);
+        transfer::transfer(publisher, tx_context::sender(ctx));
+        transfer::transfer(display, tx_context::sender(ctx));
+    }
+
+    /// Creates a boar with an `age` dynamic field and a `piglet` dynamic object field.
+    public entry fun create(name: vector<u8>, ctx: &mut TxContext) {
+        let boar = Boar { id: object::new(ctx), name: utf8(name) };
+        dynamic_field::add(&mut boar.id, b"age", 3u64);
+        let piglet = Boar { id: object::new(ctx), name: utf8(b"piglet") };
+        dynamic_object_field::add(&mut boar.id, b"piglet", piglet);
+        transfer::transfer(boar, tx_context::sender(ctx));
+    }
+
+    /// Publishes a new version of the display of boars.
+    public entry fun rename(display: &mut Display<Boar>) {
+        display::edit(display, utf8(b"name"), utf8(b"{name} the boar"));
+        display::update_version(display);
+    }
+}
@@ -5,28 +5,43 @@
 #[cfg(feature = "pg_integration")]
 mod pg_integration {
     use diesel::migration::MigrationSource;
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
     use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+    use futures::StreamExt;
     use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+    use jsonrpsee::ws_client::WsClientBuilder;
     use move_core_types::ident_str;
     use move_core_types::identifier::Identifier;
     use move_core_types::language_storage::StructTag;
     use prometheus::Registry;
+    use serde_json::json;
+    use std::collections::BTreeMap;
     use std::env;
+    use std::path::PathBuf;
     use std::str::FromStr;
+    use std::time::Duration;
     use sui_config::SUI_KEYSTORE_FILENAME;
+    use sui_framework_build::compiled_package::BuildConfig;
+    use sui_indexer::consistency_checker::ConsistencyChecker;
     use sui_indexer::errors::IndexerError;
-    use sui_indexer::store::{IndexerStore, PgIndexerStore};
-    use sui_indexer::{new_pg_connection_pool, Indexer, IndexerConfig, PgPoolConnection};
-    use sui_json_rpc::api::EventReadApiClient;
+    use sui_indexer::models::checkpoints::Checkpoint;
+    use sui_indexer::models::stake_events::StakeEvent;
+    use sui_indexer::schema::{addresses, objects_history, stake_events};
+    use sui_indexer::store::{IndexerStore, PgIndexerStore, TemporaryCheckpointStore};
+    use sui_indexer::{
+        new_pg_connection_pool, new_rpc_client, Indexer, IndexerConfig, PgPoolConnection,
+    };
+    use sui_json::SuiJsonValue;
+    use sui_json_rpc::api::{CoinReadApiClient, EventReadApiClient, ExtendedApiClient};
     use sui_json_rpc::api::{ReadApiClient, TransactionBuilderClient, WriteApiClient};
     use sui_json_rpc_types::{
         EventFilter, SuiMoveObject, SuiObjectDataOptions, SuiObjectResponse,
-        SuiObjectResponseQuery, SuiParsedMoveObject, SuiTransactionResponseOptions,
-        SuiTransactionResponseQuery, TransactionBytes,
+        SuiObjectResponseQuery, SuiObjectsQuery, SuiParsedMoveObject,
+        SuiTransactionResponseOptions, SuiTransactionResponseQuery, TransactionBytes,
     };
     use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-    use sui_types::base_types::ObjectID;
-    use sui_types::digests::TransactionDigest;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::{CheckpointDigest, TransactionDigest};
     use sui_types::gas_coin::GasCoin;
     use sui_types::messages::ExecuteTransactionRequestType;
     use sui_types::object::ObjectFormatOptions;
@@ -34,7 +49,10 @@ mod pg_integration {
     use sui_types::utils::to_sender_signed_transaction;
     use sui_types::SUI_FRAMEWORK_ADDRESS;
     use test_utils::network::{TestCluster, TestClusterBuilder};
-    use test_utils::transaction::{create_devnet_nft, delete_devnet_nft, transfer_coin};
+    use test_utils::transaction::{
+        create_devnet_nft, delete_devnet_nft, publish_package_with_wallet, submit_move_transaction,
+        transfer_coin,
+    };
 
     use tokio::task::JoinHandle;
     const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
@@ -302,6 +320,45 @@ mod pg_integration {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_event_subscription_replay() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let (_, _, digest_one) = create_devnet_nft(context).await.unwrap();
+        wait_until_transaction_synced(&store, digest_one.base58_encode().as_str()).await;
+        let (_, _, digest_two) = create_devnet_nft(context).await.unwrap();
+        wait_until_transaction_synced(&store, digest_two.base58_encode().as_str()).await;
+
+        let filter = EventFilter::MoveEventType(
+            StructTag::from_str("0x2::devnet_nft::MintNFTEvent").unwrap(),
+        );
+        let first_event = indexer_rpc_client
+            .query_events(filter.clone(), None, Some(1), None)
+            .await?
+            .data
+            .remove(0);
+        assert_eq!(first_event.id.tx_digest, digest_one);
+
+        let config = IndexerConfig::default();
+        let ws_client = WsClientBuilder::default()
+            .build(format!(
+                "ws://{}:{}",
+                config.rpc_server_url, config.rpc_server_port
+            ))
+            .await?;
+        let mut subscription = ws_client
+            .subscribe_event(filter, Some(first_event.id))
+            .await?;
+        // The events after the cursor are replayed, then the live ones follow
+        let replayed = subscription.next().await.unwrap()?;
+        assert_eq!(replayed.id.tx_digest, digest_two);
+        let (_, _, digest_three) = create_devnet_nft(context).await.unwrap();
+        let live = subscription.next().await.unwrap()?;
+        assert_eq!(live.id.tx_digest, digest_three);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_event_query_pagination_e2e() -> Result<(), anyhow::Error> {
         let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
@@ -399,17 +456,550 @@ mod pg_integration {
         drop(handle);
     }
 
+    #[tokio::test]
+    async fn test_coin_and_balance_query_e2e() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        // Wait for two checkpoints so that the genesis objects are indexed, then refresh the
+        // balances instead of waiting for the periodic refresh.
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_checkpoint(&store).await;
+        store.refresh_balances()?;
+        let address = test_cluster.get_address_0();
+        let fullnode_rpc_client = test_cluster.rpc_client();
+
+        let mut fullnode_coins = fullnode_rpc_client
+            .get_coins(address, None, None, None)
+            .await?
+            .data;
+        fullnode_coins.sort_by_key(|coin| coin.coin_object_id);
+        let indexer_coins = indexer_rpc_client
+            .get_coins(address, None, None, None)
+            .await?;
+        assert!(!indexer_coins.has_next_page);
+        assert_eq!(
+            fullnode_coins
+                .iter()
+                .map(|coin| (coin.coin_object_id, coin.balance))
+                .collect::<Vec<_>>(),
+            indexer_coins
+                .data
+                .iter()
+                .map(|coin| (coin.coin_object_id, coin.balance))
+                .collect::<Vec<_>>()
+        );
+
+        let first_page = indexer_rpc_client
+            .get_all_coins(address, None, Some(2))
+            .await?;
+        assert!(first_page.has_next_page);
+        assert_eq!(first_page.data.len(), 2);
+        let second_page = indexer_rpc_client
+            .get_all_coins(address, first_page.next_cursor, None)
+            .await?;
+        assert_eq!(second_page.data.len(), fullnode_coins.len() - 2);
+
+        let fullnode_balance = fullnode_rpc_client.get_balance(address, None).await?;
+        let indexer_balance = indexer_rpc_client.get_balance(address, None).await?;
+        assert_eq!(fullnode_balance.coin_type, indexer_balance.coin_type);
+        assert_eq!(
+            fullnode_balance.coin_object_count,
+            indexer_balance.coin_object_count
+        );
+        assert_eq!(
+            fullnode_balance.total_balance,
+            indexer_balance.total_balance
+        );
+
+        let indexer_balances = indexer_rpc_client.get_all_balances(address).await?;
+        assert_eq!(indexer_balances.len(), 1);
+        assert_eq!(
+            indexer_balances[0].total_balance,
+            fullnode_balance.total_balance
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_objects_by_type() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        let coins = test_cluster
+            .rpc_client()
+            .get_coins(test_cluster.get_address_0(), None, None, None)
+            .await?
+            .data;
+
+        let query = SuiObjectsQuery {
+            object_type: Some(GasCoin::type_().to_string()),
+            ..Default::default()
+        };
+        let mut object_ids = vec![];
+        let mut cursor = None;
+        loop {
+            let page = indexer_rpc_client
+                .query_objects(query.clone(), cursor, Some(2))
+                .await?;
+            assert!(page.data.len() <= 2);
+            for object in page.data {
+                object_ids.push(object.into_object()?.object_id);
+            }
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        assert!(object_ids.windows(2).all(|ids| ids[0] < ids[1]));
+        assert!(coins
+            .iter()
+            .all(|coin| object_ids.contains(&coin.coin_object_id)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_address_and_package_activity() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        let (_, sender, receiver, transfer_digest, _, _) =
+            transfer_coin(&mut test_cluster.wallet).await?;
+        wait_until_transaction_synced(&store, transfer_digest.base58_encode().as_str()).await;
+        let (nft_sender, _, nft_digest) = create_devnet_nft(&mut test_cluster.wallet).await?;
+        wait_until_transaction_synced(&store, nft_digest.base58_encode().as_str()).await;
+        assert_eq!(nft_sender, sender);
+
+        let sender_stats = indexer_rpc_client
+            .get_address_stats(sender)
+            .await?
+            .expect("Sender should have stats");
+        assert!(sender_stats.transaction_count >= 2);
+        assert!(sender_stats.sent_count >= 2);
+        assert!(sender_stats.first_activity_checkpoint <= sender_stats.last_activity_checkpoint);
+        let receiver_stats = indexer_rpc_client
+            .get_address_stats(receiver)
+            .await?
+            .expect("Receiver should have stats");
+        assert!(receiver_stats.received_count >= 1);
+
+        let counterparties = indexer_rpc_client
+            .get_address_counterparties(sender, None)
+            .await?;
+        assert!(counterparties
+            .iter()
+            .any(|counterparty| counterparty.counterparty == receiver
+                && counterparty.transaction_count >= 1));
+
+        let framework = ObjectID::from(SUI_FRAMEWORK_ADDRESS);
+        let top_packages = indexer_rpc_client.get_top_packages(None, None).await?;
+        assert!(top_packages
+            .windows(2)
+            .all(|packages| packages[0].call_count >= packages[1].call_count));
+        let framework_calls = top_packages
+            .iter()
+            .find(|package| package.package == framework)
+            .expect("Framework package should have been called")
+            .call_count;
+
+        // Backfilling the checkpoints replaces their contribution to the aggregates
+        let mut config = IndexerConfig::default();
+        config.rpc_client_url = test_cluster.rpc_url().to_string();
+        config.backfill_start = Some(0);
+        config.backfill_end = Some(store.get_latest_checkpoint_sequence_number()? as u64);
+        Indexer::backfill(&config, &Registry::default(), store.clone()).await?;
+
+        let backfilled_sender_stats = indexer_rpc_client.get_address_stats(sender).await?;
+        assert_eq!(backfilled_sender_stats, Some(sender_stats));
+        assert_eq!(
+            indexer_rpc_client
+                .get_address_counterparties(sender, None)
+                .await?,
+            counterparties
+        );
+        let backfilled_framework_calls = indexer_rpc_client
+            .get_top_packages(None, None)
+            .await?
+            .into_iter()
+            .find(|package| package.package == framework)
+            .map(|package| package.call_count);
+        assert_eq!(backfilled_framework_calls, Some(framework_calls));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_consistency_checker() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, _indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        let (_, _, _, digest, _, _) = transfer_coin(&mut test_cluster.wallet).await?;
+        wait_until_transaction_synced(&store, digest.base58_encode().as_str()).await;
+        let checkpoint = store
+            .get_transaction_by_digest(digest.base58_encode().as_str())?
+            .checkpoint_sequence_number;
+
+        let config = IndexerConfig::default();
+        let checker = ConsistencyChecker::new(
+            new_rpc_client(test_cluster.rpc_url()).await?,
+            new_rpc_client(&format!(
+                "http://{}:{}",
+                config.rpc_server_url, config.rpc_server_port
+            ))
+            .await?,
+            None,
+            &Registry::default(),
+        );
+        // The checkpoint has a sender whose balances are compared once the indexer caught up
+        assert_eq!(checker.check_checkpoint(checkpoint as u64).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_consistency_checker_stale_balances() -> Result<(), anyhow::Error> {
+        // The balances are only refreshed when the indexer starts
+        let mut config = IndexerConfig::default();
+        config.balances_refresh_interval_ms = 3_600_000;
+        let (mut test_cluster, _indexer_rpc_client, store, _handle) =
+            start_test_cluster_with_config(config.clone()).await;
+        wait_until_next_checkpoint(&store).await;
+        let (_, _, _, digest, _, _) = transfer_coin(&mut test_cluster.wallet).await?;
+        wait_until_transaction_synced(&store, digest.base58_encode().as_str()).await;
+        let checkpoint = store
+            .get_transaction_by_digest(digest.base58_encode().as_str())?
+            .checkpoint_sequence_number;
+
+        let registry = Registry::default();
+        let checker = ConsistencyChecker::new(
+            new_rpc_client(test_cluster.rpc_url()).await?,
+            new_rpc_client(&format!(
+                "http://{}:{}",
+                config.rpc_server_url, config.rpc_server_port
+            ))
+            .await?,
+            None,
+            &registry,
+        );
+        // The balances of the sender predate the transfer, they are not reported as a mismatch
+        assert_eq!(checker.check_checkpoint(checkpoint as u64).await?, 0);
+        assert_eq!(inconclusive_balance_checks(&registry), 1);
+
+        // Once refreshed, they are compared
+        store.refresh_balances()?;
+        assert_eq!(checker.check_checkpoint(checkpoint as u64).await?, 0);
+        assert_eq!(inconclusive_balance_checks(&registry), 1);
+        Ok(())
+    }
+
+    fn inconclusive_balance_checks(registry: &Registry) -> u64 {
+        registry
+            .gather()
+            .iter()
+            .filter(|family| family.get_name() == "consistency_inconclusive_checks")
+            .flat_map(|family| family.get_metric())
+            .filter(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_value() == "balance")
+            })
+            .map(|metric| metric.get_counter().get_value() as u64)
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_network_activity() -> Result<(), anyhow::Error> {
+        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_checkpoint(&store).await;
+
+        // A window wide enough to cover all indexed checkpoints
+        let tps = indexer_rpc_client
+            .get_network_tps(Some(u32::MAX as u64))
+            .await?;
+        let transaction_number = store.get_total_transaction_number()?;
+        assert!(tps.checkpoint_count >= 2);
+        assert!(tps.transaction_count > 0);
+        assert!(tps.transaction_count <= transaction_number as u64);
+        assert!(tps.window_start_ms < tps.window_end_ms);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_package_metadata() -> Result<(), anyhow::Error> {
+        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        let framework = ObjectID::from(SUI_FRAMEWORK_ADDRESS);
+
+        let versions = indexer_rpc_client.get_package_versions(framework).await?;
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].package_id, framework);
+        assert_eq!(versions[0].original_package_id, Some(framework));
+        assert_eq!(versions[0].checkpoint, Some(0));
+
+        let modules = indexer_rpc_client
+            .get_package_modules(framework)
+            .await?
+            .expect("Framework package should be indexed");
+        assert!(modules["coin"].exposed_functions.contains_key("value"));
+        assert!(indexer_rpc_client
+            .get_package_modules(ObjectID::random())
+            .await?
+            .is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backfill_checkpoint_range() -> Result<(), anyhow::Error> {
+        let (test_cluster, _indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_checkpoint(&store).await;
+        let latest = store.get_latest_checkpoint_sequence_number()? as u64;
+        let transaction_number = store.get_total_transaction_number()?;
+        let genesis_checkpoint = store.get_checkpoint(0u64.into())?;
+        let mut conn = new_pg_connection_pool(&db_url()).await?.get()?;
+        let genesis_history = count_objects_history(&mut conn, 0)?;
+        let addresses_before: Vec<(String, String)> = addresses::table
+            .select((addresses::account_address, addresses::first_appearance_tx))
+            .order(addresses::account_address)
+            .load(&mut conn)?;
+
+        let mut config = IndexerConfig::default();
+        config.rpc_client_url = test_cluster.rpc_url().to_string();
+        config.backfill_start = Some(0);
+        // Checkpoints past the latest indexed one are left to the live tail
+        config.backfill_end = Some(latest + 100);
+        config.backfill_workers = 2;
+        Indexer::backfill(&config, &Registry::default(), store.clone()).await?;
+
+        // Transactions of later checkpoints may have been indexed by the live tail meanwhile
+        assert!(store.get_total_transaction_number()? >= transaction_number);
+        let backfilled_genesis_checkpoint = store.get_checkpoint(0u64.into())?;
+        assert_eq!(
+            genesis_checkpoint.checkpoint_digest,
+            backfilled_genesis_checkpoint.checkpoint_digest
+        );
+        assert_eq!(
+            genesis_checkpoint.transactions,
+            backfilled_genesis_checkpoint.transactions
+        );
+        // The history of the backfilled checkpoints is replaced, not duplicated or dropped
+        assert_eq!(count_objects_history(&mut conn, 0)?, genesis_history);
+        // Addresses keep their first appearance
+        for (address, first_appearance_tx) in addresses_before {
+            let backfilled_first_appearance_tx: String = addresses::table
+                .select(addresses::first_appearance_tx)
+                .filter(addresses::account_address.eq(&address))
+                .first(&mut conn)?;
+            assert_eq!(backfilled_first_appearance_tx, first_appearance_tx);
+        }
+        Ok(())
+    }
+
+    fn count_objects_history(
+        conn: &mut PgPoolConnection,
+        checkpoint: i64,
+    ) -> Result<i64, anyhow::Error> {
+        Ok(objects_history::table
+            .filter(objects_history::checkpoint.eq(checkpoint))
+            .count()
+            .get_result(conn)?)
+    }
+
+    #[tokio::test]
+    async fn test_reindexing_checkpoint_keeps_stake_events_unique() -> Result<(), anyhow::Error> {
+        let (_test_cluster, _indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+
+        // A checkpoint far ahead of the live tail, persisted twice as after a crash between the
+        // stake events and the checkpoint commits.
+        let sequence_number = store.get_latest_checkpoint_sequence_number()? + 1_000_000;
+        let transaction_digest = TransactionDigest::random().base58_encode();
+        let stake_event = StakeEvent {
+            id: None,
+            transaction_digest: transaction_digest.clone(),
+            event_sequence: 0,
+            checkpoint: sequence_number,
+            epoch: 0,
+            pool_id: ObjectID::random().to_string(),
+            validator_address: SuiAddress::random_for_testing_only().to_string(),
+            staker_address: SuiAddress::random_for_testing_only().to_string(),
+            stake_activation_epoch: 1,
+            unstaking_epoch: None,
+            principal_amount: 1_000,
+            reward_amount: None,
+        };
+        for _ in 0..2 {
+            store.persist_checkpoint(&TemporaryCheckpointStore {
+                checkpoint: Checkpoint {
+                    sequence_number,
+                    checkpoint_digest: CheckpointDigest::random().base58_encode(),
+                    ..Default::default()
+                },
+                transactions: vec![],
+                events: vec![],
+                objects_changes: vec![],
+                addresses: vec![],
+                packages: vec![],
+                input_objects: vec![],
+                move_calls: vec![],
+                recipients: vec![],
+                stake_events: vec![stake_event.clone()],
+                displays: vec![],
+            })?;
+        }
+
+        let count: i64 = stake_events::table
+            .filter(stake_events::transaction_digest.eq(&transaction_digest))
+            .count()
+            .get_result(&mut new_pg_connection_pool(&db_url()).await?.get()?)?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json_checkpoint_sink() -> Result<(), anyhow::Error> {
+        let sink_dir = tempfile::tempdir()?;
+        let mut config = IndexerConfig::default();
+        config.json_sink_dir = Some(sink_dir.path().to_path_buf());
+        let (_test_cluster, _indexer_rpc_client, store, _handle) =
+            start_test_cluster_with_config(config).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+        while store.get_sink_watermark("json")?.is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        let genesis = std::fs::read_to_string(sink_dir.path().join("0.jsonl"))?;
+        let records = genesis
+            .lines()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(records[0]["kind"], "checkpoint");
+        assert_eq!(records[0]["sequenceNumber"], 0);
+
+        let checkpoint = store.get_checkpoint(0u64.into())?;
+        let transactions = records
+            .iter()
+            .filter(|record| record["kind"] == "transaction")
+            .count();
+        assert_eq!(transactions, checkpoint.transactions.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_display_and_dynamic_fields() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        let sender = test_cluster.get_address_0();
+        let package = BuildConfig::new_for_testing()
+            .build(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/display_test"))?;
+        let package_id = publish_package_with_wallet(
+            &test_cluster.wallet,
+            sender,
+            package.get_package_bytes(/* with_unpublished_deps */ false),
+            package.get_dependency_original_package_ids(),
+        )
+        .await
+        .0;
+        let tx_response = submit_move_transaction(
+            &test_cluster.wallet,
+            "boars",
+            "create",
+            package_id,
+            vec![SuiJsonValue::new(json!("Pumbaa"))?],
+            sender,
+            None,
+        )
+        .await;
+        wait_until_transaction_synced(&store, tx_response.digest.base58_encode().as_str()).await;
+
+        // The boar is rendered with the display published with the package
+        let boar_type = format!("{}::boars::Boar", package_id);
+        let query = SuiObjectsQuery {
+            object_type: Some(boar_type.clone()),
+            display: BTreeMap::from([("name".to_string(), "Pumbaa".to_string())]),
+            options: Some(SuiObjectDataOptions::new().with_display()),
+        };
+        let boars = indexer_rpc_client
+            .query_objects(query, None, None)
+            .await?
+            .data;
+        assert_eq!(boars.len(), 1);
+        let boar = boars[0].clone().into_object()?;
+        assert_eq!(
+            boar.display,
+            Some(BTreeMap::from([("name".to_string(), "Pumbaa".to_string())]))
+        );
+
+        // Both the `age` dynamic field and the `piglet` dynamic object field are indexed
+        let fields = indexer_rpc_client
+            .get_dynamic_fields(boar.object_id, None, None)
+            .await?
+            .data;
+        assert_eq!(fields.len(), 2);
+        for field in fields {
+            let field_object = indexer_rpc_client
+                .get_dynamic_field_object(boar.object_id, field.name.clone())
+                .await?
+                .into_object()?;
+            assert_eq!(field_object.object_id, field.object_id);
+        }
+
+        // Publishing a new version of the display re-renders the boar in the background
+        let display_query = SuiObjectsQuery {
+            object_type: Some(format!("0x2::display::Display<{}>", boar_type)),
+            ..Default::default()
+        };
+        let display_id = indexer_rpc_client
+            .query_objects(display_query, None, None)
+            .await?
+            .data[0]
+            .clone()
+            .into_object()?
+            .object_id;
+        submit_move_transaction(
+            &test_cluster.wallet,
+            "boars",
+            "rename",
+            package_id,
+            vec![SuiJsonValue::from_object_id(display_id)],
+            sender,
+            None,
+        )
+        .await;
+        let renamed = Some(BTreeMap::from([(
+            "name".to_string(),
+            "Pumbaa the boar".to_string(),
+        )]));
+        loop {
+            let boar = indexer_rpc_client
+                .get_object_with_options(
+                    boar.object_id,
+                    Some(SuiObjectDataOptions::new().with_display()),
+                )
+                .await?
+                .into_object()?;
+            if boar.display == renamed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        Ok(())
+    }
+
     async fn start_test_cluster() -> (
         TestCluster,
         HttpClient,
         PgIndexerStore,
         JoinHandle<Result<(), IndexerError>>,
     ) {
-        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
-        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32771".into());
-        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
-        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
-        let pg_connection_pool = new_pg_connection_pool(&db_url).await.unwrap();
+        start_test_cluster_with_config(IndexerConfig::default()).await
+    }
+
+    async fn start_test_cluster_with_config(
+        mut config: IndexerConfig,
+    ) -> (
+        TestCluster,
+        HttpClient,
+        PgIndexerStore,
+        JoinHandle<Result<(), IndexerError>>,
+    ) {
+        let pg_connection_pool = new_pg_connection_pool(&db_url()).await.unwrap();
 
         reset_database(&mut pg_connection_pool.get().unwrap());
 
@@ -419,7 +1009,6 @@ mod pg_integration {
         let store_clone = store.clone();
         let registry = Registry::default();
 
-        let mut config = IndexerConfig::default();
         config.rpc_client_url = test_cluster.rpc_url().to_string();
         let indexer_config = config.clone();
         let handle =
@@ -436,6 +1025,13 @@ mod pg_integration {
         (test_cluster, http_client, store, handle)
     }
 
+    fn db_url() -> String {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32771".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        format!("postgres://postgres:{pw}@{pg_host}:{pg_port}")
+    }
+
     async fn wait_until_next_checkpoint(store: &PgIndexerStore) {
         let mut cp = store.get_latest_checkpoint_sequence_number().unwrap();
         let target = cp + 1;
@@ -13,10 +13,12 @@ pub use sui_transaction::*;
 
 pub use balance_changes::*;
 pub use object_changes::*;
+pub use sui_activity::*;
 pub use sui_checkpoint::*;
 pub use sui_coin::*;
 pub use sui_governance::*;
 pub use sui_move::*;
+pub use sui_package::*;
 
 #[cfg(test)]
 #[path = "unit_tests/rpc_types_tests.rs"]
@@ -24,12 +26,14 @@ mod rpc_types_tests;
 
 mod balance_changes;
 mod object_changes;
+mod sui_activity;
 mod sui_checkpoint;
 mod sui_coin;
 mod sui_event;
 mod sui_governance;
 mod sui_move;
 mod sui_object;
+mod sui_package;
 mod sui_transaction;
 
 pub type DynamicFieldPage = Page<DynamicFieldInfo, ObjectID>;
@@ -0,0 +1,52 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Transactions sent and received by an address. A transaction is received by the owners of the
+/// objects it created, mutated or unwrapped, other than its sender.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressStats {
+    pub address: SuiAddress,
+    pub transaction_count: u64,
+    pub sent_count: u64,
+    pub received_count: u64,
+    pub first_activity_checkpoint: CheckpointSequenceNumber,
+    pub first_activity_timestamp_ms: u64,
+    pub last_activity_checkpoint: CheckpointSequenceNumber,
+    pub last_activity_timestamp_ms: u64,
+}
+
+/// An address that sent transactions to, or received transactions from, another address.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressCounterparty {
+    pub counterparty: SuiAddress,
+    pub transaction_count: u64,
+    pub last_activity_timestamp_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageCallCount {
+    pub package: ObjectID,
+    pub epoch: EpochId,
+    /// Number of Move calls to functions of the package in the epoch.
+    pub call_count: u64,
+}
+
+/// Transactions executed in a time window, by checkpoint timestamp.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkTps {
+    pub window_start_ms: u64,
+    pub window_end_ms: u64,
+    pub transaction_count: u64,
+    pub checkpoint_count: u64,
+    pub tps: f64,
+}
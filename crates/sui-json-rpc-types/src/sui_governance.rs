@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 
 use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SuiAddress};
 use sui_types::committee::{Committee, StakeUnit};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::Page;
 
 /// RPC representation of the [Committee] type.
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -55,3 +58,71 @@ pub struct Stake {
     #[serde(flatten)]
     pub status: StakeStatus,
 }
+
+pub type EpochPage = Page<EpochInfo, EpochId>;
+
+/// Summary of an epoch that has ended.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochInfo {
+    pub epoch: EpochId,
+    pub first_checkpoint_id: CheckpointSequenceNumber,
+    pub last_checkpoint_id: CheckpointSequenceNumber,
+    pub epoch_start_timestamp: u64,
+    pub epoch_end_timestamp: u64,
+    pub epoch_total_transactions: u64,
+    pub end_of_epoch_info: EndOfEpochInfo,
+    /// State of the validators at the end of the epoch.
+    pub validators: Vec<ValidatorEpochInfo>,
+}
+
+/// Rewards and storage fund changes computed when the epoch ended, and the parameters of the
+/// epoch that followed it.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EndOfEpochInfo {
+    pub next_epoch_protocol_version: u64,
+    pub next_epoch_reference_gas_price: u64,
+    /// Total stake of the validators for the next epoch.
+    pub total_stake: u64,
+    pub storage_fund_reinvestment: u64,
+    pub storage_charge: u64,
+    pub storage_rebate: u64,
+    pub storage_fund_balance: u64,
+    pub stake_subsidy_amount: u64,
+    pub total_gas_fees: u64,
+    pub total_stake_rewards_distributed: u64,
+    pub leftover_storage_fund_inflow: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorEpochInfo {
+    pub validator_address: SuiAddress,
+    pub reference_gas_survey_quote: u64,
+    pub stake: u64,
+    pub commission_rate: u64,
+    pub pool_staking_reward: u64,
+    pub storage_fund_staking_reward: u64,
+    /// The staking pool exchange rate is `exchange_rate_sui_amount / exchange_rate_pool_token_amount`
+    /// SUI per pool token.
+    pub exchange_rate_sui_amount: u64,
+    pub exchange_rate_pool_token_amount: u64,
+    pub tallying_rule_reporters: Vec<SuiAddress>,
+    pub tallying_rule_global_score: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorApys {
+    pub apys: Vec<ValidatorApy>,
+    /// The latest epoch the APYs were computed at.
+    pub epoch: EpochId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorApy {
+    pub address: SuiAddress,
+    pub apy: f64,
+}
@@ -890,6 +890,18 @@ pub struct SuiMovePackage {
 
 pub type ObjectsPage = Page<SuiObjectResponse, ObjectID>;
 
+/// Query of the live objects served by the indexer, objects must match all the set filters.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Default)]
+#[serde(rename_all = "camelCase", rename = "ObjectsQuery", default)]
+pub struct SuiObjectsQuery {
+    /// Only objects of this type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+    pub object_type: Option<String>,
+    /// Only objects whose rendered `Display` has all of these field values
+    pub display: BTreeMap<String, String>,
+    /// config which fields to include in the response, by default only digest is included
+    pub options: Option<SuiObjectDataOptions>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(rename = "GetPastObjectRequest", rename_all = "camelCase")]
 pub struct SuiGetPastObjectRequest {
@@ -0,0 +1,33 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// A published version of a package. All versions of a package share the ID of its first version
+/// as `originalPackageId`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiPackageVersion {
+    pub package_id: ObjectID,
+    pub original_package_id: Option<ObjectID>,
+    pub version: SequenceNumber,
+    pub publisher: SuiAddress,
+    /// The `UpgradeCap` controlling the upgrades of the package, none if it was destroyed or the
+    /// package cannot be upgraded.
+    pub upgrade_cap_id: Option<ObjectID>,
+    pub transaction_digest: Option<TransactionDigest>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// An address that called a Move function, with the number of its calls.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveFunctionCaller {
+    pub caller: SuiAddress,
+    pub call_count: u64,
+    pub last_call_checkpoint: CheckpointSequenceNumber,
+}
@@ -40,5 +40,7 @@ pub trait EventReadApi {
         &self,
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: EventFilter,
+        /// optional exclusive cursor, the matching events emitted after it are replayed before the live events. Only supported by the indexer.
+        cursor: Option<EventID>,
     );
 }
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+
+use sui_json_rpc_types::{
+    AddressCounterparty, AddressStats, EpochPage, MoveFunctionCaller, NetworkTps, ObjectsPage,
+    PackageCallCount, SuiMoveNormalizedModule, SuiObjectsQuery, SuiPackageVersion, ValidatorApys,
+};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::committee::EpochId;
+
+/// Historical queries that need data a fullnode does not keep, served by the indexer only.
+#[open_rpc(namespace = "sui", tag = "Extended API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait ExtendedApi {
+    /// Return a list of ended epochs, with their validators and end of epoch data.
+    #[method(name = "getEpochs")]
+    async fn get_epochs(
+        &self,
+        /// An optional paging cursor. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<EpochId>,
+        /// Maximum item returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest record first.
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage>;
+
+    /// Return the annual percentage yield of the stake of each validator, estimated from the
+    /// growth of its staking pool exchange rate over the latest epochs.
+    #[method(name = "getValidatorsApy")]
+    async fn get_validators_apy(&self) -> RpcResult<ValidatorApys>;
+
+    /// Return the live objects matching the query, in ascending object ID order. Objects can be
+    /// filtered by type and by the values of the fields rendered with the `Display` of their type.
+    #[method(name = "queryObjects")]
+    async fn query_objects(
+        &self,
+        /// the objects query criteria.
+        query: SuiObjectsQuery,
+        /// An optional paging cursor. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<ObjectID>,
+        /// Max number of items returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
+    /// Return the transaction counts and the first and last activity of an address, or null if
+    /// the address has not been involved in any transaction.
+    #[method(name = "getAddressStats")]
+    async fn get_address_stats(
+        &self,
+        /// the address to query the stats for.
+        address: SuiAddress,
+    ) -> RpcResult<Option<AddressStats>>;
+
+    /// Return the addresses an address has exchanged the most transactions with, most frequent first.
+    #[method(name = "getAddressCounterparties")]
+    async fn get_address_counterparties(
+        &self,
+        /// the address to query the counterparties of.
+        address: SuiAddress,
+        /// Maximum number of counterparties returned, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<AddressCounterparty>>;
+
+    /// Return the packages with the most Move calls in an epoch, most called first.
+    #[method(name = "getTopPackages")]
+    async fn get_top_packages(
+        &self,
+        /// the epoch to rank the packages in, default to the latest epoch if not specified.
+        epoch: Option<EpochId>,
+        /// Maximum number of packages returned, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageCallCount>>;
+
+    /// Return the average number of transactions per second over the latest time window.
+    #[method(name = "getNetworkTps")]
+    async fn get_network_tps(
+        &self,
+        /// the window length in milliseconds, default to 60 seconds if not specified.
+        window_ms: Option<u64>,
+    ) -> RpcResult<NetworkTps>;
+
+    /// Return all published versions of the package the given package is a version of, oldest first.
+    #[method(name = "getPackageVersions")]
+    async fn get_package_versions(
+        &self,
+        /// the ID of any version of the package.
+        package: ObjectID,
+    ) -> RpcResult<Vec<SuiPackageVersion>>;
+
+    /// Return the modules of a package with the normalized signatures of their structs and
+    /// functions, by module name, or null if the package does not exist.
+    #[method(name = "getPackageModules")]
+    async fn get_package_modules(
+        &self,
+        /// the ID of the package.
+        package: ObjectID,
+    ) -> RpcResult<Option<BTreeMap<String, SuiMoveNormalizedModule>>>;
+
+    /// Return the addresses that called a Move function, most frequent caller first.
+    #[method(name = "getFunctionCallers")]
+    async fn get_function_callers(
+        &self,
+        /// the ID of the package the function is defined in.
+        package: ObjectID,
+        /// the name of the module the function is defined in.
+        module: String,
+        /// the name of the function.
+        function: String,
+        /// Maximum number of callers returned, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionCaller>>;
+}
@@ -205,6 +205,13 @@ pub trait ReadApi {
     #[method(name = "getLatestCheckpointSequenceNumber")]
     async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber>;
 
+    /// Return the sequence number of the oldest checkpoint whose transactions and events can still be queried.
+    /// Index entries of older checkpoints have been pruned according to the node's index retention settings.
+    #[method(name = "getLowestIndexedCheckpointSequenceNumber")]
+    async fn get_lowest_indexed_checkpoint_sequence_number(
+        &self,
+    ) -> RpcResult<CheckpointSequenceNumber>;
+
     /// Return a checkpoint
     #[method(name = "getCheckpoint")]
     async fn get_checkpoint(
@@ -3,10 +3,12 @@
 
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::Stream;
 use jsonrpsee::core::error::SubscriptionClosed;
 use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 use serde::Serialize;
@@ -115,7 +117,19 @@ impl EventReadApiServer for EventReadApi {
         })
     }
 
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
+        if cursor.is_some() {
+            // The fullnode only streams live events, replaying requires the indexer.
+            sink.reject(CallError::InvalidParams(anyhow!(
+                "Event subscription cursors are not supported by the fullnode"
+            )))?;
+            return Ok(());
+        }
         spawn_subscription(sink, self.state.event_handler.subscribe(filter));
         Ok(())
     }
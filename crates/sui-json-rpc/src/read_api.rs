@@ -812,6 +812,15 @@ impl ReadApiServer for ReadApi {
             })?)
     }
 
+    async fn get_lowest_indexed_checkpoint_sequence_number(
+        &self,
+    ) -> RpcResult<CheckpointSequenceNumber> {
+        Ok(self
+            .state
+            .get_lowest_indexed_checkpoint_sequence_number()
+            .map_err(Error::SuiError)?)
+    }
+
     async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
         Ok(self.get_checkpoint_internal(id)?)
     }
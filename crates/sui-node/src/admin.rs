@@ -8,10 +8,15 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use fastcrypto::encoding::{Encoding, Hex};
 use mysten_metrics::spawn_monitored_task;
 use serde::Deserialize;
+use std::fmt::Write;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use sui_core::authority::AuthorityState;
+use sui_types::base_types::TransactionDigest;
 use sui_types::error::SuiError;
 use telemetry_subscribers::FilterHandle;
 use tracing::info;
@@ -30,11 +35,37 @@ use tracing::info;
 // Vote to close epoch 2 early
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/force-close-epoch?epoch=2'
+//
+// List the certificates pending in the recovery log of the current epoch, and the transactions
+// pending in the fullnode transaction log:
+//
+//   $ curl 'http://127.0.0.1:1337/wal'
+//
+// Show the recovery state of one certificate:
+//
+//   $ curl 'http://127.0.0.1:1337/wal?digest=<transaction digest>'
+//
+// Show the RocksDB properties of every table of the node, or of the tables of one store:
+//
+//   $ curl 'http://127.0.0.1:1337/table-stats'
+//   $ curl 'http://127.0.0.1:1337/table-stats?store=perpetual'
+//
+// Count the keys and bytes of one table (this scans the whole table):
+//
+//   $ curl 'http://127.0.0.1:1337/table-stats?store=perpetual&table=objects&summary=true'
+//
+// Compact one table, optionally only between two hex encoded raw keys:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/compact?store=indexes&table=transactions_seq'
+//   $ curl -X POST 'http://127.0.0.1:1337/compact?store=perpetual&table=objects&start=<hex>&end=<hex>'
 
 const LOGGING_ROUTE: &str = "/logging";
 const SET_BUFFER_STAKE_ROUTE: &str = "/set-override-buffer-stake";
 const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
+const WAL_ROUTE: &str = "/wal";
+const TABLE_STATS_ROUTE: &str = "/table-stats";
+const COMPACT_ROUTE: &str = "/compact";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -61,6 +92,9 @@ pub fn start_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: FilterHa
             post(clear_override_protocol_upgrade_buffer_stake),
         )
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
+        .route(WAL_ROUTE, get(get_wal))
+        .route(TABLE_STATS_ROUTE, get(get_table_stats))
+        .route(COMPACT_ROUTE, post(compact_table))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -170,3 +204,162 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+#[derive(Deserialize)]
+struct WalQuery {
+    digest: Option<TransactionDigest>,
+}
+
+async fn get_wal(
+    State(state): State<Arc<AppState>>,
+    query: Query<WalQuery>,
+) -> (StatusCode, String) {
+    let Query(WalQuery { digest }) = query;
+    let epoch_store = state.node.state().load_epoch_store_one_call_per_task();
+    let wal = epoch_store.wal();
+
+    let entries = match digest {
+        Some(digest) => wal
+            .get_entry(&digest)
+            .map(|entry| entry.into_iter().collect()),
+        None => wal.list_entries(),
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    };
+
+    let mut response = format!("recovery log of epoch {}:\n", epoch_store.epoch());
+    for entry in entries {
+        writeln!(
+            response,
+            "{} {:?} retry_count={}",
+            entry.digest, entry.state, entry.retry_count
+        )
+        .unwrap();
+    }
+
+    if let Some(orchestrator) = state.node.transaction_orchestrator() {
+        response.push_str("pending transactions:\n");
+        for tx in orchestrator.load_all_pending_transactions() {
+            if digest.map_or(true, |digest| digest == *tx.digest()) {
+                writeln!(response, "{}", tx.digest()).unwrap();
+            }
+        }
+    }
+
+    (StatusCode::OK, response)
+}
+
+#[derive(Deserialize)]
+struct TableStatsQuery {
+    store: Option<String>,
+    table: Option<String>,
+    #[serde(default)]
+    summary: bool,
+}
+
+async fn get_table_stats(
+    State(state): State<Arc<AppState>>,
+    query: Query<TableStatsQuery>,
+) -> (StatusCode, String) {
+    let Query(TableStatsQuery {
+        store,
+        table,
+        summary,
+    }) = query;
+    let authority = state.node.state();
+    let epoch_store = Arc::clone(&authority.load_epoch_store_one_call_per_task());
+
+    // Summaries scan whole tables, so the tables are read off the async runtime.
+    tokio::task::spawn_blocking(move || {
+        table_stats(&authority, &epoch_store, store, table, summary)
+    })
+    .await
+    .unwrap_or_else(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+fn table_stats(
+    authority: &AuthorityState,
+    epoch_store: &AuthorityPerEpochStore,
+    store: Option<String>,
+    table: Option<String>,
+    summary: bool,
+) -> (StatusCode, String) {
+    let mut response = String::new();
+    for (store_name, table_name, handle) in authority.raw_tables(epoch_store) {
+        if store.as_deref().map_or(false, |store| store != store_name)
+            || table.as_deref().map_or(false, |table| table != table_name)
+        {
+            continue;
+        }
+        writeln!(response, "{store_name}/{table_name}:").unwrap();
+        for (property, value) in handle.rocksdb_properties() {
+            writeln!(response, "  {property}: {value}").unwrap();
+        }
+        if summary {
+            match handle.summary() {
+                Ok(summary) => writeln!(
+                    response,
+                    "  num_keys: {}\n  key_bytes_total: {}\n  value_bytes_total: {}",
+                    summary.num_keys, summary.key_bytes_total, summary.value_bytes_total
+                )
+                .unwrap(),
+                Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+            }
+        }
+    }
+
+    if response.is_empty() {
+        return (StatusCode::NOT_FOUND, "no matching table\n".to_string());
+    }
+    (StatusCode::OK, response)
+}
+
+#[derive(Deserialize)]
+struct CompactQuery {
+    store: String,
+    table: String,
+    start: Option<String>,
+    end: Option<String>,
+}
+
+async fn compact_table(
+    State(state): State<Arc<AppState>>,
+    query: Query<CompactQuery>,
+) -> (StatusCode, String) {
+    let Query(CompactQuery {
+        store,
+        table,
+        start,
+        end,
+    }) = query;
+    let decode = |key: Option<String>| key.map(Hex::decode).transpose();
+    let (start, end) = match (decode(start), decode(end)) {
+        (Ok(start), Ok(end)) => (start, end),
+        (Err(err), _) | (_, Err(err)) => {
+            return (StatusCode::BAD_REQUEST, format!("invalid key: {err}\n"))
+        }
+    };
+
+    // Manual compactions block until they are done, which can take minutes on large tables.
+    let authority = state.node.state();
+    let result = tokio::task::spawn_blocking(move || {
+        let epoch_store = authority.load_epoch_store_one_call_per_task();
+        let tables = authority.raw_tables(&epoch_store);
+        let (_, _, handle) = tables
+            .iter()
+            .find(|(store_name, table_name, _)| *store_name == store && *table_name == table)?;
+        info!(%store, %table, "Compacting table");
+        handle.compact_range_raw(start.as_deref(), end.as_deref());
+        info!(%store, %table, "Compacted table");
+        Some(format!("{store}/{table} compacted\n"))
+    })
+    .await;
+
+    match result {
+        Ok(Some(response)) => (StatusCode::OK, response),
+        Ok(None) => (StatusCode::NOT_FOUND, "no matching table\n".to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
@@ -239,7 +239,10 @@ impl SuiNode {
         let index_store = if is_validator {
             None
         } else {
-            Some(Arc::new(IndexStore::new(config.db_path().join("indexes"))))
+            Some(Arc::new(IndexStore::new_with_config(
+                config.db_path().join("indexes"),
+                config.index_store_config.clone(),
+            )))
         };
 
         // Create network
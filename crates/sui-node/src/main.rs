@@ -5,10 +5,11 @@ use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
 use std::time::Duration;
+use sui_config::node::TelemetryConfig;
 use sui_config::{Config, NodeConfig};
 use sui_node::metrics;
 use sui_protocol_config::SupportedProtocolVersions;
-use sui_telemetry::send_telemetry_event;
+use sui_telemetry::{send_telemetry_event, telemetry_payload, TelemetryDestination};
 use sui_types::multiaddr::Multiaddr;
 use tokio::task;
 use tokio::time::sleep;
@@ -41,6 +42,28 @@ struct Args {
 
     #[clap(long, help = "Specify address to listen on")]
     listen_address: Option<Multiaddr>,
+
+    #[clap(
+        long,
+        help = "Print the telemetry payload the node would send and where it would go, then exit"
+    )]
+    print_telemetry: bool,
+}
+
+/// Where the telemetry events of the node go, if anywhere.
+fn telemetry_destination(config: Option<&TelemetryConfig>) -> Option<TelemetryDestination> {
+    let Some(config) = config else {
+        return Some(TelemetryDestination::Mysten);
+    };
+    if !config.enabled {
+        None
+    } else if config.log_only {
+        Some(TelemetryDestination::Log)
+    } else if let Some(url) = &config.url {
+        Some(TelemetryDestination::Url(url.clone()))
+    } else {
+        Some(TelemetryDestination::Mysten)
+    }
 }
 
 #[tokio::main]
@@ -57,6 +80,22 @@ async fn main() -> Result<()> {
     );
     config.supported_protocol_versions = Some(SupportedProtocolVersions::SYSTEM_DEFAULT);
 
+    let is_validator = config.consensus_config().is_some();
+    let telemetry_destination = telemetry_destination(config.telemetry.as_ref());
+    if args.print_telemetry {
+        match telemetry_destination {
+            Some(destination) => {
+                println!("Telemetry is sent every hour to: {destination}");
+                if destination == TelemetryDestination::Mysten {
+                    println!("The node_address is set to the public IP of the node when sent.");
+                }
+                println!("{}", telemetry_payload(is_validator, false).await.to_json());
+            }
+            None => println!("Telemetry is disabled, nothing is sent."),
+        }
+        return Ok(());
+    }
+
     let registry_service = metrics::start_prometheus_server(config.metrics_address);
     let prometheus_registry = registry_service.default_registry();
     prometheus_registry
@@ -86,13 +125,16 @@ async fn main() -> Result<()> {
         config.network_address = listen_address;
     }
 
-    let is_validator = config.consensus_config().is_some();
-    task::spawn(async move {
-        loop {
-            sleep(Duration::from_secs(3600)).await;
-            send_telemetry_event(is_validator).await;
-        }
-    });
+    if let Some(destination) = telemetry_destination {
+        task::spawn(async move {
+            loop {
+                sleep(Duration::from_secs(3600)).await;
+                send_telemetry_event(is_validator, &destination).await;
+            }
+        });
+    } else {
+        info!("Telemetry is disabled");
+    }
 
     let node = sui_node::SuiNode::start(&config, registry_service).await?;
     sui_node::admin::start_admin_server(node.clone(), config.admin_interface_port, filter_handle);
@@ -102,3 +144,41 @@ async fn main() -> Result<()> {
         tokio::time::sleep(Duration::from_secs(1000)).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn telemetry_destination_precedence() {
+        let url = "http://127.0.0.1:8080/telemetry".to_string();
+        let config = |enabled, log_only, url: Option<String>| TelemetryConfig {
+            enabled,
+            url,
+            log_only,
+        };
+
+        assert_eq!(
+            telemetry_destination(None),
+            Some(TelemetryDestination::Mysten)
+        );
+        assert_eq!(
+            telemetry_destination(Some(&config(true, false, None))),
+            Some(TelemetryDestination::Mysten)
+        );
+        assert_eq!(
+            telemetry_destination(Some(&config(true, false, Some(url.clone())))),
+            Some(TelemetryDestination::Url(url.clone()))
+        );
+        // log_only wins over a url
+        assert_eq!(
+            telemetry_destination(Some(&config(true, true, Some(url.clone())))),
+            Some(TelemetryDestination::Log)
+        );
+        // and disabled wins over everything
+        assert_eq!(
+            telemetry_destination(Some(&config(false, true, Some(url)))),
+            None
+        );
+    }
+}
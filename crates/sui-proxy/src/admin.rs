@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::config::{PeerValidationConfig, RemoteWriteConfig};
+use crate::config::{RemoteWriteConfig, TlsConfig};
 use crate::handlers::publish_metrics;
 use crate::middleware::{expect_mysten_proxy_header, expect_valid_public_key};
-use crate::peers::SuiNodeProvider;
+use crate::networks::NetworkRouter;
 use anyhow::Result;
 
 use axum::routing::post as axum_post;
@@ -84,20 +84,13 @@ pub fn make_reqwest_client(settings: RemoteWriteConfig) -> ReqwestClient {
 }
 
 /// App will configure our routes. This fn is also used to instrument our tests
-pub fn app(network: String, client: ReqwestClient, allower: Option<SuiNodeProvider>) -> Router {
+pub fn app(network_router: NetworkRouter) -> Router {
     // build our application with a route and our sender mpsc
-    let mut router = Router::new()
+    Router::new()
         .route("/publish/metrics", axum_post(publish_metrics))
-        .route_layer(middleware::from_fn(expect_mysten_proxy_header));
-
-    if let Some(allower) = allower {
-        router = router
-            .route_layer(middleware::from_fn(expect_valid_public_key))
-            .layer(Extension(Arc::new(allower)));
-    }
-    router
-        .layer(Extension(network))
-        .layer(Extension(client))
+        .route_layer(middleware::from_fn(expect_mysten_proxy_header))
+        .route_layer(middleware::from_fn(expect_valid_public_key))
+        .layer(Extension(Arc::new(network_router)))
         .layer(
             ServiceBuilder::new().layer(
                 TraceLayer::new_for_http().on_response(
@@ -190,19 +183,17 @@ pub fn create_server_cert_default_allow(
     )
 }
 
-/// Verify clients against sui blockchain, clients that are not found in sui_getValidators
-/// will be rejected
+/// Verify clients against the networks of the router, clients that are neither found in
+/// sui_getValidators nor allowlisted for one of the networks will be rejected
 pub fn create_server_cert_enforce_peer(
-    peer_config: PeerValidationConfig,
-) -> Result<(ServerConfig, Option<SuiNodeProvider>), sui_tls::rustls::Error> {
-    let (Some(certificate_path), Some(private_key_path)) = (peer_config.certificate_file, peer_config.private_key) else {
+    tls_config: TlsConfig,
+    network_router: NetworkRouter,
+) -> Result<ServerConfig, sui_tls::rustls::Error> {
+    let (Some(certificate_path), Some(private_key_path)) = (tls_config.certificate_file, tls_config.private_key) else {
         return Err(sui_tls::rustls::Error::General("missing certs to initialize server".into()));
     };
-    let allower = SuiNodeProvider::new(peer_config.url, peer_config.interval);
-    allower.poll_peer_list();
-    let c = CertVerifier::new(allower.clone()).rustls_server_config(
+    CertVerifier::new(network_router).rustls_server_config(
         load_certs(&certificate_path),
         load_private_key(&private_key_path),
-    )?;
-    Ok((c, Some(allower)))
+    )
 }
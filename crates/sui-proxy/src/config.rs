@@ -1,21 +1,137 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{Context, Result};
+use crate::peers::SuiPeer;
+use anyhow::{anyhow, bail, Context, Result};
 use core::time::Duration;
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use multiaddr::Multiaddr;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::{serde_as, DurationSeconds};
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use tracing::debug;
 
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case", try_from = "ProxyConfigFile")]
 pub struct ProxyConfig {
-    pub network: String,
     pub listen_address: SocketAddr,
+    pub metrics_address: SocketAddr,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    #[serde(default)]
+    pub remote_write_queue: RemoteWriteQueueConfig,
+    /// the networks we accept metrics for, a client is routed to the first network that knows
+    /// its public key
+    pub networks: Vec<NetworkConfig>,
+}
+
+/// ProxyConfigFile is what we read from disk.  Besides the current shape it accepts the single
+/// network configs written before we supported several networks, where network, remote-write
+/// and json-rpc were top level keys and json-rpc also held the tls settings
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ProxyConfigFile {
+    listen_address: SocketAddr,
+    metrics_address: SocketAddr,
+    tls: Option<TlsConfig>,
+    #[serde(default)]
+    remote_write_queue: RemoteWriteQueueConfig,
+    #[serde(default)]
+    networks: Vec<NetworkConfig>,
+    network: Option<String>,
+    remote_write: Option<RemoteWriteConfig>,
+    json_rpc: Option<LegacyPeerValidationConfig>,
+}
+
+#[derive(Deserialize)]
+struct LegacyPeerValidationConfig {
+    #[serde(flatten)]
+    peer_validation: PeerValidationConfig,
+    #[serde(flatten)]
+    tls: TlsConfig,
+}
+
+impl TryFrom<ProxyConfigFile> for ProxyConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(file: ProxyConfigFile) -> Result<Self> {
+        let mut networks = file.networks;
+        let mut tls = file.tls;
+        match (file.network, file.remote_write, file.json_rpc) {
+            (None, None, None) => {}
+            (Some(network), Some(remote_write), Some(json_rpc)) => {
+                if !networks.is_empty() {
+                    bail!("network, remote-write and json-rpc cannot be used along with networks");
+                }
+                networks.push(NetworkConfig {
+                    network,
+                    remote_write,
+                    json_rpc: json_rpc.peer_validation,
+                    labels: BTreeMap::new(),
+                    static_peers: vec![],
+                });
+                tls = tls.or(Some(json_rpc.tls));
+            }
+            _ => bail!("network, remote-write and json-rpc must be set together"),
+        }
+        if networks.is_empty() {
+            bail!("at least one network must be configured");
+        }
+        Ok(Self {
+            listen_address: file.listen_address,
+            metrics_address: file.metrics_address,
+            tls: tls.unwrap_or_default(),
+            remote_write_queue: file.remote_write_queue,
+            networks,
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TlsConfig {
+    /// if certificate_file and private_key are not provided, we'll create a self-signed
+    /// cert using this hostname
+    #[serde(default = "hostname_default")]
+    pub hostname: Option<String>,
+
+    /// incoming client connections to this proxy will be presented with this pub key
+    /// please use an aboslute path
+    pub certificate_file: Option<String>,
+    /// private key for tls
+    /// please use an absolute path
+    pub private_key: Option<String>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            hostname: hostname_default(),
+            certificate_file: None,
+            private_key: None,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkConfig {
+    /// the sui blockchain name, eg mainnet. it is added as the network label
+    pub network: String,
     pub remote_write: RemoteWriteConfig,
     pub json_rpc: PeerValidationConfig,
-    pub metrics_address: SocketAddr,
+    /// extra labels added to every metric pushed by the peers of this network
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// clients that are not validators, eg our fullnodes, allowed to push metrics
+    #[serde(default)]
+    pub static_peers: Vec<StaticPeerConfig>,
 }
 
 #[serde_as]
@@ -36,6 +152,40 @@ pub struct RemoteWriteConfig {
     pub pool_max_idle_per_host: usize,
 }
 
+/// remote_write requests are queued on disk, per network, and replayed in order so that short
+/// outages of the remote_write api don't drop metrics
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteWriteQueueConfig {
+    /// the queue of each network is kept in a sub directory named after the network
+    /// please use an absolute path
+    #[serde(default = "queue_directory_default")]
+    pub directory: PathBuf,
+    /// the maximum number of requests queued per network, requests are dropped once it is reached
+    #[serde(default = "queue_max_entries_default")]
+    pub max_entries: usize,
+    /// delay before the first retry of a failed request, doubled on each retry
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(default = "queue_initial_backoff_default")]
+    pub initial_backoff: Duration,
+    /// the maximum delay between retries
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(default = "queue_max_backoff_default")]
+    pub max_backoff: Duration,
+}
+
+impl Default for RemoteWriteQueueConfig {
+    fn default() -> Self {
+        Self {
+            directory: queue_directory_default(),
+            max_entries: queue_max_entries_default(),
+            initial_backoff: queue_initial_backoff_default(),
+            max_backoff: queue_max_backoff_default(),
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -44,17 +194,30 @@ pub struct PeerValidationConfig {
     pub url: String,
     #[serde_as(as = "DurationSeconds<u64>")]
     pub interval: Duration,
-    /// if certificate_file and private_key are not provided, we'll create a self-signed
-    /// cert using this hostname
-    #[serde(default = "hostname_default")]
-    pub hostname: Option<String>,
+}
 
-    /// incoming client connections to this proxy will be presented with this pub key
-    /// please use an aboslute path
-    pub certificate_file: Option<String>,
-    /// private key for tls
-    /// please use an absolute path
-    pub private_key: Option<String>,
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StaticPeerConfig {
+    /// the host label of the metrics pushed by this peer
+    pub name: String,
+    /// hex encoded ed25519 public key of the tls certificate the peer presents
+    pub public_key: String,
+}
+
+impl StaticPeerConfig {
+    pub fn sui_peer(&self) -> Result<SuiPeer> {
+        let bytes = Hex::decode(&self.public_key)
+            .map_err(|e| anyhow!("invalid public key for static peer {}: {e}", self.name))?;
+        let public_key = Ed25519PublicKey::from_bytes(&bytes)
+            .map_err(|e| anyhow!("invalid public key for static peer {}: {e}", self.name))?;
+        Ok(SuiPeer {
+            name: self.name.to_owned(),
+            p2p_address: Multiaddr::empty(),
+            public_key,
+        })
+    }
 }
 
 /// the default idle worker per host (reqwest to remote write url call)
@@ -67,6 +230,26 @@ fn hostname_default() -> Option<String> {
     Some("localhost".to_string())
 }
 
+/// the default directory of the remote write queues
+fn queue_directory_default() -> PathBuf {
+    PathBuf::from("./remote-write-queue")
+}
+
+/// the default number of queued remote write requests per network
+fn queue_max_entries_default() -> usize {
+    10_000
+}
+
+/// the default delay before retrying a remote write request
+fn queue_initial_backoff_default() -> Duration {
+    Duration::from_secs(1)
+}
+
+/// the default maximum delay between remote write retries
+fn queue_max_backoff_default() -> Duration {
+    Duration::from_secs(60)
+}
+
 /// the default remote write url
 fn remote_write_url() -> String {
     "http://metrics-gw.testnet.sui.io/api/v1/push".to_string()
@@ -88,6 +271,50 @@ mod tests {
     fn config_load() {
         const TEMPLATE: &str = include_str!("./data/config.yaml");
 
-        let _template: ProxyConfig = serde_yaml::from_str(TEMPLATE).unwrap();
+        let template: ProxyConfig = serde_yaml::from_str(TEMPLATE).unwrap();
+        assert_eq!(template.networks.len(), 2);
+        assert_eq!(
+            template.networks[0].labels.get("region"),
+            Some(&"us-east".to_string())
+        );
+        let peer = template.networks[0].static_peers[0].sui_peer().unwrap();
+        assert_eq!(peer.name, "joenet-fullnode-0");
+        assert!(template.networks[1].static_peers.is_empty());
+        assert_eq!(template.remote_write_queue.max_entries, 500);
+        assert_eq!(
+            template.remote_write_queue.max_backoff,
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn legacy_config_load() {
+        const TEMPLATE: &str = r#"
+network: joenet
+listen-address: 192.168.0.2:8080
+remote-write:
+  url: http://unittest.abcd.io/api/v1/push
+  username: foo
+  password: fooman
+json-rpc:
+  url: http://127.0.0.1:9000
+  interval: 30
+  certificate-file: /opt/joeman/fullchain.pem
+  private-key: /opt/joeman/privkey.pem
+metrics-address: 192.168.0.2:9184
+"#;
+
+        let template: ProxyConfig = serde_yaml::from_str(TEMPLATE).unwrap();
+        assert_eq!(template.networks.len(), 1);
+        assert_eq!(template.networks[0].network, "joenet");
+        assert_eq!(
+            template.networks[0].json_rpc.interval,
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            template.tls.certificate_file,
+            Some("/opt/joeman/fullchain.pem".to_string())
+        );
+        assert_eq!(template.tls.hostname, Some("localhost".to_string()));
     }
 }
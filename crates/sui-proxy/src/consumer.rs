@@ -3,6 +3,7 @@
 
 use crate::admin::ReqwestClient;
 use crate::prom_to_mimir::Mimir;
+use crate::queue::RemoteWriteQueue;
 use anyhow::Result;
 use axum::body::Bytes;
 use axum::http::StatusCode;
@@ -12,17 +13,19 @@ use multiaddr::Multiaddr;
 use prometheus::proto;
 use prost::Message;
 use protobuf::CodedInputStream;
+use std::collections::BTreeMap;
 use std::io::Read;
 use tracing::{debug, error};
 
 /// NodeMetric holds metadata and a metric payload from the calling node
 #[derive(Debug)]
 pub struct NodeMetric {
-    pub name: String,                 // the sui node name from the blockchain
-    pub network: String,              // the sui blockchain name, mainnet
-    pub peer_addr: Multiaddr,         // the sockaddr source address from the incoming request
-    pub public_key: Ed25519PublicKey, // the public key from the sui blockchain
-    pub data: Bytes,                  // raw post data from node
+    pub name: String,                     // the sui node name from the blockchain
+    pub network: String,                  // the sui blockchain name, mainnet
+    pub labels: BTreeMap<String, String>, // extra labels configured for the network
+    pub peer_addr: Multiaddr,             // the sockaddr source address from the incoming request
+    pub public_key: Ed25519PublicKey,     // the public key from the sui blockchain
+    pub data: Bytes,                      // raw post data from node
 }
 
 /// The ProtobufDecoder will decode message delimited protobuf messages from prom_model.proto types
@@ -53,7 +56,7 @@ impl ProtobufDecoder {
 }
 
 pub async fn convert_to_remote_write(
-    rc: ReqwestClient,
+    queue: &RemoteWriteQueue,
     nm: NodeMetric,
 ) -> (StatusCode, &'static str) {
     let mut decoder = ProtobufDecoder::new(nm.data.reader());
@@ -78,7 +81,13 @@ pub async fn convert_to_remote_write(
     host.set_name("host".into());
     host.set_value(nm.name);
 
-    let labels = vec![network, host];
+    let mut labels = vec![network, host];
+    for (name, value) in nm.labels {
+        let mut label = proto::LabelPair::default();
+        label.set_name(name);
+        label.set_value(value);
+        labels.push(label);
+    }
 
     // add our extra labels to our incoming metric data
     for mf in decoded.iter_mut() {
@@ -110,67 +119,83 @@ pub async fn convert_to_remote_write(
             }
         };
 
-        let response = match rc
-            .client
-            .post(rc.settings.url.to_owned())
-            .header(reqwest::header::CONTENT_ENCODING, "snappy")
-            .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
-            .header("X-Prometheus-Remote-Write-Version", "0.1.0")
-            .basic_auth(
-                rc.settings.username.to_owned(),
-                Some(rc.settings.password.to_owned()),
-            )
-            .body(compressed)
-            .send()
-            .await
-        {
-            Ok(response) => response,
-            Err(error) => {
-                error!("DROPPING METRICS due to post error: {error}");
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "DROPPING METRICS due to post error",
-                );
-            }
-        };
+        // the queue relays requests to the remote_write api in order, retrying while it is down
+        if let Err(error) = queue.push(compressed).await {
+            error!("DROPPING METRICS, unable to queue remote_write request; {error}");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "DROPPING METRICS, unable to queue remote_write request",
+            );
+        }
+    }
+    (StatusCode::CREATED, "created")
+}
 
-        match response.status() {
-            reqwest::StatusCode::OK => {
-                debug!("({}) SUCCESS: {:?}", reqwest::StatusCode::OK, timeseries);
-            }
-            reqwest::StatusCode::BAD_REQUEST => {
-                error!("TRIED: {:?}", timeseries);
-                let body = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "response body cannot be decoded".into());
-
-                if body.contains("err-mimir-sample-out-of-order") {
-                    error!("({}) ERROR: {:?}", reqwest::StatusCode::BAD_REQUEST, body);
-                    return (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "IGNORNING METRICS due to err-mimir-sample-out-of-order",
-                    );
-                }
-                error!("({}) ERROR: {:?}", reqwest::StatusCode::BAD_REQUEST, body);
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "unknown bad request error encountered in remote_push",
-                );
+/// RemoteWriteError tells the queue whether a failed remote_write request is worth retrying
+#[derive(Debug)]
+pub enum RemoteWriteError {
+    /// the remote_write api is unreachable or temporarily failing, try again later
+    Retryable(String),
+    /// the remote_write api will never accept this request
+    Rejected(String),
+}
+
+/// post_remote_write sends a snappy compressed remote_write request to the upstream tsdb
+pub async fn post_remote_write(
+    rc: &ReqwestClient,
+    compressed: Vec<u8>,
+) -> Result<(), RemoteWriteError> {
+    let response = match rc
+        .client
+        .post(rc.settings.url.to_owned())
+        .header(reqwest::header::CONTENT_ENCODING, "snappy")
+        .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
+        .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+        .basic_auth(
+            rc.settings.username.to_owned(),
+            Some(rc.settings.password.to_owned()),
+        )
+        .body(compressed)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(error) => {
+            return Err(RemoteWriteError::Retryable(format!("post error: {error}")));
+        }
+    };
+
+    match response.status() {
+        code if code.is_success() => {
+            debug!("({}) SUCCESS", code);
+            Ok(())
+        }
+        reqwest::StatusCode::BAD_REQUEST => {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "response body cannot be decoded".into());
+
+            if body.contains("err-mimir-sample-out-of-order") {
+                return Err(RemoteWriteError::Rejected(format!(
+                    "err-mimir-sample-out-of-order: {body:?}"
+                )));
             }
-            code => {
-                error!("TRIED: {:?}", timeseries);
-                let body = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "response body cannot be decoded".into());
-                error!("({}) ERROR: {:?}", code, body);
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "unknown error encountered in remote_push",
-                );
+            Err(RemoteWriteError::Rejected(format!(
+                "({}) {body:?}",
+                reqwest::StatusCode::BAD_REQUEST
+            )))
+        }
+        code => {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "response body cannot be decoded".into());
+            if code.is_server_error() || code == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                Err(RemoteWriteError::Retryable(format!("({code}) {body:?}")))
+            } else {
+                Err(RemoteWriteError::Rejected(format!("({code}) {body:?}")))
             }
         }
     }
-    (StatusCode::CREATED, "created")
 }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::admin::ReqwestClient;
 use crate::consumer::{convert_to_remote_write, NodeMetric};
+use crate::networks::NetworkRoute;
 use crate::peers::SuiPeer;
 use axum::{
     body::Body,
@@ -12,13 +12,12 @@ use multiaddr::Multiaddr;
 use std::net::SocketAddr;
 
 /// Publish handler which receives metrics from nodes.  Nodes will call us at this endpoint
-/// and we relay them to the upstream tsdb
+/// and we relay them to the upstream tsdb of the network the node belongs to
 ///
 /// An mpsc is used within this handler so that we can immediately return an accept to calling nodes.
 /// Downstream processing failures may still result in metrics being dropped.
 pub async fn publish_metrics(
-    Extension(network): Extension<String>,
-    Extension(client): Extension<ReqwestClient>,
+    Extension(route): Extension<NetworkRoute>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(peer): Extension<SuiPeer>,
     request: Request<Body>,
@@ -31,10 +30,11 @@ pub async fn publish_metrics(
     };
 
     convert_to_remote_write(
-        client.clone(),
+        &route.queue,
         NodeMetric {
             name: peer.name,
-            network,
+            network: route.network,
+            labels: route.labels,
             data,
             peer_addr: Multiaddr::from(addr.ip()),
             public_key: peer.public_key,
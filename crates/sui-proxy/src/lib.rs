@@ -6,8 +6,10 @@ pub mod consumer;
 pub mod handlers;
 pub mod metrics;
 pub mod middleware;
+pub mod networks;
 pub mod peers;
 pub mod prom_to_mimir;
+pub mod queue;
 pub mod remote_write;
 
 /// var extracts environment variables at runtime with a default fallback value
@@ -35,6 +37,9 @@ mod tests {
     use super::*;
     use crate::prom_to_mimir::tests::*;
 
+    use crate::config::RemoteWriteQueueConfig;
+    use crate::networks::{NetworkRoute, NetworkRouter};
+    use crate::queue::{QueueMetrics, RemoteWriteQueue};
     use crate::{admin::CertKeyPair, config::RemoteWriteConfig, peers::SuiNodeProvider};
     use axum::http::{header, StatusCode};
     use axum::routing::post;
@@ -43,6 +48,7 @@ mod tests {
     use prometheus::Encoder;
     use prometheus::PROTOBUF_FORMAT;
     use protobuf::RepeatedField;
+    use std::collections::BTreeMap;
     use std::net::TcpListener;
     use std::time::Duration;
     use sui_tls::{CertVerifier, TlsAcceptor, TlsConnectionInfo};
@@ -87,25 +93,46 @@ mod tests {
 
         // init the tls config and allower
         let mut allower = SuiNodeProvider::new("".into(), Duration::from_secs(30));
-        let tls_config = CertVerifier::new(allower.clone())
-            .rustls_server_config(
-                vec![server_priv_cert.rustls_certificate()],
-                server_priv_cert.rustls_private_key(),
-            )
-            .unwrap();
-
         let client = admin::make_reqwest_client(RemoteWriteConfig {
             url: dummy_remote_write_url.to_owned(),
             username: "bar".into(),
             password: "foo".into(),
             ..Default::default()
         });
+        let queue_dir = tempfile::tempdir().unwrap();
+        let queue_config = RemoteWriteQueueConfig {
+            directory: queue_dir.path().to_owned(),
+            ..Default::default()
+        };
+        let queue = RemoteWriteQueue::open(
+            "unittest-network",
+            &queue_config,
+            QueueMetrics::new(&prometheus::Registry::new()),
+        )
+        .unwrap();
+        queue.replay(
+            client,
+            queue_config.initial_backoff,
+            queue_config.max_backoff,
+        );
+        let network_router = NetworkRouter::new(vec![NetworkRoute {
+            network: "unittest-network".into(),
+            queue,
+            labels: BTreeMap::from([("region".into(), "unittest".into())]),
+            allower: allower.clone(),
+        }]);
+        let tls_config = CertVerifier::new(network_router.clone())
+            .rustls_server_config(
+                vec![server_priv_cert.rustls_certificate()],
+                server_priv_cert.rustls_private_key(),
+            )
+            .unwrap();
 
         // add handler to server
         async fn handler(tls_info: axum::Extension<TlsConnectionInfo>) -> String {
             tls_info.public_key().unwrap().to_string()
         }
-        let app = admin::app("unittest-network".into(), client, Some(allower.clone()));
+        let app = admin::app(network_router);
 
         let listener = std::net::TcpListener::bind("localhost:0").unwrap();
         let server_address = listener.local_addr().unwrap();
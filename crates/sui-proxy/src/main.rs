@@ -1,16 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use sui_proxy::config::ProxyConfig;
 use sui_proxy::{
-    admin::{
-        app, create_server_cert_default_allow, create_server_cert_enforce_peer,
-        make_reqwest_client, server,
-    },
+    admin::{app, create_server_cert_default_allow, create_server_cert_enforce_peer, server},
     config::load,
     metrics,
+    networks::{NetworkRoute, NetworkRouter},
+    queue::QueueMetrics,
 };
 use sui_tls::TlsAcceptor;
 use telemetry_subscribers::TelemetryConfig;
@@ -38,34 +37,46 @@ async fn main() -> Result<()> {
 
     let config: ProxyConfig = load(args.config)?;
 
-    info!(
-        "listen on {:?} send to {:?}",
-        config.listen_address, config.remote_write.url
-    );
+    for network in config.networks.iter() {
+        info!(
+            "listen on {:?} send {} to {:?}",
+            config.listen_address, network.network, network.remote_write.url
+        );
+    }
 
     let listener = std::net::TcpListener::bind(config.listen_address).unwrap();
 
-    let (tls_config, allower) =
-        if config.json_rpc.certificate_file.is_none() || config.json_rpc.private_key.is_none() {
-            (
-                create_server_cert_default_allow(config.json_rpc.hostname.unwrap())
-                    .expect("unable to create self-signed server cert"),
-                None,
-            )
-        } else {
-            create_server_cert_enforce_peer(config.json_rpc)
-                .expect("unable to create tls server config")
-        };
-    let acceptor = TlsAcceptor::new(tls_config);
-    let client = make_reqwest_client(config.remote_write);
-    let app = app(config.network, client, allower);
-
     let registry_service = metrics::start_prometheus_server(config.metrics_address);
     let prometheus_registry = registry_service.default_registry();
     prometheus_registry
         .register(mysten_metrics::uptime_metric(VERSION))
         .unwrap();
 
+    let queue_metrics = QueueMetrics::new(&prometheus_registry);
+    let routes = config
+        .networks
+        .into_iter()
+        .map(|network| {
+            NetworkRoute::new(network, &config.remote_write_queue, queue_metrics.clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut network_router = NetworkRouter::new(routes);
+    network_router.poll_peer_lists();
+
+    let tls_config = if config.tls.certificate_file.is_none() || config.tls.private_key.is_none() {
+        // without certs we don't verify clients, they are all accepted by the only network
+        network_router = network_router.allow_unknown().context(
+            "tls certificate-file and private-key are required to serve more than one network",
+        )?;
+        create_server_cert_default_allow(config.tls.hostname.unwrap())
+            .expect("unable to create self-signed server cert")
+    } else {
+        create_server_cert_enforce_peer(config.tls, network_router.clone())
+            .expect("unable to create tls server config")
+    };
+    let acceptor = TlsAcceptor::new(tls_config);
+    let app = app(network_router);
+
     server(listener, app, Some(acceptor)).await.unwrap();
 
     Ok(())
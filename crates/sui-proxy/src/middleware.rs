@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::peers::SuiNodeProvider;
+use crate::networks::NetworkRouter;
 use axum::{
     extract::Extension,
     headers::ContentType,
@@ -28,19 +28,20 @@ pub async fn expect_mysten_proxy_header<B>(
     }
 }
 
-/// we expect that calling sui-nodes are known on the blockchain and we enforce
-/// their pub key tls creds here
+/// we expect that calling sui-nodes are known on the blockchain of one of our networks, or
+/// allowlisted for it, and we enforce their pub key tls creds here
 pub async fn expect_valid_public_key<B>(
-    Extension(allower): Extension<Arc<SuiNodeProvider>>,
+    Extension(router): Extension<Arc<NetworkRouter>>,
     Extension(tls_connect_info): Extension<TlsConnectionInfo>,
     mut request: Request<B>,
     next: Next<B>,
 ) -> Result<Response, (StatusCode, &'static str)> {
-    let Some(peer) = allower.get(tls_connect_info.public_key().unwrap()) else {
+    let Some((route, peer)) = tls_connect_info.public_key().and_then(|key| router.route(key)) else {
         error!("node with unknown pub key tried to connect");
         return Err((StatusCode::FORBIDDEN, "unknown clients are not allowed"));
     };
 
     request.extensions_mut().insert(peer);
+    request.extensions_mut().insert(route);
     Ok(next.run(request).await)
 }
@@ -0,0 +1,194 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::admin::make_reqwest_client;
+use crate::config::{NetworkConfig, RemoteWriteQueueConfig};
+use crate::peers::{SuiNodeProvider, SuiPeer};
+use crate::queue::{QueueMetrics, RemoteWriteQueue};
+use anyhow::{bail, Result};
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use multiaddr::Multiaddr;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use sui_tls::Allower;
+
+/// NetworkRoute holds what we need to relay the metrics pushed by the peers of one network
+#[derive(Clone)]
+pub struct NetworkRoute {
+    pub network: String,
+    pub queue: RemoteWriteQueue,
+    pub labels: BTreeMap<String, String>,
+    pub allower: SuiNodeProvider,
+}
+
+impl NetworkRoute {
+    /// new opens the remote_write queue of the network and starts replaying it
+    pub fn new(
+        config: NetworkConfig,
+        queue_config: &RemoteWriteQueueConfig,
+        queue_metrics: QueueMetrics,
+    ) -> Result<Self> {
+        let static_peers = config
+            .static_peers
+            .iter()
+            .map(|peer| peer.sui_peer())
+            .collect::<Result<Vec<_>>>()?;
+        let allower = SuiNodeProvider::new(config.json_rpc.url, config.json_rpc.interval)
+            .with_static_peers(static_peers);
+        let queue = RemoteWriteQueue::open(&config.network, queue_config, queue_metrics)?;
+        queue.replay(
+            make_reqwest_client(config.remote_write),
+            queue_config.initial_backoff,
+            queue_config.max_backoff,
+        );
+        Ok(Self {
+            network: config.network,
+            queue,
+            labels: config.labels,
+            allower,
+        })
+    }
+}
+
+/// NetworkRouter picks the network of a client by its tls public key.  A client is routed to the
+/// first network whose peer list holds its key.  We pass this struct to the tls verifier so clients
+/// unknown to every network are rejected during the handshake.
+#[derive(Clone)]
+pub struct NetworkRouter {
+    routes: Arc<Vec<NetworkRoute>>,
+    allow_unknown: bool,
+}
+
+impl Allower for NetworkRouter {
+    fn allowed(&self, key: &Ed25519PublicKey) -> bool {
+        self.routes.iter().any(|route| route.allower.allowed(key))
+    }
+}
+
+impl NetworkRouter {
+    pub fn new(routes: Vec<NetworkRoute>) -> Self {
+        Self {
+            routes: Arc::new(routes),
+            allow_unknown: false,
+        }
+    }
+
+    /// allow_unknown routes clients that no network knows to the only network we have, named
+    /// after their key.  This is the open mode used with a self-signed cert, where we can't pick
+    /// a network for unknown clients if there is more than one.
+    pub fn allow_unknown(self) -> Result<Self> {
+        if self.routes.len() != 1 {
+            bail!(
+                "unknown clients can only be allowed with a single network, {} are configured",
+                self.routes.len()
+            );
+        }
+        Ok(Self {
+            allow_unknown: true,
+            ..self
+        })
+    }
+
+    /// route returns the network of the client with this key, along with its peer info
+    pub fn route(&self, key: &Ed25519PublicKey) -> Option<(NetworkRoute, SuiPeer)> {
+        self.routes
+            .iter()
+            .find_map(|route| route.allower.get(key).map(|peer| (route.to_owned(), peer)))
+            .or_else(|| {
+                let route = self.routes.first().filter(|_| self.allow_unknown)?;
+                let peer = SuiPeer {
+                    name: Hex::encode(key.as_bytes()),
+                    p2p_address: Multiaddr::empty(),
+                    public_key: key.to_owned(),
+                };
+                Some((route.to_owned(), peer))
+            })
+    }
+
+    /// poll_peer_lists starts refreshing the validators of every network
+    pub fn poll_peer_lists(&self) {
+        for route in self.routes.iter() {
+            route.allower.poll_peer_list();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::{generate_self_cert, CertKeyPair};
+    use prometheus::Registry;
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn route(dir: &Path, network: &str, peer: &str, public_key: &Ed25519PublicKey) -> NetworkRoute {
+        let queue_config = RemoteWriteQueueConfig {
+            directory: dir.to_owned(),
+            ..Default::default()
+        };
+        NetworkRoute {
+            network: network.into(),
+            queue: RemoteWriteQueue::open(
+                network,
+                &queue_config,
+                QueueMetrics::new(&Registry::new()),
+            )
+            .unwrap(),
+            labels: BTreeMap::new(),
+            allower: SuiNodeProvider::new("".into(), Duration::from_secs(30)).with_static_peers(
+                vec![SuiPeer {
+                    name: peer.into(),
+                    p2p_address: Multiaddr::empty(),
+                    public_key: public_key.to_owned(),
+                }],
+            ),
+        }
+    }
+
+    #[test]
+    fn route_by_public_key() {
+        let CertKeyPair(_, joenet_key) = generate_self_cert("sui".into());
+        let CertKeyPair(_, bobnet_key) = generate_self_cert("sui".into());
+        let CertKeyPair(_, unknown_key) = generate_self_cert("sui".into());
+
+        let dir = tempfile::tempdir().unwrap();
+        let router = NetworkRouter::new(vec![
+            route(dir.path(), "joenet", "joenet-fullnode", &joenet_key),
+            route(dir.path(), "bobnet", "bobnet-fullnode", &bobnet_key),
+        ]);
+
+        let (route, peer) = router.route(&bobnet_key).unwrap();
+        assert_eq!(route.network, "bobnet");
+        assert_eq!(peer.name, "bobnet-fullnode");
+        assert!(router.allowed(&joenet_key));
+
+        assert!(router.route(&unknown_key).is_none());
+        assert!(!router.allowed(&unknown_key));
+        assert!(router.allow_unknown().is_err());
+    }
+
+    #[test]
+    fn route_unknown_to_single_network() {
+        let CertKeyPair(_, joenet_key) = generate_self_cert("sui".into());
+        let CertKeyPair(_, unknown_key) = generate_self_cert("sui".into());
+
+        let dir = tempfile::tempdir().unwrap();
+        let router = NetworkRouter::new(vec![route(
+            dir.path(),
+            "joenet",
+            "joenet-fullnode",
+            &joenet_key,
+        )])
+        .allow_unknown()
+        .unwrap();
+
+        let (route, peer) = router.route(&joenet_key).unwrap();
+        assert_eq!(route.network, "joenet");
+        assert_eq!(peer.name, "joenet-fullnode");
+
+        let (route, peer) = router.route(&unknown_key).unwrap();
+        assert_eq!(route.network, "joenet");
+        assert_eq!(peer.name, Hex::encode(unknown_key.as_bytes()));
+    }
+}
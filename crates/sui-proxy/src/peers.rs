@@ -32,6 +32,7 @@ pub struct SuiPeer {
 #[derive(Debug, Clone)]
 pub struct SuiNodeProvider {
     nodes: SuiPeers,
+    static_peers: Vec<SuiPeer>,
     rpc_url: String,
     rpc_poll_interval: Duration,
 }
@@ -47,11 +48,24 @@ impl SuiNodeProvider {
         let nodes = Arc::new(RwLock::new(HashMap::new()));
         Self {
             nodes,
+            static_peers: vec![],
             rpc_url,
             rpc_poll_interval,
         }
     }
 
+    /// with_static_peers allows clients that are not validators, eg fullnodes, in addition to the
+    /// validators found on chain.  They stay on the allow list across refreshes.
+    pub fn with_static_peers(mut self, static_peers: Vec<SuiPeer>) -> Self {
+        self.nodes.write().unwrap().extend(
+            static_peers
+                .iter()
+                .map(|peer| (peer.public_key.clone(), peer.clone())),
+        );
+        self.static_peers = static_peers;
+        self
+    }
+
     /// get is used to retrieve peer info in our handlers
     pub fn get(&self, key: &Ed25519PublicKey) -> Option<SuiPeer> {
         debug!("look for {:?}", key);
@@ -112,6 +126,7 @@ impl SuiNodeProvider {
         let rpc_poll_interval = self.rpc_poll_interval;
         let rpc_url = self.rpc_url.to_owned();
         let nodes = self.nodes.clone();
+        let static_peers = self.static_peers.clone();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(rpc_poll_interval);
             interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
@@ -126,6 +141,11 @@ impl SuiNodeProvider {
                         let mut allow = nodes.write().unwrap();
                         allow.clear();
                         allow.extend(peers);
+                        allow.extend(
+                            static_peers
+                                .iter()
+                                .map(|peer| (peer.public_key.clone(), peer.clone())),
+                        );
                         info!("{} peers managed to make it on the allow list", allow.len());
                     }
                     Err(error) => error!("unable to refresh peer list: {error}"),
@@ -0,0 +1,366 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::admin::ReqwestClient;
+use crate::config::RemoteWriteQueueConfig;
+use crate::consumer::{post_remote_write, RemoteWriteError};
+use anyhow::{bail, Context, Result};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tracing::{error, info, warn};
+
+/// file extension of a queued request, the file stem is its sequence number
+const ENTRY_EXTENSION: &str = "pb";
+
+/// QueueMetrics are shared by the queues of every network, labeled by network
+#[derive(Clone)]
+pub struct QueueMetrics {
+    depth: IntGaugeVec,
+    dropped: IntCounterVec,
+    retries: IntCounterVec,
+}
+
+impl QueueMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            depth: register_int_gauge_vec_with_registry!(
+                "remote_write_queue_depth",
+                "Number of remote_write requests waiting to be sent",
+                &["network"],
+                registry,
+            )
+            .unwrap(),
+            dropped: register_int_counter_vec_with_registry!(
+                "remote_write_queue_dropped",
+                "Number of remote_write requests dropped, by reason",
+                &["network", "reason"],
+                registry,
+            )
+            .unwrap(),
+            retries: register_int_counter_vec_with_registry!(
+                "remote_write_queue_retries",
+                "Number of remote_write requests retried after a failure",
+                &["network"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// RemoteWriteQueue is a bounded on-disk FIFO of snappy compressed remote_write requests for one
+/// network.  Each request is a file named after its sequence number.  A single replay task sends
+/// them in order, so the samples of a series reach the remote_write api in the order they were
+/// pushed, even across retries and restarts.
+#[derive(Clone)]
+pub struct RemoteWriteQueue {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    network: String,
+    directory: PathBuf,
+    max_entries: usize,
+    /// sequence numbers of the queued requests, oldest first
+    entries: Mutex<Entries>,
+    notify: Notify,
+    metrics: QueueMetrics,
+}
+
+struct Entries {
+    queued: VecDeque<u64>,
+    next: u64,
+}
+
+impl RemoteWriteQueue {
+    /// open the queue of a network, picking up the requests left over by a previous run
+    pub fn open(
+        network: &str,
+        config: &RemoteWriteQueueConfig,
+        metrics: QueueMetrics,
+    ) -> Result<Self> {
+        let directory = config.directory.join(network);
+        std::fs::create_dir_all(&directory)
+            .context(format!("cannot create queue directory {:?}", directory))?;
+
+        let mut queued = vec![];
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(ENTRY_EXTENSION) {
+                continue;
+            }
+            match path.file_stem().and_then(|s| s.to_str()).map(str::parse) {
+                Some(Ok(seq)) => queued.push(seq),
+                _ => warn!(
+                    "ignoring unexpected file {:?} in the remote_write queue",
+                    path
+                ),
+            }
+        }
+        queued.sort_unstable();
+        let next = queued.last().map_or(0, |seq| seq + 1);
+        info!(
+            "opened remote_write queue for {network} with {} pending requests",
+            queued.len()
+        );
+        metrics
+            .depth
+            .with_label_values(&[network])
+            .set(queued.len() as i64);
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                network: network.to_owned(),
+                directory,
+                max_entries: config.max_entries,
+                entries: Mutex::new(Entries {
+                    queued: queued.into(),
+                    next,
+                }),
+                notify: Notify::new(),
+                metrics,
+            }),
+        })
+    }
+
+    /// push a request at the back of the queue, it is dropped if the queue is full
+    pub async fn push(&self, compressed: Vec<u8>) -> Result<()> {
+        let mut entries = self.inner.entries.lock().await;
+        if entries.queued.len() >= self.inner.max_entries {
+            self.dropped("queue_full");
+            bail!("queue is full with {} requests", entries.queued.len());
+        }
+        let seq = entries.next;
+        // write to a temporary file first so that we never replay a partial request
+        let tmp = self.inner.directory.join(format!("{seq:020}.tmp"));
+        tokio::fs::write(&tmp, compressed).await?;
+        tokio::fs::rename(&tmp, self.path(seq)).await?;
+        entries.next += 1;
+        entries.queued.push_back(seq);
+        self.set_depth(entries.queued.len());
+        drop(entries);
+        self.inner.notify.notify_one();
+        Ok(())
+    }
+
+    /// the number of queued requests
+    pub async fn len(&self) -> usize {
+        self.inner.entries.lock().await.queued.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// the oldest request in the queue and its sequence number
+    async fn front(&self) -> Option<(u64, Result<Vec<u8>>)> {
+        let seq = *self.inner.entries.lock().await.queued.front()?;
+        let data = tokio::fs::read(self.path(seq))
+            .await
+            .context(format!("cannot read queued request {seq}"));
+        Some((seq, data))
+    }
+
+    /// remove the oldest request, once it was sent or dropped
+    async fn pop(&self, seq: u64) {
+        let mut entries = self.inner.entries.lock().await;
+        if entries.queued.front() != Some(&seq) {
+            return;
+        }
+        entries.queued.pop_front();
+        self.set_depth(entries.queued.len());
+        if let Err(error) = tokio::fs::remove_file(self.path(seq)).await {
+            error!("unable to remove sent remote_write request {seq}: {error}");
+        }
+    }
+
+    /// replay sends the queued requests in order with the given client, forever.  A request that
+    /// fails with a retryable error blocks the queue and is retried with exponential backoff.
+    pub fn replay(&self, client: ReqwestClient, initial_backoff: Duration, max_backoff: Duration) {
+        let queue = self.clone();
+        tokio::spawn(async move {
+            let mut backoff = initial_backoff;
+            loop {
+                let Some((seq, data)) = queue.front().await else {
+                    queue.inner.notify.notified().await;
+                    continue;
+                };
+                let data = match data {
+                    Ok(data) => data,
+                    Err(error) => {
+                        error!("DROPPING METRICS, {error}");
+                        queue.dropped("unreadable");
+                        queue.pop(seq).await;
+                        continue;
+                    }
+                };
+                match post_remote_write(&client, data).await {
+                    Ok(()) => {
+                        queue.pop(seq).await;
+                        backoff = initial_backoff;
+                    }
+                    Err(RemoteWriteError::Rejected(error)) => {
+                        error!("DROPPING METRICS rejected by remote_write: {error}");
+                        queue.dropped("rejected");
+                        queue.pop(seq).await;
+                        backoff = initial_backoff;
+                    }
+                    Err(RemoteWriteError::Retryable(error)) => {
+                        warn!(
+                            "remote_write failed for {}, retrying in {:?}: {error}",
+                            queue.inner.network, backoff
+                        );
+                        queue
+                            .inner
+                            .metrics
+                            .retries
+                            .with_label_values(&[&queue.inner.network])
+                            .inc();
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(max_backoff);
+                    }
+                }
+            }
+        });
+    }
+
+    fn path(&self, seq: u64) -> PathBuf {
+        entry_path(&self.inner.directory, seq)
+    }
+
+    fn set_depth(&self, depth: usize) {
+        self.inner
+            .metrics
+            .depth
+            .with_label_values(&[&self.inner.network])
+            .set(depth as i64);
+    }
+
+    fn dropped(&self, reason: &str) {
+        self.inner
+            .metrics
+            .dropped
+            .with_label_values(&[&self.inner.network, reason])
+            .inc();
+    }
+}
+
+fn entry_path(directory: &Path, seq: u64) -> PathBuf {
+    directory.join(format!("{seq:020}.{ENTRY_EXTENSION}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::make_reqwest_client;
+    use crate::config::RemoteWriteConfig;
+    use axum::body::Bytes;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Extension, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn queue_config(directory: &Path, max_entries: usize) -> RemoteWriteQueueConfig {
+        RemoteWriteQueueConfig {
+            directory: directory.to_owned(),
+            max_entries,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(40),
+        }
+    }
+
+    #[tokio::test]
+    async fn bounded_and_persistent() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = queue_config(dir.path(), 2);
+        let metrics = QueueMetrics::new(&Registry::new());
+
+        let queue = RemoteWriteQueue::open("unittest", &config, metrics.clone()).unwrap();
+        queue.push(vec![1]).await.unwrap();
+        queue.push(vec![2]).await.unwrap();
+        queue.push(vec![3]).await.unwrap_err();
+        assert_eq!(
+            metrics
+                .dropped
+                .with_label_values(&["unittest", "queue_full"])
+                .get(),
+            1
+        );
+
+        // a new queue picks up where the old one left off, in order
+        let registry = Registry::new();
+        let queue =
+            RemoteWriteQueue::open("unittest", &config, QueueMetrics::new(&registry)).unwrap();
+        assert_eq!(queue.len().await, 2);
+        let (seq, data) = queue.front().await.unwrap();
+        assert_eq!(data.unwrap(), vec![1]);
+        queue.pop(seq).await;
+        queue.push(vec![3]).await.unwrap();
+        let (seq, data) = queue.front().await.unwrap();
+        assert_eq!(data.unwrap(), vec![2]);
+        queue.pop(seq).await;
+        let (_, data) = queue.front().await.unwrap();
+        assert_eq!(data.unwrap(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn replay_in_order_after_outage() {
+        /// fail the first requests like a remote_write api that is down, then record bodies
+        async fn handler(
+            Extension(state): Extension<Arc<(AtomicUsize, std::sync::Mutex<Vec<Bytes>>)>>,
+            body: Bytes,
+        ) -> StatusCode {
+            if state.0.fetch_add(1, Ordering::SeqCst) < 3 {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            state.1.lock().unwrap().push(body);
+            StatusCode::OK
+        }
+        let state = Arc::new((AtomicUsize::new(0), std::sync::Mutex::new(vec![])));
+        let app = Router::new()
+            .route("/v1/push", post(handler))
+            .layer(Extension(state.clone()));
+        let listener = std::net::TcpListener::bind("localhost:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service())
+                .await
+                .unwrap();
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = queue_config(dir.path(), 10);
+        let metrics = QueueMetrics::new(&Registry::new());
+        let queue = RemoteWriteQueue::open("unittest", &config, metrics.clone()).unwrap();
+        for i in 0..5u8 {
+            queue.push(vec![i]).await.unwrap();
+        }
+        let client = make_reqwest_client(RemoteWriteConfig {
+            url: format!("http://localhost:{port}/v1/push"),
+            ..Default::default()
+        });
+        queue.replay(client, config.initial_backoff, config.max_backoff);
+
+        for _ in 0..100 {
+            if queue.is_empty().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(queue.is_empty().await);
+        let received = state.1.lock().unwrap().clone();
+        assert_eq!(
+            received,
+            (0..5u8).map(|i| Bytes::from(vec![i])).collect::<Vec<_>>()
+        );
+        assert_eq!(metrics.retries.with_label_values(&["unittest"]).get(), 3);
+    }
+}
@@ -16,10 +16,11 @@ use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
     Amount, Coin, SubAccount, SubAccountType,
 };
-use crate::{OnlineServerContext, SuiEnv};
+use crate::{OnlineServerContext, SuiEnv, SUI};
 
 /// Get an array of all AccountBalances for an AccountIdentifier and the BlockIdentifier
-/// at which the balance lookup was performed.
+/// at which the balance lookup was performed. The balances are in the requested currencies, SUI if
+/// none is requested.
 /// [Rosetta API Spec](https://www.rosetta-api.org/docs/AccountApi.html#accountbalance)
 pub async fn balance(
     State(ctx): State<OnlineServerContext>,
@@ -45,13 +46,23 @@ pub async fn balance(
             ctx.blocks().current_block_identifier().await?
         };
 
-        ctx.blocks()
-            .get_balance_at_block(address, block_identifier.index)
-            .await
-            .map(|balance| AccountBalanceResponse {
-                block_identifier,
-                balances: vec![Amount::new(balance)],
-            })
+        let currencies = if request.currencies.is_empty() {
+            vec![SUI.clone()]
+        } else {
+            request.currencies
+        };
+        let mut balances = vec![];
+        for currency in currencies {
+            let balance = ctx
+                .blocks()
+                .get_balance_at_block(address, &currency, block_identifier.index)
+                .await?;
+            balances.push(Amount::new_coin(balance, currency));
+        }
+        Ok(AccountBalanceResponse {
+            block_identifier,
+            balances,
+        })
     }
 }
 
@@ -134,23 +134,29 @@ pub async fn submit(
             SuiTransactionResponseOptions::new()
                 .with_input()
                 .with_effects()
-                .with_balance_changes(),
+                .with_balance_changes()
+                .with_events(),
             None,
         )
         .await?;
 
     if let SuiExecutionStatus::Failure { error } = response
         .effects
+        .as_ref()
         .expect("Execute transaction should return effects")
         .status()
     {
         return Err(Error::TransactionExecutionError(error.to_string()));
     }
 
+    let hash = response.digest;
+    // Keep the transaction in the mempool until it is indexed in a block.
+    if response.checkpoint.is_none() {
+        context.mempool.insert(response);
+    }
+
     Ok(TransactionIdentifierResponse {
-        transaction_identifier: TransactionIdentifier {
-            hash: response.digest,
-        },
+        transaction_identifier: TransactionIdentifier { hash },
         metadata: None,
     })
 }
@@ -216,6 +222,28 @@ pub async fn metadata(
             let amount = amounts.iter().sum::<u64>();
             (Some(amount), vec![], 2000)
         }
+        InternalOperation::Pay {
+            amounts, currency, ..
+        } => {
+            // The coins paid are input objects, the gas only has to cover the budget.
+            let amount = amounts.iter().sum::<u64>();
+            let coins = context
+                .client
+                .coin_read_api()
+                .select_coins(
+                    sender,
+                    Some(currency.coin_type()?.to_string()),
+                    amount.into(),
+                    None,
+                    vec![],
+                )
+                .await?;
+            let coin_refs = coins
+                .into_iter()
+                .map(|c| c.object_ref())
+                .collect::<Vec<_>>();
+            (Some(0), coin_refs, 10000)
+        }
         InternalOperation::Stake { amount, .. } => (*amount, vec![], 2000),
         InternalOperation::WithdrawStake { sender, stake_ids } => {
             let stake_ids = if stake_ids.is_empty() {
@@ -0,0 +1,49 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use crate::types::{BlockEvent, BlockEventType, EventsBlocksRequest, EventsBlocksResponse};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Events API](https://www.rosetta-api.org/docs/EventsApi.html)
+
+const MAX_LIMIT: u64 = 100;
+
+/// Get the blocks added to the chain, in order. Sui checkpoints are final, so every event is a
+/// `block_added` event whose sequence is the checkpoint sequence number.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+pub async fn blocks(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<EventsBlocksRequest>, Error>,
+) -> Result<EventsBlocksResponse, Error> {
+    debug!("Called /events/blocks endpoint");
+    env.check_network_identifier(&request.network_identifier)?;
+    let blocks = context.blocks();
+    let max_sequence = blocks.current_block_identifier().await?.index;
+    let limit = request.limit.unwrap_or(MAX_LIMIT).clamp(1, MAX_LIMIT);
+    // Without an offset, return the most recent events.
+    let offset = request
+        .offset
+        .unwrap_or_else(|| (max_sequence + 1).saturating_sub(limit));
+
+    let mut events = vec![];
+    for sequence in offset..offset.saturating_add(limit) {
+        if sequence > max_sequence {
+            break;
+        }
+        events.push(BlockEvent {
+            sequence,
+            block_identifier: blocks.get_block_identifier(sequence).await?,
+            type_: BlockEventType::BlockAdded,
+        });
+    }
+    Ok(EventsBlocksResponse {
+        max_sequence,
+        events,
+    })
+}
@@ -15,7 +15,7 @@ use mysten_metrics::spawn_monitored_task;
 use sui_sdk::SuiClient;
 
 use crate::errors::Error;
-use crate::state::{CheckpointBlockProvider, OnlineServerContext};
+use crate::state::{CheckpointBlockProvider, CoinMetadataCache, Mempool, OnlineServerContext};
 use crate::types::{Currency, SuiEnv};
 
 /// This lib implements the Rosetta online and offline server defined by the [Rosetta API Spec](https://www.rosetta-api.org/docs/Reference.html)
@@ -23,14 +23,18 @@ mod account;
 mod block;
 mod construction;
 mod errors;
+mod events;
+mod mempool;
 mod network;
 pub mod operations;
+mod search;
 mod state;
 pub mod types;
 
 pub static SUI: Lazy<Currency> = Lazy::new(|| Currency {
     symbol: "SUI".to_string(),
     decimals: 9,
+    metadata: None,
 });
 
 pub struct RosettaOnlineServer {
@@ -40,10 +44,17 @@ pub struct RosettaOnlineServer {
 
 impl RosettaOnlineServer {
     pub fn new(env: SuiEnv, client: SuiClient, data_path: &Path) -> Self {
-        let blocks = Arc::new(CheckpointBlockProvider::spawn(client.clone(), data_path));
+        let coin_metadata_cache = CoinMetadataCache::new(client.clone());
+        let mempool = Mempool::default();
+        let blocks = Arc::new(CheckpointBlockProvider::spawn(
+            client.clone(),
+            coin_metadata_cache.clone(),
+            mempool.clone(),
+            data_path,
+        ));
         Self {
             env,
-            context: OnlineServerContext::new(client, blocks),
+            context: OnlineServerContext::new(client, coin_metadata_cache, mempool, blocks),
         }
     }
 
@@ -59,6 +70,10 @@ impl RosettaOnlineServer {
             .route("/network/status", post(network::status))
             .route("/network/list", post(network::list))
             .route("/network/options", post(network::options))
+            .route("/search/transactions", post(search::transactions))
+            .route("/events/blocks", post(events::blocks))
+            .route("/mempool", post(mempool::mempool))
+            .route("/mempool/transaction", post(mempool::transaction))
             .layer(Extension(self.env))
             .with_state(self.context);
         let server = axum::Server::bind(&addr).serve(app.into_make_service());
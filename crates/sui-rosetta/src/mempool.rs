@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use crate::types::{
+    MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+    Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Mempool API](https://www.rosetta-api.org/docs/MempoolApi.html)
+
+/// Get the transactions submitted through this server which are not yet in a block.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+pub async fn mempool(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<MempoolRequest>, Error>,
+) -> Result<MempoolResponse, Error> {
+    debug!("Called /mempool endpoint");
+    env.check_network_identifier(&request.network_identifier)?;
+    let transaction_identifiers = context
+        .mempool
+        .digests()
+        .into_iter()
+        .map(|hash| TransactionIdentifier { hash })
+        .collect();
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Get a transaction in the mempool by its Transaction Identifier.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+pub async fn transaction(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<MempoolTransactionRequest>, Error>,
+) -> Result<MempoolTransactionResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let digest = request.transaction_identifier.hash;
+    let response = context
+        .mempool
+        .get(&digest)
+        .ok_or(Error::TransactionNotInMempool(digest))?;
+    // The operations of a pending transaction have no status until it is in a block.
+    let operations = context
+        .coin_metadata_cache
+        .operations(response)
+        .await?
+        .set_status(None);
+
+    Ok(MempoolTransactionResponse {
+        transaction: Transaction {
+            transaction_identifier: TransactionIdentifier { hash: digest },
+            operations,
+            related_transactions: vec![],
+            metadata: None,
+        },
+        metadata: None,
+    })
+}
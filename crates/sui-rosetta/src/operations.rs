@@ -8,7 +8,7 @@ use std::vec;
 
 use anyhow::anyhow;
 use move_core_types::ident_str;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::MoveTypeLayout;
 use serde::Deserialize;
 use serde::Serialize;
@@ -23,18 +23,19 @@ use sui_sdk::rpc_types::{
     SuiTransactionResponse,
 };
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
-use sui_types::gas_coin::{GasCoin, GAS};
+use sui_types::coin::{PAY_MODULE_NAME, PAY_SPLIT_AND_TRANSFER_FUNC_NAME};
+use sui_types::gas_coin::GasCoin;
 use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages::TransactionData;
 use sui_types::object::Owner;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
-use sui_types::{SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID};
+use sui_types::{parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID};
 
 use crate::types::{
-    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, InternalOperation,
-    OperationIdentifier, OperationStatus, OperationType,
+    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType,
 };
-use crate::Error;
+use crate::{Error, SUI};
 
 #[cfg(test)]
 #[path = "unit_tests/operations_tests.rs"]
@@ -95,6 +96,10 @@ impl Operations {
         self.0.first().map(|op| op.type_)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Operation> {
+        self.0.iter()
+    }
+
     /// Parse operation input from rosetta operation to intermediate internal operation;
     pub fn into_internal(self) -> Result<InternalOperation, Error> {
         let type_ = self
@@ -102,6 +107,7 @@ impl Operations {
             .ok_or_else(|| Error::MissingInput("Operation type".into()))?;
         match type_ {
             OperationType::PaySui => self.pay_sui_ops_to_internal(),
+            OperationType::Pay => self.pay_ops_to_internal(),
             OperationType::Stake => self.stake_ops_to_internal(),
             OperationType::WithdrawStake => self.withdraw_stake_ops_to_internal(),
             op => Err(Error::UnsupportedOperation(op)),
@@ -136,6 +142,52 @@ impl Operations {
         })
     }
 
+    fn pay_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let mut recipients = vec![];
+        let mut amounts = vec![];
+        let mut sender = None;
+        let mut currency = None;
+        for op in self {
+            let (Some(amount), Some(account)) = (op.amount, op.account) else {
+                continue;
+            };
+            match &currency {
+                None => currency = Some(amount.currency.clone()),
+                Some(currency) if *currency != amount.currency => {
+                    return Err(Error::MalformedOperationError(
+                        "Pay operations should all be in the same currency.".into(),
+                    ))
+                }
+                Some(_) => {}
+            }
+            if amount.value.is_negative() {
+                sender = Some(account.address)
+            } else {
+                recipients.push(account.address);
+                let amount = amount.value.abs();
+                if amount > u64::MAX as i128 {
+                    return Err(Error::InvalidInput(
+                        "Input amount exceed u64::MAX".to_string(),
+                    ));
+                }
+                amounts.push(amount as u64)
+            }
+        }
+        let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
+        let currency = currency.ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
+        if currency.is_sui() {
+            return Err(Error::MalformedOperationError(
+                "SUI should be paid with PaySui operations.".into(),
+            ));
+        }
+        Ok(InternalOperation::Pay {
+            sender,
+            recipients,
+            amounts,
+            currency,
+        })
+    }
+
     fn stake_ops_to_internal(self) -> Result<InternalOperation, Error> {
         let mut ops = self
             .0
@@ -214,10 +266,11 @@ impl Operations {
         tx: SuiTransactionKind,
         sender: SuiAddress,
         status: Option<OperationStatus>,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> Result<Vec<Operation>, Error> {
         Ok(match tx {
             SuiTransactionKind::ProgrammableTransaction(pt) => {
-                Self::parse_programmable_transaction(sender, status, pt)?
+                Self::parse_programmable_transaction(sender, status, pt, currencies)?
             }
             _ => vec![Operation::generic_op(status, sender, tx)],
         })
@@ -227,6 +280,7 @@ impl Operations {
         sender: SuiAddress,
         status: Option<OperationStatus>,
         pt: SuiProgrammableTransaction,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> Result<Vec<Operation>, Error> {
         #[derive(Debug)]
         enum KnownValue {
@@ -241,8 +295,8 @@ impl Operations {
                 .get(i as usize)
                 .and_then(|inner| inner.get(j as usize))
         }
-        fn split_coin(inputs: &[SuiJsonValue], amount: SuiArgument) -> Option<Vec<KnownValue>> {
-            let amount: u64 = match amount {
+        fn pure_u64(inputs: &[SuiJsonValue], arg: SuiArgument) -> Option<u64> {
+            match arg {
                 SuiArgument::Input(i) => {
                     let input = inputs[i as usize]
                         .to_bcs_bytes(&MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)))
@@ -253,14 +307,49 @@ impl Operations {
                         bcs::from_bytes::<Vec<u8>>(&input).ok()?
                     };
                     // convert to u64
-                    bcs::from_bytes(&input).ok()?
+                    bcs::from_bytes(&input).ok()
                 }
                 SuiArgument::GasCoin | SuiArgument::Result(_) | SuiArgument::NestedResult(_, _) => {
-                    return None
+                    None
                 }
-            };
+            }
+        }
+        fn split_coin(inputs: &[SuiJsonValue], amount: SuiArgument) -> Option<Vec<KnownValue>> {
+            let amount = pure_u64(inputs, amount)?;
             Some(vec![KnownValue::GasCoin(amount)])
         }
+        fn pay_call(
+            aggregated_payments: &mut HashMap<Currency, HashMap<SuiAddress, u64>>,
+            inputs: &[SuiJsonValue],
+            call: &SuiProgrammableMoveCall,
+            currencies: &HashMap<TypeTag, Currency>,
+        ) -> Result<Option<Vec<KnownValue>>, Error> {
+            let (Some(coin_type), Some((amount, recipient))) = (
+                pay_coin_type(call),
+                pay_amount_and_recipient(inputs, call),
+            ) else {
+                return Ok(None);
+            };
+            let currency = Currency::from_coin_type(&coin_type, currencies)?;
+            let aggregate = aggregated_payments
+                .entry(currency)
+                .or_default()
+                .entry(recipient)
+                .or_default();
+            *aggregate += amount;
+            Ok(Some(vec![]))
+        }
+        fn pay_amount_and_recipient(
+            inputs: &[SuiJsonValue],
+            call: &SuiProgrammableMoveCall,
+        ) -> Option<(u64, SuiAddress)> {
+            let [_, amount, SuiArgument::Input(recipient)] = call.arguments[..] else {
+                return None;
+            };
+            let amount = pure_u64(inputs, amount)?;
+            let recipient = inputs[recipient as usize].to_sui_address().ok()?;
+            Some((amount, recipient))
+        }
         fn transfer_object(
             aggregated_recipients: &mut HashMap<SuiAddress, u64>,
             inputs: &[SuiJsonValue],
@@ -349,6 +438,7 @@ impl Operations {
         let SuiProgrammableTransaction { inputs, commands } = &pt;
         let mut known_results: Vec<Vec<KnownValue>> = vec![];
         let mut aggregated_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+        let mut aggregated_payments: HashMap<Currency, HashMap<SuiAddress, u64>> = HashMap::new();
         let mut needs_generic = false;
         let mut operations = vec![];
         let mut stake_ids = vec![];
@@ -362,6 +452,11 @@ impl Operations {
                     objs,
                     *addr,
                 ),
+                // Merging coins does not change balances.
+                SuiCommand::MergeCoins(..) => Some(vec![]),
+                SuiCommand::MoveCall(m) if Self::is_pay_call(m) => {
+                    pay_call(&mut aggregated_payments, inputs, m, currencies)?
+                }
                 SuiCommand::MoveCall(m) if Self::is_stake_call(m) => {
                     stake_call(inputs, &known_results, m)?.map(|(amount, validator)| {
                         let amount = amount.map(|amount| Amount::new(-(amount as i128)));
@@ -392,16 +487,34 @@ impl Operations {
             }
         }
 
-        if !needs_generic && !aggregated_recipients.is_empty() {
-            let total_paid: u64 = aggregated_recipients.values().copied().sum();
-            operations.extend(
-                aggregated_recipients
-                    .into_iter()
-                    .map(|(recipient, amount)| {
-                        Operation::pay_sui(status, recipient, amount.into())
-                    }),
-            );
-            operations.push(Operation::pay_sui(status, sender, -(total_paid as i128)));
+        if !needs_generic && (!aggregated_recipients.is_empty() || !aggregated_payments.is_empty())
+        {
+            if !aggregated_recipients.is_empty() {
+                let total_paid: u64 = aggregated_recipients.values().copied().sum();
+                operations.extend(
+                    aggregated_recipients
+                        .into_iter()
+                        .map(|(recipient, amount)| {
+                            Operation::pay_sui(status, recipient, amount.into())
+                        }),
+                );
+                operations.push(Operation::pay_sui(status, sender, -(total_paid as i128)));
+            }
+            for (currency, recipients) in aggregated_payments {
+                let total_paid: u64 = recipients.values().copied().sum();
+                operations.extend(recipients.into_iter().map(|(recipient, amount)| {
+                    Operation::pay(
+                        status,
+                        recipient,
+                        Amount::new_coin(amount.into(), currency.clone()),
+                    )
+                }));
+                operations.push(Operation::pay(
+                    status,
+                    sender,
+                    Amount::new_coin(-(total_paid as i128), currency),
+                ));
+            }
         } else if !stake_ids.is_empty() {
             let stake_ids = stake_ids.into_iter().flatten().collect::<Vec<_>>();
             let metadata = stake_ids
@@ -427,6 +540,12 @@ impl Operations {
         Ok(operations)
     }
 
+    pub(crate) fn is_pay_call(tx: &SuiProgrammableMoveCall) -> bool {
+        tx.package == SUI_FRAMEWORK_OBJECT_ID
+            && tx.module == PAY_MODULE_NAME.as_str()
+            && tx.function == PAY_SPLIT_AND_TRANSFER_FUNC_NAME.as_str()
+    }
+
     fn is_stake_call(tx: &SuiProgrammableMoveCall) -> bool {
         tx.package == SUI_FRAMEWORK_OBJECT_ID
             && tx.module == SUI_SYSTEM_MODULE_NAME.as_str()
@@ -444,26 +563,24 @@ impl Operations {
         gas_used: i128,
         balance_changes: &[BalanceChange],
         status: Option<OperationStatus>,
-        balances: HashMap<SuiAddress, i128>,
-    ) -> impl Iterator<Item = Operation> {
-        let mut balances = balance_changes
-            .iter()
-            .fold(balances, |mut balances, balance_change| {
-                // Rosetta only care about address owner
-                if let Owner::AddressOwner(owner) = balance_change.owner {
-                    if balance_change.coin_type == GAS::type_tag() {
-                        *balances.entry(owner).or_default() += balance_change.amount;
-                    }
-                }
-                balances
-            });
+        mut balances: HashMap<(SuiAddress, Currency), i128>,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<impl Iterator<Item = Operation>, Error> {
+        for balance_change in balance_changes {
+            // Rosetta only care about address owner
+            if let Owner::AddressOwner(owner) = balance_change.owner {
+                let currency = Currency::from_coin_type(&balance_change.coin_type, currencies)?;
+                *balances.entry((owner, currency)).or_default() += balance_change.amount;
+            }
+        }
         // separate gas from balances
-        *balances.entry(gas_owner).or_default() -= gas_used;
+        *balances.entry((gas_owner, SUI.clone())).or_default() -= gas_used;
 
-        let balance_change = balances
-            .into_iter()
-            .filter(|(_, amount)| *amount != 0)
-            .map(move |(addr, amount)| Operation::balance_change(status, addr, amount));
+        let balance_change = balances.into_iter().filter(|(_, amount)| *amount != 0).map(
+            move |((addr, currency), amount)| {
+                Operation::balance_change(status, addr, Amount::new_coin(amount, currency))
+            },
+        );
 
         let gas = if gas_used != 0 {
             vec![Operation::gas(gas_owner, gas_used)]
@@ -471,25 +588,46 @@ impl Operations {
             // Gas can be 0 for system tx
             vec![]
         };
-        balance_change.chain(gas)
+        Ok(balance_change.chain(gas))
     }
 }
 
 impl TryFrom<SuiTransactionData> for Operations {
     type Error = Error;
     fn try_from(data: SuiTransactionData) -> Result<Self, Self::Error> {
+        Self::try_from_data(data, &HashMap::new())
+    }
+}
+
+impl TryFrom<SuiTransactionResponse> for Operations {
+    type Error = Error;
+    fn try_from(response: SuiTransactionResponse) -> Result<Self, Self::Error> {
+        Self::try_from_response(response, &HashMap::new())
+    }
+}
+
+impl Operations {
+    /// Operations of the transaction of `data`, in the `currencies` of the coin types they involve.
+    /// Fails if a coin type other than SUI has no currency in `currencies`.
+    pub fn try_from_data(
+        data: SuiTransactionData,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let sender = *data.sender();
         Ok(Self::new(Self::from_transaction(
             data.transaction().clone(),
             sender,
             None,
+            currencies,
         )?))
     }
-}
 
-impl TryFrom<SuiTransactionResponse> for Operations {
-    type Error = Error;
-    fn try_from(response: SuiTransactionResponse) -> Result<Self, Self::Error> {
+    /// Operations of the transaction of `response`, in the `currencies` of the coin types they
+    /// involve. Fails if a coin type other than SUI has no currency in `currencies`.
+    pub fn try_from_response(
+        response: SuiTransactionResponse,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let tx = response
             .transaction
             .ok_or_else(|| anyhow!("Response input should not be empty"))?;
@@ -504,8 +642,9 @@ impl TryFrom<SuiTransactionResponse> for Operations {
             - gas_summary.computation_cost as i128;
 
         let status = Some(effect.into_status().into());
-        let ops: Operations = tx.data.try_into()?;
-        let ops = ops.set_status(status).into_iter();
+        let ops = Self::try_from_data(tx.data, currencies)?
+            .set_status(status)
+            .into_iter();
 
         // We will need to subtract the operation amounts from the actual balance
         // change amount extracted from event to prevent double counting.
@@ -516,7 +655,9 @@ impl TryFrom<SuiTransactionResponse> for Operations {
                     if let (Some(acc), Some(amount), Some(OperationStatus::Success)) =
                         (&op.account, &op.amount, &op.status)
                     {
-                        *balances.entry(acc.address).or_default() -= amount.value;
+                        *balances
+                            .entry((acc.address, amount.currency.clone()))
+                            .or_default() -= amount.value;
                     }
                     balances
                 });
@@ -548,8 +689,8 @@ impl TryFrom<SuiTransactionResponse> for Operations {
             }
         }
         let staking_balance = if principal_amounts != 0 {
-            *accounted_balances.entry(sender).or_default() -= principal_amounts;
-            *accounted_balances.entry(sender).or_default() -= reward_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= principal_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= reward_amounts;
             vec![
                 Operation::stake_principle(status, sender, principal_amounts),
                 Operation::stake_reward(status, sender, reward_amounts),
@@ -567,7 +708,8 @@ impl TryFrom<SuiTransactionResponse> for Operations {
                 .ok_or_else(|| anyhow!("Response balance changes should not be empty."))?,
             status,
             accounted_balances,
-        );
+            currencies,
+        )?;
 
         Ok(ops
             .into_iter()
@@ -577,6 +719,14 @@ impl TryFrom<SuiTransactionResponse> for Operations {
     }
 }
 
+/// The coin type paid by a call to `pay::split_and_transfer`.
+pub(crate) fn pay_coin_type(call: &SuiProgrammableMoveCall) -> Option<TypeTag> {
+    let [coin_type] = &call.type_arguments[..] else {
+        return None;
+    };
+    parse_sui_type_tag(coin_type).ok()
+}
+
 fn is_unstake_event(tag: &StructTag) -> bool {
     tag.address == SUI_FRAMEWORK_ADDRESS
         && tag.module.as_ident_str() == ident_str!("validator")
@@ -674,13 +824,25 @@ impl Operation {
         }
     }
 
-    fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: i128) -> Self {
+    fn pay(status: Option<OperationStatus>, address: SuiAddress, amount: Amount) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::Pay,
+            status,
+            account: Some(address.into()),
+            amount: Some(amount),
+            coin_change: None,
+            metadata: None,
+        }
+    }
+
+    fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: Amount) -> Self {
         Self {
             operation_identifier: Default::default(),
             type_: OperationType::SuiBalanceChange,
             status,
             account: Some(addr.into()),
-            amount: Some(Amount::new(amount)),
+            amount: Some(amount),
             coin_change: None,
             metadata: None,
         }
@@ -0,0 +1,377 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use sui_json_rpc_types::{
+    SuiTransactionResponse, SuiTransactionResponseOptions, SuiTransactionResponseQuery,
+};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::query::TransactionFilter;
+
+use crate::operations::{Operation, Operations};
+use crate::types::{
+    BlockTransaction, CoinIdentifier, Currency, OperationStatus, OperationType, Operator,
+    SearchTransactionsRequest, SearchTransactionsResponse, Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Search API](https://www.rosetta-api.org/docs/SearchApi.html)
+
+const MAX_LIMIT: u64 = 100;
+const QUERY_PAGE_SIZE: usize = 50;
+/// Maximum number of transactions looked at by a request, a request returns fewer transactions
+/// than its limit when a filter matches none of them, or none with a `next_offset` to continue
+/// from when it did not get past its offset yet.
+const MAX_SCANNED: usize = 1000;
+/// Maximum number of searches kept to be resumed by their next page.
+const MAX_SEARCH_CURSORS: usize = 1000;
+
+/// Search for transactions matching a set of conditions, newest first.
+/// Only the `and` operator is supported, and unless a transaction identifier is given, the
+/// search must be scoped to an account.
+/// The search of the next page resumes where the previous page ended, for its offset to stay
+/// consistent when new transactions are added. `total_count` is the number of transactions up to
+/// the end of the page, the total number of matches is only known on the last page.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+pub async fn transactions(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<SearchTransactionsRequest>, Error>,
+) -> Result<SearchTransactionsResponse, Error> {
+    debug!("Called /search/transactions endpoint");
+    env.check_network_identifier(&request.network_identifier)?;
+    if request.operator == Some(Operator::Or) {
+        return Err(Error::InvalidInput(
+            "Operator `or` is not supported".to_string(),
+        ));
+    }
+    let filter = SearchFilter::try_from(&request)?;
+    let offset = request.offset.unwrap_or_default();
+    let limit = request.limit.unwrap_or(MAX_LIMIT).clamp(1, MAX_LIMIT);
+    let search = search_key(&request)?;
+
+    // The number of matches before the candidates, the search moves past the ones before `offset`.
+    let (mut candidates, mut skipped) = if let Some(tx) = &request.transaction_identifier {
+        (Candidates::Single(Some(tx.hash)), 0)
+    } else if let Some(address) = filter.address {
+        match context.search_cursors.take(&search, offset) {
+            Some((transactions, skipped)) => (Candidates::Account(transactions), skipped),
+            None => (Candidates::Account(AccountTransactions::new(address)), 0),
+        }
+    } else {
+        return Err(Error::MissingInput(
+            "account_identifier or transaction_identifier".to_string(),
+        ));
+    };
+
+    let mut block_identifiers = HashMap::new();
+    let mut transactions = vec![];
+    let mut scanned = 0;
+    while (transactions.len() as u64) < limit && scanned < MAX_SCANNED {
+        let Some(response) = candidates.next(&context.client).await? else {
+            break;
+        };
+        scanned += 1;
+        let Some(checkpoint) = response.checkpoint else {
+            // Not in a block yet.
+            continue;
+        };
+        if matches!(request.max_block, Some(max_block) if checkpoint > max_block) {
+            continue;
+        }
+        let digest = response.digest;
+        let operations = context.coin_metadata_cache.operations(response).await?;
+        if !filter.matches(&operations) {
+            continue;
+        }
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+        let block_identifier = match block_identifiers.get(&checkpoint) {
+            Some(block_identifier) => *block_identifier,
+            None => {
+                let block_identifier = context.blocks().get_block_identifier(checkpoint).await?;
+                block_identifiers.insert(checkpoint, block_identifier);
+                block_identifier
+            }
+        };
+        transactions.push(BlockTransaction {
+            block_identifier,
+            transaction: Transaction {
+                transaction_identifier: TransactionIdentifier { hash: digest },
+                operations,
+                related_transactions: vec![],
+                metadata: None,
+            },
+        });
+    }
+
+    // A search which did not get past its offset yet continues from the same offset.
+    let next_offset = offset + transactions.len() as u64;
+    let has_more = match candidates {
+        Candidates::Single(digest) => digest.is_some(),
+        Candidates::Account(mut candidates) => {
+            let has_more = candidates.has_more(&context.client).await?;
+            if has_more {
+                let skipped = skipped + transactions.len() as u64;
+                context
+                    .search_cursors
+                    .insert(search, next_offset, skipped, candidates);
+            }
+            has_more
+        }
+    };
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count: next_offset,
+        next_offset: has_more.then_some(next_offset),
+    })
+}
+
+/// Identifies the searches of the same transactions, the request without its page.
+fn search_key(request: &SearchTransactionsRequest) -> Result<String, Error> {
+    let mut key = serde_json::to_value(request).map_err(|e| anyhow!(e))?;
+    if let Some(key) = key.as_object_mut() {
+        key.remove("offset");
+        key.remove("limit");
+    }
+    Ok(key.to_string())
+}
+
+/// Searches which have more results, by search and offset of their next page, to be resumed when
+/// that page is requested, with the number of matches they moved past, which is less than the
+/// offset while they are moving past it. The oldest ones are dropped past `MAX_SEARCH_CURSORS`.
+#[derive(Clone, Default)]
+pub struct SearchCursors {
+    cursors: Arc<Mutex<VecDeque<(String, u64, u64, AccountTransactions)>>>,
+}
+
+impl SearchCursors {
+    fn take(&self, search: &str, offset: u64) -> Option<(AccountTransactions, u64)> {
+        let mut cursors = self.cursors.lock().unwrap();
+        let index = cursors
+            .iter()
+            .position(|(key, next_offset, ..)| key == search && *next_offset == offset)?;
+        cursors
+            .remove(index)
+            .map(|(_, _, skipped, transactions)| (transactions, skipped))
+    }
+
+    fn insert(&self, search: String, offset: u64, skipped: u64, transactions: AccountTransactions) {
+        let mut cursors = self.cursors.lock().unwrap();
+        if cursors.len() == MAX_SEARCH_CURSORS {
+            cursors.pop_front();
+        }
+        cursors.push_back((search, offset, skipped, transactions));
+    }
+}
+
+fn response_options() -> SuiTransactionResponseOptions {
+    SuiTransactionResponseOptions::new()
+        .with_input()
+        .with_events()
+        .with_effects()
+        .with_balance_changes()
+}
+
+/// Conditions on the operations of a transaction, each one has to be met by at least one of
+/// its operations.
+struct SearchFilter {
+    address: Option<SuiAddress>,
+    coin_identifier: Option<CoinIdentifier>,
+    currency: Option<Currency>,
+    status: Option<OperationStatus>,
+    type_: Option<OperationType>,
+    success: Option<bool>,
+}
+
+impl TryFrom<&SearchTransactionsRequest> for SearchFilter {
+    type Error = Error;
+
+    fn try_from(request: &SearchTransactionsRequest) -> Result<Self, Self::Error> {
+        let address = match (&request.account_identifier, request.address) {
+            (Some(account), Some(address)) if account.address != address => {
+                return Err(Error::InvalidInput(format!(
+                    "account_identifier {} does not match address {address}",
+                    account.address
+                )))
+            }
+            (Some(account), _) => Some(account.address),
+            (None, address) => address,
+        };
+        Ok(Self {
+            address,
+            coin_identifier: request.coin_identifier.clone(),
+            currency: request.currency.clone(),
+            status: request.status,
+            type_: request.type_,
+            success: request.success,
+        })
+    }
+}
+
+impl SearchFilter {
+    fn matches(&self, operations: &Operations) -> bool {
+        let any = |f: &dyn Fn(&Operation) -> bool| operations.iter().any(f);
+        if let Some(address) = self.address {
+            if !any(&|op| matches!(&op.account, Some(account) if account.address == address)) {
+                return false;
+            }
+        }
+        if let Some(coin) = &self.coin_identifier {
+            if !any(
+                &|op| matches!(&op.coin_change, Some(change) if &change.coin_identifier == coin),
+            ) {
+                return false;
+            }
+        }
+        if let Some(currency) = &self.currency {
+            if !any(&|op| matches!(&op.amount, Some(amount) if &amount.currency == currency)) {
+                return false;
+            }
+        }
+        if let Some(status) = self.status {
+            if !any(&|op| op.status == Some(status)) {
+                return false;
+            }
+        }
+        if let Some(type_) = self.type_ {
+            if !any(&|op| op.type_ == type_) {
+                return false;
+            }
+        }
+        if let Some(success) = self.success {
+            if any(&|op| op.status == Some(OperationStatus::Success)) != success {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+enum Candidates {
+    Single(Option<TransactionDigest>),
+    Account(AccountTransactions),
+}
+
+impl Candidates {
+    async fn next(&mut self, client: &SuiClient) -> Result<Option<SuiTransactionResponse>, Error> {
+        match self {
+            Candidates::Single(digest) => match digest.take() {
+                Some(digest) => Ok(Some(
+                    client
+                        .read_api()
+                        .get_transaction_with_options(digest, response_options())
+                        .await?,
+                )),
+                None => Ok(None),
+            },
+            Candidates::Account(transactions) => transactions.next(client).await,
+        }
+    }
+}
+
+/// Transactions sent and received by an address, newest first.
+struct AccountTransactions {
+    sent: TransactionQuery,
+    received: TransactionQuery,
+    /// Transactions returned by checkpoint, to skip them when returned by the other query. Only
+    /// the checkpoints the queries have not yet gone past are kept.
+    seen: BTreeMap<u64, HashSet<TransactionDigest>>,
+}
+
+impl AccountTransactions {
+    fn new(address: SuiAddress) -> Self {
+        Self {
+            sent: TransactionQuery::new(TransactionFilter::FromAddress(address)),
+            received: TransactionQuery::new(TransactionFilter::ToAddress(address)),
+            seen: BTreeMap::new(),
+        }
+    }
+
+    async fn next(&mut self, client: &SuiClient) -> Result<Option<SuiTransactionResponse>, Error> {
+        loop {
+            // Transactions not yet in a checkpoint are the newest.
+            let sent = self
+                .sent
+                .peek(client)
+                .await?
+                .map(|tx| tx.checkpoint.unwrap_or(u64::MAX));
+            let received = self
+                .received
+                .peek(client)
+                .await?
+                .map(|tx| tx.checkpoint.unwrap_or(u64::MAX));
+            let next = match (sent, received) {
+                (None, None) => return Ok(None),
+                (Some(sent), Some(received)) if sent < received => self.received.pop(),
+                (Some(_), _) => self.sent.pop(),
+                (None, Some(_)) => self.received.pop(),
+            };
+            // Neither query returns transactions of later checkpoints anymore.
+            if let Some(newest) = sent.max(received).and_then(|newest| newest.checked_add(1)) {
+                self.seen.split_off(&newest);
+            }
+            if let Some(tx) = next {
+                if self
+                    .seen
+                    .entry(tx.checkpoint.unwrap_or(u64::MAX))
+                    .or_default()
+                    .insert(tx.digest)
+                {
+                    return Ok(Some(tx));
+                }
+            }
+        }
+    }
+
+    async fn has_more(&mut self, client: &SuiClient) -> Result<bool, Error> {
+        Ok(self.sent.peek(client).await?.is_some() || self.received.peek(client).await?.is_some())
+    }
+}
+
+/// Pages through the results of a descending `query_transactions`.
+struct TransactionQuery {
+    query: SuiTransactionResponseQuery,
+    cursor: Option<TransactionDigest>,
+    has_next_page: bool,
+    buffer: VecDeque<SuiTransactionResponse>,
+}
+
+impl TransactionQuery {
+    fn new(filter: TransactionFilter) -> Self {
+        Self {
+            query: SuiTransactionResponseQuery::new(Some(filter), Some(response_options())),
+            cursor: None,
+            has_next_page: true,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    async fn peek(&mut self, client: &SuiClient) -> Result<Option<&SuiTransactionResponse>, Error> {
+        if self.buffer.is_empty() && self.has_next_page {
+            let page = client
+                .read_api()
+                .query_transactions(self.query.clone(), self.cursor, Some(QUERY_PAGE_SIZE), true)
+                .await?;
+            self.cursor = page.next_cursor;
+            self.has_next_page = page.has_next_page;
+            self.buffer.extend(page.data);
+        }
+        Ok(self.buffer.front())
+    }
+
+    fn pop(&mut self) -> Option<SuiTransactionResponse> {
+        self.buffer.pop_front()
+    }
+}
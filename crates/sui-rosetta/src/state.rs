@@ -1,28 +1,34 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::operations::Operations;
+use crate::operations::{pay_coin_type, Operations};
+use crate::search::SearchCursors;
 use crate::types::{
-    Block, BlockHash, BlockIdentifier, BlockResponse, OperationStatus, OperationType, Transaction,
-    TransactionIdentifier,
+    Block, BlockHash, BlockIdentifier, BlockResponse, Currency, CurrencyMetadata, OperationStatus,
+    OperationType, Transaction, TransactionIdentifier,
 };
-use crate::Error;
+use crate::{Error, SUI};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use rocksdb::Options;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::str::FromStr;
-use std::sync::Arc;
-use std::time::{Duration, UNIX_EPOCH};
-use sui_json_rpc_types::SuiTransactionResponseOptions;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use sui_json_rpc_types::{
+    SuiCommand, SuiTransactionDataAPI, SuiTransactionKind, SuiTransactionResponse,
+    SuiTransactionResponseOptions,
+};
 use sui_sdk::rpc_types::Checkpoint;
 use sui_sdk::SuiClient;
-use sui_types::base_types::{EpochId, SuiAddress};
+use sui_types::base_types::{EpochId, SuiAddress, TransactionDigest};
+use sui_types::gas_coin::GAS;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::{debug, error, info, warn};
-use typed_store::rocks::{point_lookup_db_options, DBMap, DBOptions, MetricConf};
+use typed_store::rocks::{point_lookup_db_options, DBMap, DBOptions, MetricConf, TypedStoreError};
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
 use typed_store::Map;
@@ -31,17 +37,35 @@ use typed_store_derive::DBMapUtils;
 #[cfg(test)]
 #[path = "unit_tests/balance_changing_tx_tests.rs"]
 mod balance_changing_tx_tests;
+#[cfg(test)]
+#[path = "unit_tests/mempool_tests.rs"]
+mod mempool_tests;
+
+/// How long a transaction is kept in the mempool at most, for the ones the fullnode never reports
+/// in a checkpoint not to stay there forever.
+const MEMPOOL_TTL: Duration = Duration::from_secs(600);
 
 #[derive(Clone)]
 pub struct OnlineServerContext {
     pub client: SuiClient,
+    pub coin_metadata_cache: CoinMetadataCache,
+    pub mempool: Mempool,
+    pub(crate) search_cursors: SearchCursors,
     block_provider: Arc<dyn BlockProvider + Send + Sync>,
 }
 
 impl OnlineServerContext {
-    pub fn new(client: SuiClient, block_provider: Arc<dyn BlockProvider + Send + Sync>) -> Self {
+    pub fn new(
+        client: SuiClient,
+        coin_metadata_cache: CoinMetadataCache,
+        mempool: Mempool,
+        block_provider: Arc<dyn BlockProvider + Send + Sync>,
+    ) -> Self {
         Self {
             client,
+            coin_metadata_cache,
+            mempool,
+            search_cursors: SearchCursors::default(),
             block_provider,
         }
     }
@@ -59,17 +83,146 @@ pub trait BlockProvider {
     async fn genesis_block_identifier(&self) -> Result<BlockIdentifier, Error>;
     async fn oldest_block_identifier(&self) -> Result<BlockIdentifier, Error>;
     async fn current_block_identifier(&self) -> Result<BlockIdentifier, Error>;
+    async fn get_block_identifier(&self, index: u64) -> Result<BlockIdentifier, Error>;
     async fn get_balance_at_block(
         &self,
         addr: SuiAddress,
+        currency: &Currency,
         block_height: u64,
     ) -> Result<i128, Error>;
 }
 
+/// Currencies of the coin types other than SUI, from the `CoinMetadata` of each coin type.
+#[derive(Clone)]
+pub struct CoinMetadataCache {
+    client: SuiClient,
+    currencies: Arc<Mutex<HashMap<TypeTag, Currency>>>,
+}
+
+impl CoinMetadataCache {
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            client,
+            currencies: Default::default(),
+        }
+    }
+
+    pub async fn get_currency(&self, coin_type: &TypeTag) -> Result<Currency, Error> {
+        if *coin_type == GAS::type_tag() {
+            return Ok(SUI.clone());
+        }
+        if let Some(currency) = self.currencies.lock().unwrap().get(coin_type) {
+            return Ok(currency.clone());
+        }
+        let metadata = self
+            .client
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_string())
+            .await?;
+        let currency = Currency {
+            symbol: metadata.symbol,
+            decimals: metadata.decimals.into(),
+            metadata: Some(CurrencyMetadata {
+                coin_type: coin_type.to_string(),
+            }),
+        };
+        self.currencies
+            .lock()
+            .unwrap()
+            .insert(coin_type.clone(), currency.clone());
+        Ok(currency)
+    }
+
+    /// Currencies of the coin types of the balance changes and the payments of `response`. Fails
+    /// if the metadata of one of them cannot be read.
+    pub async fn get_currencies(
+        &self,
+        response: &SuiTransactionResponse,
+    ) -> Result<HashMap<TypeTag, Currency>, Error> {
+        let payments = response
+            .transaction
+            .iter()
+            .flat_map(|tx| match tx.data.transaction() {
+                SuiTransactionKind::ProgrammableTransaction(pt) => pt.commands.iter().collect(),
+                _ => vec![],
+            })
+            .filter_map(|command| match command {
+                SuiCommand::MoveCall(call) if Operations::is_pay_call(call) => pay_coin_type(call),
+                _ => None,
+            });
+        let coin_types = response
+            .balance_changes
+            .iter()
+            .flatten()
+            .map(|balance_change| balance_change.coin_type.clone())
+            .chain(payments);
+
+        let mut currencies = HashMap::new();
+        for coin_type in coin_types {
+            if !currencies.contains_key(&coin_type) {
+                let currency = self.get_currency(&coin_type).await?;
+                currencies.insert(coin_type, currency);
+            }
+        }
+        Ok(currencies)
+    }
+
+    /// Operations of the transaction of `response`, in the currencies of the coin types involved.
+    pub async fn operations(&self, response: SuiTransactionResponse) -> Result<Operations, Error> {
+        let currencies = self.get_currencies(&response).await?;
+        Operations::try_from_response(response, &currencies)
+    }
+}
+
+/// Transactions submitted through `/construction/submit` which are not yet in an indexed
+/// checkpoint, with when they were submitted.
+#[derive(Clone, Default)]
+pub struct Mempool {
+    transactions: Arc<Mutex<BTreeMap<TransactionDigest, (Instant, SuiTransactionResponse)>>>,
+}
+
+impl Mempool {
+    pub fn insert(&self, response: SuiTransactionResponse) {
+        self.transactions
+            .lock()
+            .unwrap()
+            .insert(response.digest, (Instant::now(), response));
+    }
+
+    pub fn get(&self, digest: &TransactionDigest) -> Option<SuiTransactionResponse> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .get(digest)
+            .map(|(_, response)| response.clone())
+    }
+
+    pub fn digests(&self) -> Vec<TransactionDigest> {
+        self.transactions.lock().unwrap().keys().copied().collect()
+    }
+
+    fn remove<'a>(&self, digests: impl IntoIterator<Item = &'a TransactionDigest>) {
+        let mut transactions = self.transactions.lock().unwrap();
+        for digest in digests {
+            transactions.remove(digest);
+        }
+    }
+
+    /// Removes the transactions submitted more than `ttl` ago.
+    fn remove_expired(&self, ttl: Duration) {
+        self.transactions
+            .lock()
+            .unwrap()
+            .retain(|_, (submitted, _)| submitted.elapsed() < ttl);
+    }
+}
+
 #[derive(Clone)]
 pub struct CheckpointBlockProvider {
     index_store: Arc<CheckpointIndexStore>,
     client: SuiClient,
+    coin_metadata_cache: CoinMetadataCache,
+    mempool: Mempool,
 }
 
 #[async_trait]
@@ -102,35 +255,64 @@ impl BlockProvider for CheckpointBlockProvider {
             .await
     }
 
+    async fn get_block_identifier(&self, index: u64) -> Result<BlockIdentifier, Error> {
+        self.create_block_identifier(index).await
+    }
+
     async fn get_balance_at_block(
         &self,
         addr: SuiAddress,
+        currency: &Currency,
         block_height: u64,
     ) -> Result<i128, Error> {
+        if currency.is_sui() {
+            return Ok(self
+                .index_store
+                .balances
+                .iter()
+                .skip_prior_to(&(addr, block_height))?
+                .next()
+                .and_then(
+                    |((address, _), balance)| {
+                        if address == addr {
+                            Some(balance)
+                        } else {
+                            None
+                        }
+                    },
+                )
+                .unwrap_or_default());
+        }
+        let coin_type = currency.coin_type()?.to_string();
         Ok(self
             .index_store
-            .balances
+            .coin_balances
             .iter()
-            .skip_prior_to(&(addr, block_height))?
+            .skip_prior_to(&(addr, coin_type.clone(), block_height))?
             .next()
-            .and_then(
-                |((address, _), balance)| {
-                    if address == addr {
-                        Some(balance)
-                    } else {
-                        None
-                    }
-                },
-            )
+            .and_then(|((address, balance_coin_type, _), balance)| {
+                if address == addr && balance_coin_type == coin_type {
+                    Some(balance)
+                } else {
+                    None
+                }
+            })
             .unwrap_or_default())
     }
 }
 
 impl CheckpointBlockProvider {
-    pub fn spawn(client: SuiClient, db_path: &Path) -> Self {
+    pub fn spawn(
+        client: SuiClient,
+        coin_metadata_cache: CoinMetadataCache,
+        mempool: Mempool,
+        db_path: &Path,
+    ) -> Self {
         let blocks = Self {
             index_store: Arc::new(CheckpointIndexStore::open(db_path, None)),
             client,
+            coin_metadata_cache,
+            mempool,
         };
 
         let update_interval = option_env!("CHECKPOINT_UPDATE_INTERVAL")
@@ -141,8 +323,13 @@ impl CheckpointBlockProvider {
 
         let f = blocks.clone();
         spawn_monitored_task!(async move {
+            if !f.index_store.is_empty() && !f.index_store.is_current().unwrap() {
+                info!("Index Store was built by an older version, re-indexing from genesis block.");
+                f.index_store.reset().unwrap();
+            }
             if f.index_store.is_empty() {
                 info!("Index Store is empty, indexing genesis block.");
+                f.index_store.set_current().unwrap();
                 let mut checkpoint = None;
                 while checkpoint.is_none() {
                     checkpoint = f.client.read_api().get_checkpoint(0.into()).await.ok();
@@ -184,13 +371,35 @@ impl CheckpointBlockProvider {
                     checkpoint.transactions.len(),
                     DateTime::<Utc>::from(timestamp).format("%Y-%m-%d %H:%M:%S")
                 );
+                let digests = checkpoint.transactions.clone();
                 let resp = self.create_block_response(checkpoint).await?;
                 self.update_balance(resp.block).await?;
                 self.index_store.last_checkpoint.insert(&true, &seq)?;
+                self.mempool.remove(&digests);
             }
         } else {
             debug!("No new checkpoints.")
         };
+        self.prune_mempool().await
+    }
+
+    /// Removes the transactions of the mempool in an indexed checkpoint which were not removed
+    /// with it, as they were submitted after it was indexed, and the ones past `MEMPOOL_TTL`.
+    async fn prune_mempool(&self) -> Result<(), Error> {
+        self.mempool.remove_expired(MEMPOOL_TTL);
+        let last_checkpoint = self.last_indexed_checkpoint()?;
+        let mut indexed = vec![];
+        for digest in self.mempool.digests() {
+            let response = self
+                .client
+                .read_api()
+                .get_transaction_with_options(digest, SuiTransactionResponseOptions::new())
+                .await?;
+            if matches!(response.checkpoint, Some(checkpoint) if checkpoint <= last_checkpoint) {
+                indexed.push(digest);
+            }
+        }
+        self.mempool.remove(&indexed);
         Ok(())
     }
 
@@ -201,28 +410,37 @@ impl CheckpointBlockProvider {
         } else {
             block_height - 1
         };
-        let balances: HashMap<SuiAddress, i128> =
+        let balances: HashMap<(SuiAddress, Currency), i128> =
             block
                 .transactions
                 .into_iter()
                 .fold(HashMap::new(), |mut changes, tx| {
-                    for (address, balance) in extract_balance_changes_from_ops(tx.operations) {
-                        *changes.entry(address).or_default() += balance;
+                    for (key, balance) in extract_balance_changes_from_ops(tx.operations) {
+                        *changes.entry(key).or_default() += balance;
                     }
                     changes
                 });
 
-        for (addr, value) in balances {
-            let current_balance = self.get_balance_at_block(addr, last_block_height).await?;
+        for ((addr, currency), value) in balances {
+            let current_balance = self
+                .get_balance_at_block(addr, &currency, last_block_height)
+                .await?;
             let new_balance = current_balance + value;
             if new_balance < 0 {
                 // This can happen due to missing transactions data due to unstable validators, causing balance to
                 // fall below zero temporarily. The problem should go away when we start using checkpoints for event and indexing
-                warn!("Account gas value fall below 0 at block {block_height}, address: [{addr}], current balance = {current_balance}, balance change = {value}.");
+                warn!("Account {} value fall below 0 at block {block_height}, address: [{addr}], current balance = {current_balance}, balance change = {value}.", currency.symbol);
+            }
+            if currency.is_sui() {
+                self.index_store
+                    .balances
+                    .insert(&(addr, block_height), &new_balance)?;
+            } else {
+                let coin_type = currency.coin_type()?.to_string();
+                self.index_store
+                    .coin_balances
+                    .insert(&(addr, coin_type, block_height), &new_balance)?;
             }
-            self.index_store
-                .balances
-                .insert(&(addr, block_height), &new_balance)?;
         }
         Ok(())
     }
@@ -246,7 +464,7 @@ impl CheckpointBlockProvider {
                 .await?;
             transactions.push(Transaction {
                 transaction_identifier: TransactionIdentifier { hash: tx.digest },
-                operations: Operations::try_from(tx)?,
+                operations: self.coin_metadata_cache.operations(tx).await?,
                 related_transactions: vec![],
                 metadata: None,
             })
@@ -303,32 +521,45 @@ impl CheckpointBlockProvider {
     }
 }
 
-fn extract_balance_changes_from_ops(ops: Operations) -> HashMap<SuiAddress, i128> {
-    ops.into_iter()
-        .fold(HashMap::<SuiAddress, i128>::new(), |mut changes, op| {
+fn extract_balance_changes_from_ops(ops: Operations) -> HashMap<(SuiAddress, Currency), i128> {
+    ops.into_iter().fold(
+        HashMap::<(SuiAddress, Currency), i128>::new(),
+        |mut changes, op| {
             if let Some(OperationStatus::Success) = op.status {
                 match op.type_ {
                     OperationType::SuiBalanceChange
                     | OperationType::Gas
                     | OperationType::PaySui
+                    | OperationType::Pay
                     | OperationType::Stake => {
                         if let (Some(addr), Some(amount)) = (op.account, op.amount) {
-                            *changes.entry(addr.address).or_default() += amount.value
+                            *changes.entry((addr.address, amount.currency)).or_default() +=
+                                amount.value
                         }
                     }
                     _ => {}
                 };
             }
             changes
-        })
+        },
+    )
 }
 
+/// Version of the content of `CheckpointIndexStore`, a store built by another version is re-indexed
+/// from the genesis block. Version 1 added `coin_balances`.
+const INDEX_VERSION: u64 = 1;
+
 #[derive(DBMapUtils)]
 pub struct CheckpointIndexStore {
     #[default_options_override_fn = "default_config"]
     balances: DBMap<(SuiAddress, EpochId), i128>,
+    /// Balances of the coin types other than SUI, by address, coin type and block height.
+    #[default_options_override_fn = "default_config"]
+    coin_balances: DBMap<(SuiAddress, String, u64), i128>,
     #[default_options_override_fn = "default_config"]
     last_checkpoint: DBMap<bool, CheckpointSequenceNumber>,
+    #[default_options_override_fn = "default_config"]
+    index_version: DBMap<bool, u64>,
 }
 
 impl CheckpointIndexStore {
@@ -342,7 +573,24 @@ impl CheckpointIndexStore {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.last_checkpoint.is_empty() && self.balances.is_empty()
+        self.last_checkpoint.is_empty() && self.balances.is_empty() && self.coin_balances.is_empty()
+    }
+
+    /// Whether the store was built by this version of the indexing, stores built before
+    /// `index_version` was added have none.
+    fn is_current(&self) -> Result<bool, TypedStoreError> {
+        Ok(self.index_version.get(&true)? == Some(INDEX_VERSION))
+    }
+
+    fn set_current(&self) -> Result<(), TypedStoreError> {
+        self.index_version.insert(&true, &INDEX_VERSION)
+    }
+
+    fn reset(&self) -> Result<(), TypedStoreError> {
+        self.balances.clear()?;
+        self.coin_balances.clear()?;
+        self.last_checkpoint.clear()?;
+        self.index_version.clear()
     }
 }
 
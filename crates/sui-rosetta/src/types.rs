@@ -1,13 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use fastcrypto::encoding::Hex;
 use fastcrypto::traits::ToFromBytes;
+use move_core_types::language_storage::TypeTag;
 use serde::de::Error as DeError;
 use serde::{Deserialize, Serializer};
 use serde::{Deserializer, Serialize};
@@ -17,15 +20,18 @@ use strum_macros::EnumString;
 
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionKind};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::coin::{PAY_MODULE_NAME, PAY_SPLIT_AND_TRANSFER_FUNC_NAME};
 use sui_types::crypto::PublicKey as SuiPublicKey;
 use sui_types::crypto::SignatureScheme;
+use sui_types::gas_coin::GAS;
 use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages::{Argument, CallArg, Command, ObjectArg, TransactionData};
 use sui_types::messages_checkpoint::CheckpointDigest;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::{
-    SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+    parse_sui_type_tag, SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
 };
 
 use crate::errors::{Error, ErrorType};
@@ -98,11 +104,74 @@ impl From<SuiAddress> for AccountIdentifier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+/// A currency is identified by its coin type, SUI when it has no metadata. The symbol and decimals
+/// come from the `CoinMetadata` of the coin type, and are not compared.
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Currency {
     pub symbol: String,
     pub decimals: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CurrencyMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CurrencyMetadata {
+    pub coin_type: String,
+}
+
+impl Currency {
+    /// The currency of `coin_type` among the `currencies` built from the `CoinMetadata` of their
+    /// coin type. Fails if the currency of a coin type other than SUI is not known, its decimals
+    /// cannot be guessed.
+    pub fn from_coin_type(
+        coin_type: &TypeTag,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
+        if *coin_type == GAS::type_tag() {
+            return Ok(SUI.clone());
+        }
+        currencies.get(coin_type).cloned().ok_or_else(|| {
+            Error::DataError(format!(
+                "Unknown currency of coin type [{coin_type}], its CoinMetadata is not available"
+            ))
+        })
+    }
+
+    pub fn coin_type(&self) -> Result<TypeTag, Error> {
+        match &self.metadata {
+            None => Ok(GAS::type_tag()),
+            Some(CurrencyMetadata { coin_type }) => parse_sui_type_tag(coin_type)
+                .map_err(|e| Error::InvalidInput(format!("Invalid coin type [{coin_type}]: {e}"))),
+        }
+    }
+
+    pub fn is_sui(&self) -> bool {
+        self.coin_type()
+            .map_or(false, |coin_type| coin_type == GAS::type_tag())
+    }
+
+    fn key(&self) -> String {
+        match self.coin_type() {
+            Ok(coin_type) => coin_type.to_string(),
+            Err(_) => format!("{:?}", self.metadata),
+        }
+    }
 }
+
+impl PartialEq for Currency {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Currency {}
+
+impl Hash for Currency {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AccountBalanceRequest {
     pub network_identifier: NetworkIdentifier,
@@ -155,6 +224,13 @@ impl Amount {
             metadata: None,
         }
     }
+    pub fn new_coin(value: i128, currency: Currency) -> Self {
+        Self {
+            value,
+            currency,
+            metadata: None,
+        }
+    }
     pub fn new_stake(value: i128, stake_id: ObjectID, validator: SuiAddress) -> Self {
         Self {
             value,
@@ -396,6 +472,7 @@ pub enum OperationType {
     StakePrinciple,
     // sui-rosetta supported operation type
     PaySui,
+    Pay,
     Stake,
     WithdrawStake,
     // All other Sui transaction types, readonly
@@ -537,6 +614,7 @@ pub struct ConstructionPreprocessRequest {
 #[derive(Serialize, Deserialize)]
 pub enum PreprocessMetadata {
     PaySui,
+    Pay,
     Delegation,
 }
 
@@ -823,6 +901,133 @@ impl IntoResponse for BlockTransactionResponse {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct SearchTransactionsRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<Operator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_identifier: Option<CoinIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<OperationStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "type")]
+    pub type_: Option<OperationType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    Or,
+    And,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchTransactionsResponse {
+    pub transactions: Vec<BlockTransaction>,
+    pub total_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
+impl IntoResponse for SearchTransactionsResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockTransaction {
+    pub block_identifier: BlockIdentifier,
+    pub transaction: Transaction,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventsBlocksRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventsBlocksResponse {
+    pub max_sequence: u64,
+    pub events: Vec<BlockEvent>,
+}
+
+impl IntoResponse for EventsBlocksResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockEvent {
+    pub sequence: u64,
+    pub block_identifier: BlockIdentifier,
+    #[serde(rename = "type")]
+    pub type_: BlockEventType,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEventType {
+    BlockAdded,
+    BlockRemoved,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MempoolRequest {
+    pub network_identifier: NetworkIdentifier,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MempoolResponse {
+    pub transaction_identifiers: Vec<TransactionIdentifier>,
+}
+
+impl IntoResponse for MempoolResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MempoolTransactionRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MempoolTransactionResponse {
+    pub transaction: Transaction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+impl IntoResponse for MempoolTransactionResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct PrefundedAccount {
     pub privkey: String,
@@ -838,6 +1043,12 @@ pub enum InternalOperation {
         recipients: Vec<SuiAddress>,
         amounts: Vec<u64>,
     },
+    Pay {
+        sender: SuiAddress,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        currency: Currency,
+    },
     Stake {
         sender: SuiAddress,
         validator: SuiAddress,
@@ -854,6 +1065,7 @@ impl InternalOperation {
     pub fn sender(&self) -> SuiAddress {
         match self {
             InternalOperation::PaySui { sender, .. }
+            | InternalOperation::Pay { sender, .. }
             | InternalOperation::Stake { sender, .. }
             | InternalOperation::WithdrawStake { sender, .. } => *sender,
         }
@@ -870,6 +1082,40 @@ impl InternalOperation {
                 builder.pay_sui(recipients, amounts)?;
                 builder.finish()
             }
+            Self::Pay {
+                recipients,
+                amounts,
+                currency,
+                ..
+            } => {
+                // The coins are paid with `pay::split_and_transfer`, for the coin type to be part
+                // of the transaction.
+                let coin_type = currency.coin_type()?;
+                let mut builder = ProgrammableTransactionBuilder::new();
+                let mut coins = metadata.objects.into_iter();
+                let coin = coins.next().ok_or_else(|| {
+                    Error::InvalidInput(format!("No {} coin to pay with", currency.symbol))
+                })?;
+                let coin = builder.obj(ObjectArg::ImmOrOwnedObject(coin))?;
+                let merged = coins
+                    .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if !merged.is_empty() {
+                    builder.command(Command::MergeCoins(coin, merged));
+                }
+                for (recipient, amount) in recipients.into_iter().zip(amounts) {
+                    let amount = builder.pure(amount)?;
+                    let recipient = builder.pure(recipient)?;
+                    builder.programmable_move_call(
+                        SUI_FRAMEWORK_OBJECT_ID,
+                        PAY_MODULE_NAME.to_owned(),
+                        PAY_SPLIT_AND_TRANSFER_FUNC_NAME.to_owned(),
+                        vec![coin_type.clone()],
+                        vec![coin, amount, recipient],
+                    );
+                }
+                builder.finish()
+            }
             InternalOperation::Stake {
                 validator, amount, ..
             } => {
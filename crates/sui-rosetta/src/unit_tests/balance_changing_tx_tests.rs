@@ -7,6 +7,7 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
 use rand::seq::{IteratorRandom, SliceRandom};
 use serde_json::json;
 use signature::rand_core::OsRng;
@@ -26,15 +27,17 @@ use sui_sdk::rpc_types::{
 };
 use sui_sdk::SuiClient;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
-use sui_types::gas_coin::GasCoin;
+use sui_types::coin::Coin;
+use sui_types::gas_coin::GAS;
 use sui_types::messages::{
     CallArg, ExecuteTransactionRequestType, InputObjectKind, ObjectArg, ProgrammableTransaction,
     Transaction, TransactionData, TransactionDataAPI, TransactionKind, DUMMY_GAS_PRICE,
 };
+use sui_types::parse_sui_type_tag;
 use test_utils::network::TestClusterBuilder;
 
-use crate::state::extract_balance_changes_from_ops;
-use crate::types::ConstructionMetadata;
+use crate::state::{extract_balance_changes_from_ops, CoinMetadataCache};
+use crate::types::{ConstructionMetadata, InternalOperation};
 
 #[tokio::test]
 async fn test_transfer_sui() {
@@ -180,6 +183,105 @@ async fn test_publish_and_move_call() {
     test_transaction(&client, keystore, vec![], sender, pt, vec![], 10000, false).await;
 }
 
+#[tokio::test]
+async fn test_pay_custom_coin() {
+    let network = TestClusterBuilder::new().build().await.unwrap();
+    let client = network.wallet.get_client().await.unwrap();
+    let keystore = &network.wallet.config.keystore;
+
+    // Publish the `managed` coin and mint some to the sender
+    let sender = get_random_address(&network.accounts, vec![]);
+    let recipient = get_random_address(&network.accounts, vec![sender]);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../sui_programmability/examples/fungible_tokens");
+    let compiled_package =
+        sui_framework::build_move_package(&path, BuildConfig::new_for_testing()).unwrap();
+    let compiled_modules_bytes =
+        compiled_package.get_package_bytes(/* with_unpublished_deps */ false);
+    let dependencies = compiled_package.get_dependency_original_package_ids();
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.publish_immutable(compiled_modules_bytes, dependencies);
+        builder.finish()
+    };
+    let response =
+        test_transaction(&client, keystore, vec![], sender, pt, vec![], 10000, false).await;
+    let object_changes = response.object_changes.unwrap();
+    let package = object_changes
+        .iter()
+        .find_map(|change| {
+            if let ObjectChange::Published { package_id, .. } = change {
+                Some(*package_id)
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    let treasury = find_module_object(&object_changes, "::TreasuryCap")
+        .reference
+        .to_object_ref();
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                package,
+                Identifier::from_str("managed").unwrap(),
+                Identifier::from_str("mint").unwrap(),
+                vec![],
+                vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(treasury)),
+                    CallArg::Pure(bcs::to_bytes(&10000u64).unwrap()),
+                    CallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+                ],
+            )
+            .unwrap();
+        builder.finish()
+    };
+    let response =
+        test_transaction(&client, keystore, vec![], sender, pt, vec![], 10000, false).await;
+    let coin = find_module_object(&response.object_changes.unwrap(), "::managed::MANAGED>")
+        .reference
+        .to_object_ref();
+
+    // Test Pay of the `managed` coin, in its currency
+    let coin_type = parse_sui_type_tag(&format!("{package}::managed::MANAGED")).unwrap();
+    let currency = CoinMetadataCache::new(client.clone())
+        .get_currency(&coin_type)
+        .await
+        .unwrap();
+    assert_eq!(currency.symbol, "MANAGED");
+    assert_eq!(currency.decimals, 2);
+    let data = InternalOperation::Pay {
+        sender,
+        recipients: vec![recipient],
+        amounts: vec![1000],
+        currency,
+    }
+    .try_into_data(ConstructionMetadata {
+        sender,
+        coins: vec![],
+        objects: vec![coin],
+        total_coin_value: 0,
+        gas_price: DUMMY_GAS_PRICE,
+        budget: 10000,
+    })
+    .unwrap();
+    let TransactionKind::ProgrammableTransaction(pt) = data.into_kind() else {
+        unreachable!()
+    };
+    test_transaction(
+        &client,
+        keystore,
+        vec![recipient],
+        sender,
+        pt,
+        vec![],
+        10000,
+        false,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_split_coin() {
     let network = TestClusterBuilder::new().build().await.unwrap();
@@ -605,7 +707,7 @@ async fn test_transaction(
     let mut addr_to_check = addr_to_check;
     addr_to_check.push(sender);
     for addr in addr_to_check {
-        balances.insert(addr, get_balance(client, addr).await);
+        balances.insert(addr, get_balances(client, addr).await);
     }
 
     let response = client
@@ -637,15 +739,32 @@ async fn test_transaction(
         ));
     }
 
-    let ops = response.clone().try_into().unwrap();
-    let balances_from_ops = extract_balance_changes_from_ops(ops);
-
-    // get actual balance changed after transaction
+    let ops = CoinMetadataCache::new(client.clone())
+        .operations(response.clone())
+        .await
+        .unwrap();
+    let balances_from_ops = extract_balance_changes_from_ops(ops)
+        .into_iter()
+        .filter(|((_, currency), balance)| currency.is_sui() || *balance != 0)
+        .map(|((addr, currency), balance)| ((addr, currency.coin_type().unwrap()), balance))
+        .collect::<HashMap<_, _>>();
+
+    // get actual balance changed after transaction, the SUI balance of every address checked and
+    // the other balances that changed
     let mut actual_balance_change = HashMap::new();
-    for (addr, balance) in balances {
-        let new_balance = get_balance(client, addr).await as i128;
-        let balance_changed = new_balance - balance as i128;
-        actual_balance_change.insert(addr, balance_changed);
+    for (addr, old_balances) in balances {
+        let mut new_balances = get_balances(client, addr).await;
+        new_balances.entry(GAS::type_tag()).or_default();
+        for coin_type in old_balances.keys() {
+            new_balances.entry(coin_type.clone()).or_default();
+        }
+        for (coin_type, new_balance) in new_balances {
+            let old_balance = old_balances.get(&coin_type).copied().unwrap_or_default();
+            let balance_changed = new_balance as i128 - old_balance as i128;
+            if coin_type == GAS::type_tag() || balance_changed != 0 {
+                actual_balance_change.insert((addr, coin_type), balance_changed);
+            }
+        }
     }
     assert_eq!(
         actual_balance_change, balances_from_ops,
@@ -699,8 +818,9 @@ fn get_random_address(addresses: &[SuiAddress], except: Vec<SuiAddress>) -> SuiA
         .unwrap()
 }
 
-async fn get_balance(client: &SuiClient, address: SuiAddress) -> u64 {
-    let coins = client
+/// Balances of `address` by coin type.
+async fn get_balances(client: &SuiClient, address: SuiAddress) -> BTreeMap<TypeTag, u64> {
+    let objects = client
         .read_api()
         .get_owned_objects(
             address,
@@ -718,26 +838,24 @@ async fn get_balance(client: &SuiClient, address: SuiAddress) -> u64 {
         .unwrap()
         .data;
 
-    let mut balance = 0u64;
-    for coin in coins {
-        let obj = coin.into_object().unwrap();
-        if obj.is_gas_coin() {
-            let object = client
-                .read_api()
-                .get_object_with_options(obj.object_id, SuiObjectDataOptions::new().with_bcs())
-                .await
-                .unwrap();
-            let coin: GasCoin = object
-                .into_object()
-                .unwrap()
-                .bcs
-                .unwrap()
-                .try_as_move()
-                .unwrap()
-                .deserialize()
-                .unwrap();
-            balance += coin.value()
+    let mut balances = BTreeMap::new();
+    for object in objects {
+        let obj = object.into_object().unwrap();
+        let object = client
+            .read_api()
+            .get_object_with_options(obj.object_id, SuiObjectDataOptions::new().with_bcs())
+            .await
+            .unwrap();
+        let move_object = object.into_object().unwrap().bcs.unwrap();
+        let Some(move_object) = move_object.try_as_move() else {
+            continue;
+        };
+        if !Coin::is_coin(&move_object.type_) {
+            continue;
         }
+        let coin_type = move_object.type_.type_params[0].clone();
+        let coin: Coin = move_object.deserialize().unwrap();
+        *balances.entry(coin_type).or_default() += coin.value();
     }
-    balance
+    balances
 }
@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_json_rpc_types::{SuiTransactionResponse, SuiTransactionResponseOptions};
+use sui_types::base_types::TransactionDigest;
+use test_utils::network::TestClusterBuilder;
+
+use crate::state::{CheckpointBlockProvider, CoinMetadataCache, Mempool};
+
+#[test]
+fn test_mempool_expired_transactions() {
+    let mempool = Mempool::default();
+    let digest = TransactionDigest::random();
+    mempool.insert(SuiTransactionResponse::new(digest));
+
+    mempool.remove_expired(Duration::from_secs(600));
+    assert_eq!(vec![digest], mempool.digests());
+
+    mempool.remove_expired(Duration::ZERO);
+    assert!(mempool.digests().is_empty());
+}
+
+#[tokio::test]
+async fn test_mempool_transaction_submitted_after_its_checkpoint_was_indexed() {
+    let network = TestClusterBuilder::new().build().await.unwrap();
+    let client = network.wallet.get_client().await.unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let mempool = Mempool::default();
+    let _blocks = CheckpointBlockProvider::spawn(
+        client.clone(),
+        CoinMetadataCache::new(client.clone()),
+        mempool.clone(),
+        tmp.path(),
+    );
+
+    // A transaction of the genesis checkpoint, indexed before it is added to the mempool, as
+    // `/construction/submit` sees it when the execution returns before the checkpoint is known.
+    let checkpoint = client.read_api().get_checkpoint(0.into()).await.unwrap();
+    let mut response = client
+        .read_api()
+        .get_transaction_with_options(
+            checkpoint.transactions[0],
+            SuiTransactionResponseOptions::new(),
+        )
+        .await
+        .unwrap();
+    response.checkpoint = None;
+    mempool.insert(response);
+
+    for _ in 0..30 {
+        if mempool.digests().is_empty() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    panic!("indexed transaction was not removed from the mempool");
+}
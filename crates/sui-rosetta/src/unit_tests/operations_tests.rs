@@ -1,15 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use fastcrypto::encoding::{Encoding, Hex};
 
 use shared_crypto::intent::IntentMessage;
+use sui_json_rpc_types::SuiTransactionData;
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::messages::TransactionData;
+use sui_types::parse_sui_type_tag;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 
 use crate::operations::Operations;
-use crate::types::{ConstructionMetadata, OperationType};
+use crate::types::{
+    ConstructionMetadata, Currency, CurrencyMetadata, InternalOperation, OperationType,
+};
 
 #[tokio::test]
 async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
@@ -44,6 +50,61 @@ async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+#[tokio::test]
+async fn test_pay_operation_data_parsing() -> Result<(), anyhow::Error> {
+    let random_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        )
+    };
+    let gas = random_ref();
+    let coins = vec![random_ref(), random_ref()];
+    let sender = SuiAddress::random_for_testing_only();
+    let coin_type = parse_sui_type_tag("0x6b1e::usdc::USDC")?;
+    let currency = Currency {
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        metadata: Some(CurrencyMetadata {
+            coin_type: coin_type.to_string(),
+        }),
+    };
+    let currencies = HashMap::from([(coin_type, currency.clone())]);
+
+    let metadata = || ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: coins.clone(),
+        total_coin_value: 0,
+        gas_price: 1,
+        budget: 1000,
+    };
+    let data = InternalOperation::Pay {
+        sender,
+        recipients: vec![SuiAddress::random_for_testing_only()],
+        amounts: vec![10000],
+        currency: currency.clone(),
+    }
+    .try_into_data(metadata())?;
+
+    // The currency of the payment cannot be told without its metadata.
+    assert!(Operations::try_from(data.clone()).is_err());
+
+    let ops = Operations::try_from_data(SuiTransactionData::try_from(data.clone())?, &currencies)?;
+    assert_eq!(OperationType::Pay, ops.type_().unwrap());
+    let InternalOperation::Pay { currency: parsed_currency, .. } = ops.clone().into_internal()? else {
+        panic!("Expected a Pay operation");
+    };
+    assert_eq!(currency, parsed_currency);
+    assert_eq!(currency.decimals, parsed_currency.decimals);
+
+    let parsed_data = ops.into_internal()?.try_into_data(metadata())?;
+    assert_eq!(data, parsed_data);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_shorter_bytearray_bug() {
     // Sometime CallArg::Pure(Vec<u8>) for u64 will serialise to 8 bytes array instead of 9 bytes (length + data), this is to test the work around until we fix it in Sui Json.
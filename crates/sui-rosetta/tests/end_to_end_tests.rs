@@ -10,8 +10,10 @@ use sui_json_rpc_types::SuiTransactionResponseOptions;
 use sui_keys::keystore::AccountKeystore;
 use sui_rosetta::operations::Operations;
 use sui_rosetta::types::{
-    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, NetworkIdentifier,
-    SubAccount, SubAccountType, SuiEnv,
+    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockEventType,
+    EventsBlocksRequest, EventsBlocksResponse, MempoolRequest, MempoolResponse,
+    MempoolTransactionRequest, MempoolTransactionResponse, NetworkIdentifier, OperationType,
+    SearchTransactionsRequest, SearchTransactionsResponse, SubAccount, SubAccountType, SuiEnv,
 };
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionEffectsAPI};
 use sui_types::messages::ExecuteTransactionRequestType;
@@ -486,3 +488,275 @@ async fn test_pay_sui_multiple_times() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_search_transactions_and_block_events() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) =
+        start_rosetta_test_server(client.clone(), test_cluster.swarm.dir()).await;
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    let digest = response.transaction_identifier.hash;
+
+    // Wait for the transaction to be included in a checkpoint.
+    let mut search = None;
+    for _ in 0..30 {
+        let response: SearchTransactionsResponse = rosetta_client
+            .call(
+                RosettaEndpoint::SearchTransactions,
+                &SearchTransactionsRequest {
+                    network_identifier: network_identifier.clone(),
+                    operator: None,
+                    max_block: None,
+                    offset: None,
+                    limit: None,
+                    transaction_identifier: None,
+                    account_identifier: Some(AccountIdentifier {
+                        address: recipient,
+                        sub_account: None,
+                    }),
+                    coin_identifier: None,
+                    currency: None,
+                    status: None,
+                    type_: Some(OperationType::PaySui),
+                    address: None,
+                    success: Some(true),
+                },
+            )
+            .await;
+        if !response.transactions.is_empty() {
+            search = Some(response);
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let search = search.expect("transaction not found by /search/transactions");
+    assert_eq!(1, search.transactions.len());
+    assert_eq!(
+        digest,
+        search.transactions[0]
+            .transaction
+            .transaction_identifier
+            .hash
+    );
+    assert!(search.transactions[0].transaction.operations.contains(&ops));
+
+    let events: EventsBlocksResponse = rosetta_client
+        .call(
+            RosettaEndpoint::EventsBlocks,
+            &EventsBlocksRequest {
+                network_identifier,
+                offset: Some(0),
+                limit: Some(10),
+            },
+        )
+        .await;
+    assert!(!events.events.is_empty());
+    for (sequence, event) in events.events.iter().enumerate() {
+        assert_eq!(sequence as u64, event.sequence);
+        assert_eq!(sequence as u64, event.block_identifier.index);
+        assert_eq!(BlockEventType::BlockAdded, event.type_);
+    }
+}
+
+#[tokio::test]
+async fn test_search_transactions_paging() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) =
+        start_rosetta_test_server(client.clone(), test_cluster.swarm.dir()).await;
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+
+    let ops: Operations = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    for _ in 0..3 {
+        rosetta_client.rosetta_flow(&ops, keystore).await;
+    }
+
+    let search = |offset, limit| SearchTransactionsRequest {
+        network_identifier: network_identifier.clone(),
+        operator: None,
+        max_block: None,
+        offset,
+        limit,
+        transaction_identifier: None,
+        account_identifier: Some(AccountIdentifier {
+            address: recipient,
+            sub_account: None,
+        }),
+        coin_identifier: None,
+        currency: None,
+        status: None,
+        type_: Some(OperationType::PaySui),
+        address: None,
+        success: Some(true),
+    };
+    let digests = |response: SearchTransactionsResponse| {
+        response
+            .transactions
+            .into_iter()
+            .map(|tx| tx.transaction.transaction_identifier.hash)
+            .collect::<Vec<_>>()
+    };
+
+    // Wait for the transactions to be included in a checkpoint.
+    let mut expected = vec![];
+    for _ in 0..30 {
+        let response: SearchTransactionsResponse = rosetta_client
+            .call(RosettaEndpoint::SearchTransactions, &search(None, None))
+            .await;
+        expected = digests(response);
+        if expected.len() == 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert_eq!(3, expected.len());
+
+    // Page through the transactions one by one, while a newer transaction is added.
+    let first: SearchTransactionsResponse = rosetta_client
+        .call(RosettaEndpoint::SearchTransactions, &search(None, Some(1)))
+        .await;
+    assert_eq!(Some(1), first.next_offset);
+    assert_eq!(1, first.total_count);
+    let mut paged = digests(first);
+
+    // A page which is not the next one of a search starts it over, moving past its offset.
+    let page: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(Some(2), Some(1)),
+        )
+        .await;
+    assert_eq!(None, page.next_offset);
+    assert_eq!(expected[2..], digests(page));
+
+    rosetta_client.rosetta_flow(&ops, keystore).await;
+    for offset in 1..3 {
+        let page: SearchTransactionsResponse = rosetta_client
+            .call(
+                RosettaEndpoint::SearchTransactions,
+                &search(Some(offset), Some(1)),
+            )
+            .await;
+        assert_eq!(offset + 1, page.total_count);
+        paged.extend(digests(page));
+    }
+    assert_eq!(expected, paged);
+}
+
+#[tokio::test]
+async fn test_mempool() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) =
+        start_rosetta_test_server(client.clone(), test_cluster.swarm.dir()).await;
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    let digest = response.transaction_identifier.hash;
+
+    // The transaction stays in the mempool until the checkpoint including it is indexed.
+    let mut in_mempool = true;
+    for _ in 0..30 {
+        let mempool: MempoolResponse = rosetta_client
+            .call(
+                RosettaEndpoint::Mempool,
+                &MempoolRequest {
+                    network_identifier: network_identifier.clone(),
+                },
+            )
+            .await;
+        if !mempool
+            .transaction_identifiers
+            .iter()
+            .any(|tx| tx.hash == digest)
+        {
+            in_mempool = false;
+            break;
+        }
+        let pending: MempoolTransactionResponse = rosetta_client
+            .call(
+                RosettaEndpoint::MempoolTransaction,
+                &MempoolTransactionRequest {
+                    network_identifier: network_identifier.clone(),
+                    transaction_identifier: response.transaction_identifier.clone(),
+                },
+            )
+            .await;
+        assert!(pending.transaction.operations.contains(&ops));
+        assert!(pending
+            .transaction
+            .operations
+            .iter()
+            .all(|op| op.status.is_none()));
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert!(!in_mempool, "transaction is still in the mempool");
+}
@@ -214,6 +214,10 @@ pub enum RosettaEndpoint {
     Submit,
     Metadata,
     Status,
+    SearchTransactions,
+    EventsBlocks,
+    Mempool,
+    MempoolTransaction,
 }
 
 impl RosettaEndpoint {
@@ -234,6 +238,10 @@ impl RosettaEndpoint {
             RosettaEndpoint::Submit => "construction/submit",
             RosettaEndpoint::Metadata => "construction/metadata",
             RosettaEndpoint::Status => "network/status",
+            RosettaEndpoint::SearchTransactions => "search/transactions",
+            RosettaEndpoint::EventsBlocks => "events/blocks",
+            RosettaEndpoint::Mempool => "mempool",
+            RosettaEndpoint::MempoolTransaction => "mempool/transaction",
         }
     }
 
@@ -253,7 +261,11 @@ impl RosettaEndpoint {
             | RosettaEndpoint::Transaction
             | RosettaEndpoint::Submit
             | RosettaEndpoint::Metadata
-            | RosettaEndpoint::Status => true,
+            | RosettaEndpoint::Status
+            | RosettaEndpoint::SearchTransactions
+            | RosettaEndpoint::EventsBlocks
+            | RosettaEndpoint::Mempool
+            | RosettaEndpoint::MempoolTransaction => true,
         }
     }
 }
@@ -5,13 +5,15 @@
 //! The main user of this data is the explorer.
 
 use std::cmp::{max, min};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::anyhow;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
 use tracing::debug;
 
 use sui_json_rpc_types::SuiObjectDataFilter;
@@ -24,12 +26,13 @@ use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::fp_ensure;
 use sui_types::messages::TransactionEvents;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::query::TransactionFilter;
-use typed_store::rocks::DBOptions;
 use typed_store::rocks::{default_db_options, point_lookup_db_options, DBMap, MetricConf};
+use typed_store::rocks::{DBBatch, DBOptions};
 use typed_store::traits::Map;
-use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::traits::{RawTable, TableSummary, TypedStoreDebug};
 use typed_store_derive::DBMapUtils;
 
 type OwnerIndexKey = (SuiAddress, ObjectID);
@@ -41,6 +44,82 @@ pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
 pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
 
+/// Number of transactions whose timestamps are read at once while pruning.
+const PRUNE_BATCH_SIZE: usize = 10_000;
+
+/// Maximum number of key prefixes of a table visited by one pruning call.
+const PRUNE_MAX_PREFIXES: usize = 100_000;
+
+/// The optional tables of `IndexStoreTables`. The transaction order, sequence and timestamp
+/// tables are always maintained since every other index and query refers to them.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum OptionalIndex {
+    TransactionsFromAddr,
+    TransactionsToAddr,
+    TransactionsByInputObjectId,
+    TransactionsByMutatedObjectId,
+    TransactionsByMoveFunction,
+    OwnerIndex,
+    DynamicFieldIndex,
+    EventOrder,
+    EventByMoveModule,
+    EventByMoveEvent,
+    EventBySender,
+    EventByTime,
+}
+
+impl OptionalIndex {
+    /// Returns true if the index is derived from the current object set rather than from
+    /// individual transactions, i.e. it can only be rebuilt from the live objects.
+    pub fn is_object_index(&self) -> bool {
+        matches!(self, Self::OwnerIndex | Self::DynamicFieldIndex)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexStoreConfig {
+    /// Indexes that are not maintained by this node. JSON-RPC queries served by a disabled
+    /// index fail with `SuiError::IndexDisabled`.
+    #[serde(default)]
+    pub disabled_indexes: BTreeSet<OptionalIndex>,
+    /// Number of most recent epochs whose transactions and events stay indexed. Index entries of
+    /// older epochs are pruned. All epochs are retained if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_retain: Option<u64>,
+    /// Number of most recent checkpoints whose transactions and events stay indexed. Index
+    /// entries of older checkpoints are pruned. All checkpoints are retained if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_checkpoints_to_retain: Option<u64>,
+}
+
+impl IndexStoreConfig {
+    pub fn is_enabled(&self, index: OptionalIndex) -> bool {
+        !self.disabled_indexes.contains(&index)
+    }
+
+    pub fn is_pruning_enabled(&self) -> bool {
+        self.num_epochs_to_retain.is_some() || self.num_checkpoints_to_retain.is_some()
+    }
+}
+
+#[derive(Default)]
 pub struct ObjectIndexChanges {
     pub deleted_owners: Vec<OwnerIndexKey>,
     pub deleted_dynamic_fields: Vec<DynamicFieldKey>,
@@ -110,11 +189,24 @@ pub struct IndexStoreTables {
     event_by_sender: DBMap<(SuiAddress, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
     event_by_time: DBMap<(u64, EventId), EventIndex>,
+
+    /// A singleton table that stores the latest checkpoint whose transactions and events were
+    /// pruned from the indexes.
+    pruned_checkpoint: DBMap<(), CheckpointSequenceNumber>,
+
+    /// A singleton table that stores the first transaction sequence number whose index entries
+    /// were not pruned.
+    pruned_sequence: DBMap<(), TxSequenceNumber>,
+
+    /// The key, serialized with BCS, where the pruning sweep of a table keyed by a prefix and a
+    /// transaction sequence number resumes, by table name.
+    prune_cursors: DBMap<String, Vec<u8>>,
 }
 
 pub struct IndexStore {
     next_sequence_number: AtomicU64,
     tables: IndexStoreTables,
+    config: IndexStoreConfig,
 }
 
 // These functions are used to initialize the DB tables
@@ -154,6 +246,10 @@ fn index_table_default_config() -> DBOptions {
 
 impl IndexStore {
     pub fn new(path: PathBuf) -> Self {
+        Self::new_with_config(path, IndexStoreConfig::default())
+    }
+
+    pub fn new_with_config(path: PathBuf, config: IndexStoreConfig) -> Self {
         let tables =
             IndexStoreTables::open_tables_read_write(path, MetricConf::default(), None, None);
         let next_sequence_number = tables
@@ -168,9 +264,24 @@ impl IndexStore {
         Self {
             tables,
             next_sequence_number,
+            config,
         }
     }
 
+    pub fn config(&self) -> &IndexStoreConfig {
+        &self.config
+    }
+
+    fn ensure_enabled(&self, index: OptionalIndex) -> SuiResult {
+        fp_ensure!(
+            self.config.is_enabled(index),
+            SuiError::IndexDisabled {
+                index: index.to_string(),
+            }
+        );
+        Ok(())
+    }
+
     pub fn index_tx(
         &self,
         sender: SuiAddress,
@@ -184,138 +295,522 @@ impl IndexStore {
     ) -> SuiResult<u64> {
         let sequence = self.next_sequence_number.fetch_add(1, Ordering::SeqCst);
 
-        let batch = self.tables.transactions_from_addr.batch();
+        let mut batch = self.tables.transactions_from_addr.batch();
 
-        let batch = batch.insert_batch(
+        batch.insert_batch_non_consuming(
             &self.tables.transaction_order,
             std::iter::once((sequence, *digest)),
         )?;
 
-        let batch = batch.insert_batch(
+        batch.insert_batch_non_consuming(
             &self.tables.transactions_seq,
             std::iter::once((*digest, sequence)),
         )?;
 
-        let batch = batch.insert_batch(
-            &self.tables.transactions_from_addr,
-            std::iter::once(((sender, sequence), *digest)),
+        batch.insert_batch_non_consuming(
+            &self.tables.timestamps,
+            std::iter::once((*digest, timestamp_ms)),
         )?;
 
-        let batch = batch.insert_batch(
-            &self.tables.transactions_by_input_object_id,
-            active_inputs.map(|id| ((id, sequence), *digest)),
+        let enabled = |index| self.config.is_enabled(index);
+        self.write_tx_indexes(
+            &mut batch,
+            enabled,
+            sequence,
+            sender,
+            active_inputs,
+            mutated_objects,
+            move_functions,
+            events,
+            digest,
+            timestamp_ms,
         )?;
+        self.write_object_index_changes(&mut batch, enabled, object_index_changes)?;
 
-        let batch = batch.insert_batch(
-            &self.tables.transactions_by_mutated_object_id,
-            mutated_objects
-                .clone()
-                .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
-        )?;
+        batch.write()?;
 
-        let batch = batch.insert_batch(
-            &self.tables.transactions_by_move_function,
-            move_functions.map(|(obj_id, module, function)| {
-                (
-                    (obj_id, module.to_string(), function.to_string(), sequence),
-                    *digest,
-                )
-            }),
-        )?;
+        Ok(sequence)
+    }
 
-        let batch = batch.insert_batch(
-            &self.tables.transactions_to_addr,
-            mutated_objects.filter_map(|(_, owner)| {
-                owner
-                    .get_owner_address()
-                    .ok()
-                    .map(|addr| ((addr, sequence), digest))
-            }),
+    /// Writes the entries of `indexes` for a transaction that has already been assigned
+    /// `sequence` in `transaction_order`. This is used to backfill indexes that were disabled
+    /// when the transaction was first indexed.
+    pub fn backfill_tx(
+        &self,
+        indexes: &BTreeSet<OptionalIndex>,
+        sequence: TxSequenceNumber,
+        sender: SuiAddress,
+        active_inputs: impl Iterator<Item = ObjectID>,
+        mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
+        move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        events: &TransactionEvents,
+        digest: &TransactionDigest,
+        timestamp_ms: u64,
+    ) -> SuiResult {
+        let mut batch = self.tables.transactions_from_addr.batch();
+        self.write_tx_indexes(
+            &mut batch,
+            |index| indexes.contains(&index),
+            sequence,
+            sender,
+            active_inputs,
+            mutated_objects,
+            move_functions,
+            events,
+            digest,
+            timestamp_ms,
         )?;
+        batch.write()?;
+        Ok(())
+    }
 
-        let batch = batch.insert_batch(
-            &self.tables.timestamps,
-            std::iter::once((*digest, timestamp_ms)),
+    /// Writes the object index entries among `indexes` from `object_index_changes`. Used together
+    /// with `reset_indexes` to rebuild the object indexes from the live object set.
+    pub fn backfill_object_indexes(
+        &self,
+        indexes: &BTreeSet<OptionalIndex>,
+        object_index_changes: ObjectIndexChanges,
+    ) -> SuiResult {
+        let mut batch = self.tables.owner_index.batch();
+        self.write_object_index_changes(
+            &mut batch,
+            |index| indexes.contains(&index),
+            object_index_changes,
         )?;
+        batch.write()?;
+        Ok(())
+    }
 
-        // Owner index
-        let batch = batch.delete_batch(
-            &self.tables.owner_index,
-            object_index_changes.deleted_owners.into_iter(),
-        )?;
-        let batch = batch.delete_batch(
-            &self.tables.dynamic_field_index,
-            object_index_changes.deleted_dynamic_fields.into_iter(),
-        )?;
-        let batch = batch.insert_batch(
-            &self.tables.owner_index,
-            object_index_changes.new_owners.into_iter(),
-        )?;
-        let batch = batch.insert_batch(
-            &self.tables.dynamic_field_index,
-            object_index_changes.new_dynamic_fields.into_iter(),
-        )?;
+    /// Removes every entry from the tables of `indexes`, so that they can be backfilled from
+    /// scratch.
+    pub fn reset_indexes(&self, indexes: &BTreeSet<OptionalIndex>) -> SuiResult {
+        for index in indexes {
+            match index {
+                OptionalIndex::TransactionsFromAddr => self.tables.transactions_from_addr.clear(),
+                OptionalIndex::TransactionsToAddr => self.tables.transactions_to_addr.clear(),
+                OptionalIndex::TransactionsByInputObjectId => {
+                    self.tables.transactions_by_input_object_id.clear()
+                }
+                OptionalIndex::TransactionsByMutatedObjectId => {
+                    self.tables.transactions_by_mutated_object_id.clear()
+                }
+                OptionalIndex::TransactionsByMoveFunction => {
+                    self.tables.transactions_by_move_function.clear()
+                }
+                OptionalIndex::OwnerIndex => self.tables.owner_index.clear(),
+                OptionalIndex::DynamicFieldIndex => self.tables.dynamic_field_index.clear(),
+                OptionalIndex::EventOrder => self.tables.event_order.clear(),
+                OptionalIndex::EventByMoveModule => self.tables.event_by_move_module.clear(),
+                OptionalIndex::EventByMoveEvent => self.tables.event_by_move_event.clear(),
+                OptionalIndex::EventBySender => self.tables.event_by_sender.clear(),
+                OptionalIndex::EventByTime => self.tables.event_by_time.clear(),
+            }?;
+        }
+        Ok(())
+    }
+
+    /// Returns every entry of the table of `index` as debug strings of its key and value. Meant
+    /// for tools and tests comparing the contents of indexes.
+    pub fn dump_index(
+        &self,
+        index: OptionalIndex,
+    ) -> Result<BTreeMap<String, String>, anyhow::Error> {
+        const PAGE_SIZE: u16 = 1024;
+        let mut entries = BTreeMap::new();
+        for page in 0.. {
+            let rows = self
+                .tables
+                .dump_table(index.to_string(), PAGE_SIZE, page)
+                .map_err(|e| anyhow!("Cannot dump index {index}: {e}"))?;
+            let len = rows.len();
+            entries.extend(rows);
+            if len < PAGE_SIZE as usize {
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write_tx_indexes(
+        &self,
+        batch: &mut DBBatch,
+        enabled: impl Fn(OptionalIndex) -> bool,
+        sequence: TxSequenceNumber,
+        sender: SuiAddress,
+        active_inputs: impl Iterator<Item = ObjectID>,
+        mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
+        move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        events: &TransactionEvents,
+        digest: &TransactionDigest,
+        timestamp_ms: u64,
+    ) -> SuiResult {
+        if enabled(OptionalIndex::TransactionsFromAddr) {
+            batch.insert_batch_non_consuming(
+                &self.tables.transactions_from_addr,
+                std::iter::once(((sender, sequence), *digest)),
+            )?;
+        }
+
+        if enabled(OptionalIndex::TransactionsByInputObjectId) {
+            batch.insert_batch_non_consuming(
+                &self.tables.transactions_by_input_object_id,
+                active_inputs.map(|id| ((id, sequence), *digest)),
+            )?;
+        }
+
+        if enabled(OptionalIndex::TransactionsByMutatedObjectId) {
+            batch.insert_batch_non_consuming(
+                &self.tables.transactions_by_mutated_object_id,
+                mutated_objects
+                    .clone()
+                    .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
+            )?;
+        }
+
+        if enabled(OptionalIndex::TransactionsByMoveFunction) {
+            batch.insert_batch_non_consuming(
+                &self.tables.transactions_by_move_function,
+                move_functions.map(|(obj_id, module, function)| {
+                    (
+                        (obj_id, module.to_string(), function.to_string(), sequence),
+                        *digest,
+                    )
+                }),
+            )?;
+        }
+
+        if enabled(OptionalIndex::TransactionsToAddr) {
+            batch.insert_batch_non_consuming(
+                &self.tables.transactions_to_addr,
+                mutated_objects.filter_map(|(_, owner)| {
+                    owner
+                        .get_owner_address()
+                        .ok()
+                        .map(|addr| ((addr, sequence), digest))
+                }),
+            )?;
+        }
 
         // events
         let event_digest = events.digest();
-        let batch = batch.insert_batch(
-            &self.tables.event_order,
-            events
-                .data
-                .iter()
-                .enumerate()
-                .map(|(i, _)| ((sequence, i), (event_digest, *digest, timestamp_ms))),
-        )?;
-        let batch = batch.insert_batch(
-            &self.tables.event_by_move_module,
-            events
-                .data
-                .iter()
-                .enumerate()
-                .map(|(i, e)| {
+        if enabled(OptionalIndex::EventOrder) {
+            batch.insert_batch_non_consuming(
+                &self.tables.event_order,
+                events
+                    .data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _)| ((sequence, i), (event_digest, *digest, timestamp_ms))),
+            )?;
+        }
+        if enabled(OptionalIndex::EventByMoveModule) {
+            batch.insert_batch_non_consuming(
+                &self.tables.event_by_move_module,
+                events
+                    .data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| {
+                        (
+                            i,
+                            ModuleId::new(e.package_id.into(), e.transaction_module.clone()),
+                        )
+                    })
+                    .map(|(i, m)| ((m, (sequence, i)), (event_digest, *digest, timestamp_ms))),
+            )?;
+        }
+        if enabled(OptionalIndex::EventBySender) {
+            batch.insert_batch_non_consuming(
+                &self.tables.event_by_sender,
+                events.data.iter().enumerate().map(|(i, e)| {
                     (
-                        i,
-                        ModuleId::new(e.package_id.into(), e.transaction_module.clone()),
+                        (e.sender, (sequence, i)),
+                        (event_digest, *digest, timestamp_ms),
                     )
-                })
-                .map(|(i, m)| ((m, (sequence, i)), (event_digest, *digest, timestamp_ms))),
+                }),
+            )?;
+        }
+        if enabled(OptionalIndex::EventByMoveEvent) {
+            batch.insert_batch_non_consuming(
+                &self.tables.event_by_move_event,
+                events.data.iter().enumerate().map(|(i, e)| {
+                    (
+                        (e.type_.clone(), (sequence, i)),
+                        (event_digest, *digest, timestamp_ms),
+                    )
+                }),
+            )?;
+        }
+        if enabled(OptionalIndex::EventByTime) {
+            batch.insert_batch_non_consuming(
+                &self.tables.event_by_time,
+                events.data.iter().enumerate().map(|(i, _)| {
+                    (
+                        (timestamp_ms, (sequence, i)),
+                        (event_digest, *digest, timestamp_ms),
+                    )
+                }),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_object_index_changes(
+        &self,
+        batch: &mut DBBatch,
+        enabled: impl Fn(OptionalIndex) -> bool,
+        object_index_changes: ObjectIndexChanges,
+    ) -> SuiResult {
+        if enabled(OptionalIndex::OwnerIndex) {
+            batch.delete_batch_non_consuming(
+                &self.tables.owner_index,
+                object_index_changes.deleted_owners.into_iter(),
+            )?;
+            batch.insert_batch_non_consuming(
+                &self.tables.owner_index,
+                object_index_changes.new_owners.into_iter(),
+            )?;
+        }
+        if enabled(OptionalIndex::DynamicFieldIndex) {
+            batch.delete_batch_non_consuming(
+                &self.tables.dynamic_field_index,
+                object_index_changes.deleted_dynamic_fields.into_iter(),
+            )?;
+            batch.insert_batch_non_consuming(
+                &self.tables.dynamic_field_index,
+                object_index_changes.new_dynamic_fields.into_iter(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Iterates over the indexed transactions in `transaction_order`, starting at `start`.
+    pub fn transactions_from_sequence(
+        &self,
+        start: TxSequenceNumber,
+    ) -> SuiResult<impl Iterator<Item = (TxSequenceNumber, TransactionDigest)> + '_> {
+        Ok(self.tables.transaction_order.iter().skip_to(&start)?)
+    }
+
+    pub fn next_sequence_number(&self) -> TxSequenceNumber {
+        self.next_sequence_number.load(Ordering::SeqCst) + 1
+    }
+
+    /// Returns the index tables by name, for inspection and compaction while the node is running.
+    pub fn raw_tables(&self) -> Vec<(&'static str, &dyn RawTable)> {
+        self.tables.raw_tables()
+    }
+
+    /// Returns the latest checkpoint whose transactions and events were pruned from the indexes,
+    /// or `None` if nothing has been pruned yet.
+    pub fn get_highest_pruned_checkpoint(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.tables.pruned_checkpoint.get(&())?)
+    }
+
+    /// Returns the first transaction sequence number whose index entries were not pruned.
+    pub fn get_pruned_sequence(&self) -> SuiResult<TxSequenceNumber> {
+        Ok(self.tables.pruned_sequence.get(&())?.unwrap_or_default())
+    }
+
+    /// Deletes the index entries of all transactions with a sequence number below `watermark`,
+    /// and records `checkpoint` as the latest pruned checkpoint. Object indexes are not affected.
+    ///
+    /// The tables keyed by a prefix such as an address are swept for at most
+    /// `PRUNE_MAX_PREFIXES` prefixes per call, resuming where the previous call stopped, so their
+    /// entries below `watermark` may only be gone after several calls.
+    pub fn prune(
+        &self,
+        watermark: TxSequenceNumber,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult {
+        self.prune_with_max_prefixes(watermark, checkpoint, PRUNE_MAX_PREFIXES)
+    }
+
+    fn prune_with_max_prefixes(
+        &self,
+        watermark: TxSequenceNumber,
+        checkpoint: CheckpointSequenceNumber,
+        max_prefixes: usize,
+    ) -> SuiResult {
+        // Everything is deleted in a single batch, so that a crash never leaves entries of
+        // pruned transactions behind `transaction_order`, which the next call starts from.
+        let mut batch = self.tables.transaction_order.batch();
+
+        // The tables keyed by transaction digest can only be point-deleted, so their keys are
+        // collected from `transaction_order`. `event_by_time` is keyed by timestamp first, which
+        // does not follow the sequence numbers, so the events of every pruned transaction are
+        // deleted under its own timestamp.
+        let mut transactions = self
+            .tables
+            .transaction_order
+            .iter()
+            .take_while(|(sequence, _)| *sequence < watermark)
+            .peekable();
+        while transactions.peek().is_some() {
+            let chunk: Vec<_> = transactions.by_ref().take(PRUNE_BATCH_SIZE).collect();
+            let digests: Vec<_> = chunk.iter().map(|(_, digest)| *digest).collect();
+            let timestamps = self.tables.timestamps.multi_get(&digests)?;
+            for ((sequence, _), timestamp_ms) in chunk.iter().zip(timestamps) {
+                if let Some(timestamp_ms) = timestamp_ms {
+                    batch.delete_range_non_consuming(
+                        &self.tables.event_by_time,
+                        &(timestamp_ms, (*sequence, 0)),
+                        &(timestamp_ms, (sequence + 1, 0)),
+                    )?;
+                }
+            }
+            batch.delete_batch_non_consuming(&self.tables.transactions_seq, &digests)?;
+            batch.delete_batch_non_consuming(&self.tables.timestamps, &digests)?;
+        }
+
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.transactions_from_addr,
+            "transactions_from_addr",
+            watermark,
+            max_prefixes,
+            |(address, sequence)| (address, sequence),
+            |address, sequence| (*address, sequence),
         )?;
-        let batch = batch.insert_batch(
-            &self.tables.event_by_sender,
-            events.data.iter().enumerate().map(|(i, e)| {
-                (
-                    (e.sender, (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.transactions_to_addr,
+            "transactions_to_addr",
+            watermark,
+            max_prefixes,
+            |(address, sequence)| (address, sequence),
+            |address, sequence| (*address, sequence),
+        )?;
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.transactions_by_input_object_id,
+            "transactions_by_input_object_id",
+            watermark,
+            max_prefixes,
+            |(id, sequence)| (id, sequence),
+            |id, sequence| (*id, sequence),
+        )?;
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.transactions_by_mutated_object_id,
+            "transactions_by_mutated_object_id",
+            watermark,
+            max_prefixes,
+            |(id, sequence)| (id, sequence),
+            |id, sequence| (*id, sequence),
+        )?;
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.transactions_by_move_function,
+            "transactions_by_move_function",
+            watermark,
+            max_prefixes,
+            |(package, module, function, sequence)| ((package, module, function), sequence),
+            |(package, module, function), sequence| {
+                (*package, module.clone(), function.clone(), sequence)
+            },
         )?;
-        let batch = batch.insert_batch(
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.event_by_move_module,
+            "event_by_move_module",
+            watermark,
+            max_prefixes,
+            |(module, (sequence, _))| (module, sequence),
+            |module, sequence| (module.clone(), (sequence, 0)),
+        )?;
+        self.prune_by_prefix(
+            &mut batch,
             &self.tables.event_by_move_event,
-            events.data.iter().enumerate().map(|(i, e)| {
-                (
-                    (e.type_.clone(), (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
+            "event_by_move_event",
+            watermark,
+            max_prefixes,
+            |(event_type, (sequence, _))| (event_type, sequence),
+            |event_type, sequence| (event_type.clone(), (sequence, 0)),
         )?;
-
-        let batch = batch.insert_batch(
-            &self.tables.event_by_time,
-            events.data.iter().enumerate().map(|(i, _)| {
-                (
-                    (timestamp_ms, (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
+        self.prune_by_prefix(
+            &mut batch,
+            &self.tables.event_by_sender,
+            "event_by_sender",
+            watermark,
+            max_prefixes,
+            |(sender, (sequence, _))| (sender, sequence),
+            |sender, sequence| (*sender, (sequence, 0)),
         )?;
+        batch.delete_range_non_consuming(&self.tables.event_order, &(0, 0), &(watermark, 0))?;
 
+        batch.delete_range_non_consuming(&self.tables.transaction_order, &0, &watermark)?;
+        batch.insert_batch_non_consuming(
+            &self.tables.pruned_checkpoint,
+            std::iter::once(((), checkpoint)),
+        )?;
+        batch.insert_batch_non_consuming(
+            &self.tables.pruned_sequence,
+            std::iter::once(((), watermark)),
+        )?;
         batch.write()?;
-
-        Ok(sequence)
+        Ok(())
     }
 
-    pub fn next_sequence_number(&self) -> TxSequenceNumber {
-        self.next_sequence_number.load(Ordering::SeqCst) + 1
+    /// Range-deletes the entries of `table` whose transaction sequence number is below
+    /// `watermark`, separately for every key prefix. `split` breaks a key into its prefix and
+    /// sequence number and `join` puts them back together. This costs one seek per prefix, so at
+    /// most `max_prefixes` prefixes are visited, starting from the cursor stored under `name`
+    /// by the previous call.
+    fn prune_by_prefix<K, V, P>(
+        &self,
+        batch: &mut DBBatch,
+        table: &DBMap<K, V>,
+        name: &str,
+        watermark: TxSequenceNumber,
+        max_prefixes: usize,
+        split: impl Fn(K) -> (P, TxSequenceNumber),
+        join: impl Fn(&P, TxSequenceNumber) -> K,
+    ) -> SuiResult
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        let cursor = self
+            .tables
+            .prune_cursors
+            .get(&name.to_string())?
+            .map(|bytes| bcs::from_bytes::<K>(&bytes))
+            .transpose()
+            .map_err(|e| SuiError::GenericStorageError(format!("Invalid {name} cursor: {e}")))?;
+        let mut iter = match &cursor {
+            Some(cursor) => table.iter().skip_to(cursor)?,
+            None => table.iter(),
+        };
+        let mut visited = 0;
+        while let Some((key, _)) = iter.next() {
+            let (prefix, sequence) = split(key);
+            if sequence < watermark {
+                batch.delete_range_non_consuming(
+                    table,
+                    &join(&prefix, 0),
+                    &join(&prefix, watermark),
+                )?;
+            }
+            let next = join(&prefix, TxSequenceNumber::MAX);
+            visited += 1;
+            if visited == max_prefixes {
+                let cursor = bcs::to_bytes(&next)
+                    .map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+                batch.insert_batch_non_consuming(
+                    &self.tables.prune_cursors,
+                    std::iter::once((name.to_string(), cursor)),
+                )?;
+                return Ok(());
+            }
+            iter = iter.skip_to(&next)?;
+        }
+        batch.delete_batch_non_consuming(
+            &self.tables.prune_cursors,
+            std::iter::once(name.to_string()),
+        )?;
+        Ok(())
     }
 
     pub fn get_transactions(
@@ -500,6 +995,7 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
+        self.ensure_enabled(OptionalIndex::TransactionsByInputObjectId)?;
         Self::get_transactions_from_index(
             &self.tables.transactions_by_input_object_id,
             input_object,
@@ -516,6 +1012,7 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
+        self.ensure_enabled(OptionalIndex::TransactionsByMutatedObjectId)?;
         Self::get_transactions_from_index(
             &self.tables.transactions_by_mutated_object_id,
             mutated_object,
@@ -532,6 +1029,7 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
+        self.ensure_enabled(OptionalIndex::TransactionsFromAddr)?;
         Self::get_transactions_from_index(
             &self.tables.transactions_from_addr,
             addr,
@@ -550,6 +1048,7 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
+        self.ensure_enabled(OptionalIndex::TransactionsByMoveFunction)?;
         let cursor_val = cursor.unwrap_or(if reverse {
             TxSequenceNumber::MAX
         } else {
@@ -606,6 +1105,7 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
+        self.ensure_enabled(OptionalIndex::TransactionsToAddr)?;
         Self::get_transactions_from_index(
             &self.tables.transactions_to_addr,
             addr,
@@ -629,6 +1129,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventOrder)?;
         Ok(if descending {
             self.tables
                 .event_order
@@ -661,6 +1162,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventOrder)?;
         let seq = self
             .get_transaction_seq(digest)?
             .ok_or(SuiError::TransactionNotFound { digest: *digest })?;
@@ -730,6 +1232,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventByMoveModule)?;
         Self::get_event_from_index(
             &self.tables.event_by_move_module,
             module,
@@ -748,6 +1251,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventByMoveEvent)?;
         Self::get_event_from_index(
             &self.tables.event_by_move_event,
             struct_name,
@@ -766,6 +1270,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventBySender)?;
         Self::get_event_from_index(
             &self.tables.event_by_sender,
             sender,
@@ -785,6 +1290,7 @@ impl IndexStore {
         limit: usize,
         descending: bool,
     ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        self.ensure_enabled(OptionalIndex::EventByTime)?;
         Ok(if descending {
             self.tables
                 .event_by_time
@@ -816,6 +1322,7 @@ impl IndexStore {
         object: ObjectID,
         cursor: Option<ObjectID>,
     ) -> SuiResult<impl Iterator<Item = DynamicFieldInfo> + '_> {
+        self.ensure_enabled(OptionalIndex::DynamicFieldIndex)?;
         debug!(?object, "get_dynamic_fields");
         Ok(self
             .tables
@@ -834,6 +1341,7 @@ impl IndexStore {
         object: ObjectID,
         name: &DynamicFieldName,
     ) -> SuiResult<Option<ObjectID>> {
+        self.ensure_enabled(OptionalIndex::DynamicFieldIndex)?;
         debug!(?object, "get_dynamic_field_object_id");
         Ok(self
             .tables
@@ -875,6 +1383,7 @@ impl IndexStore {
         count: usize,
         filter: Option<SuiObjectDataFilter>,
     ) -> SuiResult<impl Iterator<Item = ObjectInfo> + '_> {
+        self.ensure_enabled(OptionalIndex::OwnerIndex)?;
         // We use +1 to grab the next cursor
         let count = min(count, MAX_GET_OWNED_OBJECT_SIZE + 1);
         debug!(?owner, ?count, ?starting_object_id, "get_owner_objects");
@@ -928,20 +1437,166 @@ impl IndexStore {
     }
 
     pub fn insert_genesis_objects(&self, object_index_changes: ObjectIndexChanges) -> SuiResult {
-        let batch = self.tables.owner_index.batch();
-        let batch = batch.insert_batch(
-            &self.tables.owner_index,
-            object_index_changes.new_owners.into_iter(),
-        )?;
-        let batch = batch.insert_batch(
-            &self.tables.dynamic_field_index,
-            object_index_changes.new_dynamic_fields.into_iter(),
+        let mut batch = self.tables.owner_index.batch();
+        self.write_object_index_changes(
+            &mut batch,
+            |index| self.config.is_enabled(index),
+            object_index_changes,
         )?;
         batch.write()?;
         Ok(())
     }
 
     pub fn is_empty(&self) -> bool {
-        self.tables.owner_index.is_empty()
+        self.tables.owner_index.is_empty() && self.tables.dynamic_field_index.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber};
+    use sui_types::event::Event;
+
+    /// Indexes `count` transactions, each sent by its own address to `recipient`, and returns
+    /// their senders in sequence order.
+    fn index_transactions(
+        index_store: &IndexStore,
+        recipient: SuiAddress,
+        count: usize,
+    ) -> Vec<SuiAddress> {
+        (0..count)
+            .map(|_| {
+                let sender = SuiAddress::random_for_testing_only();
+                let object_ref = (ObjectID::random(), SequenceNumber::new(), ObjectDigest::MIN);
+                index_store
+                    .index_tx(
+                        sender,
+                        std::iter::once(object_ref.0),
+                        std::iter::once((object_ref, Owner::AddressOwner(recipient))),
+                        std::iter::empty(),
+                        &TransactionEvents::default(),
+                        ObjectIndexChanges::default(),
+                        &TransactionDigest::random(),
+                        0,
+                    )
+                    .unwrap();
+                sender
+            })
+            .collect()
+    }
+
+    fn transactions_from(index_store: &IndexStore, sender: SuiAddress) -> usize {
+        index_store
+            .get_transactions_from_addr(sender, None, None, false)
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn prune_deletes_entries_below_watermark() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+        let recipient = SuiAddress::random_for_testing_only();
+        let senders = index_transactions(&index_store, recipient, 4);
+
+        index_store.prune(2, 7).unwrap();
+
+        assert_eq!(
+            index_store.get_highest_pruned_checkpoint().unwrap(),
+            Some(7)
+        );
+        assert_eq!(index_store.get_pruned_sequence().unwrap(), 2);
+        let remaining: Vec<_> = senders
+            .iter()
+            .map(|sender| transactions_from(&index_store, *sender))
+            .collect();
+        assert_eq!(remaining, vec![0, 0, 1, 1]);
+        assert_eq!(
+            index_store
+                .get_transactions_to_addr(recipient, None, None, false)
+                .unwrap()
+                .len(),
+            2
+        );
+        let sequences: Vec<_> = index_store
+            .transactions_from_sequence(0)
+            .unwrap()
+            .map(|(sequence, _)| sequence)
+            .collect();
+        assert_eq!(sequences, vec![2, 3]);
+    }
+
+    #[test]
+    fn prune_deletes_events_by_sequence_not_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+        let sender = SuiAddress::random_for_testing_only();
+        let events = TransactionEvents {
+            data: vec![Event {
+                package_id: ObjectID::random(),
+                transaction_module: Identifier::new("test").unwrap(),
+                sender,
+                type_: StructTag {
+                    address: AccountAddress::ONE,
+                    module: Identifier::new("test").unwrap(),
+                    name: Identifier::new("Event").unwrap(),
+                    type_params: vec![],
+                },
+                contents: vec![],
+            }],
+        };
+        // Node-local timestamps do not follow the order of the transactions.
+        for timestamp_ms in [10, 30, 20] {
+            index_store
+                .index_tx(
+                    sender,
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    &events,
+                    ObjectIndexChanges::default(),
+                    &TransactionDigest::random(),
+                    timestamp_ms,
+                )
+                .unwrap();
+        }
+
+        index_store.prune(2, 1).unwrap();
+
+        let remaining: Vec<_> = index_store
+            .event_iterator(0, u64::MAX, 0, 0, 10, false)
+            .unwrap()
+            .into_iter()
+            .map(|(_, _, _, timestamp_ms)| timestamp_ms)
+            .collect();
+        assert_eq!(remaining, vec![20]);
+    }
+
+    #[test]
+    fn prune_resumes_sweep_from_stored_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let recipient = SuiAddress::random_for_testing_only();
+        let senders = {
+            let index_store = IndexStore::new(dir.path().to_path_buf());
+            let senders = index_transactions(&index_store, recipient, 3);
+            // Only one sender prefix is swept per call.
+            index_store.prune_with_max_prefixes(3, 1, 1).unwrap();
+            let remaining: usize = senders
+                .iter()
+                .map(|sender| transactions_from(&index_store, *sender))
+                .sum();
+            assert_eq!(remaining, 2);
+            senders
+        };
+
+        // The cursor survives a restart and the sweep completes in the following calls.
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+        index_store.prune_with_max_prefixes(3, 1, 1).unwrap();
+        index_store.prune_with_max_prefixes(3, 1, 1).unwrap();
+        for sender in senders {
+            assert_eq!(transactions_from(&index_store, sender), 0);
+        }
     }
 }
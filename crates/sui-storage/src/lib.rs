@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod indexes;
-pub use indexes::{IndexStore, IndexStoreTables};
+pub use indexes::{IndexStore, IndexStoreConfig, IndexStoreTables};
 
 pub mod mutex_table;
 pub mod object_store;
@@ -60,6 +60,34 @@ pub enum TransactionCommitPhase<ExecutionOutput> {
     Executed(ExecutionOutput),
 }
 
+/// The commit phase of a transaction in the log, without its execution output.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecoveryState {
+    /// The transaction will be executed again when it is recovered.
+    Uncommitted,
+    /// The transaction was executed, and its stored output will be written to permanent storage
+    /// when it is recovered.
+    Executed,
+}
+
+impl<ExecutionOutput> From<&TransactionCommitPhase<ExecutionOutput>> for RecoveryState {
+    fn from(phase: &TransactionCommitPhase<ExecutionOutput>) -> Self {
+        match phase {
+            TransactionCommitPhase::Uncommitted => RecoveryState::Uncommitted,
+            TransactionCommitPhase::Executed(_) => RecoveryState::Executed,
+        }
+    }
+}
+
+/// Describes a transaction that is pending in the log, for inspection by operators.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct LogEntrySummary {
+    pub digest: TransactionDigest,
+    pub state: RecoveryState,
+    /// How many times the transaction has been retried after a failure or a crash.
+    pub retry_count: u32,
+}
+
 // WriteAheadLog is parameterized on the value type (C) because:
 // - it's a pain to make a ConfirmationTransaction in tests.
 // - we might end up storing either a ConfirmationTransaction or a (sequence, ConfirmationTransaction)
@@ -284,6 +312,67 @@ where
         }
     }
 
+    /// Returns a summary of every transaction currently in the log.
+    pub fn list_entries(&self) -> SuiResult<Vec<LogEntrySummary>> {
+        self.tables
+            .log
+            .iter()
+            .map(|(tx, (_cert, phase))| self.summarize_entry(tx, &phase))
+            .collect()
+    }
+
+    /// Returns a summary of `tx` if it is in the log.
+    pub fn get_entry(&self, tx: &TransactionDigest) -> SuiResult<Option<LogEntrySummary>> {
+        self.get_tx(tx)?
+            .map(|(_cert, phase)| self.summarize_entry(*tx, &phase))
+            .transpose()
+    }
+
+    fn summarize_entry(
+        &self,
+        tx: TransactionDigest,
+        phase: &TransactionCommitPhase<ExecutionOutput>,
+    ) -> SuiResult<LogEntrySummary> {
+        Ok(LogEntrySummary {
+            digest: tx,
+            state: phase.into(),
+            retry_count: self.get_retry_count(&tx)?,
+        })
+    }
+
+    /// Removes `tx` from the log, so that it is not recovered. Returns false if `tx` was not in
+    /// the log. This is a repair operation for the log of a stopped node.
+    pub fn drop_entry(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        if !self.tables.log.contains_key(tx)? {
+            return Ok(false);
+        }
+        self.commit_tx(tx, false)?;
+        self.recoverable_txes.lock().unwrap().retain(|t| t != tx);
+        Ok(true)
+    }
+
+    /// Discards the execution output and the retry count of `tx`, so that it is executed again
+    /// from scratch when it is recovered. Returns false if `tx` was not in the log. This is a
+    /// repair operation for the log of a stopped node.
+    pub fn reset_entry(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        let cert = match self.get_tx(tx)? {
+            Some((cert, _phase)) => cert,
+            None => return Ok(false),
+        };
+        let write_batch = self.tables.log.batch();
+        let write_batch = write_batch.insert_batch(
+            &self.tables.log,
+            std::iter::once((
+                tx,
+                (cert, TransactionCommitPhase::<ExecutionOutput>::Uncommitted),
+            )),
+        )?;
+        let write_batch =
+            write_batch.delete_batch(&self.tables.retry_count, std::iter::once(tx))?;
+        write_batch.write()?;
+        Ok(true)
+    }
+
     fn commit_tx(&self, tx: &TransactionDigest, is_commit: bool) -> SuiResult {
         if is_commit {
             debug!("committing tx");
@@ -391,7 +480,10 @@ mod tests {
 
     use std::time::Duration;
 
-    use crate::write_ahead_log::{DBWriteAheadLog, TransactionCommitPhase, TxGuard, WriteAheadLog};
+    use crate::write_ahead_log::{
+        DBWriteAheadLog, LogEntrySummary, RecoveryState, TransactionCommitPhase, TxGuard,
+        WriteAheadLog,
+    };
     use anyhow;
     use sui_types::base_types::TransactionDigest;
 
@@ -526,4 +618,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_write_ahead_log_repair() -> Result<(), anyhow::Error> {
+        let working_dir = tempfile::tempdir()?;
+        let log: DBWriteAheadLog<u32, u32> = DBWriteAheadLog::new(working_dir.path().to_path_buf());
+
+        let tx1_id = TransactionDigest::random();
+        let tx2_id = TransactionDigest::random();
+        {
+            let _tx1 = log.begin_tx(&tx1_id, &1).await.unwrap();
+            let _tx2 = log.begin_tx(&tx2_id, &2).await.unwrap();
+            log.write_execution_output(&tx2_id, 0).unwrap();
+            // implicit drop
+        }
+        {
+            // beginning a tx that is still in the log counts as a retry
+            let _tx2 = log.begin_tx(&tx2_id, &2).await.unwrap();
+        }
+
+        assert_eq!(
+            log.get_entry(&tx2_id).unwrap(),
+            Some(LogEntrySummary {
+                digest: tx2_id,
+                state: RecoveryState::Executed,
+                retry_count: 1,
+            })
+        );
+        assert_eq!(log.list_entries().unwrap().len(), 2);
+
+        assert!(log.reset_entry(&tx2_id).unwrap());
+        assert_eq!(
+            log.get_entry(&tx2_id).unwrap(),
+            Some(LogEntrySummary {
+                digest: tx2_id,
+                state: RecoveryState::Uncommitted,
+                retry_count: 0,
+            })
+        );
+
+        assert!(log.drop_entry(&tx1_id).unwrap());
+        assert!(!log.drop_entry(&tx1_id).unwrap());
+        assert_eq!(log.get_entry(&tx1_id).unwrap(), None);
+        assert!(!log.reset_entry(&tx1_id).unwrap());
+        assert_eq!(log.list_entries().unwrap().len(), 1);
+
+        Ok(())
+    }
 }
@@ -4,7 +4,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tracing::trace;
+use tracing::{info, trace};
 
 pub(crate) const GA_API_SECRET: &str = "zeq-aYEzS0aGdRJ8kNZTEg";
 pub(crate) const GA_EVENT_NAME: &str = "node_telemetry_event";
@@ -26,19 +26,78 @@ struct TelemetryEvent {
 // The payload needs to meet this requirement in
 // https://developers.google.com/analytics/devguides/collection/protocol/ga4/reference?client_type=gtag#payload_post_body
 #[derive(Debug, Serialize, Deserialize)]
-struct TelemetryPayload {
+pub struct TelemetryPayload {
     client_id: String,
     events: Vec<TelemetryEvent>,
 }
 
+impl TelemetryPayload {
+    /// The payload exactly as it is posted.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Telemetry payload should serialize")
+    }
+}
+
+/// Where telemetry events go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryDestination {
+    /// The Mysten Labs Google Analytics property.
+    Mysten,
+    /// A custom collector, the payload is posted to the url as JSON.
+    Url(String),
+    /// The payload is only logged by the node.
+    Log,
+}
+
+impl std::fmt::Display for TelemetryDestination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TelemetryDestination::Mysten => write!(f, "{GA_URL}"),
+            TelemetryDestination::Url(url) => write!(f, "{url}"),
+            TelemetryDestination::Log => write!(f, "local log"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct IpResponse {
     ip: String,
 }
 
-pub async fn send_telemetry_event(is_validator: bool) {
+pub async fn send_telemetry_event(is_validator: bool, destination: &TelemetryDestination) {
+    // only Mysten Labs gets the public IP of the node, we don't look it up for anyone else
+    let lookup_ip = *destination == TelemetryDestination::Mysten;
+    let telemetry_payload = telemetry_payload(is_validator, lookup_ip).await;
+    match destination {
+        TelemetryDestination::Mysten => {
+            send_telemetry_event_impl(
+                format!(
+                    "{}?&measurement_id={}&api_secret={}",
+                    GA_URL, GA_MEASUREMENT_ID, GA_API_SECRET
+                ),
+                telemetry_payload,
+            )
+            .await
+        }
+        TelemetryDestination::Url(url) => {
+            send_telemetry_event_impl(url.clone(), telemetry_payload).await
+        }
+        TelemetryDestination::Log => {
+            info!("Telemetry event: {}", telemetry_payload.to_json())
+        }
+    }
+}
+
+/// The payload of the telemetry event of this node. With `lookup_ip` the public IP of the node
+/// is looked up and added to the payload, otherwise no network call is made and the address is
+/// left unknown.
+pub async fn telemetry_payload(is_validator: bool, lookup_ip: bool) -> TelemetryPayload {
     let git_rev = env!("CARGO_PKG_VERSION").to_string();
-    let ip_address = get_ip().await;
+    let ip_address = if lookup_ip {
+        get_ip().await
+    } else {
+        UNKNOWN_STRING.into()
+    };
     let since_the_epoch = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Now should be later than epoch!");
@@ -62,12 +121,10 @@ pub async fn send_telemetry_event(is_validator: bool) {
         ]),
     };
 
-    let telemetry_payload = TelemetryPayload {
+    TelemetryPayload {
         client_id: HARDCODED_CLIENT_ID.into(),
         events: vec![telemetry_event],
-    };
-
-    send_telemetry_event_impl(telemetry_payload).await
+    }
 }
 
 async fn get_ip() -> String {
@@ -81,13 +138,10 @@ async fn get_ip() -> String {
     }
 }
 
-async fn send_telemetry_event_impl(telemetry_payload: TelemetryPayload) {
+async fn send_telemetry_event_impl(url: String, telemetry_payload: TelemetryPayload) {
     let client = reqwest::Client::new();
     let response_result = client
-        .post(format!(
-            "{}?&measurement_id={}&api_secret={}",
-            GA_URL, GA_MEASUREMENT_ID, GA_API_SECRET
-        ))
+        .post(url)
         .json::<TelemetryPayload>(&telemetry_payload)
         .send()
         .await;
@@ -15,7 +15,9 @@ use sui_cluster_test::{
     config::{ClusterTestOpt, Env},
     faucet::{FaucetClient, FaucetClientFactory},
 };
-use sui_faucet::{FaucetRequest, FixedAmountRequest};
+use sui_faucet::{FaucetError, FaucetRequest, FaucetResponse, FixedAmountRequest};
+use sui_indexer::store::SqliteIndexerStore;
+use sui_indexer::{new_sqlite_connection_pool, Indexer, IndexerConfig};
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 
@@ -38,6 +40,18 @@ struct Args {
     /// The duration for epochs (defaults to one minute)
     #[clap(long, default_value = "60000")]
     epoch_duration_ms: u64,
+
+    /// Also start an indexer following the fullnode, and serve its RPC
+    #[clap(long)]
+    with_indexer: bool,
+
+    /// Port to start the indexer RPC server on
+    #[clap(long, default_value = "9124", requires = "with-indexer")]
+    indexer_rpc_port: u16,
+
+    /// SQLite database of the indexer, `sqlite://<path>` to keep it across restarts
+    #[clap(long, default_value = "sqlite://:memory:", requires = "with-indexer")]
+    indexer_db_url: String,
 }
 
 #[tokio::main]
@@ -58,11 +72,37 @@ async fn main() -> Result<()> {
 
     println!("Fullnode RPC URL: {}", cluster.fullnode_url());
 
+    if args.with_indexer {
+        start_indexer(&cluster, args.indexer_rpc_port, &args.indexer_db_url).await?;
+    }
+
     start_faucet(&cluster, args.faucet_port).await?;
 
     Ok(())
 }
 
+async fn start_indexer(cluster: &LocalNewCluster, port: u16, db_url: &str) -> Result<()> {
+    let config = IndexerConfig {
+        db_url: db_url.to_string(),
+        rpc_client_url: cluster.fullnode_url().to_string(),
+        rpc_server_url: "127.0.0.1".to_string(),
+        rpc_server_port: port,
+        ..IndexerConfig::default()
+    };
+    let store = SqliteIndexerStore::new(new_sqlite_connection_pool(db_url).await?)?;
+
+    println!("Indexer RPC URL: http://127.0.0.1:{}", port);
+
+    tokio::spawn(async move {
+        let registry = prometheus::Registry::default();
+        if let Err(e) = Indexer::start(&config, &registry, store).await {
+            eprintln!("Indexer stopped with error: {:?}", e);
+        }
+    });
+
+    Ok(())
+}
+
 struct AppState {
     faucet: Arc<dyn FaucetClient + Sync + Send>,
 }
@@ -111,6 +151,12 @@ async fn faucet_request(
         FaucetRequest::FixedAmountRequest(FixedAmountRequest { recipient }) => {
             state.faucet.request_sui_coins(recipient).await
         }
+        FaucetRequest::CoinRequest(_) => {
+            let error = FaucetError::InvalidRequest(
+                "only fixed amount requests are supported by the local faucet".to_string(),
+            );
+            return (StatusCode::BAD_REQUEST, Json(FaucetResponse::from(error)));
+        }
     };
 
     if !result.transferred_gas_objects.is_empty() {
@@ -3,13 +3,18 @@
 
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    get_object, get_transaction, make_clients, restore_from_db_checkpoint, ConciseObjectOutput,
-    GroupedObjectOutput, VerboseObjectOutput,
+    get_object, get_transaction,
+    indexes::{backfill_indexes, rebuild_indexes_at_path},
+    make_clients, restore_from_db_checkpoint,
+    wal::{execute_wal_command, WalCommand, WalName},
+    ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
 };
 use anyhow::Result;
 use std::path::PathBuf;
+use strum::IntoEnumIterator;
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
+use sui_storage::indexes::OptionalIndex;
 
 use sui_types::{base_types::*, object::Owner};
 
@@ -139,6 +144,49 @@ pub enum ToolCommand {
         #[clap(long = "db-checkpoint-path")]
         db_checkpoint_path: PathBuf,
     },
+
+    /// Backfill indexes of a stopped fullnode from its stored transactions, effects and events.
+    /// Use this after enabling an index that was previously disabled in the node config.
+    #[clap(name = "backfill-index")]
+    BackfillIndex {
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        /// Names of the index tables to backfill, e.g. `owner_index` or `event_by_sender`
+        #[clap(long = "index", required = true, num_args = 1..)]
+        indexes: Vec<OptionalIndex>,
+    },
+
+    /// Rebuild the index store of a stopped fullnode offline, by replaying the locally stored
+    /// transactions and effects in execution order.
+    #[clap(name = "rebuild-indexes")]
+    RebuildIndexes {
+        /// Path to the fullnode's database directory, containing the `store` and `indexes` dbs
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Names of the index tables to rebuild. All optional indexes are rebuilt if omitted.
+        #[clap(long = "tables", num_args = 1..)]
+        tables: Vec<OptionalIndex>,
+        /// Transaction sequence number to resume an interrupted rebuild from. Transaction
+        /// indexes are only cleared when this is 0.
+        #[clap(long = "start-sequence", default_value_t = 0)]
+        start_sequence: u64,
+    },
+
+    /// Inspect and repair the write-ahead logs of a stopped node.
+    #[clap(name = "wal")]
+    Wal {
+        /// Path to the node's database directory, containing the `store` db
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// The log to open
+        #[clap(long = "log", value_enum, default_value = "recovery")]
+        log: WalName,
+        /// The epoch whose recovery log to open. Defaults to the latest epoch in the store.
+        #[clap(long = "epoch")]
+        epoch: Option<EpochId>,
+        #[clap(subcommand)]
+        cmd: WalCommand,
+    },
 }
 
 trait OptionDebug<T> {
@@ -289,6 +337,33 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::BackfillIndex {
+                config_path,
+                indexes,
+            } => {
+                let config = sui_config::NodeConfig::load(config_path)?;
+                backfill_indexes(&config, indexes.into_iter().collect()).await?;
+            }
+            ToolCommand::RebuildIndexes {
+                db_path,
+                tables,
+                start_sequence,
+            } => {
+                let indexes = if tables.is_empty() {
+                    OptionalIndex::iter().collect()
+                } else {
+                    tables.into_iter().collect()
+                };
+                rebuild_indexes_at_path(&db_path, indexes, start_sequence).await?;
+            }
+            ToolCommand::Wal {
+                db_path,
+                log,
+                epoch,
+                cmd,
+            } => {
+                execute_wal_command(&db_path, log, epoch, cmd)?;
+            }
         };
         Ok(())
     }
@@ -0,0 +1,285 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use sui_config::NodeConfig;
+use sui_core::authority::{object_index_changes_for_live_objects, AuthorityStore};
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_storage::indexes::OptionalIndex;
+use sui_storage::IndexStore;
+use sui_types::base_types::TxSequenceNumber;
+use sui_types::messages::{TransactionDataAPI, TransactionEffectsAPI};
+use tracing::info;
+
+/// Number of live objects whose index entries are written in one batch.
+const OBJECT_BATCH_SIZE: usize = 10_000;
+
+/// Number of transactions between two progress reports.
+const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// Rebuilds `indexes` of the node's index store from the transactions, effects and events in its
+/// authority store. The node must not be running.
+pub async fn backfill_indexes(config: &NodeConfig, indexes: BTreeSet<OptionalIndex>) -> Result<()> {
+    if let Some(index) = indexes
+        .iter()
+        .find(|index| !config.index_store_config.is_enabled(**index))
+    {
+        bail!("Index {index} is disabled in the node config, enable it before backfilling it");
+    }
+
+    let genesis = config.genesis()?;
+    let committee_store = Arc::new(CommitteeStore::new(
+        config.db_path().join("epochs"),
+        &genesis.committee()?,
+        None,
+    ));
+    let store = AuthorityStore::open(
+        &config.db_path().join("store"),
+        None,
+        genesis,
+        &committee_store,
+        config.indirect_objects_threshold,
+    )
+    .await?;
+    let index_store = IndexStore::new_with_config(
+        config.db_path().join("indexes"),
+        config.index_store_config.clone(),
+    );
+    rebuild_indexes(&store, &index_store, indexes, 0)
+}
+
+/// Regenerates `indexes` of the stopped fullnode whose database directory is `db_path`, without
+/// needing its config or genesis. See [`rebuild_indexes`].
+pub async fn rebuild_indexes_at_path(
+    db_path: &Path,
+    indexes: BTreeSet<OptionalIndex>,
+    start_sequence: TxSequenceNumber,
+) -> Result<()> {
+    let store = AuthorityStore::open_existing(&db_path.join("store"), None, usize::MAX).await?;
+    let index_store = IndexStore::new(db_path.join("indexes"));
+    rebuild_indexes(&store, &index_store, indexes, start_sequence)
+}
+
+/// Replays the transactions of `index_store` in `transaction_order`, starting at
+/// `start_sequence`, to regenerate the transaction and event indexes among `indexes`, then
+/// rebuilds the object indexes among `indexes` from the live object set of `store`.
+///
+/// The transaction indexes are only cleared when starting from sequence 0, so an interrupted run
+/// can be resumed from the last sequence number it reported.
+pub fn rebuild_indexes(
+    store: &AuthorityStore,
+    index_store: &IndexStore,
+    indexes: BTreeSet<OptionalIndex>,
+    start_sequence: TxSequenceNumber,
+) -> Result<()> {
+    let (object_indexes, tx_indexes): (BTreeSet<_>, BTreeSet<_>) = indexes
+        .into_iter()
+        .partition(|index| index.is_object_index());
+
+    if !tx_indexes.is_empty() {
+        if start_sequence == 0 {
+            index_store.reset_indexes(&tx_indexes)?;
+        }
+        let total = index_store.next_sequence_number();
+        info!(
+            ?tx_indexes,
+            start_sequence, total, "Rebuilding transaction indexes"
+        );
+
+        let started_at = Instant::now();
+        let mut count = 0;
+        let mut last_sequence = None;
+        for (sequence, digest) in index_store.transactions_from_sequence(start_sequence)? {
+            let transaction = store
+                .get_transaction(&digest)?
+                .ok_or_else(|| anyhow!("Transaction {digest:?} not found in the store"))?;
+            let effects = store
+                .get_executed_effects(&digest)?
+                .ok_or_else(|| anyhow!("Effects of transaction {digest:?} not found"))?;
+            let events = effects
+                .events_digest()
+                .map(|events_digest| store.multi_get_events(&[*events_digest]))
+                .transpose()?
+                .and_then(|mut events| events.pop().flatten())
+                .unwrap_or_default();
+            let timestamp_ms = index_store.get_timestamp_ms(&digest)?.unwrap_or_default();
+            let data = &transaction.data().intent_message().value;
+
+            index_store.backfill_tx(
+                &tx_indexes,
+                sequence,
+                data.sender(),
+                data.input_objects()?.iter().map(|o| o.object_id()),
+                effects
+                    .all_changed_objects()
+                    .into_iter()
+                    .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
+                data.move_calls()
+                    .into_iter()
+                    .map(|(package, module, function)| {
+                        (*package, module.to_owned(), function.to_owned())
+                    }),
+                &events,
+                &digest,
+                timestamp_ms,
+            )?;
+
+            count += 1;
+            last_sequence = Some(sequence);
+            if count % PROGRESS_INTERVAL == 0 {
+                info!(
+                    sequence,
+                    total,
+                    tps = count as f64 / started_at.elapsed().as_secs_f64(),
+                    "Rebuilt transaction indexes up to sequence {sequence}"
+                );
+            }
+        }
+        info!(
+            count,
+            ?last_sequence,
+            elapsed_secs = started_at.elapsed().as_secs(),
+            "Finished rebuilding transaction indexes"
+        );
+    }
+
+    if !object_indexes.is_empty() {
+        info!(
+            ?object_indexes,
+            "Rebuilding object indexes from the live object set"
+        );
+        index_store.reset_indexes(&object_indexes)?;
+        let mut live_objects = store.iter_live_object_set().peekable();
+        let mut count = 0;
+        while live_objects.peek().is_some() {
+            let mut objects = Vec::with_capacity(OBJECT_BATCH_SIZE);
+            for (object_id, version, _) in live_objects.by_ref().take(OBJECT_BATCH_SIZE) {
+                if let Some(object) = store.get_object_by_key(&object_id, version)? {
+                    objects.push(object);
+                }
+            }
+            count += objects.len();
+            index_store.backfill_object_indexes(
+                &object_indexes,
+                object_index_changes_for_live_objects(objects.iter(), store, store)?,
+            )?;
+            info!(count, "Rebuilt object indexes");
+        }
+        info!(count, "Finished rebuilding object indexes");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use strum::IntoEnumIterator;
+    use sui_config::builder::ConfigBuilder;
+    use sui_core::authority::AuthorityState;
+    use sui_core::test_utils::make_transfer_sui_transaction;
+    use sui_types::base_types::{dbg_addr, ObjectID};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::messages::CertifiedTransaction;
+    use sui_types::object::Object;
+
+    /// Starts a fullnode state in `path` and executes a few transfers on it, so that every
+    /// transaction index has entries.
+    async fn state_with_transactions(path: &Path) -> Arc<AuthorityState> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let network_config = ConfigBuilder::new(&config_dir).build();
+        let genesis = network_config.genesis;
+        let key_pair = network_config.validator_configs[0]
+            .protocol_key_pair()
+            .copy();
+        let state = AuthorityState::new_for_testing(
+            genesis.committee().unwrap(),
+            &key_pair,
+            Some(path.to_path_buf()),
+            &genesis,
+        )
+        .await;
+
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        for i in 0..3 {
+            let gas = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+            state.insert_genesis_object(gas.clone()).await;
+            let transaction = make_transfer_sui_transaction(
+                gas.compute_object_reference(),
+                dbg_addr(i),
+                Some(100),
+                sender,
+                &sender_key,
+                None,
+            );
+            let epoch_store = state.load_epoch_store_one_call_per_task();
+            let vote = state
+                .handle_transaction(&epoch_store, transaction.clone())
+                .await
+                .unwrap()
+                .status
+                .into_signed_for_testing();
+            let committee = state.clone_committee_for_testing();
+            let certificate =
+                CertifiedTransaction::new(transaction.into_message(), vec![vote], &committee)
+                    .unwrap()
+                    .verify(&committee)
+                    .unwrap();
+            state.try_execute_for_test(&certificate).await.unwrap();
+        }
+        state
+    }
+
+    fn dump_indexes(index_store: &IndexStore) -> BTreeMap<OptionalIndex, BTreeMap<String, String>> {
+        OptionalIndex::iter()
+            .map(|index| (index, index_store.dump_index(index).unwrap()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn rebuilt_indexes_match_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_transactions(dir.path()).await;
+        let store = state.db();
+        let index_store = state.index_store_for_testing().unwrap();
+        let original = dump_indexes(index_store);
+        assert!(!original[&OptionalIndex::TransactionsFromAddr].is_empty());
+        assert!(!original[&OptionalIndex::OwnerIndex].is_empty());
+
+        let all: BTreeSet<_> = OptionalIndex::iter().collect();
+        index_store.reset_indexes(&all).unwrap();
+        assert!(dump_indexes(index_store)
+            .values()
+            .all(|entries| entries.is_empty()));
+
+        rebuild_indexes(&store, index_store, all, 0).unwrap();
+        assert_eq!(dump_indexes(index_store), original);
+    }
+
+    #[tokio::test]
+    async fn backfill_and_resume_only_touch_the_given_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_transactions(dir.path()).await;
+        let store = state.db();
+        let index_store = state.index_store_for_testing().unwrap();
+        let original = dump_indexes(index_store);
+
+        // An index that was disabled while the transactions were executed is backfilled.
+        let backfilled = BTreeSet::from([OptionalIndex::TransactionsToAddr]);
+        index_store.reset_indexes(&backfilled).unwrap();
+        let mut expected = original.clone();
+        expected.insert(OptionalIndex::TransactionsToAddr, BTreeMap::new());
+        assert_eq!(dump_indexes(index_store), expected);
+        rebuild_indexes(&store, index_store, backfilled.clone(), 0).unwrap();
+        assert_eq!(dump_indexes(index_store), original);
+
+        // Resuming from a later sequence number keeps the entries of earlier transactions.
+        rebuild_indexes(&store, index_store, backfilled, 1).unwrap();
+        assert_eq!(dump_indexes(index_store), original);
+    }
+}
@@ -21,6 +21,8 @@ use anyhow::anyhow;
 
 pub mod commands;
 pub mod db_tool;
+pub mod indexes;
+pub mod wal;
 
 fn make_clients(
     genesis: PathBuf,
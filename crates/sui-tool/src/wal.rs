@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Inspection and repair of the write-ahead logs of a stopped node: the per-epoch recovery log of
+//! certificates being executed, and the fullnode log of transactions being submitted.
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, ValueEnum};
+use std::fs;
+use std::path::Path;
+use sui_core::authority::authority_per_epoch_store::{
+    AuthorityEpochTables, ExecutionRecoveryLog, EPOCH_DB_PREFIX, RECOVERY_LOG_DIR,
+};
+use sui_core::transaction_orchestrator::PENDING_TRANSACTIONS_DIR;
+use sui_storage::write_ahead_log::LogEntrySummary;
+use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
+use sui_types::base_types::{EpochId, TransactionDigest};
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum WalName {
+    /// The recovery log of certificates being executed, one per epoch
+    Recovery,
+    /// The fullnode log of transactions submitted through the transaction orchestrator
+    PendingTransactions,
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum WalCommand {
+    /// List the transactions pending in the log, with their recovery state
+    List,
+    /// Show the recovery state of one transaction
+    State {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+    /// Remove a transaction from the log, so that the node does not recover it on restart
+    Drop {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+    /// Discard the execution output and retry count of a transaction, so that the node executes
+    /// it again from scratch on restart
+    Replay {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+}
+
+pub fn execute_wal_command(
+    db_path: &Path,
+    log: WalName,
+    epoch: Option<EpochId>,
+    cmd: WalCommand,
+) -> Result<()> {
+    match log {
+        WalName::Recovery => {
+            let wal = open_recovery_log(db_path, epoch)?;
+            match cmd {
+                WalCommand::List => {
+                    println!("{:<46} {:<12} retry count", "digest", "state");
+                    for entry in wal.list_entries()? {
+                        print_entry(&entry);
+                    }
+                }
+                WalCommand::State { digest } => match wal.get_entry(&digest)? {
+                    Some(entry) => print_entry(&entry),
+                    None => println!("{digest} is not in the recovery log"),
+                },
+                WalCommand::Drop { digest } => {
+                    if !wal.drop_entry(&digest)? {
+                        bail!("{digest} is not in the recovery log");
+                    }
+                    println!("Dropped {digest} from the recovery log");
+                }
+                WalCommand::Replay { digest } => {
+                    if !wal.reset_entry(&digest)? {
+                        bail!("{digest} is not in the recovery log");
+                    }
+                    println!("{digest} will be executed again when the node restarts");
+                }
+            }
+        }
+        WalName::PendingTransactions => {
+            let path = db_path.join(PENDING_TRANSACTIONS_DIR);
+            if !path.exists() {
+                bail!("No pending transaction log found at {}", path.display());
+            }
+            let log = WritePathPendingTransactionLog::new(path);
+            let is_pending = |digest: &TransactionDigest| {
+                log.load_all_pending_transactions()
+                    .iter()
+                    .any(|tx| tx.digest() == digest)
+            };
+            match cmd {
+                WalCommand::List => {
+                    for tx in log.load_all_pending_transactions() {
+                        println!("{}", tx.digest());
+                    }
+                }
+                WalCommand::State { digest } => {
+                    if is_pending(&digest) {
+                        println!("{digest} is pending and will be submitted again on restart");
+                    } else {
+                        println!("{digest} is not in the pending transaction log");
+                    }
+                }
+                WalCommand::Drop { digest } => {
+                    if !is_pending(&digest) {
+                        bail!("{digest} is not in the pending transaction log");
+                    }
+                    log.finish_transaction(&digest)?;
+                    println!("Dropped {digest} from the pending transaction log");
+                }
+                WalCommand::Replay { .. } => bail!(
+                    "Pending transactions are always submitted again on restart, there is nothing to replay"
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_entry(entry: &LogEntrySummary) {
+    let digest = entry.digest.to_string();
+    let state = format!("{:?}", entry.state);
+    println!("{:<46} {:<12} {}", digest, state, entry.retry_count);
+}
+
+/// Opens the recovery log of `epoch`, or of the latest epoch found in the store if unset.
+fn open_recovery_log(db_path: &Path, epoch: Option<EpochId>) -> Result<ExecutionRecoveryLog> {
+    let store_path = db_path.join("store");
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => latest_epoch(&store_path)?,
+    };
+    let path = AuthorityEpochTables::path(epoch, &store_path).join(RECOVERY_LOG_DIR);
+    if !path.exists() {
+        bail!("No recovery log found at {}", path.display());
+    }
+    Ok(ExecutionRecoveryLog::new(path))
+}
+
+fn latest_epoch(store_path: &Path) -> Result<EpochId> {
+    fs::read_dir(store_path)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?
+                .strip_prefix(EPOCH_DB_PREFIX)?
+                .parse::<EpochId>()
+                .ok()
+        })
+        .max()
+        .ok_or_else(|| anyhow!("No epoch db found in {}", store_path.display()))
+}
@@ -39,6 +39,7 @@ pub const PAY_MODULE_NAME: &IdentStr = ident_str!("pay");
 pub const PAY_JOIN_FUNC_NAME: &IdentStr = ident_str!("join");
 pub const PAY_SPLIT_N_FUNC_NAME: &IdentStr = ident_str!("divide_and_keep");
 pub const PAY_SPLIT_VEC_FUNC_NAME: &IdentStr = ident_str!("split_vec");
+pub const PAY_SPLIT_AND_TRANSFER_FUNC_NAME: &IdentStr = ident_str!("split_and_transfer");
 
 pub const LOCKED_COIN_MODULE_NAME: &IdentStr = ident_str!("locked_coin");
 pub const LOCKED_COIN_STRUCT_NAME: &IdentStr = ident_str!("LockedCoin");
@@ -426,6 +426,9 @@ pub enum SuiError {
     #[error("Index store not available on this Fullnode.")]
     IndexStoreNotAvailable,
 
+    #[error("Index {index} is disabled on this Fullnode.")]
+    IndexDisabled { index: String },
+
     #[error("Failed to read dynamic field from table in the object store: {0}")]
     DynamicFieldReadError(String),
 
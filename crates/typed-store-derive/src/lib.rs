@@ -339,6 +339,20 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let secondary_db_map_struct_name: proc_macro2::TokenStream =
         secondary_db_map_struct_name_str.parse().unwrap();
 
+    // Only DBMap tables can be handled independently of their key and value types
+    let raw_tables_fn = if simple_field_type_name_str == "DBMap" {
+        quote! {
+            /// Returns the tables by name, for operations that do not depend on their key and value types
+            pub fn raw_tables(&self) -> Vec<(&'static str, &dyn typed_store::traits::RawTable)> {
+                vec![#(
+                    (stringify!(#field_names), &self.#field_names as &dyn typed_store::traits::RawTable),
+                )*]
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     TokenStream::from(quote! {
 
         // <----------- This section generates the configurator struct -------------->
@@ -500,6 +514,8 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 )*].into_iter().collect()
             }
 
+            #raw_tables_fn
+
             /// This opens the DB in read only mode and returns a struct which exposes debug features
             pub fn get_read_only_handle (
                 primary_path: std::path::PathBuf,
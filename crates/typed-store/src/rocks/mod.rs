@@ -8,7 +8,7 @@ pub(crate) mod values;
 
 use crate::{
     metrics::{DBMetrics, RocksDBPerfContext, SamplingInterval},
-    traits::{Map, TableSummary},
+    traits::{Map, RawTable, TableSummary},
 };
 use bincode::Options;
 use collectable::TryExtend;
@@ -922,6 +922,61 @@ impl<K, V> DBMap<K, V> {
     }
 }
 
+/// The column family properties exposed through `RawTable::rocksdb_properties`.
+const RAW_TABLE_PROPERTIES: &[&std::ffi::CStr] = &[
+    properties::TOTAL_SST_FILES_SIZE,
+    properties::LIVE_SST_FILES_SIZE,
+    properties::ESTIMATE_LIVE_DATA_SIZE,
+    properties::ESTIMATE_PENDING_COMPACTION_BYTES,
+    properties::SIZE_ALL_MEM_TABLES,
+    properties::ESTIMATE_NUM_KEYS,
+    properties::ESTIMATE_TABLE_READERS_MEM,
+    properties::ESTIMATE_OLDEST_KEY_TIME,
+    properties::NUM_SNAPSHOTS,
+    properties::OLDEST_SNAPSHOT_TIME,
+    properties::ACTUAL_DELAYED_WRITE_RATE,
+    properties::IS_WRITE_STOPPED,
+    properties::BLOCK_CACHE_CAPACITY,
+    properties::BLOCK_CACHE_USAGE,
+    properties::BLOCK_CACHE_PINNED_USAGE,
+    properties::MEM_TABLE_FLUSH_PENDING,
+    properties::COMPACTION_PENDING,
+    properties::NUM_RUNNING_COMPACTIONS,
+    properties::NUM_RUNNING_FLUSHES,
+    properties::BACKGROUND_ERRORS,
+];
+
+impl<K, V> RawTable for DBMap<K, V> {
+    fn table_name(&self) -> &str {
+        &self.cf
+    }
+
+    fn db_name(&self) -> String {
+        self.rocksdb.db_name()
+    }
+
+    fn rocksdb_properties(&self) -> BTreeMap<String, i64> {
+        let cf = self.cf();
+        RAW_TABLE_PROPERTIES
+            .iter()
+            .map(|property| {
+                (
+                    property.to_string_lossy().into_owned(),
+                    Self::get_int_property(&self.rocksdb, &cf, *property).unwrap_or(METRICS_ERROR),
+                )
+            })
+            .collect()
+    }
+
+    fn summary(&self) -> eyre::Result<TableSummary> {
+        self.table_summary()
+    }
+
+    fn compact_range_raw(&self, start: Option<&[u8]>, end: Option<&[u8]>) {
+        self.rocksdb.compact_range_cf(&self.cf(), start, end);
+    }
+}
+
 /// Provides a mutable struct to form a collection of database write operations, and execute them.
 ///
 /// Batching write and delete operations is faster than performing them one by one and ensures their atomicity,
@@ -148,6 +148,27 @@ pub struct TableSummary {
     pub value_hist: hdrhistogram::Histogram<u64>,
 }
 
+/// Operations on a table that do not depend on its key and value types, so that the tables of a
+/// store can be inspected and maintained uniformly while it is open, e.g. from an admin server.
+pub trait RawTable {
+    /// The name of the column family backing the table.
+    fn table_name(&self) -> &str;
+
+    /// The name of the database the table belongs to.
+    fn db_name(&self) -> String;
+
+    /// Integer RocksDB properties of the table's column family, by property name. These are the
+    /// properties reported as column family metrics by `DBMetrics`.
+    fn rocksdb_properties(&self) -> BTreeMap<String, i64>;
+
+    /// Key and value sizes of the table. This scans the whole table.
+    fn summary(&self) -> eyre::Result<TableSummary>;
+
+    /// Triggers a manual compaction of the table between the raw (serialized) keys `start` and
+    /// `end`, or of the whole table if they are `None`. Blocks until the compaction is done.
+    fn compact_range_raw(&self, start: Option<&[u8]>, end: Option<&[u8]>);
+}
+
 pub trait TypedStoreDebug {
     /// Dump a DB table with pagination
     fn dump_table(
@@ -163,6 +163,24 @@ async fn macro_test() {
     assert_eq!(3, m.len());
     assert_eq!(format!("\"7\""), *m.get(&"\"7\"".to_string()).unwrap());
     assert_eq!(format!("\"8\""), *m.get(&"\"8\"".to_string()).unwrap());
+
+    // Check that the tables can be handled independently of their key and value types
+    let raw_tables = tbls_primary.raw_tables();
+    assert_eq!(
+        vec!["table1", "table2"],
+        raw_tables.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+    );
+    for (name, table) in raw_tables {
+        assert_eq!(name, table.table_name());
+        table.compact_range_raw(None, None);
+        assert!(table
+            .rocksdb_properties()
+            .contains_key("rocksdb.estimate-num-keys"));
+    }
+    assert_eq!(
+        19,
+        tbls_primary.raw_tables()[0].1.summary().unwrap().num_keys
+    );
 }
 
 #[derive(SallyDB)]